@@ -0,0 +1,213 @@
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+
+use osm_tags::{TagKey, TagsRef};
+use strum::{EnumString, IntoStaticStr};
+
+use crate::TurnDirection;
+
+/// <https://wiki.openstreetmap.org/wiki/Key:access#Lane_dependent_restrictions>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum Access {
+    #[strum(serialize = "")]
+    None,
+    No,
+    Yes,
+    Designated,
+}
+
+/// Cycling infrastructure of a single lane, from `cycleway:lanes=*`
+///
+/// <https://wiki.openstreetmap.org/wiki/Key:cycleway:lanes>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum Cycleway {
+    #[strum(serialize = "", serialize = "none")]
+    None,
+    No,
+    Lane,
+    SharedLane,
+    ShareBusway,
+}
+
+/// Turn markings of a single lane, from `turn:lanes=*`,
+/// where `none` or an empty value is a lane without markings
+///
+/// <https://wiki.openstreetmap.org/wiki/Key:turn>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Turn(pub Vec<TurnDirection>);
+
+impl FromStr for Turn {
+    type Err = strum::ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(';')
+            .map(str::trim)
+            .filter(|turn| !turn.is_empty() && *turn != "none")
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+/// Split the `|` separated values of each lane
+fn split<T: FromStr>(lanes: &str) -> Result<Vec<T>, T::Err> {
+    lanes.split('|').map(str::parse).collect()
+}
+
+/// Get value from tags given a key
+fn get<'tag, T: FromStr>(
+    tags: &'tag TagsRef<'_>,
+    key: &TagKey,
+) -> Result<Option<Vec<T>>, LaneDependentError<'tag>> {
+    match tags.get(key) {
+        Some(s) => match split(s) {
+            Ok(lanes) => Ok(Some(lanes)),
+            Err(_parse_error) => Err(LaneDependentError::Unknown(key.clone(), s)),
+        },
+        None => Ok(None),
+    }
+}
+
+/// Values of a `:lanes` suffixed key,
+/// either left to right across the way, or for the lanes in a direction of travel
+/// from `:lanes:forward` and `:lanes:backward`
+#[derive(Debug)]
+pub enum LaneDependent<T> {
+    LeftToRight(Vec<T>),
+    Forward(Vec<T>),
+    Backward(Vec<T>),
+    ForwardBackward { forward: Vec<T>, backward: Vec<T> },
+}
+
+/// Lane dependent access, e.g. `bus:lanes=*`
+pub type LaneDependentAccess = LaneDependent<Access>;
+
+#[derive(Debug)]
+pub enum LaneDependentError<'tag> {
+    Unknown(TagKey, &'tag str),
+    Conflict,
+}
+
+impl Display for LaneDependentError<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            LaneDependentError::Conflict => write!(f, "conflicting tags"),
+            LaneDependentError::Unknown(key, val) => {
+                write!(f, "unknown tag value {key}={val}")
+            },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LaneDependentError<'_> {}
+
+impl<T> LaneDependent<T>
+where
+    T: FromStr + PartialEq,
+{
+    /// Parse | separated lanes in `Tags` given `TagKey`
+    ///
+    /// # Errors
+    /// When the tags conflict or a value is unknown.
+    pub fn from_tags<'tag>(
+        tags: &'tag TagsRef<'_>,
+        key: &TagKey,
+    ) -> Result<Option<Self>, LaneDependentError<'tag>> {
+        let key_forward = key + "forward";
+        let key_backward = key + "backward";
+        Ok(
+            match (
+                get(tags, key)?,
+                (get(tags, &key_forward)?, get(tags, &key_backward)?),
+            ) {
+                (None, (Some(forward), None)) => Some(Self::Forward(forward)),
+                (None, (None, Some(backward))) => Some(Self::Backward(backward)),
+                (total, (Some(forward), Some(backward))) => {
+                    if let Some(total) = total {
+                        if forward.len().checked_add(backward.len()).unwrap() != total.len() {
+                            return Err(LaneDependentError::Conflict);
+                        }
+                        if forward
+                            .iter()
+                            .chain(backward.iter().rev())
+                            .zip(total.iter())
+                            .any(|(l, r)| l != r)
+                        {
+                            return Err(LaneDependentError::Conflict);
+                        }
+                    }
+                    Some(Self::ForwardBackward { forward, backward })
+                },
+                (Some(total), (forward, backward)) => {
+                    if let Some(forward) = forward {
+                        if total.iter().zip(forward.iter()).any(|(l, r)| l != r) {
+                            return Err(LaneDependentError::Conflict);
+                        }
+                    }
+                    if let Some(backward) = backward {
+                        if total
+                            .iter()
+                            .rev()
+                            .zip(backward.iter().rev())
+                            .any(|(l, r)| l != r)
+                        {
+                            return Err(LaneDependentError::Conflict);
+                        }
+                    }
+                    Some(Self::LeftToRight(total))
+                },
+                (None, (None, None)) => None,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use osm_tags::{TagKey, Tags};
+
+    use super::{Cycleway, LaneDependent, LaneDependentError, Turn};
+    use crate::TurnDirection;
+
+    const CYCLEWAY_LANES: TagKey = TagKey::from_static("cycleway:lanes");
+    const TURN_LANES: TagKey = TagKey::from_static("turn:lanes");
+
+    #[test]
+    fn test_cycleway_lanes() {
+        let tags = Tags::from_pair("cycleway:lanes", "none|lane|");
+        assert!(matches!(
+            LaneDependent::<Cycleway>::from_tags(&tags, &CYCLEWAY_LANES),
+            Ok(Some(LaneDependent::LeftToRight(lanes)))
+                if lanes == [Cycleway::None, Cycleway::Lane, Cycleway::None]
+        ));
+        let tags = Tags::from_pair("cycleway:lanes:forward", "no|track");
+        assert!(matches!(
+            LaneDependent::<Cycleway>::from_tags(&tags, &CYCLEWAY_LANES),
+            Err(LaneDependentError::Unknown(..))
+        ));
+    }
+
+    #[test]
+    fn test_turn_lanes() {
+        let tags = Tags::from_pair("turn:lanes", "left;through|none|");
+        assert!(matches!(
+            LaneDependent::<Turn>::from_tags(&tags, &TURN_LANES),
+            Ok(Some(LaneDependent::LeftToRight(lanes)))
+                if lanes == [
+                    Turn(vec![TurnDirection::Left, TurnDirection::Through]),
+                    Turn(vec![]),
+                    Turn(vec![]),
+                ]
+        ));
+        let tags = Tags::from_pair("turn:lanes:backward", "through|sideways");
+        assert!(matches!(
+            LaneDependent::<Turn>::from_tags(&tags, &TURN_LANES),
+            Err(LaneDependentError::Unknown(..))
+        ));
+    }
+}
@@ -0,0 +1,138 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+/// A value that only applies under a condition, from a `<key>:conditional=*` restriction
+///
+/// <https://wiki.openstreetmap.org/wiki/Conditional_restrictions>
+///
+/// ```
+/// use osm_tag_schemes::{Access, Conditional};
+/// let restrictions =
+///     Conditional::<Access>::split("no @ (Mo-Fr 07:00-10:00; Sa 08:00-12:00); destination @ wet")
+///         .unwrap();
+/// assert_eq!(restrictions[0].value, Access::No);
+/// assert_eq!(restrictions[0].condition, "Mo-Fr 07:00-10:00; Sa 08:00-12:00");
+/// assert_eq!(restrictions[1].value, Access::Destination);
+/// assert_eq!(restrictions[1].condition, "wet");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Conditional<T> {
+    pub value: T,
+    /// The condition, e.g. opening hours such as `Mo-Fr 07:00-10:00`,
+    /// without any enclosing parentheses
+    pub condition: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(clippy::module_name_repetitions)]
+pub enum ConditionalError<'tag> {
+    /// A restriction without ` @ condition`
+    MissingCondition(&'tag str),
+    /// A value that does not parse
+    UnknownValue(&'tag str),
+}
+
+impl core::fmt::Display for ConditionalError<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::MissingCondition(restriction) => {
+                write!(f, "missing condition in '{restriction}'")
+            },
+            Self::UnknownValue(value) => write!(f, "unknown value '{value}'"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ConditionalError<'_> {}
+
+impl<T: FromStr> Conditional<T> {
+    /// Split the `;` separated `value @ condition` restrictions of a conditional tag,
+    /// where a condition containing `;` is enclosed in parentheses.
+    ///
+    /// # Errors
+    /// When a restriction has no condition, or its value does not parse.
+    pub fn split(value: &str) -> Result<Vec<Self>, ConditionalError<'_>> {
+        split_restrictions(value)
+            .into_iter()
+            .map(|restriction| {
+                let (value, condition) = restriction
+                    .split_once('@')
+                    .ok_or(ConditionalError::MissingCondition(restriction))?;
+                let condition = condition.trim();
+                let condition = condition
+                    .strip_prefix('(')
+                    .and_then(|condition| condition.strip_suffix(')'))
+                    .unwrap_or(condition)
+                    .trim();
+                if condition.is_empty() {
+                    return Err(ConditionalError::MissingCondition(restriction));
+                }
+                let value = value.trim();
+                Ok(Self {
+                    value: value
+                        .parse()
+                        .map_err(|_e| ConditionalError::UnknownValue(value))?,
+                    condition: condition.into(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Split at each `;` outside of parentheses
+fn split_restrictions(value: &str) -> Vec<&str> {
+    let mut restrictions = Vec::new();
+    let mut depth = 0_usize;
+    let mut start = 0;
+    for (index, c) in value.char_indices() {
+        match c {
+            '(' => depth = depth.saturating_add(1),
+            ')' => depth = depth.saturating_sub(1),
+            ';' if depth == 0 => {
+                restrictions.push(value.get(start..index).unwrap());
+                start = index.saturating_add(1);
+            },
+            _ => {},
+        }
+    }
+    restrictions.push(value.get(start..).unwrap());
+    restrictions
+        .into_iter()
+        .map(str::trim)
+        .filter(|restriction| !restriction.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Access;
+
+    #[test]
+    fn test_conditional_lanes() {
+        let restrictions =
+            Conditional::<String>::split("designated|no @ (Mo-Fr 07:00-10:00)").unwrap();
+        assert_eq!(
+            restrictions,
+            [Conditional {
+                value: "designated|no".into(),
+                condition: "Mo-Fr 07:00-10:00".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_conditional_errors() {
+        assert_eq!(
+            Conditional::<Access>::split("no"),
+            Err(ConditionalError::MissingCondition("no"))
+        );
+        assert_eq!(
+            Conditional::<Access>::split("maybe @ wet"),
+            Err(ConditionalError::UnknownValue("maybe"))
+        );
+    }
+}
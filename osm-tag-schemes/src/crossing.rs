@@ -0,0 +1,27 @@
+use strum::{EnumString, IntoStaticStr};
+
+use crate::{keys, FromTagsDefault};
+
+/// Type of crossing, for a way crossing a road,
+/// e.g. `highway=footway` with `footway=crossing`
+///
+/// <https://wiki.openstreetmap.org/wiki/Key:crossing>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Crossing {
+    /// A crossing without further detail
+    Yes,
+    Marked,
+    Unmarked,
+    Uncontrolled,
+    TrafficSignals,
+    /// Legacy `crossing=zebra`, or `crossing_ref=zebra`
+    Zebra,
+    Informal,
+}
+
+impl FromTagsDefault for Crossing {
+    const KEY: osm_tags::TagKey = keys::CROSSING;
+}
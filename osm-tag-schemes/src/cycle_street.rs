@@ -0,0 +1,46 @@
+use osm_tags::{TagKey, TagsRef};
+
+use crate::{keys, TagError};
+
+/// A street designated for cycling, on which motor vehicles are guests
+///
+/// <https://wiki.openstreetmap.org/wiki/Key:cyclestreet>
+/// <https://wiki.openstreetmap.org/wiki/Key:bicycle_road>
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum CycleStreet {
+    /// `cyclestreet=yes`, e.g. a fietsstraat in the Netherlands and Belgium
+    Cyclestreet,
+    /// `bicycle_road=yes`, e.g. a Fahrradstraße in Germany
+    BicycleRoad,
+}
+
+impl CycleStreet {
+    /// From `bicycle_road=yes` or `cyclestreet=yes`
+    ///
+    /// # Errors
+    /// A value other than `yes` or `no`.
+    pub fn from_tags<'tag>(tags: &'tag TagsRef<'_>) -> Result<Option<Self>, TagError<'tag>> {
+        for (key, cycle_street) in [
+            (keys::BICYCLE_ROAD, Self::BicycleRoad),
+            (keys::CYCLESTREET, Self::Cyclestreet),
+        ] {
+            match tags.get(&key) {
+                Some("yes") => return Ok(Some(cycle_street)),
+                None | Some("no") => {},
+                Some(val) => return Err(TagError(key, val)),
+            }
+        }
+        Ok(None)
+    }
+
+    /// The key tagged `=yes`
+    #[must_use]
+    pub fn key(self) -> TagKey {
+        match self {
+            Self::Cyclestreet => keys::CYCLESTREET,
+            Self::BicycleRoad => keys::BICYCLE_ROAD,
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use strum::{EnumString, IntoStaticStr};
+
+/// Legal status of a painted cycle lane, from `cycleway:SIDE:lane=*`
+///
+/// <https://wiki.openstreetmap.org/wiki/Key:cycleway:lane>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum CyclewayLane {
+    /// Motor vehicles may use the lane when needed, usually marked with a dashed line
+    Advisory,
+    /// Reserved for bicycles, usually marked with a solid line
+    Exclusive,
+    /// Only bicycle pictograms on the carriageway, without a marked lane
+    Pictogram,
+}
@@ -0,0 +1,29 @@
+use strum::{EnumString, IntoStaticStr};
+
+/// Symbol shown on a guide sign for a destination,
+/// a value of `destination:symbol=*` or `destination:symbol:lanes=*`
+///
+/// <https://wiki.openstreetmap.org/wiki/Key:destination:symbol>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum DestinationSymbol {
+    Airport,
+    BusStation,
+    /// City or town centre
+    Centre,
+    Ferry,
+    Fuel,
+    Hospital,
+    Industrial,
+    Motorroad,
+    Motorway,
+    Parking,
+    Shopping,
+    Stadium,
+    TrainStation,
+    University,
+    /// No symbol, keeping the symbols aligned with the signed destinations
+    None,
+}
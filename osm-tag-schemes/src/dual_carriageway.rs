@@ -0,0 +1,19 @@
+use strum::{EnumString, IntoStaticStr};
+
+use crate::{keys, FromTagsDefault};
+
+/// One of the two directional ways of a road with a physically separated median
+///
+/// <https://wiki.openstreetmap.org/wiki/Key:dual_carriageway>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum DualCarriageway {
+    Yes,
+    No,
+}
+
+impl FromTagsDefault for DualCarriageway {
+    const KEY: osm_tags::TagKey = keys::DUAL_CARRIAGEWAY;
+}
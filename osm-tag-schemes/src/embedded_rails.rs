@@ -0,0 +1,22 @@
+use strum::{EnumString, IntoStaticStr};
+
+use crate::{keys, FromTagsDefault};
+
+/// Rails embedded in the road surface
+///
+/// <https://wiki.openstreetmap.org/wiki/Key:embedded_rails>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum EmbeddedRails {
+    Tram,
+    LightRail,
+    Rail,
+    NarrowGauge,
+    Subway,
+}
+
+impl FromTagsDefault for EmbeddedRails {
+    const KEY: osm_tags::TagKey = keys::EMBEDDED_RAILS;
+}
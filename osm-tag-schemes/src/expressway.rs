@@ -0,0 +1,19 @@
+use strum::{EnumString, IntoStaticStr};
+
+use crate::{keys, FromTagsDefault};
+
+/// A road built to higher standards than its classification, similar to a motorroad
+///
+/// <https://wiki.openstreetmap.org/wiki/Key:expressway>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Expressway {
+    Yes,
+    No,
+}
+
+impl FromTagsDefault for Expressway {
+    const KEY: osm_tags::TagKey = keys::EXPRESSWAY;
+}
@@ -1,4 +1,6 @@
-use osm_tags::Tags;
+use alloc::vec::Vec;
+
+use osm_tags::{TagKey, TagsRef};
 use strum::ParseError;
 
 use crate::{keys, FromTags, Tagged};
@@ -39,8 +41,8 @@ pub enum HighwayImportance {
     Tertiary,
 }
 
-impl std::fmt::Display for HighwayImportance {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for HighwayImportance {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::Motorway => write!(f, "motorway"),
             Self::Trunk => write!(f, "trunk"),
@@ -59,8 +61,8 @@ pub enum NonTravel {
     Raceway,
 }
 
-impl std::fmt::Display for NonTravel {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for NonTravel {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::Escape => write!(f, "escape"),
             Self::Raceway => write!(f, "raceway"),
@@ -68,7 +70,7 @@ impl std::fmt::Display for NonTravel {
     }
 }
 
-impl std::str::FromStr for HighwayType {
+impl core::str::FromStr for HighwayType {
     type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(match s {
@@ -103,8 +105,8 @@ impl std::str::FromStr for HighwayType {
     }
 }
 
-impl std::fmt::Display for HighwayType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for HighwayType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::Classified(importance) => write!(f, "{}", importance),
             Self::Link(importance) => write!(f, "{}_link", importance),
@@ -168,7 +170,7 @@ pub struct Highway {
 #[cfg(feature = "serde")]
 fn serialize_display<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
 where
-    T: std::fmt::Display,
+    T: core::fmt::Display,
     S: serde::Serializer,
 {
     serializer.collect_str(value)
@@ -178,16 +180,16 @@ where
 fn deserialize_from_str<'de, T, D>(deserializer: D) -> Result<T, D::Error>
 where
     D: serde::Deserializer<'de>,
-    T: std::str::FromStr,
-    <T as std::str::FromStr>::Err: std::fmt::Display,
+    T: core::str::FromStr,
+    <T as core::str::FromStr>::Err: core::fmt::Display,
 {
     use serde::Deserialize;
     let s = String::deserialize(deserializer)?;
-    std::str::FromStr::from_str(&s).map_err(serde::de::Error::custom)
+    core::str::FromStr::from_str(&s).map_err(serde::de::Error::custom)
 }
 
-impl std::fmt::Display for Highway {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Highway {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.highway)
     }
 }
@@ -206,8 +208,8 @@ pub enum Error<'tag> {
     Proposed(&'tag str),
 }
 
-impl std::fmt::Display for Error<'_> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Error<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Error::Highway(s) => write!(f, "highway={s}"),
             Error::ConstructionNone => write!(f, "construction missing"),
@@ -218,31 +220,55 @@ impl std::fmt::Display for Error<'_> {
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for Error<'_> {}
 
 impl Highway {
-    /// Get Highway From Tags
+    /// Get Highway From Tags,
+    /// where the first of multiple `;` separated values is used
     ///
     /// # Errors
     ///
     /// If highway missing return None
     /// If highway tag value unknown, `Err`
-    pub fn from_tags(tags: &Tags) -> Result<Option<Self>, Error<'_>> {
-        match HighwayType::from_tags(tags, &keys::HIGHWAY) {
+    pub fn from_tags<'tag>(tags: &'tag TagsRef<'_>) -> Result<Option<Self>, Error<'tag>> {
+        Self::from_tags_first(tags, &mut Vec::new())
+    }
+
+    /// Like [`Highway::from_tags`], collecting the keys and values of which only the first is used
+    pub(crate) fn from_tags_first<'tag>(
+        tags: &'tag TagsRef<'_>,
+        ambiguous: &mut Vec<(TagKey, &'tag str)>,
+    ) -> Result<Option<Self>, Error<'tag>> {
+        match first_type(tags, keys::HIGHWAY, ambiguous) {
             Tagged::None => Ok(None),
-            Tagged::Some(t) => Ok(Some(Highway::active(t))),
-            Tagged::Unknown(s) => match s {
-                "construction" => match HighwayType::from_tags(tags, &keys::CONSTRUCTION) {
-                    Tagged::None => Err(Error::ConstructionNone),
-                    Tagged::Some(t) => Ok(Some(Highway::construction(t))),
-                    Tagged::Unknown(s) => Err(Error::Construction(s)),
-                },
-                "proposed" => match HighwayType::from_tags(tags, &keys::PROPOSED) {
-                    Tagged::None => Err(Error::ProposedNone),
-                    Tagged::Some(t) => Ok(Some(Highway::construction(t))),
-                    Tagged::Unknown(s) => Err(Error::Construction(s)),
-                },
-                val => Err(Error::Highway(val)),
+            Tagged::Some(t) | Tagged::Ambiguous(t, _) => Ok(Some(Highway::active(t))),
+            Tagged::Unknown(s) => {
+                let values = tags.get_values(&keys::HIGHWAY);
+                let lifecycle = values
+                    .first()
+                    .copied()
+                    .filter(|value| ["construction", "proposed"].contains(value));
+                if lifecycle.is_some() && values.len() > 1 {
+                    ambiguous.push((keys::HIGHWAY, s));
+                }
+                match lifecycle {
+                    Some("construction") => match first_type(tags, keys::CONSTRUCTION, ambiguous) {
+                        Tagged::None => Err(Error::ConstructionNone),
+                        Tagged::Some(t) | Tagged::Ambiguous(t, _) => {
+                            Ok(Some(Highway::construction(t)))
+                        },
+                        Tagged::Unknown(s) => Err(Error::Construction(s)),
+                    },
+                    Some("proposed") => match first_type(tags, keys::PROPOSED, ambiguous) {
+                        Tagged::None => Err(Error::ProposedNone),
+                        Tagged::Some(t) | Tagged::Ambiguous(t, _) => {
+                            Ok(Some(Highway::construction(t)))
+                        },
+                        Tagged::Unknown(s) => Err(Error::Construction(s)),
+                    },
+                    _ => Err(Error::Highway(s)),
+                }
             },
         }
     }
@@ -304,3 +330,16 @@ impl Highway {
         self.highway
     }
 }
+
+/// The type of highway for the key, collecting the key and value if only the first value is used
+fn first_type<'tag>(
+    tags: &'tag TagsRef<'_>,
+    key: TagKey,
+    ambiguous: &mut Vec<(TagKey, &'tag str)>,
+) -> Tagged<'tag, HighwayType> {
+    let tagged = HighwayType::from_tags(tags, &key);
+    if let Tagged::Ambiguous(_, s) = &tagged {
+        ambiguous.push((key, *s));
+    }
+    tagged
+}
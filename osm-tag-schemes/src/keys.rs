@@ -9,13 +9,83 @@ pub const PROPOSED: TagKey = TagKey::from_static("proposed");
 pub const LIFECYCLE: [TagKey; 3] = [HIGHWAY, CONSTRUCTION, PROPOSED];
 
 pub const ONEWAY: TagKey = TagKey::from_static("oneway");
+pub const ONEWAY_BICYCLE: TagKey = TagKey::from_static("oneway:bicycle");
 
 pub const LIT: TagKey = TagKey::from_static("lit");
 
 pub const TRACK_TYPE: TagKey = TagKey::from_static("tracktype");
 pub const SMOOTHNESS: TagKey = TagKey::from_static("smoothness");
+pub const SURFACE: TagKey = TagKey::from_static("surface");
+pub const SURFACE_LANES: TagKey = TagKey::from_static("surface:lanes");
+
+pub const WIDTH: TagKey = TagKey::from_static("width");
+pub const WIDTH_LANES: TagKey = TagKey::from_static("width:lanes");
+
+pub const EMBEDDED_RAILS: TagKey = TagKey::from_static("embedded_rails");
+pub const EMBEDDED_RAILS_LANES: TagKey = TagKey::from_static("embedded_rails:lanes");
+
+pub const TROLLEY_WIRE: TagKey = TagKey::from_static("trolley_wire");
+pub const TROLLEYBUS: TagKey = TagKey::from_static("trolleybus");
+
+pub const TRAFFIC_SIGN: TagKey = TagKey::from_static("traffic_sign");
+pub const TRAFFIC_SIGN_FORWARD: TagKey = TagKey::from_static("traffic_sign:forward");
+pub const TRAFFIC_SIGN_BACKWARD: TagKey = TagKey::from_static("traffic_sign:backward");
+
+pub const PRIORITY_ROAD: TagKey = TagKey::from_static("priority_road");
+pub const PASSING_PLACES: TagKey = TagKey::from_static("passing_places");
+pub const EXPRESSWAY: TagKey = TagKey::from_static("expressway");
+pub const DUAL_CARRIAGEWAY: TagKey = TagKey::from_static("dual_carriageway");
+pub const RUMBLE_STRIPS: TagKey = TagKey::from_static("rumble_strips");
+pub const SEPARATION: TagKey = TagKey::from_static("separation");
+
+pub const MAXSPEED: TagKey = TagKey::from_static("maxspeed");
+pub const MAXSPEED_FORWARD: TagKey = TagKey::from_static("maxspeed:forward");
+pub const MAXSPEED_BACKWARD: TagKey = TagKey::from_static("maxspeed:backward");
+pub const MAXSPEED_LANES: TagKey = TagKey::from_static("maxspeed:lanes");
+pub const MAXHEIGHT: TagKey = TagKey::from_static("maxheight");
+pub const MAXHEIGHT_LANES: TagKey = TagKey::from_static("maxheight:lanes");
+pub const MAXWEIGHT: TagKey = TagKey::from_static("maxweight");
+pub const MAXWEIGHT_LANES: TagKey = TagKey::from_static("maxweight:lanes");
+pub const MAXLENGTH: TagKey = TagKey::from_static("maxlength");
+pub const MAXLENGTH_LANES: TagKey = TagKey::from_static("maxlength:lanes");
+
+pub const DESTINATION_LANES: TagKey = TagKey::from_static("destination:lanes");
+pub const DESTINATION_REF_LANES: TagKey = TagKey::from_static("destination:ref:lanes");
+pub const DESTINATION_SYMBOL_LANES: TagKey = TagKey::from_static("destination:symbol:lanes");
+/// Prefix of `destination:lang:<language>:lanes=*`
+pub const DESTINATION_LANG: TagKey = TagKey::from_static("destination:lang");
+
+pub const TURN_LANES: TagKey = TagKey::from_static("turn:lanes");
+
+pub const FOOTWAY: TagKey = TagKey::from_static("footway");
+pub const FOOT: TagKey = TagKey::from_static("foot");
+pub const SEGREGATED: TagKey = TagKey::from_static("segregated");
+pub const CROSSING: TagKey = TagKey::from_static("crossing");
+pub const CROSSING_REF: TagKey = TagKey::from_static("crossing_ref");
+pub const IS_SIDEPATH: TagKey = TagKey::from_static("is_sidepath");
+
+pub const ACCESS: TagKey = TagKey::from_static("access");
+pub const VEHICLE: TagKey = TagKey::from_static("vehicle");
+pub const BICYCLE: TagKey = TagKey::from_static("bicycle");
+pub const MOTOR_VEHICLE: TagKey = TagKey::from_static("motor_vehicle");
+pub const ACCESS_LANES: TagKey = TagKey::from_static("access:lanes");
+pub const VEHICLE_LANES: TagKey = TagKey::from_static("vehicle:lanes");
+pub const MOTOR_VEHICLE_LANES: TagKey = TagKey::from_static("motor_vehicle:lanes");
+
+pub const SHARED_SPACE: TagKey = TagKey::from_static("shared_space");
+
+pub const CYCLESTREET: TagKey = TagKey::from_static("cyclestreet");
+pub const BICYCLE_ROAD: TagKey = TagKey::from_static("bicycle_road");
+pub const CYCLEWAY_LANES: TagKey = TagKey::from_static("cycleway:lanes");
+pub const BICYCLE_LANES: TagKey = TagKey::from_static("bicycle:lanes");
 
 pub const LANES: TagKey = TagKey::from_static("lanes");
 // Unstable: const evaluation https://github.com/rust-lang/rust/issues/90080
 pub const LANES_FORWARD: TagKey = TagKey::from_static("lanes:forward");
 pub const LANES_BACKWARD: TagKey = TagKey::from_static("lanes:backward");
+
+pub const PLACEMENT: TagKey = TagKey::from_static("placement");
+pub const PLACEMENT_FORWARD: TagKey = TagKey::from_static("placement:forward");
+pub const PLACEMENT_BACKWARD: TagKey = TagKey::from_static("placement:backward");
+pub const PLACEMENT_START: TagKey = TagKey::from_static("placement:start");
+pub const PLACEMENT_END: TagKey = TagKey::from_static("placement:end");
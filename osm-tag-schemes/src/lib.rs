@@ -2,6 +2,7 @@
 //!
 //! Provides various structs and enums representing tagging schemes
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(explicit_outlives_requirements)]
 #![warn(missing_abi)]
 #![deny(non_ascii_idents)]
@@ -45,11 +46,14 @@
     clippy::use_debug
 )]
 
-use std::borrow::Borrow;
-use std::hash::Hash;
-use std::str::FromStr;
+extern crate alloc;
 
-use osm_tags::{TagKey, Tags};
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+use osm_tags::{TagKey, TagsRef};
 
 pub mod keys;
 
@@ -65,32 +69,102 @@ pub use track_type::TrackType;
 mod smoothness;
 pub use smoothness::Smoothness;
 
+mod surface;
+pub use surface::Surface;
+
+mod embedded_rails;
+pub use embedded_rails::EmbeddedRails;
+
+mod traffic_sign;
+pub use traffic_sign::TrafficSign;
+
+mod trolley_wire;
+pub use trolley_wire::TrolleyWire;
+
+mod priority_road;
+pub use priority_road::PriorityRoad;
+
+mod passing_places;
+pub use passing_places::PassingPlaces;
+
+mod expressway;
+pub use expressway::Expressway;
+
+mod dual_carriageway;
+pub use dual_carriageway::DualCarriageway;
+
+mod separation;
+pub use separation::{RumbleStrips, Separation};
+
+mod crossing;
+pub use crossing::Crossing;
+
+mod cycle_street;
+pub use cycle_street::CycleStreet;
+
+mod cycleway_lane;
+pub use cycleway_lane::CyclewayLane;
+
+mod destination_symbol;
+pub use destination_symbol::DestinationSymbol;
+
 mod access;
 pub use access::Access;
 
-mod access_by_lane;
-pub use access_by_lane::{Access as LaneAccess, LaneDependentAccess, LaneDependentAccessError};
+mod parking;
+pub use parking::Parking;
+
+mod parking_condition;
+pub use parking_condition::ParkingCondition;
+
+mod parking_lane;
+pub use parking_lane::ParkingLane;
+
+mod parking_orientation;
+pub use parking_orientation::ParkingOrientation;
+
+mod parking_position;
+pub use parking_position::ParkingPosition;
+
+mod placement;
+pub use placement::{Placement, PlacementScheme};
+
+mod turn_direction;
+pub use turn_direction::TurnDirection;
+
+mod by_lane;
+pub use by_lane::{
+    Access as LaneAccess, Cycleway as LaneCycleway, LaneDependent, LaneDependentAccess,
+    LaneDependentError, Turn as LaneTurn,
+};
+
+mod conditional;
+pub use conditional::{Conditional, ConditionalError};
 
 #[derive(Debug)]
 pub struct TagError<'tag>(TagKey, &'tag str);
 
-impl std::fmt::Display for TagError<'_> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for TagError<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}={}", self.0, self.1)
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for TagError<'_> {}
 
 /// A single tagged key value pair
 ///
 /// Either it is left untagged,
 /// the tagged value is known,
+/// only the first of multiple `;` separated values is used,
 /// or the tagged value is unknown.
 pub enum Tagged<'tag, T> {
     /// Untagged
     None,
     Some(T),
+    /// The first value, and the whole tagged value
+    Ambiguous(T, &'tag str),
     Unknown(&'tag str),
 }
 
@@ -99,19 +173,20 @@ impl<'tag, T> Tagged<'tag, T> {
     pub fn unwrap(self) -> T {
         match self {
             Tagged::None | Tagged::Unknown(_) => panic!(),
-            Tagged::Some(v) => v,
+            Tagged::Some(v) | Tagged::Ambiguous(v, _) => v,
         }
     }
     pub fn ok(self) -> Option<T> {
         match self {
             Tagged::None | Tagged::Unknown(_) => None,
-            Tagged::Some(v) => Some(v),
+            Tagged::Some(v) | Tagged::Ambiguous(v, _) => Some(v),
         }
     }
     pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Tagged<'tag, U> {
         match self {
             Tagged::None => Tagged::None,
             Tagged::Some(t) => Tagged::Some(f(t)),
+            Tagged::Ambiguous(t, s) => Tagged::Ambiguous(f(t), s),
             Tagged::Unknown(s) => Tagged::Unknown(s),
         }
     }
@@ -120,27 +195,51 @@ impl<'tag, T> Tagged<'tag, T> {
     ///
     /// # Errors
     ///
-    /// From `Tagged::Unknown`.
+    /// From `Tagged::Unknown` or `Tagged::Ambiguous`.
     pub fn ok_with(self, key: TagKey) -> Result<Option<T>, TagError<'tag>> {
         match self {
             Tagged::None => Ok(None),
             Tagged::Some(val) => Ok(Some(val)),
-            Tagged::Unknown(s) => Err(TagError(key, s)),
+            Tagged::Ambiguous(_, s) | Tagged::Unknown(s) => Err(TagError(key, s)),
+        }
+    }
+
+    /// `Result<Option>`, where the first of multiple values takes precedence,
+    /// collecting the key and value of `Tagged::Ambiguous`
+    ///
+    /// # Errors
+    ///
+    /// From `Tagged::Unknown`.
+    fn ok_with_first(
+        self,
+        key: TagKey,
+        ambiguous: &mut Vec<(TagKey, &'tag str)>,
+    ) -> Result<Option<T>, TagError<'tag>> {
+        match self {
+            Tagged::Ambiguous(val, s) => {
+                ambiguous.push((key, s));
+                Ok(Some(val))
+            },
+            tagged => tagged.ok_with(key),
         }
     }
 }
 
 trait FromTags: FromStr {
-    /// From tags given key
-    fn from_tags<'tag, Q>(tags: &'tag Tags, key: &Q) -> Tagged<'tag, Self>
+    /// From tags given key,
+    /// where the first of multiple `;` separated values is `Tagged::Ambiguous`,
+    /// e.g. `asphalt` for `surface=asphalt;concrete`
+    fn from_tags<'tag, Q>(tags: &'tag TagsRef<'_>, key: &Q) -> Tagged<'tag, Self>
     where
-        TagKey: Borrow<Q>,
-        Q: Ord + Hash + Eq + ?Sized,
+        Q: AsRef<str> + ?Sized,
     {
         match tags.get(key) {
-            Some(s) => match s.parse() {
-                Ok(val) => Tagged::Some(val),
-                Err(_) => Tagged::Unknown(s),
+            Some(s) => match tags.get_values(key).as_slice() {
+                [val] => val.parse().map_or(Tagged::Unknown(s), Tagged::Some),
+                [first, ..] => first
+                    .parse()
+                    .map_or(Tagged::Unknown(s), |val| Tagged::Ambiguous(val, s)),
+                [] => Tagged::Unknown(s),
             },
             None => Tagged::None,
         }
@@ -154,9 +253,17 @@ trait FromTagsDefault: FromTags {
     const KEY: TagKey;
 
     /// From tags with default key
-    fn from_tags_default(tags: &Tags) -> Tagged<Self> {
+    fn from_tags_default<'tag>(tags: &'tag TagsRef<'_>) -> Tagged<'tag, Self> {
         Self::from_tags(tags, &Self::KEY)
     }
+
+    /// From tags with default key, where the first of multiple values takes precedence
+    fn from_tags_first<'tag>(
+        tags: &'tag TagsRef<'_>,
+        ambiguous: &mut Vec<(TagKey, &'tag str)>,
+    ) -> Result<Option<Self>, TagError<'tag>> {
+        Self::from_tags_default(tags).ok_with_first(Self::KEY, ambiguous)
+    }
 }
 
 pub struct Schemes<'tag> {
@@ -169,18 +276,92 @@ pub struct Schemes<'tag> {
     pub lit: Result<Option<Lit>, TagError<'tag>>,
     pub tracktype: Result<Option<TrackType>, TagError<'tag>>,
     pub smoothness: Result<Option<Smoothness>, TagError<'tag>>,
+    pub surface: Result<Option<Surface>, TagError<'tag>>,
+    pub embedded_rails: Result<Option<EmbeddedRails>, TagError<'tag>>,
+    pub trolley_wire: Result<Option<TrolleyWire>, TagError<'tag>>,
+    pub priority_road: Result<Option<PriorityRoad>, TagError<'tag>>,
+    pub passing_places: Result<Option<PassingPlaces>, TagError<'tag>>,
+    pub expressway: Result<Option<Expressway>, TagError<'tag>>,
+    pub dual_carriageway: Result<Option<DualCarriageway>, TagError<'tag>>,
+    pub cycle_street: Result<Option<CycleStreet>, TagError<'tag>>,
+    pub placement: Result<Option<PlacementScheme>, TagError<'tag>>,
+
+    /// Tags of which only the first of multiple `;` separated values is used,
+    /// e.g. `surface=asphalt;concrete`
+    pub ambiguous: Vec<(TagKey, &'tag str)>,
 }
 
 impl<'tag> Schemes<'tag> {
     #[must_use]
-    pub fn from_tags(tags: &'tag Tags) -> Self {
+    pub fn from_tags(tags: &'tag TagsRef<'_>) -> Self {
+        let mut ambiguous = Vec::new();
         Self {
             name: tags.get(&keys::NAME).map(ToOwned::to_owned),
             r#ref: tags.get(&keys::REF).map(ToOwned::to_owned),
-            highway: Highway::from_tags(tags),
-            lit: Lit::from_tags_default(tags).ok_with(Lit::KEY),
-            tracktype: TrackType::from_tags_default(tags).ok_with(TrackType::KEY),
-            smoothness: Smoothness::from_tags_default(tags).ok_with(Smoothness::KEY),
+            highway: Highway::from_tags_first(tags, &mut ambiguous),
+            lit: Lit::from_tags_first(tags, &mut ambiguous),
+            tracktype: TrackType::from_tags_first(tags, &mut ambiguous),
+            smoothness: Smoothness::from_tags_first(tags, &mut ambiguous),
+            surface: Surface::from_tags_first(tags, &mut ambiguous),
+            embedded_rails: EmbeddedRails::from_tags_first(tags, &mut ambiguous),
+            trolley_wire: TrolleyWire::from_tags_first(tags, &mut ambiguous),
+            priority_road: PriorityRoad::from_tags_first(tags, &mut ambiguous),
+            passing_places: PassingPlaces::from_tags_first(tags, &mut ambiguous),
+            expressway: Expressway::from_tags_first(tags, &mut ambiguous),
+            dual_carriageway: DualCarriageway::from_tags_first(tags, &mut ambiguous),
+            cycle_street: CycleStreet::from_tags(tags),
+            placement: PlacementScheme::from_tags(tags),
+            ambiguous,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use osm_tags::Tags;
+
+    use crate::{keys, Highway, HighwayImportance, HighwayType, Schemes, Surface};
+
+    #[test]
+    fn test_first_value() {
+        let tags = Tags::from_pairs([("surface", "asphalt;concrete"), ("lit", "yes")]).unwrap();
+        let schemes = Schemes::from_tags(&tags);
+        assert_eq!(schemes.surface.unwrap(), Some(Surface::Asphalt));
+        assert_eq!(schemes.ambiguous, [(keys::SURFACE, "asphalt;concrete")]);
+    }
+
+    #[test]
+    fn test_first_value_highway() {
+        let tags = Tags::from_pair("highway", "secondary;tertiary");
+        let schemes = Schemes::from_tags(&tags);
+        assert_eq!(
+            schemes.highway.unwrap(),
+            Some(Highway::active(HighwayType::Classified(
+                HighwayImportance::Secondary
+            )))
+        );
+        assert_eq!(schemes.ambiguous, [(keys::HIGHWAY, "secondary;tertiary")]);
+
+        let tags = Tags::from_pairs([
+            ("highway", "construction;footway"),
+            ("construction", "primary"),
+        ])
+        .unwrap();
+        let schemes = Schemes::from_tags(&tags);
+        assert!(schemes.highway.unwrap().unwrap().is_construction());
+        assert_eq!(schemes.ambiguous, [(keys::HIGHWAY, "construction;footway")]);
+
+        let tags = Tags::from_pair("highway", "lava;secondary");
+        let schemes = Schemes::from_tags(&tags);
+        assert!(schemes.highway.is_err());
+        assert!(schemes.ambiguous.is_empty());
+    }
+
+    #[test]
+    fn test_first_value_unknown() {
+        let tags = Tags::from_pair("surface", "lava;asphalt");
+        let schemes = Schemes::from_tags(&tags);
+        assert!(schemes.surface.is_err());
+        assert!(schemes.ambiguous.is_empty());
+    }
+}
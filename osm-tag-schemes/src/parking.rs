@@ -0,0 +1,41 @@
+use strum::{EnumString, IntoStaticStr};
+
+use crate::ParkingPosition;
+
+/// Position of parking along the side of the road, from `parking:SIDE=*`,
+/// which replaces `parking:lane:SIDE=*`
+///
+/// <https://wiki.openstreetmap.org/wiki/Key:parking>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum Parking {
+    /// On the carriageway
+    Lane,
+    /// In a lay-by or parking bay beside the carriageway
+    StreetSide,
+    /// On the kerb, without using the carriageway
+    OnKerb,
+    /// Partly on the kerb and partly on the carriageway
+    HalfOnKerb,
+    /// On the shoulder of the road
+    Shoulder,
+    /// Parking of an unknown position
+    Yes,
+    No,
+    /// Parking is mapped as a separate area or way
+    Separate,
+}
+
+impl Parking {
+    /// Where cars are parked relative to the carriageway, if known
+    #[must_use]
+    pub fn position(self) -> Option<ParkingPosition> {
+        match self {
+            Self::Lane => Some(ParkingPosition::OnStreet),
+            Self::StreetSide => Some(ParkingPosition::StreetSide),
+            Self::OnKerb => Some(ParkingPosition::OnKerb),
+            Self::HalfOnKerb => Some(ParkingPosition::HalfOnKerb),
+            Self::Shoulder | Self::Yes | Self::No | Self::Separate => None,
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use strum::{EnumString, IntoStaticStr};
+
+/// Who may park, and how, from `parking:condition:SIDE=*`
+///
+/// <https://wiki.openstreetmap.org/wiki/Key:parking:condition>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ParkingCondition {
+    /// Parking is free of charge
+    Free,
+    /// Parking requires a ticket, paid for at a machine or by phone
+    Ticket,
+    /// Parking for a limited time, shown with a parking disc
+    Disc,
+    /// Parking for residents with a permit
+    Residents,
+    /// Parking for customers of nearby businesses
+    Customers,
+    /// Parking for the owner and those they permit
+    Private,
+    /// Parking for disabled people with a permit
+    Disabled,
+    /// Only for loading and unloading
+    Loading,
+    /// Stopping is allowed, but parking is not
+    NoParking,
+    /// Neither stopping nor parking are allowed
+    NoStopping,
+}
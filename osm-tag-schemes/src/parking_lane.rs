@@ -0,0 +1,36 @@
+use strum::{EnumString, IntoStaticStr};
+
+use crate::ParkingOrientation;
+
+/// Parking along the side of the road, from `parking:lane:SIDE=*`
+///
+/// <https://wiki.openstreetmap.org/wiki/Key:parking:lane>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum ParkingLane {
+    Parallel,
+    Diagonal,
+    Perpendicular,
+    /// Stopping is allowed, but parking is not
+    NoParking,
+    /// Neither stopping nor parking are allowed
+    NoStopping,
+    /// Stopping and parking are not allowed, to keep the way clear for emergency vehicles
+    FireLane,
+    No,
+    /// Parking is mapped as a separate area or way
+    Separate,
+}
+
+impl ParkingLane {
+    /// The orientation of the parked cars, if there is parking
+    #[must_use]
+    pub fn orientation(self) -> Option<ParkingOrientation> {
+        match self {
+            Self::Parallel => Some(ParkingOrientation::Parallel),
+            Self::Diagonal => Some(ParkingOrientation::Diagonal),
+            Self::Perpendicular => Some(ParkingOrientation::Perpendicular),
+            Self::NoParking | Self::NoStopping | Self::FireLane | Self::No | Self::Separate => None,
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use strum::{EnumString, IntoStaticStr};
+
+/// How cars are parked relative to the carriageway
+///
+/// <https://wiki.openstreetmap.org/wiki/Key:parking:lane#Parking_orientation>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ParkingOrientation {
+    /// In line with the carriageway, one car behind the other
+    Parallel,
+    /// At an angle to the carriageway
+    Diagonal,
+    /// At a right angle to the carriageway, side by side
+    Perpendicular,
+}
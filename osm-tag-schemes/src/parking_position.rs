@@ -0,0 +1,20 @@
+use strum::{EnumString, IntoStaticStr};
+
+/// Where cars are parked relative to the carriageway,
+/// from `parking:lane:SIDE:ORIENTATION=*`
+///
+/// <https://wiki.openstreetmap.org/wiki/Key:parking:lane#Parking_position>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ParkingPosition {
+    /// On the carriageway
+    OnStreet,
+    /// Partly on the kerb and partly on the carriageway
+    HalfOnKerb,
+    /// On the kerb, without using the carriageway
+    OnKerb,
+    /// In a lay-by or parking bay beside the carriageway
+    StreetSide,
+}
@@ -0,0 +1,19 @@
+use strum::{EnumString, IntoStaticStr};
+
+use crate::{keys, FromTagsDefault};
+
+/// Places along a single track road where opposing traffic can pass
+///
+/// <https://wiki.openstreetmap.org/wiki/Key:passing_places>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum PassingPlaces {
+    Yes,
+    No,
+}
+
+impl FromTagsDefault for PassingPlaces {
+    const KEY: osm_tags::TagKey = keys::PASSING_PLACES;
+}
@@ -0,0 +1,147 @@
+use core::num::NonZeroUsize;
+
+use osm_tags::TagsRef;
+use strum::ParseError;
+
+use crate::{keys, FromTags, TagError};
+
+/// Where the OSM way is positioned in the cross-section,
+/// lanes are numbered from 1, left to right in the direction of travel
+/// <https://wiki.openstreetmap.org/wiki/Proposed_features/placement>
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Placement {
+    /// The way gradually changes position
+    Transition,
+    LeftOf(NonZeroUsize),
+    MiddleOf(NonZeroUsize),
+    RightOf(NonZeroUsize),
+}
+
+impl core::str::FromStr for Placement {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "transition" {
+            return Ok(Self::Transition);
+        }
+        let (position, lane) = s.split_once(':').ok_or(ParseError::VariantNotFound)?;
+        let lane = lane.parse().map_err(|_e| ParseError::VariantNotFound)?;
+        match position {
+            "left_of" => Ok(Self::LeftOf(lane)),
+            "middle_of" => Ok(Self::MiddleOf(lane)),
+            "right_of" => Ok(Self::RightOf(lane)),
+            _ => Err(ParseError::VariantNotFound),
+        }
+    }
+}
+
+impl core::fmt::Display for Placement {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Transition => write!(f, "transition"),
+            Self::LeftOf(lane) => write!(f, "left_of:{lane}"),
+            Self::MiddleOf(lane) => write!(f, "middle_of:{lane}"),
+            Self::RightOf(lane) => write!(f, "right_of:{lane}"),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Placement {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Placement {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// `placement=*`, `placement:forward=*`, and `placement:backward=*`,
+/// with `placement:start=*` and `placement:end=*` for `placement=transition`
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlacementScheme {
+    /// Lanes are counted in the direction of the way
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub both: Option<Placement>,
+    /// Only forward lanes are counted
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub forward: Option<Placement>,
+    /// Only backward lanes are counted, in the backward direction
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub backward: Option<Placement>,
+    /// Placement at the start of the way, lanes are counted in the direction of the way
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub start: Option<Placement>,
+    /// Placement at the end of the way, lanes are counted in the direction of the way
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub end: Option<Placement>,
+}
+
+impl PlacementScheme {
+    /// Get Placement From Tags
+    ///
+    /// # Errors
+    ///
+    /// If a placement tag value is unknown
+    pub fn from_tags<'tag>(tags: &'tag TagsRef<'_>) -> Result<Option<Self>, TagError<'tag>> {
+        let scheme = Self {
+            both: Placement::from_tags(tags, &keys::PLACEMENT).ok_with(keys::PLACEMENT)?,
+            forward: Placement::from_tags(tags, &keys::PLACEMENT_FORWARD)
+                .ok_with(keys::PLACEMENT_FORWARD)?,
+            backward: Placement::from_tags(tags, &keys::PLACEMENT_BACKWARD)
+                .ok_with(keys::PLACEMENT_BACKWARD)?,
+            start: Placement::from_tags(tags, &keys::PLACEMENT_START)
+                .ok_with(keys::PLACEMENT_START)?,
+            end: Placement::from_tags(tags, &keys::PLACEMENT_END).ok_with(keys::PLACEMENT_END)?,
+        };
+        Ok((scheme != Self::default()).then_some(scheme))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::num::NonZeroUsize;
+
+    use osm_tags::Tags;
+
+    use super::{Placement, PlacementScheme};
+
+    #[test]
+    fn test_placement() {
+        let lane = NonZeroUsize::new(2).unwrap();
+        assert_eq!("left_of:2".parse(), Ok(Placement::LeftOf(lane)));
+        assert_eq!("middle_of:2".parse(), Ok(Placement::MiddleOf(lane)));
+        assert_eq!("right_of:2".parse(), Ok(Placement::RightOf(lane)));
+        assert_eq!("transition".parse(), Ok(Placement::Transition));
+        assert!("left_of:0".parse::<Placement>().is_err());
+        assert!("above:1".parse::<Placement>().is_err());
+        assert_eq!(Placement::RightOf(lane).to_string(), "right_of:2");
+    }
+
+    #[test]
+    fn test_placement_scheme() {
+        let tags = Tags::from_pair("placement:backward", "left_of:1");
+        let scheme = PlacementScheme::from_tags(&tags).unwrap().unwrap();
+        assert_eq!(scheme.both, None);
+        assert_eq!(scheme.forward, None);
+        assert_eq!(
+            scheme.backward,
+            Some(Placement::LeftOf(NonZeroUsize::new(1).unwrap()))
+        );
+        assert!(PlacementScheme::from_tags(&Tags::default())
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,31 @@
+use strum::{EnumString, IntoStaticStr};
+
+use crate::{keys, FromTagsDefault};
+
+/// Priority over the crossing roads at junctions
+///
+/// <https://wiki.openstreetmap.org/wiki/Key:priority_road>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum PriorityRoad {
+    /// Priority is signposted
+    Designated,
+    /// Priority without signposting, e.g. from local rules or road markings
+    YesUnposted,
+    /// Priority ends along the way
+    End,
+}
+
+impl PriorityRoad {
+    /// The road has priority along the whole way
+    #[must_use]
+    pub fn has_priority(self) -> bool {
+        matches!(self, Self::Designated | Self::YesUnposted)
+    }
+}
+
+impl FromTagsDefault for PriorityRoad {
+    const KEY: osm_tags::TagKey = keys::PRIORITY_ROAD;
+}
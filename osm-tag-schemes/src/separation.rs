@@ -0,0 +1,56 @@
+use strum::{EnumString, IntoStaticStr};
+
+use crate::{keys, FromTagsDefault};
+
+/// Rumble strips milled or raised into the road surface
+///
+/// `yes` has rumble strips both along the centre line and the edges.
+///
+/// <https://wiki.openstreetmap.org/wiki/Key:rumble_strips>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum RumbleStrips {
+    Yes,
+    Centre,
+    Edge,
+    No,
+}
+
+impl RumbleStrips {
+    /// Along the centre line
+    #[must_use]
+    pub fn centre(self) -> bool {
+        matches!(self, Self::Yes | Self::Centre)
+    }
+
+    /// Along the edges of the carriageway
+    #[must_use]
+    pub fn edge(self) -> bool {
+        matches!(self, Self::Yes | Self::Edge)
+    }
+}
+
+impl FromTagsDefault for RumbleStrips {
+    const KEY: osm_tags::TagKey = keys::RUMBLE_STRIPS;
+}
+
+/// Physical separation between lanes, in addition to or instead of paint
+///
+/// <https://wiki.openstreetmap.org/wiki/Key:separation>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Separation {
+    RumbleStrip,
+    BottsDots,
+    RaisedSeparator,
+    Studs,
+    No,
+}
+
+impl FromTagsDefault for Separation {
+    const KEY: osm_tags::TagKey = keys::SEPARATION;
+}
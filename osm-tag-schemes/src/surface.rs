@@ -0,0 +1,44 @@
+use strum::{EnumString, IntoStaticStr};
+
+use crate::{keys, FromTagsDefault};
+
+/// <https://wiki.openstreetmap.org/wiki/Key:surface>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Surface {
+    // Paved
+    Paved,
+    Asphalt,
+    Chipseal,
+    Concrete,
+    #[strum(serialize = "concrete:lanes")]
+    #[cfg_attr(feature = "serde", serde(rename = "concrete:lanes"))]
+    ConcreteLanes,
+    #[strum(serialize = "concrete:plates")]
+    #[cfg_attr(feature = "serde", serde(rename = "concrete:plates"))]
+    ConcretePlates,
+    PavingStones,
+    Sett,
+    UnhewnCobblestone,
+    Cobblestone,
+    Bricks,
+    Metal,
+    Wood,
+    // Unpaved
+    Unpaved,
+    Compacted,
+    FineGravel,
+    Gravel,
+    Pebblestone,
+    Ground,
+    Dirt,
+    Grass,
+    GrassPaver,
+    Sand,
+}
+
+impl FromTagsDefault for Surface {
+    const KEY: osm_tags::TagKey = keys::SURFACE;
+}
@@ -0,0 +1,49 @@
+use alloc::vec::Vec;
+
+/// A single traffic sign from `traffic_sign=*`
+///
+/// <https://wiki.openstreetmap.org/wiki/Key:traffic_sign>
+///
+/// ```
+/// use osm_tag_schemes::TrafficSign;
+/// let signs = TrafficSign::split("DE:245,1022-10;city_limit");
+/// assert_eq!(signs[0], TrafficSign { country: Some("DE"), code: "245" });
+/// assert_eq!(signs[1], TrafficSign { country: Some("DE"), code: "1022-10" });
+/// assert_eq!(signs[2], TrafficSign { country: None, code: "city_limit" });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrafficSign<'tag> {
+    /// ISO 3166-1 alpha-2 prefix, absent for human readable values
+    pub country: Option<&'tag str>,
+    /// Sign code within the country
+    pub code: &'tag str,
+}
+
+impl<'tag> TrafficSign<'tag> {
+    /// Split a `;` separated list of signs,
+    /// where signs of the same country may also be `,` separated after a single prefix.
+    #[must_use]
+    pub fn split(value: &'tag str) -> Vec<Self> {
+        value
+            .split(';')
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+            .flat_map(|part| {
+                let (country, codes) = match part.split_once(':') {
+                    Some((country, codes))
+                        if country.len() == 2
+                            && country.chars().all(|c| c.is_ascii_uppercase()) =>
+                    {
+                        (Some(country), codes)
+                    },
+                    _ => (None, part),
+                };
+                codes
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|code| !code.is_empty())
+                    .map(move |code| Self { country, code })
+            })
+            .collect()
+    }
+}
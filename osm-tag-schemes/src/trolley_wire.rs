@@ -0,0 +1,19 @@
+use strum::{EnumString, IntoStaticStr};
+
+use crate::{keys, FromTagsDefault};
+
+/// Overhead wires for trolleybuses
+///
+/// <https://wiki.openstreetmap.org/wiki/Key:trolley_wire>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum TrolleyWire {
+    Yes,
+    No,
+}
+
+impl FromTagsDefault for TrolleyWire {
+    const KEY: osm_tags::TagKey = keys::TROLLEY_WIRE;
+}
@@ -0,0 +1,25 @@
+use strum::{EnumString, IntoStaticStr};
+
+/// Direction marked on a lane for turning at the next junction,
+/// a value of `turn:lanes=*`
+///
+/// <https://wiki.openstreetmap.org/wiki/Key:turn>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum TurnDirection {
+    Left,
+    SlightLeft,
+    SharpLeft,
+    Through,
+    Right,
+    SlightRight,
+    SharpRight,
+    /// A U-turn
+    Reverse,
+    /// The lane ends, merging into the lane to its left
+    MergeToLeft,
+    /// The lane ends, merging into the lane to its right
+    MergeToRight,
+}
@@ -0,0 +1,85 @@
+use std::collections::BTreeMap;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use osm_tags::{TagKey, TagVal, Tags};
+
+/// Keys of a busy way, the first of them make up a way with fewer tags
+const KEYS: [&str; 30] = [
+    "highway",
+    "name",
+    "ref",
+    "lanes",
+    "maxspeed",
+    "surface",
+    "oneway",
+    "lit",
+    "sidewalk",
+    "cycleway:right",
+    "lanes:forward",
+    "lanes:backward",
+    "turn:lanes:forward",
+    "turn:lanes:backward",
+    "destination:lanes:forward",
+    "parking:lane:both",
+    "parking:condition:both",
+    "sidewalk:both:surface",
+    "cycleway:right:width",
+    "cycleway:left",
+    "width",
+    "smoothness",
+    "bus:lanes",
+    "psv:lanes",
+    "maxheight",
+    "maxweight",
+    "overtaking",
+    "placement",
+    "source:maxspeed",
+    "wikidata",
+];
+
+const SIZES: [usize; 4] = [5, 10, 20, 30];
+
+/// Keys looked up by the transform, including a key that is not tagged
+fn lookups(size: usize) -> impl Iterator<Item = &'static str> {
+    KEYS.iter().take(size).copied().chain(["shoulder"])
+}
+
+/// The sorted `Vec` of [`Tags`] compared to the `BTreeMap` it replaced,
+/// for the 5 to 30 tags typical of a way
+pub fn benchmark_tags(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tags_from_pairs");
+    for size in SIZES {
+        let pairs = &KEYS[..size];
+        group.bench_with_input(BenchmarkId::new("vec", size), pairs, |b, pairs| {
+            b.iter(|| Tags::from_pairs(pairs.iter().map(|key| (*key, "yes"))).unwrap());
+        });
+        group.bench_with_input(BenchmarkId::new("btree_map", size), pairs, |b, pairs| {
+            b.iter(|| {
+                pairs
+                    .iter()
+                    .map(|key| (TagKey::from(*key), TagVal::from("yes")))
+                    .collect::<BTreeMap<TagKey, TagVal>>()
+            });
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("tags_get");
+    for size in SIZES {
+        let tags = Tags::from_pairs(KEYS[..size].iter().map(|key| (*key, "yes"))).unwrap();
+        group.bench_with_input(BenchmarkId::new("vec", size), &tags, |b, tags| {
+            b.iter(|| lookups(size).filter_map(|key| tags.get(key)).count());
+        });
+        let map: BTreeMap<TagKey, TagVal> = KEYS[..size]
+            .iter()
+            .map(|key| (TagKey::from(*key), TagVal::from("yes")))
+            .collect();
+        group.bench_with_input(BenchmarkId::new("btree_map", size), &map, |b, map| {
+            b.iter(|| lookups(size).filter_map(|key| map.get(key)).count());
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_tags);
+criterion_main!(benches);
@@ -0,0 +1,143 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::Display;
+
+use crate::{Part, TagKey, TagVal, Tags, TagsRef};
+
+/// The changes from one set of tags to another, see [`TagsRef::diff`]
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug, Default)]
+pub struct TagsDiff {
+    /// Pairs only in the other tags
+    pub added: Tags,
+    /// Pairs only in the original tags
+    pub removed: Tags,
+    /// Keys in both, with the original value followed by the other value, ordered by key
+    pub changed: Vec<(TagKey, TagVal, TagVal)>,
+}
+
+impl TagsDiff {
+    /// If the tags are the same
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl Display for TagsDiff {
+    /// Describe the changes, e.g. `remove 'a=b', add 'c=d', change 'e=f' to 'e=g'`
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut separator = "";
+        if !self.removed.is_empty() {
+            write!(f, "remove '{}'", self.removed.to_vec().as_slice().join(" "))?;
+            separator = ", ";
+        }
+        if !self.added.is_empty() {
+            write!(
+                f,
+                "{}add '{}'",
+                separator,
+                self.added.to_vec().as_slice().join(" ")
+            )?;
+            separator = ", ";
+        }
+        for (key, from, to) in &self.changed {
+            write!(
+                f,
+                "{}change '{}={}' to '{}={}'",
+                separator,
+                key,
+                from.as_str(),
+                key,
+                to.as_str()
+            )?;
+            separator = ", ";
+        }
+        Ok(())
+    }
+}
+
+impl TagsRef<'_> {
+    /// The changes from these tags to the other tags
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use osm_tags::Tags;
+    /// let deprecated = Tags::from_str("cycleway=opposite_lane\noneway=yes").unwrap();
+    /// let suggested = Tags::from_str("cycleway:left=lane\noneway=yes").unwrap();
+    /// let diff = deprecated.diff(&suggested);
+    /// assert_eq!(diff.removed.to_vec(), ["cycleway=opposite_lane"]);
+    /// assert_eq!(diff.added.to_vec(), ["cycleway:left=lane"]);
+    /// assert!(diff.changed.is_empty());
+    /// assert_eq!(
+    ///     diff.to_string(),
+    ///     "remove 'cycleway=opposite_lane', add 'cycleway:left=lane'"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn diff(&self, other: &TagsRef<'_>) -> TagsDiff {
+        let mut diff = TagsDiff::default();
+        // Both are sorted by key, so are merged in a single pass
+        let mut ours = self.pairs.iter().peekable();
+        let mut theirs = other.pairs.iter().peekable();
+        loop {
+            let order = match (ours.peek(), theirs.peek()) {
+                (None, None) => break,
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (Some((our_key, _)), Some((their_key, _))) => {
+                    our_key.as_str().cmp(their_key.as_str())
+                },
+            };
+            match order {
+                Ordering::Less => {
+                    if let Some((key, val)) = ours.next() {
+                        diff.removed
+                            .pairs
+                            .push((Part::Owned(key.to_key()), Part::Owned(val.to_val())));
+                    }
+                },
+                Ordering::Greater => {
+                    if let Some((key, val)) = theirs.next() {
+                        diff.added
+                            .pairs
+                            .push((Part::Owned(key.to_key()), Part::Owned(val.to_val())));
+                    }
+                },
+                Ordering::Equal => {
+                    if let (Some((key, our_val)), Some((_, their_val))) =
+                        (ours.next(), theirs.next())
+                    {
+                        if our_val.as_str() != their_val.as_str() {
+                            diff.changed
+                                .push((key.to_key(), our_val.to_val(), their_val.to_val()));
+                        }
+                    }
+                },
+            }
+        }
+        diff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use crate::Tags;
+
+    #[test]
+    fn test_diff() {
+        let from = Tags::from_pairs([("a", "1"), ("b", "2"), ("c", "3")]).unwrap();
+        let to = Tags::from_pairs([("b", "2"), ("c", "4"), ("d", "5")]).unwrap();
+        let diff = from.diff(&to);
+        assert_eq!(diff.removed.to_str_pairs(), [("a", "1")]);
+        assert_eq!(diff.added.to_str_pairs(), [("d", "5")]);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(
+            diff.to_string(),
+            "remove 'a=1', add 'd=5', change 'c=3' to 'c=4'"
+        );
+        assert!(from.diff(&from).is_empty());
+    }
+}
@@ -1,6 +1,7 @@
-use std::borrow::Borrow;
-use std::fmt::Display;
-use std::ops::Deref;
+use alloc::string::String;
+use core::borrow::Borrow;
+use core::fmt::Display;
+use core::ops::Deref;
 
 use kstring::KString;
 
@@ -11,13 +12,39 @@ use kstring::KString;
 /// const example_key: TagKey = TagKey::from_static("example");
 /// assert_eq!(example_key.as_str(), "example");
 /// assert_eq!((example_key + "foo").as_str(), "example:foo");
+/// assert_eq!((example_key + "a_longer_suffix").as_str(), "example:a_longer_suffix");
 /// ```
 #[allow(clippy::module_name_repetitions)]
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct TagKey(KString);
 
+/// A `'static` [`TagKey`] built at compile time,
+/// from a key or from parts of a key that are joined with `:`,
+/// unlike `TagKey + &str` which may allocate at runtime.
+///
+/// The parts must be string literals.
+///
+/// ```
+/// use osm_tags::{tag_key, TagKey};
+/// const WIDTH: TagKey = tag_key!("cycleway:left:width");
+/// const BUFFER: TagKey = tag_key!("cycleway", "left", "buffer");
+/// assert_eq!(WIDTH.as_str(), "cycleway:left:width");
+/// assert_eq!(BUFFER.as_str(), "cycleway:left:buffer");
+/// assert_eq!(BUFFER, TagKey::from_static("cycleway") + "left" + "buffer");
+/// ```
+#[allow(clippy::module_name_repetitions)]
+#[macro_export]
+macro_rules! tag_key {
+    ($key:literal $(,)?) => {
+        $crate::TagKey::from_static($key)
+    };
+    ($key:literal $(, $part:literal)+ $(,)?) => {
+        $crate::TagKey::from_static(concat!($key $(, ":", $part)+))
+    };
+}
+
 impl Display for TagKey {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.0)
     }
 }
@@ -76,8 +103,8 @@ impl From<&'static str> for TagKey {
     }
 }
 
-impl std::str::FromStr for TagKey {
-    type Err = std::convert::Infallible;
+impl core::str::FromStr for TagKey {
+    type Err = core::convert::Infallible;
     #[inline]
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(Self(KString::from_ref(s)))
@@ -90,17 +117,37 @@ impl AsRef<str> for TagKey {
     }
 }
 
-impl<'any> std::ops::Add<&str> for &'any TagKey {
+/// Longest key that `KString` stores inline, without a heap allocation
+const INLINE_CAPACITY: usize = 15;
+
+impl<'any> core::ops::Add<&str> for &'any TagKey {
     type Output = TagKey;
     fn add(self, other: &str) -> Self::Output {
-        let mut s = self.to_string();
+        let len = self.len() + 1 + other.len();
+        if len <= INLINE_CAPACITY {
+            // Concatenate on the stack, such that short keys are never allocated
+            let mut buf = [0_u8; INLINE_CAPACITY];
+            for (dst, src) in buf
+                .iter_mut()
+                .zip(self.as_bytes().iter().chain(b":").chain(other.as_bytes()))
+            {
+                *dst = *src;
+            }
+            let s = buf
+                .get(..len)
+                .and_then(|bytes| core::str::from_utf8(bytes).ok())
+                .expect("concatenated keys are valid utf8");
+            return Self::Output::from_ref(s);
+        }
+        let mut s = String::with_capacity(len);
+        s.push_str(self.as_str());
         s.push(':');
         s.push_str(other);
         Self::Output::from_string(s)
     }
 }
 
-impl<'any> std::ops::Add for &'any TagKey {
+impl<'any> core::ops::Add for &'any TagKey {
     type Output = TagKey;
     fn add(self, other: Self) -> Self::Output {
         self.add(other.as_str())
@@ -109,16 +156,73 @@ impl<'any> std::ops::Add for &'any TagKey {
 
 // To satisfy the `+` API
 
-impl std::ops::Add<&str> for TagKey {
+impl core::ops::Add<&str> for TagKey {
     type Output = Self;
     fn add(self, other: &str) -> Self::Output {
         (&self).add(other)
     }
 }
 
-impl std::ops::Add for TagKey {
+impl core::ops::Add for TagKey {
     type Output = Self;
     fn add(self, other: Self) -> Self::Output {
         (&self).add(&other)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::INLINE_CAPACITY;
+    use crate::{TagKey, Tags};
+
+    #[test]
+    fn test_tag_key_macro() {
+        const KEY: TagKey = tag_key!("cycleway");
+        const JOINED: TagKey = tag_key!("cycleway", "left", "width");
+        assert_eq!(KEY, TagKey::from_static("cycleway"));
+        assert_eq!(tag_key!("cycleway",), TagKey::from_static("cycleway"));
+        assert_eq!(
+            tag_key!("cycleway:left"),
+            TagKey::from_static("cycleway:left")
+        );
+        assert_eq!(JOINED, TagKey::from_static("cycleway:left:width"));
+        assert_eq!(
+            tag_key!("cycleway", "left", "width",),
+            TagKey::from_static("cycleway:left:width")
+        );
+        assert_eq!(tag_key!("cycleway:left", "width"), JOINED);
+        assert_eq!(JOINED, KEY + "left" + "width");
+    }
+
+    #[test]
+    fn test_add() {
+        const KEY: TagKey = TagKey::from_static("cycleway");
+        // Shorter than, as long as, and longer than the inline capacity
+        for len in 0..=INLINE_CAPACITY {
+            let suffix = "x".repeat(len);
+            let expected = format!("cycleway:{suffix}");
+            assert_eq!((&KEY + suffix.as_str()).as_str(), expected);
+            assert_eq!(KEY + suffix.as_str(), TagKey::from_string(expected));
+        }
+        assert_eq!((TagKey::from_static("") + "").as_str(), ":");
+        assert_eq!((TagKey::from_static("a") + "é").as_str(), "a:é");
+
+        // Concatenated keys find the same pairs as their strings
+        let tags = Tags::from_pairs([
+            ("cycleway:left", "lane"),
+            ("cycleway:left:width", "1.5"),
+            ("cycleway:left:separation", "kerb"),
+        ])
+        .unwrap();
+        for (key, val) in tags.iter() {
+            let parts: Vec<&str> = key.split(':').collect();
+            let (first, rest) = parts.split_first().unwrap();
+            let concatenated = rest
+                .iter()
+                .fold(TagKey::from_ref(first), |key, part| key + *part);
+            assert_eq!(tags.get(&concatenated), Some(val));
+            assert_eq!(tags.get(key), Some(val));
+        }
+        assert_eq!(tags.get(&(KEY + "right")), None);
+    }
+}
@@ -0,0 +1,147 @@
+/// Slots of the hash table, a power of two that is at least twice the number of keys
+const SLOTS: usize = 256;
+
+/// Marks a slot without a key
+const EMPTY: u8 = u8::MAX;
+
+/// Seeds that are tried before giving up on finding a perfect hash
+const MAX_SEED: u32 = 1024;
+
+/// A fixed set of keys, such as those understood by a transform,
+/// with a perfect hash found at compile time.
+///
+/// A key is looked up with a single hash and at most one string comparison,
+/// instead of comparing it to each key of the set,
+/// and is interned as its index in the set.
+///
+/// ```
+/// use osm_tags::KeySet;
+/// const KEYS: KeySet<3> = KeySet::new(["highway", "lanes", "oneway"]);
+/// assert_eq!(KEYS.get("lanes"), Some(1));
+/// assert_eq!(KEYS.get("lanes:forward"), None);
+/// assert!(KEYS.contains("oneway"));
+/// assert_eq!(KEYS.keys(), &["highway", "lanes", "oneway"]);
+/// ```
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug)]
+pub struct KeySet<const N: usize> {
+    keys: [&'static str; N],
+    seed: u32,
+    /// The index of the key in each slot
+    slots: [u8; SLOTS],
+}
+
+impl<const N: usize> KeySet<N> {
+    /// Find a perfect hash of the keys
+    ///
+    /// # Panics
+    ///
+    /// If there are too many keys, if a key is repeated, or if no perfect hash is found,
+    /// which fails the build when used in a `const`.
+    #[must_use]
+    #[allow(
+        clippy::indexing_slicing,
+        clippy::as_conversions,
+        clippy::cast_possible_truncation
+    )]
+    pub const fn new(keys: [&'static str; N]) -> Self {
+        assert!(N * 2 <= SLOTS, "too many keys for a key set");
+        let mut seed = 0;
+        while seed < MAX_SEED {
+            let mut slots = [EMPTY; SLOTS];
+            let mut index = 0;
+            let mut perfect = true;
+            while index < N {
+                let slot = slot(seed, keys[index]);
+                if slots[slot] != EMPTY {
+                    assert!(
+                        !str_eq(keys[slots[slot] as usize], keys[index]),
+                        "repeated key in a key set"
+                    );
+                    perfect = false;
+                    break;
+                }
+                slots[slot] = index as u8;
+                index += 1;
+            }
+            if perfect {
+                return Self { keys, seed, slots };
+            }
+            seed += 1;
+        }
+        panic!("no perfect hash for the key set");
+    }
+
+    /// The interned index of the key in the set
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<usize> {
+        let index = usize::from(*self.slots.get(slot(self.seed, key))?);
+        (self.keys.get(index)? == &key).then(|| index)
+    }
+
+    /// If the key is in the set
+    #[must_use]
+    pub fn contains(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// The keys, in the order of their interned index
+    #[must_use]
+    pub fn keys(&self) -> &[&'static str; N] {
+        &self.keys
+    }
+}
+
+/// The slot of the key, from a seeded FNV-1a hash
+#[allow(clippy::indexing_slicing, clippy::as_conversions)]
+const fn slot(seed: u32, key: &str) -> usize {
+    let bytes = key.as_bytes();
+    let mut hash = 0x811c_9dc5_u32 ^ seed.wrapping_mul(0x9e37_79b9);
+    let mut index = 0;
+    while index < bytes.len() {
+        hash ^= bytes[index] as u32;
+        hash = hash.wrapping_mul(0x0100_0193);
+        index += 1;
+    }
+    (hash ^ (hash >> 16_u32)) as usize % SLOTS
+}
+
+#[allow(clippy::indexing_slicing)]
+const fn str_eq(left: &str, right: &str) -> bool {
+    let (left, right) = (left.as_bytes(), right.as_bytes());
+    if left.len() != right.len() {
+        return false;
+    }
+    let mut index = 0;
+    while index < left.len() {
+        if left[index] != right[index] {
+            return false;
+        }
+        index += 1;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KeySet;
+
+    #[test]
+    fn test_key_set() {
+        const KEYS: KeySet<4> = KeySet::new(["cycleway", "cycleway:left", "lanes", "oneway"]);
+        for (index, key) in KEYS.keys().iter().enumerate() {
+            assert_eq!(KEYS.get(key), Some(index));
+        }
+        for key in [
+            "",
+            "cycleway_foo",
+            "cycleway:",
+            "cyclewa",
+            "lane",
+            "lanes:forward",
+            "highway",
+        ] {
+            assert!(!KEYS.contains(key), "{key}");
+        }
+    }
+}
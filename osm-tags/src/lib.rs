@@ -2,6 +2,7 @@
 //!
 //! Provides `Tags`, `TagKey`, and `TagVal` structures to represent and help manipulate OpenStreetMap tags
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(explicit_outlives_requirements)]
 #![warn(missing_abi)]
 #![deny(non_ascii_idents)]
@@ -45,11 +46,14 @@
     clippy::use_debug
 )]
 
-use std::borrow::Borrow;
-use std::collections::btree_map::Entry;
-use std::collections::BTreeMap;
-use std::hash::Hash;
-use std::str::FromStr;
+extern crate alloc;
+
+use alloc::borrow::ToOwned;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::str::FromStr;
+use core::sync::atomic::{AtomicBool, Ordering};
 
 mod key;
 pub use key::TagKey;
@@ -57,6 +61,12 @@ pub use key::TagKey;
 mod val;
 pub use val::TagVal;
 
+mod diff;
+pub use diff::TagsDiff;
+
+mod key_set;
+pub use key_set::KeySet;
+
 #[derive(Debug, Clone)]
 pub struct DuplicateKeyError(TagKey);
 
@@ -66,27 +76,120 @@ impl From<String> for DuplicateKeyError {
     }
 }
 
-impl std::fmt::Display for DuplicateKeyError {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for DuplicateKeyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(f, "duplicate tag key {}", self.0.as_str())
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for DuplicateKeyError {}
 
-/// A map from string keys to string values. This makes copies of strings for
-/// convenience; don't use in performance sensitive contexts.
+/// A map from string keys to string values, that owns its strings.
+/// This makes copies of strings for convenience,
+/// although short keys are stored inline without allocating.
+/// To avoid the copies, borrow the strings with [`TagsRef::from_borrowed_pairs`].
+pub type Tags = TagsRef<'static>;
+
+/// A map from string keys to string values,
+/// each either owned or borrowed for `'tags`, see [`Tags`].
+/// To process many ways, reuse the storage with [`TagsRef::assign_pairs`].
 //
-// BTreeMap chosen for deterministic serialization.
+// Pairs are kept sorted by key for deterministic serialization.
 // We often need to compare output directly, so cannot tolerate reordering
 //
-// TODO: fix this in the serialization by having the keys sorted.
+// A sorted Vec rather than a BTreeMap, as ways typically have 5 to 30 tags,
+// for which a binary search over contiguous memory is faster than walking tree nodes,
+// and most of the transform only reads the tags, see `benches/benchmark_tags.rs`.
+#[allow(clippy::module_name_repetitions)]
 #[derive(Clone, Debug, Default)]
-pub struct Tags {
-    map: BTreeMap<TagKey, TagVal>,
+pub struct TagsRef<'tags> {
+    pairs: Vec<(Part<'tags, TagKey>, Part<'tags, TagVal>)>,
+    /// Whether each pair has been read, if tracked, see [`Tags::with_read_tracking`]
+    reads: Option<Reads>,
+}
+
+/// A key or value, owned or borrowed
+#[derive(Clone, Debug)]
+enum Part<'tags, T> {
+    Owned(T),
+    Borrowed(&'tags str),
+}
+
+impl<T: AsRef<str>> Part<'_, T> {
+    fn as_str(&self) -> &str {
+        match self {
+            Self::Owned(owned) => owned.as_ref(),
+            Self::Borrowed(borrowed) => borrowed,
+        }
+    }
 }
 
-impl Tags {
+impl<T> Part<'_, T> {
+    /// An owned part, that borrows for any lifetime
+    fn into_owned_part<'other>(self) -> Part<'other, T>
+    where
+        T: From<String>,
+    {
+        match self {
+            Self::Owned(owned) => Part::Owned(owned),
+            Self::Borrowed(borrowed) => Part::Owned(T::from(borrowed.to_owned())),
+        }
+    }
+}
+
+impl Part<'_, TagKey> {
+    fn to_key(&self) -> TagKey {
+        match self {
+            Self::Owned(key) => key.clone(),
+            Self::Borrowed(key) => TagKey::from_ref(key),
+        }
+    }
+}
+
+impl Part<'_, TagVal> {
+    fn to_val(&self) -> TagVal {
+        match self {
+            Self::Owned(val) => val.clone(),
+            Self::Borrowed(val) => TagVal::from(*val),
+        }
+    }
+}
+
+/// Flags alongside the pairs, atomic so that tags can be read from many threads
+#[derive(Debug, Default)]
+struct Reads(Vec<AtomicBool>);
+
+impl Reads {
+    fn new(len: usize) -> Self {
+        Self((0..len).map(|_| AtomicBool::new(false)).collect())
+    }
+
+    fn mark(&self, index: usize) {
+        if let Some(read) = self.0.get(index) {
+            read.store(true, Ordering::Relaxed);
+        }
+    }
+
+    fn is_read(&self, index: usize) -> bool {
+        self.0
+            .get(index)
+            .map_or(false, |read| read.load(Ordering::Relaxed))
+    }
+}
+
+impl Clone for Reads {
+    fn clone(&self) -> Self {
+        Self(
+            self.0
+                .iter()
+                .map(|read| AtomicBool::new(read.load(Ordering::Relaxed)))
+                .collect(),
+        )
+    }
+}
+
+impl<'tags> TagsRef<'tags> {
     /// Construct from iterator of pairs
     ///
     /// # Errors
@@ -99,17 +202,115 @@ impl Tags {
         K: Into<TagKey>,
         V: Into<TagVal>,
     {
-        let mut map: BTreeMap<TagKey, TagVal> = BTreeMap::new();
-        for tag_pair in tags {
-            let key: TagKey = tag_pair.0.into();
-            let val: TagVal = tag_pair.1.into();
-            // This may become cleaner with https://github.com/rust-lang/rust/issues/82766
-            match map.entry(key) {
-                Entry::Vacant(entry) => entry.insert(val),
-                Entry::Occupied(entry) => return Err(DuplicateKeyError(entry.remove_entry().0)),
-            };
+        let mut tags = Self {
+            pairs: tags
+                .into_iter()
+                .map(|(key, val)| (Part::Owned(key.into()), Part::Owned(val.into())))
+                .collect(),
+            reads: None,
+        };
+        tags.sort()?;
+        Ok(tags)
+    }
+
+    /// Construct from borrowed pairs, such as those of a way read from a PBF file,
+    /// without copying the keys and values
+    ///
+    /// # Errors
+    ///
+    /// If a duplicate key is provided.
+    ///
+    /// ```
+    /// use osm_tags::TagsRef;
+    /// let pairs = vec![(String::from("lanes"), String::from("2")), (String::from("highway"), String::from("primary"))];
+    /// let tags = TagsRef::from_borrowed_pairs(pairs.iter().map(|(k, v)| (k.as_str(), v.as_str()))).unwrap();
+    /// assert_eq!(tags.to_str_pairs(), [("highway", "primary"), ("lanes", "2")]);
+    /// ```
+    pub fn from_borrowed_pairs<I>(pairs: I) -> Result<Self, DuplicateKeyError>
+    where
+        I: IntoIterator<Item = (&'tags str, &'tags str)>,
+    {
+        let mut tags = Self {
+            pairs: pairs
+                .into_iter()
+                .map(|(key, val)| (Part::Borrowed(key), Part::Borrowed(val)))
+                .collect(),
+            reads: None,
+        };
+        tags.sort()?;
+        Ok(tags)
+    }
+
+    /// Replace the tags with borrowed pairs, such as those of a way read from a PBF file,
+    /// without copying the keys and values, and reusing the storage of the previous tags,
+    /// which may borrow for a different lifetime
+    ///
+    /// # Errors
+    ///
+    /// If a duplicate key is provided.
+    ///
+    /// ```
+    /// use osm_tags::Tags;
+    /// let mut tags = Tags::default();
+    /// for way in [
+    ///     vec![("highway", "primary"), ("lanes", "4")],
+    ///     vec![("highway", "service")],
+    ///     vec![("highway", "residential"), ("lanes", "2"), ("name", "Main Street")],
+    /// ] {
+    ///     let pairs: Vec<(String, String)> = way.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+    ///     let borrowed = tags.assign_pairs(pairs.iter().map(|(k, v)| (k.as_str(), v.as_str()))).unwrap();
+    ///     assert_eq!(borrowed.to_str_pairs(), way);
+    ///     tags = borrowed.assign_pairs([]).unwrap();
+    /// }
+    /// ```
+    pub fn assign_pairs<'other, I>(self, pairs: I) -> Result<TagsRef<'other>, DuplicateKeyError>
+    where
+        I: IntoIterator<Item = (&'other str, &'other str)>,
+    {
+        let mut storage = self.pairs;
+        storage.clear();
+        // Collecting an empty vector into one of a type with the same layout reuses its allocation
+        let mut storage: Vec<(Part<'other, TagKey>, Part<'other, TagVal>)> = storage
+            .into_iter()
+            .map(|(key, val)| (key.into_owned_part(), val.into_owned_part()))
+            .collect();
+        storage.extend(
+            pairs
+                .into_iter()
+                .map(|(key, val)| (Part::Borrowed(key), Part::Borrowed(val))),
+        );
+        let mut tags = TagsRef {
+            pairs: storage,
+            reads: None,
+        };
+        tags.sort()?;
+        Ok(tags)
+    }
+
+    /// Copy any borrowed keys and values, such that the tags outlive what they borrowed
+    #[must_use]
+    pub fn into_owned(self) -> Tags {
+        TagsRef {
+            pairs: self
+                .pairs
+                .iter()
+                .map(|(key, val)| (Part::Owned(key.to_key()), Part::Owned(val.to_val())))
+                .collect(),
+            reads: self.reads,
+        }
+    }
+
+    /// Sort the pairs by key, checking that the keys are unique
+    fn sort(&mut self) -> Result<(), DuplicateKeyError> {
+        self.pairs
+            .sort_unstable_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+        if let Some(duplicate) = self.pairs.windows(2).find_map(|window| match window {
+            [(a, _), (b, _)] if a.as_str() == b.as_str() => Some(a),
+            _ => None,
+        }) {
+            return Err(DuplicateKeyError(duplicate.to_key()));
         }
-        Ok(Self { map })
+        Ok(())
     }
 
     /// Construct from pair
@@ -119,25 +320,27 @@ impl Tags {
         K: Into<TagKey>,
         V: Into<TagVal>,
     {
-        let mut map = BTreeMap::default();
-        let duplicate_val = map.insert(key.into(), val.into());
-        debug_assert!(duplicate_val.is_none());
-        Self { map }
+        Self {
+            pairs: vec![(Part::Owned(key.into()), Part::Owned(val.into()))],
+            reads: None,
+        }
     }
 
     /// Expose data as vector of pairs
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.map.is_empty()
+        self.pairs.is_empty()
     }
 
     /// Expose data as vector of pairs
     #[must_use]
     pub fn to_str_pairs(&self) -> Vec<(&str, &str)> {
-        self.map
-            .iter()
-            .map(|(k, v)| (k.as_str(), v.as_str()))
-            .collect()
+        self.iter().collect()
+    }
+
+    /// Iterate over pairs, ordered by key
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.pairs.iter().map(|(k, v)| (k.as_str(), v.as_str()))
     }
 
     /// Vector of `=` separated strings
@@ -153,10 +356,93 @@ impl Tags {
     /// Get value from tags given a key
     pub fn get<Q>(&self, q: &Q) -> Option<&str>
     where
-        TagKey: Borrow<Q>,
-        Q: Ord + Hash + Eq + ?Sized,
+        Q: AsRef<str> + ?Sized,
+    {
+        let index = self.position(q).ok()?;
+        self.mark_read(index);
+        self.pairs.get(index).map(|(_key, val)| val.as_str())
+    }
+
+    /// Get the values of a `;` separated list given a key,
+    /// trimmed and without empty values, or none if the key does not exist.
+    ///
+    /// The values are in the order they are tagged,
+    /// where the first is often the predominant one, e.g. `surface=asphalt;concrete`.
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use osm_tags::Tags;
+    /// let tags = Tags::from_str("cycleway=lane; track\nsurface=asphalt").unwrap();
+    /// assert_eq!(tags.get_values("cycleway"), ["lane", "track"]);
+    /// assert_eq!(tags.get_values("surface"), ["asphalt"]);
+    /// assert!(tags.get_values("sidewalk").is_empty());
+    /// ```
+    pub fn get_values<Q>(&self, q: &Q) -> Vec<&str>
+    where
+        Q: AsRef<str> + ?Sized,
+    {
+        self.get(q).map_or_else(Vec::new, |val| {
+            val.split(';')
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .collect()
+        })
+    }
+
+    /// Start recording which keys are read by lookups,
+    /// such as [`Tags::get`] or [`Tags::iter_with_prefix`], but not by [`Tags::iter`],
+    /// to find the tags that were never consulted, see [`Tags::unread`]
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use osm_tags::Tags;
+    /// let tags = Tags::from_str("highway=secondary\ncylceway=lane")
+    ///     .unwrap()
+    ///     .with_read_tracking();
+    /// assert_eq!(tags.get("highway"), Some("secondary"));
+    /// assert_eq!(tags.get("cycleway"), None);
+    /// let unread: Vec<_> = tags.unread().collect();
+    /// assert_eq!(unread, [("cylceway", "lane")]);
+    /// ```
+    #[must_use]
+    pub fn with_read_tracking(mut self) -> Self {
+        self.reads = Some(Reads::new(self.pairs.len()));
+        self
+    }
+
+    /// Iterate over the pairs that have not been read since [`Tags::with_read_tracking`],
+    /// ordered by key, or over none if reads are not tracked
+    pub fn unread(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        let reads = self.reads.as_ref();
+        self.pairs
+            .iter()
+            .enumerate()
+            .filter(move |(index, _pair)| reads.map_or(false, |reads| !reads.is_read(*index)))
+            .map(|(_index, (key, val))| (key.as_str(), val.as_str()))
+    }
+
+    fn mark_read(&self, index: usize) {
+        if let Some(reads) = &self.reads {
+            reads.mark(index);
+        }
+    }
+
+    fn mark_read_range(&self, range: core::ops::Range<usize>) {
+        if let Some(reads) = &self.reads {
+            for index in range {
+                reads.mark(index);
+            }
+        }
+    }
+
+    /// Index of the key if it exists, or where it would be inserted
+    fn position<Q>(&self, q: &Q) -> Result<usize, usize>
+    where
+        Q: AsRef<str> + ?Sized,
     {
-        self.map.get(q).map(TagVal::as_str)
+        let q = q.as_ref();
+        self.pairs
+            .binary_search_by(|(key, _val)| key.as_str().cmp(q))
     }
 
     /// Return if tags key has value,
@@ -164,8 +450,7 @@ impl Tags {
     #[must_use]
     pub fn is<Q>(&self, q: &Q, v: &str) -> bool
     where
-        TagKey: Borrow<Q>,
-        Q: Ord + Hash + Eq + ?Sized,
+        Q: AsRef<str> + ?Sized,
     {
         self.get(q) == Some(v)
     }
@@ -175,8 +460,7 @@ impl Tags {
     #[must_use]
     pub fn is_any<Q>(&self, q: &Q, values: &[&str]) -> bool
     where
-        TagKey: Borrow<Q>,
-        Q: Ord + Hash + Eq + ?Sized,
+        Q: AsRef<str> + ?Sized,
     {
         if let Some(v) = self.get(q) {
             values.contains(&v)
@@ -185,16 +469,15 @@ impl Tags {
         }
     }
 
-    /// Get a subset of the tags
+    /// Get a subset of the tags, owning its strings
     #[must_use]
-    pub fn subset<'any, I, Q, O>(&self, keys: I) -> Self
+    pub fn subset<'any, I, Q, O>(&self, keys: I) -> Tags
     where
         I: IntoIterator<Item = &'any Q>,
-        TagKey: Borrow<Q>,
-        Q: 'any + Ord + Hash + Eq + ?Sized + ToOwned<Owned = O>,
+        Q: 'any + AsRef<str> + ?Sized + ToOwned<Owned = O>,
         O: Into<TagKey>,
     {
-        let mut map = Self::default();
+        let mut map = Tags::default();
         for key in keys {
             if let Some(val) = self.get(key) {
                 let owned: O = key.to_owned();
@@ -210,14 +493,119 @@ impl Tags {
     where
         Q: AsRef<str> + ?Sized,
     {
-        self.map
+        // Keys are ordered, so the keys with the stem are contiguous, starting from the stem
+        let stem = q.as_ref();
+        let start = self
+            .pairs
+            .partition_point(|(key, _val)| key.as_str() < stem);
+        let pairs: Vec<(&str, &str)> = self
+            .pairs
             .iter()
-            .filter_map(|(key, val)| {
-                key.as_str()
-                    .starts_with(q.as_ref())
-                    .then(|| (key.as_str(), val.as_str()))
+            .skip(start)
+            .take_while(|(key, _val)| key.as_str().starts_with(stem))
+            .map(|(key, val)| (key.as_str(), val.as_str()))
+            .collect();
+        self.mark_read_range(start..start + pairs.len());
+        pairs
+    }
+
+    /// Iterate over the pairs of a key and the keys under it, ordered by key,
+    /// e.g. `cycleway=*` and `cycleway:left:width=*` for the prefix `cycleway`,
+    /// but not `cycleways=*`
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use osm_tags::Tags;
+    /// let tags = Tags::from_str("cycleway=lane\ncycleway:left=no\ncycleways=yes").unwrap();
+    /// let cycleway: Vec<_> = tags.iter_with_prefix("cycleway").collect();
+    /// assert_eq!(cycleway, [("cycleway", "lane"), ("cycleway:left", "no")]);
+    /// ```
+    pub fn iter_with_prefix<'this, Q>(
+        &'this self,
+        q: &Q,
+    ) -> impl Iterator<Item = (&'this str, &'this str)>
+    where
+        Q: AsRef<str> + ?Sized,
+    {
+        let prefix = q.as_ref();
+        // Keys are ordered, so the keys under the prefix are contiguous, starting from the prefix
+        let start = self
+            .pairs
+            .partition_point(|(key, _val)| key.as_str() < prefix);
+        let pairs = self.pairs.get(start..).unwrap_or_default();
+        let end = pairs.partition_point(|(key, _val)| key.as_str().starts_with(prefix));
+        let len = prefix.len();
+        pairs
+            .get(..end)
+            .unwrap_or_default()
+            .iter()
+            .enumerate()
+            .filter(move |(_offset, (key, _val))| {
+                matches!(key.as_str().as_bytes().get(len), None | Some(b':'))
             })
-            .collect()
+            .map(move |(offset, (key, val))| {
+                self.mark_read(start + offset);
+                (key.as_str(), val.as_str())
+            })
+    }
+
+    /// Get the subset of a key and the keys under it, owning its strings,
+    /// see [`TagsRef::iter_with_prefix`]
+    #[must_use]
+    pub fn subset_by_prefix<Q>(&self, q: &Q) -> Tags
+    where
+        Q: AsRef<str> + ?Sized,
+    {
+        // Already sorted and unique
+        TagsRef {
+            pairs: self
+                .iter_with_prefix(q)
+                .map(|(key, val)| {
+                    (
+                        Part::Owned(TagKey::from_ref(key)),
+                        Part::Owned(TagVal::from(val)),
+                    )
+                })
+                .collect(),
+            reads: None,
+        }
+    }
+
+    /// Normalize the values, trimming the whitespace around them.
+    /// The values of the keys for which `keyword` holds, such as `oneway=*` but not `name=*`,
+    /// are also lowercased, trimmed around the parts of `;` and `|` separated lists,
+    /// and the empty parts of `;` separated lists are dropped.
+    /// Returns the original pairs of the values that changed.
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use osm_tags::Tags;
+    /// let mut tags = Tags::from_str("name=Main Street \noneway=Yes\nturn:lanes=left ; through|").unwrap();
+    /// let original = tags.normalize(|key| key != "name");
+    /// assert_eq!(tags.get("name"), Some("Main Street"));
+    /// assert_eq!(tags.get("oneway"), Some("yes"));
+    /// assert_eq!(tags.get("turn:lanes"), Some("left;through|"));
+    /// assert_eq!(original.to_vec().len(), 3);
+    /// ```
+    #[must_use]
+    pub fn normalize<F>(&mut self, keyword: F) -> Self
+    where
+        F: Fn(&str) -> bool,
+    {
+        let mut original = Self::default();
+        for (key, val) in &mut self.pairs {
+            let normalized = if keyword(key.as_str()) {
+                normalize_keyword(val.as_str())
+            } else {
+                val.as_str().trim().to_owned()
+            };
+            if normalized != val.as_str() {
+                // Keys are already sorted and unique
+                let val = core::mem::replace(val, Part::Owned(TagVal::from(normalized)));
+                original.pairs.push((key.clone(), val));
+            }
+        }
+        original
     }
 
     /// # Errors
@@ -230,23 +618,43 @@ impl Tags {
         val: V,
     ) -> Result<(), DuplicateKeyError> {
         let key: TagKey = key.into();
-        // This may become cleaner with https://github.com/rust-lang/rust/issues/82766
-        match self.map.entry(key) {
-            Entry::Vacant(entry) => entry.insert(val.into()),
-            Entry::Occupied(entry) => return Err(DuplicateKeyError(entry.remove_entry().0)),
-        };
-        Ok(())
+        match self.position(&key) {
+            Ok(_index) => Err(DuplicateKeyError(key)),
+            Err(index) => {
+                self.pairs
+                    .insert(index, (Part::Owned(key), Part::Owned(val.into())));
+                if let Some(reads) = &mut self.reads {
+                    reads.0.insert(index, AtomicBool::new(false));
+                }
+                Ok(())
+            },
+        }
     }
 }
 
+/// See [`Tags::normalize`]
+fn normalize_keyword(val: &str) -> String {
+    val.split('|')
+        .map(|part| {
+            part.split(';')
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .collect::<Vec<&str>>()
+                .join(";")
+        })
+        .collect::<Vec<String>>()
+        .join("|")
+        .to_lowercase()
+}
+
 #[derive(Debug)]
 pub enum ParseTagsError {
     MissingEquals(String),
     DuplicateKey(DuplicateKeyError),
 }
 
-impl std::fmt::Display for ParseTagsError {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for ParseTagsError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         match self {
             Self::MissingEquals(_val) => write!(f, "tag must be = separated"),
             Self::DuplicateKey(duplicate_key_err) => duplicate_key_err.fmt(f),
@@ -254,6 +662,7 @@ impl std::fmt::Display for ParseTagsError {
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for ParseTagsError {}
 
 impl FromStr for Tags {
@@ -281,7 +690,7 @@ impl FromStr for Tags {
     }
 }
 
-impl ToString for Tags {
+impl ToString for TagsRef<'_> {
     /// Return tags as an '=' separated list
     ///
     /// ```
@@ -299,14 +708,14 @@ impl ToString for Tags {
 /// A Visitor holds methods that a Deserializer can drive
 #[cfg(feature = "serde")]
 struct TagsVisitor {
-    marker: std::marker::PhantomData<fn() -> Tags>,
+    marker: core::marker::PhantomData<fn() -> Tags>,
 }
 
 #[cfg(feature = "serde")]
 impl TagsVisitor {
     fn new() -> Self {
         TagsVisitor {
-            marker: std::marker::PhantomData,
+            marker: core::marker::PhantomData,
         }
     }
 }
@@ -315,8 +724,8 @@ impl TagsVisitor {
 #[cfg(feature = "serde")]
 impl<'de> serde::de::Visitor<'de> for TagsVisitor {
     type Value = Tags;
-    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-        formatter.write_str("OSM Tags as Map")
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        formatter.write_str("OSM Tags as Map, or as Sequence of key and value pairs")
     }
     fn visit_map<M>(self, mut access: M) -> Result<Self::Value, M::Error>
     where
@@ -329,9 +738,22 @@ impl<'de> serde::de::Visitor<'de> for TagsVisitor {
         }
         Ok(tags)
     }
+    fn visit_seq<S>(self, mut access: S) -> Result<Self::Value, S::Error>
+    where
+        S: serde::de::SeqAccess<'de>,
+    {
+        let mut tags = Tags::default();
+        while let Some((key, value)) = access.next_element::<(String, String)>()? {
+            // Consistent with maps, where duplicate tags are ignored
+            let _ignored = tags.checked_insert(&key, value);
+        }
+        Ok(tags)
+    }
 }
 
-/// Informs Serde how to deserialize Tags
+/// Informs Serde how to deserialize Tags,
+/// from a map such as `{"highway": "residential"}`,
+/// or from a sequence of pairs such as `[["highway", "residential"]]`
 #[cfg(feature = "serde")]
 impl<'de> serde::Deserialize<'de> for Tags {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -340,19 +762,20 @@ impl<'de> serde::Deserialize<'de> for Tags {
     {
         // Instantiate our Visitor and ask the Deserializer to drive
         // it over the input data, resulting in an instance of MyMap.
-        deserializer.deserialize_map(TagsVisitor::new())
+        deserializer.deserialize_any(TagsVisitor::new())
     }
 }
 
+/// Informs Serde how to serialize Tags, always as a map ordered by key
 #[cfg(feature = "serde")]
-impl serde::Serialize for Tags {
+impl serde::Serialize for TagsRef<'_> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
         use serde::ser::SerializeMap;
-        let mut map = serializer.serialize_map(Some(self.map.len()))?;
-        for (k, v) in &self.map {
+        let mut map = serializer.serialize_map(Some(self.pairs.len()))?;
+        for (k, v) in &self.pairs {
             map.serialize_entry(k.as_str(), v.as_str())?;
         }
         map.end()
@@ -361,7 +784,7 @@ impl serde::Serialize for Tags {
 
 #[cfg(test)]
 mod tests {
-    use crate::{DuplicateKeyError, TagKey, Tags};
+    use crate::{DuplicateKeyError, TagKey, Tags, TagsRef};
 
     #[test]
     fn test_tags() {
@@ -387,6 +810,10 @@ mod tests {
         assert_eq!(serde_json::to_string(&tags).unwrap(), tags_str);
         let de_tags: Tags = serde_json::from_str(tags_str).unwrap();
         assert_eq!(de_tags.to_str_pairs(), tags.to_str_pairs());
+        let pairs_str = "[[\"foo\",\"bar\"],[\"abra\",\"cadabra\"],[\"foo:multi:key\",\"value\"],[\"multivalue\",\"apple;banana;chocolate covered capybara\"]]";
+        let de_tags: Tags = serde_json::from_str(pairs_str).unwrap();
+        assert_eq!(de_tags.to_str_pairs(), tags.to_str_pairs());
+        assert_eq!(serde_json::to_string(&de_tags).unwrap(), tags_str);
 
         // Misc
         let mut other_tags = tags.clone();
@@ -398,6 +825,10 @@ mod tests {
         assert!(other_tags
             .checked_insert(String::from("owned"), "val")
             .is_ok());
+        assert!(matches!(
+            Tags::from_pairs([("foo", "bar"), ("abra", "cadabra"), ("foo", "baz")]).unwrap_err(),
+            DuplicateKeyError(_),
+        ));
 
         // String interfaces
         assert_eq!(tags.get("foo"), Some("bar"));
@@ -417,6 +848,18 @@ mod tests {
         assert_eq!(tags.subset(["foo", "bar"]).to_vec(), vec!["foo=bar"]);
         assert!(tags.subset(["bar"]).to_vec().is_empty());
 
+        // Prefix interfaces
+        assert_eq!(
+            tags.iter_with_prefix("foo").collect::<Vec<_>>(),
+            vec![("foo", "bar"), ("foo:multi:key", "value")]
+        );
+        assert_eq!(
+            tags.subset_by_prefix("foo:multi").to_vec(),
+            vec!["foo:multi:key=value"]
+        );
+        assert!(tags.subset_by_prefix("fo").is_empty());
+        assert!(tags.subset_by_prefix("multi").is_empty());
+
         // Key interfaces
         const FOO_KEY: TagKey = TagKey::from_static("foo");
         assert_eq!(tags.get(&FOO_KEY), Some("bar"));
@@ -435,4 +878,143 @@ mod tests {
 
         // TODO: Multi Value
     }
+
+    #[test]
+    fn test_assign_pairs() {
+        let mut tags = Tags::from_pairs([("owned", "yes"), ("highway", "primary")]).unwrap();
+        for way in [
+            vec![
+                ("lanes", "4"),
+                ("highway", "primary"),
+                ("cycleway:left", "lane"),
+            ],
+            vec![("highway", "service")],
+            vec![],
+            vec![
+                ("name", "Main Street"),
+                ("cycleway", "no"),
+                ("cycleway_foo", "x"),
+            ],
+        ] {
+            let pairs: Vec<(String, String)> = way
+                .iter()
+                .map(|(key, val)| (key.to_string(), val.to_string()))
+                .collect();
+            let borrowed = tags
+                .assign_pairs(pairs.iter().map(|(key, val)| (key.as_str(), val.as_str())))
+                .unwrap();
+            // Sorted, without the pairs of the previous way
+            let mut sorted = way.clone();
+            sorted.sort_unstable();
+            assert_eq!(borrowed.to_str_pairs(), sorted);
+            for (key, val) in &way {
+                assert_eq!(borrowed.get(key), Some(*val));
+            }
+            assert_eq!(borrowed.get("owned"), None);
+            assert_eq!(
+                borrowed.subset_by_prefix("cycleway").to_str_pairs(),
+                sorted
+                    .iter()
+                    .copied()
+                    .filter(|(key, _val)| key.starts_with("cycleway") && *key != "cycleway_foo")
+                    .collect::<Vec<_>>()
+            );
+            assert_eq!(borrowed.clone().into_owned().to_str_pairs(), sorted);
+            tags = borrowed.assign_pairs([]).unwrap();
+            assert!(tags.is_empty());
+        }
+
+        // Duplicates are found whatever the previous tags
+        let pairs = [
+            ("highway", "primary"),
+            ("lanes", "2"),
+            ("highway", "service"),
+        ];
+        assert!(matches!(
+            tags.assign_pairs(pairs).unwrap_err(),
+            DuplicateKeyError(key) if key.as_str() == "highway",
+        ));
+        assert!(TagsRef::from_borrowed_pairs(pairs).is_err());
+        assert!(Tags::default()
+            .assign_pairs([("lanes", "2"), ("highway", "service")])
+            .is_ok());
+    }
+
+    #[test]
+    fn test_sorted_pairs() {
+        // Ordered by the bytes of the key, whatever the order of insertion
+        let keys = [
+            "cycleway_foo",
+            "cycleway:left",
+            "cyclewayz",
+            "cycleway",
+            "cycleway.foo",
+            "cycleway-foo",
+            "cycleway:",
+            "cycleway:left:width",
+            "Cycleway",
+        ];
+        let mut sorted = keys.to_vec();
+        sorted.sort_unstable();
+        let tags = Tags::from_pairs(keys.map(|key| (key, key))).unwrap();
+        assert_eq!(
+            tags.iter().map(|(key, _val)| key).collect::<Vec<_>>(),
+            sorted
+        );
+        for key in keys {
+            assert_eq!(tags.get(key), Some(key));
+        }
+        assert_eq!(tags.get("cyclewa"), None);
+        assert_eq!(tags.get("cycleway:right"), None);
+
+        let mut inserted = Tags::default();
+        for key in keys {
+            inserted.checked_insert(key, key).unwrap();
+        }
+        assert_eq!(inserted.to_str_pairs(), tags.to_str_pairs());
+
+        // Duplicates are found wherever they are inserted
+        for (first, second) in [(0, 1), (0, keys.len() - 1), (3, 4)] {
+            let mut pairs = keys.to_vec();
+            pairs.insert(second, keys.get(first).copied().unwrap());
+            assert!(matches!(
+                Tags::from_pairs(pairs.iter().map(|key| (*key, "dup"))).unwrap_err(),
+                DuplicateKeyError(key) if key.as_str() == *keys.get(first).unwrap(),
+            ));
+        }
+        for key in keys {
+            let mut tags = tags.clone();
+            assert!(tags.checked_insert(key, "dup").is_err());
+            assert_eq!(tags.get(key), Some(key));
+        }
+
+        // Only the key and the keys under it, at a `:` boundary
+        assert_eq!(
+            tags.iter_with_prefix("cycleway").collect::<Vec<_>>(),
+            vec![
+                ("cycleway", "cycleway"),
+                ("cycleway:", "cycleway:"),
+                ("cycleway:left", "cycleway:left"),
+                ("cycleway:left:width", "cycleway:left:width"),
+            ]
+        );
+        assert_eq!(
+            tags.subset_by_prefix("cycleway:left").to_vec(),
+            vec![
+                "cycleway:left=cycleway:left",
+                "cycleway:left:width=cycleway:left:width"
+            ]
+        );
+        assert_eq!(
+            tags.subset_by_prefix("cycleway_foo").to_vec(),
+            vec!["cycleway_foo=cycleway_foo"]
+        );
+        assert!(tags.subset_by_prefix("cycleway:l").is_empty());
+        assert!(tags.subset_by_prefix("cycleway:right").is_empty());
+        assert!(tags.subset_by_prefix("cyclewayzz").is_empty());
+        assert!(Tags::default()
+            .iter_with_prefix("cycleway")
+            .next()
+            .is_none());
+    }
 }
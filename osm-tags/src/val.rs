@@ -1,7 +1,9 @@
-use std::ops::Deref;
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use core::ops::Deref;
 
-/// A Tag Value
-/// A String is used as a placeholder until `|` separated values are supported
+/// A Tag Value, as tagged,
+/// see [`crate::TagsRef::get_values`] for `;` separated values
 #[allow(clippy::module_name_repetitions)]
 #[derive(Clone, Debug)]
 pub struct TagVal(String);
@@ -32,3 +34,9 @@ impl From<String> for TagVal {
         TagVal(val)
     }
 }
+
+impl AsRef<str> for TagVal {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
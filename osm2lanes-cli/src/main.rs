@@ -6,7 +6,9 @@ use clap::{Parser, Subcommand};
 use futures::executor::block_on;
 use osm2lanes::locale::Locale;
 use osm2lanes::overpass::get_way;
-use osm2lanes::transform::{tags_to_lanes, TagsToLanesConfig};
+use osm2lanes::transform::{
+    tags_to_lanes, tags_to_lanes_with_trace, RoadError, RoadFromTags, TagsToLanesConfig, WayContext,
+};
 use osm_tags::Tags;
 
 /// Generate lanes from an OSM way
@@ -15,6 +17,9 @@ use osm_tags::Tags;
 struct Cli {
     #[clap(short, long, parse(from_occurrences))]
     verbose: usize,
+    /// Print compact output with rounded widths, which can be byte-compared
+    #[clap(long)]
+    canonical: bool,
     #[clap(subcommand)]
     command: Command,
 }
@@ -35,6 +40,25 @@ enum Command {
         #[clap(required = true, parse(from_os_str))]
         path: PathBuf,
     },
+    /// Convert OSM way tags to lanes, printing an HTML report of each stage
+    #[clap(arg_required_else_help = true)]
+    Debug {
+        /// JSON of OSM Tags
+        #[clap(required = true, parse(from_os_str))]
+        path: PathBuf,
+    },
+}
+
+fn print_lanes(lanes: Result<RoadFromTags, RoadError>, canonical: bool) {
+    if canonical {
+        let lanes = lanes.map(|mut lanes| {
+            lanes.canonicalize();
+            lanes
+        });
+        println!("{}", serde_json::to_string(&lanes).unwrap());
+    } else {
+        println!("{}", serde_json::to_string_pretty(&lanes).unwrap());
+    }
 }
 
 #[tokio::main]
@@ -49,15 +73,28 @@ async fn main() {
             let (tags, _geom, locale) = block_on(get_way(id)).unwrap();
             log::info!("{:#?}", tags);
             log::info!("{:#?}", locale);
-            let lanes = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::default());
-            println!("{}", serde_json::to_string_pretty(&lanes).unwrap());
+            let config = TagsToLanesConfig::default().with_context(WayContext::way(*id));
+            let lanes = tags_to_lanes(&tags, &locale, &config);
+            print_lanes(lanes, args.canonical);
         },
         Command::Convert { path } => {
             let tags: Tags =
                 serde_json::from_reader(BufReader::new(File::open(path).unwrap())).unwrap();
             let locale = Locale::builder().build();
-            let lanes = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::default());
-            println!("{}", serde_json::to_string_pretty(&lanes).unwrap());
+            let config = TagsToLanesConfig::default().with_context(WayContext {
+                way_id: None,
+                source: Some(path.display().to_string()),
+            });
+            let lanes = tags_to_lanes(&tags, &locale, &config);
+            print_lanes(lanes, args.canonical);
+        },
+        Command::Debug { path } => {
+            let tags: Tags =
+                serde_json::from_reader(BufReader::new(File::open(path).unwrap())).unwrap();
+            let locale = Locale::builder().build();
+            let (lanes, trace) =
+                tags_to_lanes_with_trace(&tags, &locale, &TagsToLanesConfig::default());
+            println!("{}", trace.html(&tags, &lanes));
         },
     }
 }
@@ -9,6 +9,7 @@ use wasm_bindgen::JsCast;
 use web_sys::{window, HtmlCanvasElement};
 use yew::{html, Callback, Component, Context, Properties};
 
+use crate::style::RenderStyle;
 use crate::{draw, State};
 
 #[derive(Debug)]
@@ -62,10 +63,8 @@ impl Component for Canvas {
     }
 
     fn rendered(&mut self, ctx: &Context<Self>, _first_render: bool) {
-        if let Err(e) = Self::draw_canvas(
-            ctx.props().state.borrow().road.as_ref(),
-            &ctx.props().state.borrow().locale,
-        ) {
+        let state = ctx.props().state.borrow();
+        if let Err(e) = Self::draw_canvas(state.road.as_ref(), &state.locale, &state.style) {
             ctx.props().callback_error.emit(format!("Error: {}", e));
         }
     }
@@ -75,7 +74,11 @@ impl Canvas {
     #[allow(clippy::as_conversions)]
     #[allow(clippy::cast_possible_truncation)]
     #[allow(clippy::cast_sign_loss)]
-    fn draw_canvas(road: Option<&Road>, locale: &Locale) -> Result<(), RenderError> {
+    fn draw_canvas(
+        road: Option<&Road>,
+        locale: &Locale,
+        style: &RenderStyle,
+    ) -> Result<(), RenderError> {
         if let Some(road) = road {
             let window = window().unwrap();
             let canvas = window
@@ -100,7 +103,7 @@ impl Canvas {
             context.scale(dpr, dpr).unwrap();
             let mut rc = WebRenderContext::new(context, window);
 
-            draw::lanes(&mut rc, (canvas_width, canvas_height), road, locale)?;
+            draw::lanes(&mut rc, (canvas_width, canvas_height), road, locale, style)?;
         }
         Ok(())
     }
@@ -136,6 +136,10 @@ impl Component for Control {
             .link()
             .callback(|_e: Event| Msg::from(AppMsg::ToggleDrivingSide));
 
+        let legend_onchange = ctx
+            .link()
+            .callback(|_e: Event| Msg::from(AppMsg::ToggleLegend));
+
         let way_id: String = state
             .id
             .as_ref()
@@ -206,6 +210,12 @@ impl Component for Control {
                         }
                     }
                     <hr/>
+                    <label class="row-item" for="legend">{"Legend"}</label>
+                    <input class="row-item" type="checkbox" id="legend"
+                        checked={state.style.legend}
+                        onchange={legend_onchange}
+                    />
+                    <hr/>
                     <label class="row-item" for="way">{"OSM Way ID"}</label>
                     <input class="row-item" type="text" id="way" name="way" size="12"
                         ref={state.way_ref.clone()}
@@ -1,25 +1,11 @@
 use osm2lanes::locale::Locale;
 use osm2lanes::metric::Metre;
-use osm2lanes::road::{
-    Color as MarkingColor, Designated, Direction, Lane, Printable, Road, Semantic, Style,
-};
+use osm2lanes::road::{Designated, Direction, Lane, Printable, Road, Semantic};
 use piet::kurbo::{Line, Point, Rect};
-use piet::{
-    Color as PietColor, FontFamily, RenderContext, StrokeStyle, Text, TextAttribute,
-    TextLayoutBuilder,
-};
+use piet::{FontFamily, RenderContext, Text, TextAttribute, TextLayoutBuilder};
 
 use crate::canvas::RenderError;
-
-// TODO: newtype + From?
-fn color_into(c: MarkingColor) -> PietColor {
-    match c {
-        MarkingColor::White => PietColor::WHITE,
-        MarkingColor::Yellow => PietColor::YELLOW,
-        MarkingColor::Red => PietColor::RED,
-        MarkingColor::Green => PietColor::GREEN,
-    }
-}
+use crate::style::{Arrow, RenderStyle};
 
 struct Scale(f64);
 
@@ -34,6 +20,7 @@ pub(crate) fn lanes<R: RenderContext>(
     (canvas_width, canvas_height): (u32, u32),
     road: &Road,
     locale: &Locale,
+    style: &RenderStyle,
 ) -> Result<(), RenderError> {
     let canvas_width = f64::from(canvas_width);
     let canvas_height = f64::from(canvas_height);
@@ -47,7 +34,7 @@ pub(crate) fn lanes<R: RenderContext>(
     );
 
     // Background
-    rc.clear(None, PietColor::OLIVE);
+    rc.clear(None, style.verge);
 
     rc.fill(
         Rect::new(
@@ -56,7 +43,7 @@ pub(crate) fn lanes<R: RenderContext>(
             scale.scale(grassy_verge + asphalt_buffer + road.width(locale) + asphalt_buffer),
             canvas_height,
         ),
-        &PietColor::BLACK,
+        &style.asphalt,
     );
 
     let mut left_edge = grassy_verge + asphalt_buffer;
@@ -79,41 +66,25 @@ pub(crate) fn lanes<R: RenderContext>(
                 *direction,
                 canvas_height,
                 lane,
+                style,
             )?,
-            Lane::Parking {
-                designated, width, ..
-            } => {
-                let width =
-                    width.unwrap_or_else(|| locale.travel_width(designated, road.highway.r#type()));
+            Lane::Parking { .. } => {
+                let width = lane.width(locale, road.highway.r#type());
                 let x = scale.scale(left_edge + (0.5 * width));
-                let font_size = 24.0;
-                let layout = rc
-                    .text()
-                    .new_text_layout(lane.as_utf8().to_string())
-                    .font(FontFamily::SYSTEM_UI, font_size)
-                    .default_attribute(TextAttribute::TextColor(PietColor::WHITE))
-                    .build()?;
-                rc.draw_text(&layout, (x - (0.5 * font_size), 0.5 * canvas_height));
+                draw_text(rc, lane.as_utf8(), x, 0.5 * canvas_height, style)?;
                 left_edge += width;
             },
-            Lane::Shoulder { width } => {
+            Lane::Shoulder { width, .. } => {
                 let width = width.unwrap_or(default_lane_width);
                 let x = scale.scale(left_edge + (0.5 * width));
-                let font_size = 24.0;
-                let layout = rc
-                    .text()
-                    .new_text_layout(lane.as_utf8().to_string())
-                    .font(FontFamily::SYSTEM_UI, font_size)
-                    .default_attribute(TextAttribute::TextColor(PietColor::WHITE))
-                    .build()?;
-                rc.draw_text(&layout, (x - (0.5 * font_size), 0.5 * canvas_height));
+                draw_text(rc, lane.as_utf8(), x, 0.5 * canvas_height, style)?;
                 left_edge += width;
             },
             Lane::Separator {
                 markings: Some(markings),
                 ..
             } => {
-                draw_separator(rc, &mut left_edge, markings, &scale, canvas_height);
+                draw_separator(rc, &mut left_edge, markings, &scale, canvas_height, style);
             },
             Lane::Separator {
                 markings: None,
@@ -128,7 +99,7 @@ pub(crate) fn lanes<R: RenderContext>(
                             scale.scale(left_edge + width),
                             canvas_height,
                         ),
-                        &PietColor::GREEN,
+                        &style.green_verge,
                     );
                     left_edge += width;
                 }
@@ -136,6 +107,10 @@ pub(crate) fn lanes<R: RenderContext>(
         }
     }
 
+    if style.legend {
+        draw_legend(rc, canvas_height, style)?;
+    }
+
     rc.finish().unwrap();
     Ok(())
 }
@@ -153,28 +128,12 @@ fn draw_travel_lane<R: RenderContext>(
     direction: Option<Direction>,
     canvas_height: f64,
     lane: &Lane,
+    style: &RenderStyle,
 ) -> Result<(), RenderError> {
-    let width = width.unwrap_or_else(|| locale.travel_width(&designated, road.highway.r#type()));
+    let width =
+        width.unwrap_or_else(|| locale.travel_width(&designated, direction, road.highway.r#type()));
     let x = scale.scale(*left_edge + (0.5 * width));
-    if let Some(direction) = direction {
-        draw_arrow(
-            rc,
-            Point {
-                x,
-                y: 0.3 * canvas_height,
-            },
-            direction,
-        );
-        draw_arrow(
-            rc,
-            Point {
-                x,
-                y: 0.7 * canvas_height,
-            },
-            direction,
-        );
-    }
-    if lane.is_foot() {
+    if let Some(color) = style.designated(designated) {
         rc.fill(
             Rect::new(
                 scale.scale(*left_edge),
@@ -182,17 +141,29 @@ fn draw_travel_lane<R: RenderContext>(
                 scale.scale(*left_edge + width),
                 canvas_height,
             ),
-            &PietColor::GRAY,
+            &color,
         );
     }
-    let font_size = 24.0;
-    let layout = rc
-        .text()
-        .new_text_layout(lane.as_utf8().to_string())
-        .font(FontFamily::SYSTEM_UI, font_size)
-        .default_attribute(TextAttribute::TextColor(PietColor::WHITE))
-        .build()?;
-    rc.draw_text(&layout, (x - (0.5 * font_size), 0.5 * canvas_height));
+    if let Some(direction) = direction {
+        for y in [0.3 * canvas_height, 0.7 * canvas_height] {
+            match &style.arrow {
+                Arrow::Lines => draw_arrow(rc, Point { x, y }, direction, style),
+                Arrow::Glyphs {
+                    forward,
+                    backward,
+                    both,
+                } => {
+                    let glyph = match direction {
+                        Direction::Forward => *forward,
+                        Direction::Backward => *backward,
+                        Direction::Both | Direction::Alternating | Direction::Reversible => *both,
+                    };
+                    draw_text(rc, glyph, x, y, style)?;
+                },
+            }
+        }
+    }
+    draw_text(rc, lane.as_utf8(), x, 0.5 * canvas_height, style)?;
     *left_edge += width;
     Ok(())
 }
@@ -203,20 +174,13 @@ fn draw_separator<R: RenderContext>(
     markings: &osm2lanes::road::Markings,
     scale: &Scale,
     canvas_height: f64,
+    style: &RenderStyle,
 ) {
     for marking in markings.iter() {
         let width = marking.width.unwrap_or_else(|| Metre::new(0.2));
         let x = scale.scale(*left_edge + 0.5 * width);
-        let color = match (marking.style, marking.color) {
-            (_, Some(c)) => color_into(c),
-            (Style::KerbUp | Style::KerbDown, None) => PietColor::GRAY,
-            // Remains for debugging
-            _ => PietColor::BLUE,
-            // _ => return Err(RenderError::UnknownSeparator),
-        };
-        if let Style::NoFill = marking.style {
-            // nope
-        } else {
+        let color = style.marking_color(marking.style, marking.color);
+        if let Some(stroke) = style.marking_stroke(marking.style) {
             rc.stroke_styled(
                 Line::new(
                     Point { x, y: 0.0 },
@@ -227,26 +191,68 @@ fn draw_separator<R: RenderContext>(
                 ),
                 &color,
                 scale.scale(width),
-                &match marking.style {
-                    Style::DottedLine => StrokeStyle::new().dash_pattern(&[50.0, 100.0]),
-                    Style::DashedLine => StrokeStyle::new().dash_pattern(&[100.0, 100.0]),
-                    Style::BrokenLine => StrokeStyle::new().dash_pattern(&[100.0, 50.0]),
-                    Style::SolidLine | Style::KerbUp | Style::KerbDown => StrokeStyle::new(),
-                    Style::NoFill => unreachable!(),
-                    // _ => return Err(RenderError::UnknownSeparator),
-                },
+                stroke,
             );
         }
         *left_edge += width;
     }
 }
 
-fn draw_arrow<R: RenderContext>(rc: &mut R, mid: Point, direction: Direction) {
-    fn draw_point<R: RenderContext>(rc: &mut R, mid: Point, direction: Direction) {
+/// Draw a character centred at a point
+fn draw_text<R: RenderContext>(
+    rc: &mut R,
+    c: char,
+    x: f64,
+    y: f64,
+    style: &RenderStyle,
+) -> Result<(), RenderError> {
+    let layout = rc
+        .text()
+        .new_text_layout(c.to_string())
+        .font(FontFamily::SYSTEM_UI, style.font_size)
+        .default_attribute(TextAttribute::TextColor(style.text_color))
+        .build()?;
+    rc.draw_text(&layout, (x - (0.5 * style.font_size), y));
+    Ok(())
+}
+
+/// Draw the legend in the bottom left corner
+fn draw_legend<R: RenderContext>(
+    rc: &mut R,
+    canvas_height: f64,
+    style: &RenderStyle,
+) -> Result<(), RenderError> {
+    let entries = style.legend_entries();
+    let row_height = 0.75 * style.font_size;
+    let mut y = canvas_height - row_height * f64::from(u32::try_from(entries.len()).unwrap_or(0));
+    for entry in entries {
+        rc.fill(
+            Rect::new(5.0, y + 2.0, 5.0 + row_height - 4.0, y + row_height - 2.0),
+            &entry.color,
+        );
+        let layout = rc
+            .text()
+            .new_text_layout(entry.label)
+            .font(FontFamily::SYSTEM_UI, 0.5 * style.font_size)
+            .default_attribute(TextAttribute::TextColor(style.text_color))
+            .build()?;
+        rc.draw_text(&layout, (10.0 + row_height, y));
+        y += row_height;
+    }
+    Ok(())
+}
+
+fn draw_arrow<R: RenderContext>(rc: &mut R, mid: Point, direction: Direction, style: &RenderStyle) {
+    fn draw_point<R: RenderContext>(
+        rc: &mut R,
+        mid: Point,
+        direction: Direction,
+        style: &RenderStyle,
+    ) {
         let dir_sign = match direction {
             Direction::Forward => -1.0,
             Direction::Backward => 1.0,
-            Direction::Both => unreachable!(),
+            Direction::Both | Direction::Alternating | Direction::Reversible => unreachable!(),
         };
         for x in [-10.0, 10.0] {
             rc.stroke(
@@ -260,7 +266,7 @@ fn draw_arrow<R: RenderContext>(rc: &mut R, mid: Point, direction: Direction) {
                         y: mid.y + dir_sign * 10.0,
                     },
                 ),
-                &PietColor::WHITE,
+                &style.arrow_color,
                 1.0,
             );
         }
@@ -277,14 +283,14 @@ fn draw_arrow<R: RenderContext>(rc: &mut R, mid: Point, direction: Direction) {
                 y: mid.y + 20.0,
             },
         ),
-        &PietColor::WHITE,
+        &style.arrow_color,
         1.0,
     );
     match direction {
-        Direction::Forward | Direction::Backward => draw_point(rc, mid, direction),
-        Direction::Both => {
-            draw_point(rc, mid, Direction::Forward);
-            draw_point(rc, mid, Direction::Backward);
+        Direction::Forward | Direction::Backward => draw_point(rc, mid, direction, style),
+        Direction::Both | Direction::Alternating | Direction::Reversible => {
+            draw_point(rc, mid, Direction::Forward, style);
+            draw_point(rc, mid, Direction::Backward, style);
         },
     }
 }
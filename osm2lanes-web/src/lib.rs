@@ -67,6 +67,9 @@ use canvas::Canvas;
 
 mod draw;
 
+pub mod style;
+use style::RenderStyle;
+
 mod map;
 use map::MapComponent;
 
@@ -112,6 +115,8 @@ pub struct State {
     pub normalized_tags: Option<String>,
     /// Lanes to visualise
     pub road: Option<Road>,
+    /// Style to draw the lanes and their legend
+    pub style: RenderStyle,
     /// Message for user
     pub message: Option<String>,
     /// Ref to input for way id
@@ -127,6 +132,7 @@ pub enum Msg {
         locale: Locale,
     },
     ToggleDrivingSide,
+    ToggleLegend,
     CountrySet(Result<Country, &'static str>),
     WayFetch,
     Error(String),
@@ -149,6 +155,7 @@ impl Component for App {
             edit_tags,
             normalized_tags: None,
             road: None,
+            style: RenderStyle::default(),
             message: None,
             way_ref: NodeRef::default(),
         }));
@@ -184,6 +191,11 @@ impl Component for App {
                 self.update_tags();
                 true
             },
+            Msg::ToggleLegend => {
+                let mut state = self.state.borrow_mut();
+                state.style.legend = !state.style.legend;
+                true
+            },
             Msg::CountrySet(Ok(country)) => {
                 {
                     let mut state = self.state.borrow_mut();
@@ -304,7 +316,7 @@ impl App {
         log::trace!("Locale: {:?}", locale);
         match Tags::from_str(value) {
             Ok(tags) => match tags_to_lanes(&tags, locale, &TagsToLanesConfig::default()) {
-                Ok(RoadFromTags { road, warnings }) => {
+                Ok(RoadFromTags { road, warnings, .. }) => {
                     match lanes_to_tags(&road, locale, &LanesToTagsConfig::new(false)) {
                         Ok(tags) => {
                             state.road = Some(road);
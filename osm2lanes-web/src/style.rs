@@ -0,0 +1,176 @@
+use osm2lanes::road::{Color as MarkingColor, Designated, Style as MarkingStyle};
+use piet::{Color, StrokeStyle};
+
+/// How direction arrows are drawn on travel lanes
+#[derive(Clone, Debug, PartialEq)]
+pub enum Arrow {
+    /// Arrows drawn with lines
+    Lines,
+    /// Arrows drawn as text
+    Glyphs {
+        forward: char,
+        backward: char,
+        both: char,
+    },
+}
+
+/// Colours and strokes used to render a road
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RenderStyle {
+    // Background
+    pub verge: Color,
+    pub asphalt: Color,
+    pub green_verge: Color,
+
+    // Lane fills by designation, `None` leaves the asphalt visible
+    pub foot: Option<Color>,
+    pub bicycle: Option<Color>,
+    pub motor: Option<Color>,
+    pub bus: Option<Color>,
+
+    // Marking colours
+    pub white: Color,
+    pub yellow: Color,
+    pub red: Color,
+    pub green: Color,
+    pub kerb: Color,
+    /// Physical devices without a colour
+    pub device: Color,
+    /// Markings without a colour, remains for debugging
+    pub unknown_marking: Color,
+
+    // Marking strokes
+    pub solid_line: StrokeStyle,
+    pub broken_line: StrokeStyle,
+    pub dashed_line: StrokeStyle,
+    pub dotted_line: StrokeStyle,
+
+    // Physical device strokes
+    pub rumble_strip: StrokeStyle,
+    pub botts_dots: StrokeStyle,
+    pub raised_separator: StrokeStyle,
+    pub studs: StrokeStyle,
+
+    // Symbols
+    pub arrow: Arrow,
+    pub arrow_color: Color,
+    pub text_color: Color,
+    pub font_size: f64,
+
+    /// Draw a legend of the designation fills and marking colours
+    pub legend: bool,
+}
+
+impl Default for RenderStyle {
+    fn default() -> Self {
+        Self {
+            verge: Color::OLIVE,
+            asphalt: Color::BLACK,
+            green_verge: Color::GREEN,
+            foot: Some(Color::GRAY),
+            bicycle: None,
+            motor: None,
+            bus: None,
+            white: Color::WHITE,
+            yellow: Color::YELLOW,
+            red: Color::RED,
+            green: Color::GREEN,
+            kerb: Color::GRAY,
+            device: Color::SILVER,
+            unknown_marking: Color::BLUE,
+            solid_line: StrokeStyle::new(),
+            broken_line: StrokeStyle::new().dash_pattern(&[100.0, 50.0]),
+            dashed_line: StrokeStyle::new().dash_pattern(&[100.0, 100.0]),
+            dotted_line: StrokeStyle::new().dash_pattern(&[50.0, 100.0]),
+            rumble_strip: StrokeStyle::new().dash_pattern(&[10.0, 10.0]),
+            botts_dots: StrokeStyle::new().dash_pattern(&[10.0, 40.0]),
+            raised_separator: StrokeStyle::new(),
+            studs: StrokeStyle::new().dash_pattern(&[10.0, 200.0]),
+            arrow: Arrow::Lines,
+            arrow_color: Color::WHITE,
+            text_color: Color::WHITE,
+            font_size: 24.0,
+            legend: false,
+        }
+    }
+}
+
+/// A single entry in the legend
+#[derive(Clone, Debug)]
+pub struct LegendEntry {
+    pub label: &'static str,
+    pub color: Color,
+}
+
+impl RenderStyle {
+    /// Fill of a travel lane
+    #[must_use]
+    pub fn designated(&self, designated: Designated) -> Option<Color> {
+        match designated {
+            Designated::Foot => self.foot,
+            Designated::Bicycle => self.bicycle,
+            Designated::Motor => self.motor,
+            Designated::Bus => self.bus,
+        }
+    }
+
+    /// Colour of a marking
+    #[must_use]
+    pub fn marking_color(&self, style: MarkingStyle, color: Option<MarkingColor>) -> Color {
+        match (style, color) {
+            (_, Some(MarkingColor::White)) => self.white,
+            (_, Some(MarkingColor::Yellow)) => self.yellow,
+            (_, Some(MarkingColor::Red)) => self.red,
+            (_, Some(MarkingColor::Green)) => self.green,
+            (MarkingStyle::KerbUp | MarkingStyle::KerbDown, None) => self.kerb,
+            (
+                MarkingStyle::RumbleStrip
+                | MarkingStyle::BottsDots
+                | MarkingStyle::RaisedSeparator
+                | MarkingStyle::Studs,
+                None,
+            ) => self.device,
+            _ => self.unknown_marking,
+        }
+    }
+
+    /// Stroke of a marking, `None` if the marking is not drawn
+    #[must_use]
+    pub fn marking_stroke(&self, style: MarkingStyle) -> Option<&StrokeStyle> {
+        match style {
+            MarkingStyle::SolidLine | MarkingStyle::KerbUp | MarkingStyle::KerbDown => {
+                Some(&self.solid_line)
+            },
+            MarkingStyle::BrokenLine => Some(&self.broken_line),
+            MarkingStyle::DashedLine => Some(&self.dashed_line),
+            MarkingStyle::DottedLine => Some(&self.dotted_line),
+            MarkingStyle::RumbleStrip => Some(&self.rumble_strip),
+            MarkingStyle::BottsDots => Some(&self.botts_dots),
+            MarkingStyle::RaisedSeparator => Some(&self.raised_separator),
+            MarkingStyle::Studs => Some(&self.studs),
+            MarkingStyle::NoFill => None,
+        }
+    }
+
+    /// Legend generated from the style, skipping designations without a fill
+    #[must_use]
+    pub fn legend_entries(&self) -> Vec<LegendEntry> {
+        [
+            ("sidewalk", self.foot),
+            ("cycle lane", self.bicycle),
+            ("travel lane", self.motor),
+            ("bus lane", self.bus),
+            ("verge", Some(self.green_verge)),
+            ("kerb", Some(self.kerb)),
+            ("physical device", Some(self.device)),
+            ("white marking", Some(self.white)),
+            ("yellow marking", Some(self.yellow)),
+            ("red marking", Some(self.red)),
+            ("green marking", Some(self.green)),
+        ]
+        .into_iter()
+        .filter_map(|(label, color)| color.map(|color| LegendEntry { label, color }))
+        .collect()
+    }
+}
@@ -0,0 +1,80 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use osm2lanes::locale::Locale;
+use osm2lanes::transform::{tags_to_lanes, TagsToLanesConfig, Transformer};
+use osm_tags::{Tags, TagsRef};
+
+const WAYS: &[&[(&str, &str)]] = &[
+    &[("highway", "residential"), ("name", "Main Street")],
+    &[
+        ("highway", "secondary"),
+        ("lanes", "2"),
+        ("maxspeed", "50"),
+        ("sidewalk", "both"),
+        ("cycleway:right", "lane"),
+        ("surface", "asphalt"),
+        ("name", "Station Road"),
+    ],
+    &[
+        ("highway", "primary"),
+        ("lanes", "4"),
+        ("lanes:forward", "2"),
+        ("lanes:backward", "2"),
+        ("turn:lanes:forward", "left|through"),
+        ("destination:lanes:forward", "Centre|Airport"),
+        ("maxspeed", "70"),
+        ("parking:lane:both", "no"),
+        ("sidewalk:both", "separate"),
+        ("lit", "yes"),
+        ("ref", "A1"),
+    ],
+    &[("highway", "service"), ("service", "driveway")],
+];
+
+/// Tags copied or borrowed for each way, compared to the storage of a [`Transformer`] reused across ways
+pub fn benchmark_transformer(c: &mut Criterion) {
+    let locale = Locale::builder().build();
+    let config = TagsToLanesConfig::default();
+    let mut group = c.benchmark_group("transformer");
+    group.bench_with_input(
+        BenchmarkId::new("tags_to_lanes", WAYS.len()),
+        &WAYS,
+        |b, ways| {
+            b.iter(|| {
+                for way in ways.iter() {
+                    let tags = Tags::from_pairs(way.iter().copied()).unwrap();
+                    assert!(tags_to_lanes(&tags, &locale, &config).is_ok());
+                }
+            });
+        },
+    );
+    group.bench_with_input(
+        BenchmarkId::new("tags_to_lanes_borrowed", WAYS.len()),
+        &WAYS,
+        |b, ways| {
+            b.iter(|| {
+                for way in ways.iter() {
+                    let tags = TagsRef::from_borrowed_pairs(way.iter().copied()).unwrap();
+                    assert!(tags_to_lanes(&tags, &locale, &config).is_ok());
+                }
+            });
+        },
+    );
+    group.bench_with_input(
+        BenchmarkId::new("transform_pairs", WAYS.len()),
+        &WAYS,
+        |b, ways| {
+            let mut transformer = Transformer::new(config.clone());
+            b.iter(|| {
+                for way in ways.iter() {
+                    assert!(transformer
+                        .transform_pairs(way.iter().copied(), &locale)
+                        .is_ok());
+                }
+            });
+        },
+    );
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_transformer);
+criterion_main!(benches);
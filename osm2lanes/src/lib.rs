@@ -4,6 +4,7 @@
 //! WARNING: The output specification and all of this code is just being prototyped. Don't depend
 //! on anything yet.
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(explicit_outlives_requirements)]
 #![warn(missing_abi)]
 #![deny(non_ascii_idents)]
@@ -48,6 +49,8 @@
     clippy::use_debug
 )]
 
+extern crate alloc;
+
 pub mod locale;
 pub mod metric;
 pub mod road;
@@ -1,18 +1,52 @@
+use alloc::borrow::ToOwned;
+use alloc::string::{String, ToString};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+#[cfg(feature = "country")]
 pub use celes::Country;
-use osm_tag_schemes::{HighwayImportance, HighwayType};
+use osm_tag_schemes::{HighwayImportance, HighwayType, ParkingOrientation};
+
+use crate::metric::{Metre, Speed, Units};
+use crate::road::{Color, Designated, Direction};
 
-use crate::metric::Metre;
-use crate::road::{Color, Designated};
+#[cfg(feature = "country")]
+mod boundaries;
+mod defaults;
+#[cfg(feature = "country")]
+pub use boundaries::{Boundaries, BoundariesError};
+pub use defaults::LegalDefaults;
+mod subdivisions;
+pub use subdivisions::{SubdivisionRules, Subdivisions};
+mod width_table;
+pub use width_table::{WidthTable, WidthTableError};
 
 /// Context about the place where an OSM way exists.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Locale {
     /// The ISO 3166 Country
+    #[cfg(feature = "country")]
     pub country: Option<Country>,
     pub iso_3166_2_subdivision: Option<String>,
     /// The driving side
     pub driving_side: DrivingSide,
+    /// The language of signs and localized tag values, as ISO 639-1, e.g. `de`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub language: Option<String>,
+    /// The units of distances in output, such as widths in tag values, see [`Units`]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub units: Units,
+    /// Motor lane widths, see [`WidthTable`]
+    #[cfg_attr(feature = "serde", serde(skip, default = "WidthTable::builtin"))]
+    #[cfg_attr(not(feature = "country"), allow(dead_code))]
+    width_table: WidthTable,
+    /// Rules of subdivisions, see [`Subdivisions`]
+    #[cfg_attr(feature = "serde", serde(skip, default = "Subdivisions::builtin"))]
+    #[cfg_attr(not(feature = "country"), allow(dead_code))]
+    subdivisions: Subdivisions,
 }
 
 impl Locale {
@@ -21,38 +55,170 @@ impl Locale {
         Config::default()
     }
 
+    /// Rules of the jurisdiction, such as access and oneway exemptions
     #[must_use]
+    pub fn defaults(&self) -> LegalDefaults<'_> {
+        LegalDefaults::new(self)
+    }
+
+    /// Lane width, which for cycle lanes depends on the direction of travel,
+    /// and for motorways is the wider width of an expressway
+    #[must_use]
+    pub fn travel_width(
+        &self,
+        designated: &Designated,
+        direction: Option<Direction>,
+        highway: HighwayType,
+    ) -> Metre {
+        match designated {
+            Designated::Motor | Designated::Bus => {
+                if highway == HighwayType::Classified(HighwayImportance::Motorway) {
+                    self.expressway_travel_width(designated, highway)
+                } else {
+                    self.width_table_travel_width(highway)
+                        .unwrap_or_else(|| Metre::new(3.5))
+                }
+            },
+            Designated::Foot => match highway {
+                // Guessed, a pedestrian street is a single lane across the width of a street
+                HighwayType::Pedestrian => Metre::new(6.0),
+                _ => Metre::new(2.5),
+            },
+            Designated::Bicycle => match direction {
+                Some(Direction::Both) => {
+                    if self.is_netherlands() {
+                        // https://www.crow.nl/ Design manual for bicycle traffic, two-way cycle track
+                        Metre::new(3.0)
+                    } else {
+                        // Guessed, TODO: find sources for other countries
+                        Metre::new(2.5)
+                    }
+                },
+                _ => Metre::new(2.0),
+            },
+        }
+    }
+
+    /// Lane width from the width table, for the country and subdivision
+    #[cfg(feature = "country")]
+    fn width_table_travel_width(&self, highway: HighwayType) -> Option<Metre> {
+        self.width_table.get(
+            self.country.as_ref()?.alpha2,
+            self.iso_3166_2_subdivision.as_deref(),
+            highway,
+        )
+    }
+
+    #[cfg(not(feature = "country"))]
     #[allow(clippy::unused_self)]
-    pub fn travel_width(&self, designated: &Designated, _highway: HighwayType) -> Metre {
+    fn width_table_travel_width(&self, _highway: HighwayType) -> Option<Metre> {
+        None
+    }
+
+    /// Rules of the subdivision, where they differ from the country
+    #[cfg(feature = "country")]
+    fn subdivision_rules(&self) -> Option<&SubdivisionRules> {
+        self.subdivisions.get(
+            self.country.as_ref()?.alpha2,
+            self.iso_3166_2_subdivision.as_deref()?,
+        )
+    }
+
+    #[cfg(not(feature = "country"))]
+    #[allow(clippy::unused_self)]
+    fn subdivision_rules(&self) -> Option<&SubdivisionRules> {
+        None
+    }
+
+    /// Lane width on an expressway, which is wider than the highway type alone implies
+    #[must_use]
+    pub fn expressway_travel_width(&self, designated: &Designated, highway: HighwayType) -> Metre {
         match designated {
             Designated::Motor | Designated::Bus => {
-                let uk = Country::the_united_kingdom_of_great_britain_and_northern_ireland();
-                match &self.country {
-                    // Guessed, TODO: find DfT source.
-                    Some(c) if c == &uk => Metre::new(3.0),
+                if self.is_united_kingdom() {
+                    Metre::new(3.65)
+                } else if self.is_netherlands() {
                     // https://puc.overheid.nl/rijkswaterstaat/doc/PUC_125514_31/ section 4.2.5
-                    Some(c) if c == &Country::the_netherlands() => Metre::new(3.35),
-                    _ => Metre::new(3.5),
+                    Metre::new(3.5)
+                } else {
+                    Metre::new(3.75)
                 }
             },
-            Designated::Foot => Metre::new(2.5),
-            Designated::Bicycle => Metre::new(2.0),
+            Designated::Foot | Designated::Bicycle => self.travel_width(designated, None, highway),
         }
     }
 
+    /// Narrowest width implied for a lane, e.g. when distributing the total width of the way
+    #[must_use]
+    #[allow(clippy::unused_self)]
+    pub fn min_travel_width(&self, designated: &Designated) -> Metre {
+        match designated {
+            // Guessed, a car with mirrors and a margin either side
+            Designated::Motor | Designated::Bus => Metre::new(2.5),
+            // Guessed, a person or a bicycle with a margin either side
+            Designated::Foot | Designated::Bicycle => Metre::new(1.0),
+        }
+    }
+
+    /// Width of a `highway=cycleway`, which is wider when travelled in both directions
+    #[must_use]
+    #[allow(clippy::unused_self)]
+    pub fn cycleway_width(&self, oneway: bool) -> Metre {
+        // https://www.crow.nl/ Design manual for bicycle traffic, recommended widths
+        if oneway {
+            Metre::new(2.0)
+        } else {
+            Metre::new(3.0)
+        }
+    }
+
+    /// Width of a parking lane, from the kerb to the edge of the carriageway
+    #[must_use]
+    pub fn parking_width(&self, orientation: ParkingOrientation) -> Metre {
+        match orientation {
+            ParkingOrientation::Parallel => {
+                if self.is_netherlands() {
+                    // https://www.crow.nl/ ASVV, parallel parking
+                    Metre::new(2.0)
+                } else if self.is_united_kingdom() {
+                    // Guessed, TODO: find DfT source.
+                    Metre::new(1.8)
+                } else {
+                    Metre::new(2.3)
+                }
+            },
+            // Guessed, a car at 45 degrees, TODO: find sources
+            ParkingOrientation::Diagonal => Metre::new(4.5),
+            // Guessed, the length of a car, TODO: find sources
+            ParkingOrientation::Perpendicular => Metre::new(5.0),
+        }
+    }
+
+    /// Orientation of parking where it is not tagged, parallel unless the subdivision differs
+    #[must_use]
+    pub fn default_parking_orientation(&self) -> ParkingOrientation {
+        self.subdivision_rules()
+            .and_then(SubdivisionRules::get_parking_orientation)
+            .unwrap_or(ParkingOrientation::Parallel)
+    }
+
     /// Road paint colour separating opposite directions of motor traffic
     /// default is white
     #[must_use]
+    #[cfg_attr(not(feature = "country"), allow(clippy::unused_self))]
     pub fn separator_motor_color(&self) -> Color {
-        match self
+        #[cfg(feature = "country")]
+        let region = self
             .country
             .as_ref()
             .map(|c| c.alpha3)
             .and_then(locale_codes::country::lookup)
             .and_then(|c| c.region_code)
             .and_then(locale_codes::region::lookup)
-            .map(|region| region.name.as_str())
-        {
+            .map(|region| region.name.as_str());
+        #[cfg(not(feature = "country"))]
+        let region: Option<&str> = None;
+        match region {
             Some("Americas") => Color::Yellow,
             Some(_) | None => Color::White,
         }
@@ -62,58 +228,154 @@ impl Locale {
     /// default is 0.2, TODO: is this a good default?
     #[must_use]
     pub fn separator_motor_width(&self) -> Metre {
-        match &self.country {
-            Some(c)
-                if c == &Country::the_united_kingdom_of_great_britain_and_northern_ireland() =>
-            {
-                // https://en.wikisource.org/wiki/Traffic_Signs_Manual/Chapter_5/2009/4
-                Metre::new(0.1)
+        if self.is_united_kingdom() {
+            // https://en.wikisource.org/wiki/Traffic_Signs_Manual/Chapter_5/2009/4
+            Metre::new(0.1)
+        } else {
+            Metre::new(0.2)
+        }
+    }
+
+    /// Speed limit implied on a living street or shared space, where traffic is at walking pace
+    #[must_use]
+    pub fn living_street_max_speed(&self) -> Speed {
+        if self.is_netherlands() {
+            // RVV 1990 article 45, "stapvoets", taken as 15 km/h
+            Speed::Kph(15.0)
+        } else if self.is_united_kingdom() {
+            // Guessed, home zones and shared spaces are typically 20 mph zones
+            Speed::Mph(20.0)
+        } else {
+            // e.g. the begegnungszone, zone de rencontre, or woonerf of central Europe
+            Speed::Kph(20.0)
+        }
+    }
+
+    /// Speed limit implied for the highway type, where it does not depend on the surroundings
+    #[must_use]
+    pub fn default_max_speed(&self, highway: HighwayType) -> Option<Speed> {
+        if let Some(speed) = self
+            .subdivision_rules()
+            .and_then(|rules| rules.get_max_speed(highway))
+        {
+            return Some(speed);
+        }
+        match highway {
+            HighwayType::LivingStreet => Some(self.living_street_max_speed()),
+            HighwayType::Classified(HighwayImportance::Motorway) => {
+                if self.is_united_kingdom() {
+                    Some(Speed::Mph(70.0))
+                } else if self.is_netherlands() {
+                    // RVV 1990 article 21
+                    Some(Speed::Kph(130.0))
+                } else {
+                    // e.g. Germany has no general speed limit on motorways
+                    None
+                }
             },
-            _ => Metre::new(0.2),
+            _ => None,
         }
     }
 
+    /// The country is the Netherlands, always false without the `country` feature
+    #[cfg(feature = "country")]
+    pub(crate) fn is_netherlands(&self) -> bool {
+        self.country == Some(Country::the_netherlands())
+    }
+
+    #[cfg(not(feature = "country"))]
+    #[allow(clippy::unused_self)]
+    pub(crate) fn is_netherlands(&self) -> bool {
+        false
+    }
+
+    /// The country is the United Kingdom, always false without the `country` feature
+    #[cfg(feature = "country")]
+    pub(crate) fn is_united_kingdom(&self) -> bool {
+        self.country == Some(Country::the_united_kingdom_of_great_britain_and_northern_ireland())
+    }
+
+    #[cfg(not(feature = "country"))]
+    #[allow(clippy::unused_self)]
+    pub(crate) fn is_united_kingdom(&self) -> bool {
+        false
+    }
+
+    /// The country is Belgium, always false without the `country` feature
+    #[cfg(feature = "country")]
+    pub(crate) fn is_belgium(&self) -> bool {
+        self.country == Some(Country::belgium())
+    }
+
+    #[cfg(not(feature = "country"))]
+    #[allow(clippy::unused_self)]
+    pub(crate) fn is_belgium(&self) -> bool {
+        false
+    }
+
+    /// The subdivision is California, in the United States, always false without the `country` feature
+    #[cfg(feature = "country")]
+    pub(crate) fn is_california(&self) -> bool {
+        self.country == Some(Country::the_united_states_of_america())
+            && self.iso_3166_2_subdivision.as_deref() == Some("CA")
+    }
+
+    #[cfg(not(feature = "country"))]
+    #[allow(clippy::unused_self)]
+    pub(crate) fn is_california(&self) -> bool {
+        false
+    }
+
+    /// The centre line of a two lane priority road is continuous, forbidding overtaking
+    // https://www.rovz.nl/sites/zl-rovz/files/rovz_infra_ehkfoldeng.pdf
+    pub(crate) fn has_solid_priority_road_centre_line(&self) -> bool {
+        self.is_netherlands()
+    }
+
     /// Highway type with no `lanes=*` has:
     /// - (false) one lane with travel in both directions or
-    /// - (true) two lanes with travel in opposite directions
-    #[allow(clippy::unused_self)]
+    /// - (true) lanes with travel in opposite directions
     #[must_use]
     pub fn has_split_lanes(&self, highway: HighwayType) -> bool {
-        matches!(
-            highway,
+        self.default_directional_lanes(highway).is_some()
+    }
+
+    /// Highway type with no `lanes=*` has:
+    /// - (`None`) one lane with travel in both directions or
+    /// - (`Some(n)`) n lanes in each direction, or in the direction of a oneway
+    #[must_use]
+    pub fn default_directional_lanes(&self, highway: HighwayType) -> Option<usize> {
+        match highway {
+            HighwayType::Classified(HighwayImportance::Motorway) => Some(2),
+            // https://www.crow.nl/ residential access roads have no centre line
+            HighwayType::Residential if self.is_netherlands() => None,
             HighwayType::Classified(
+                HighwayImportance::Trunk
+                | HighwayImportance::Primary
+                | HighwayImportance::Secondary
+                | HighwayImportance::Tertiary,
+            )
+            | HighwayType::Link(
                 HighwayImportance::Motorway
-                    | HighwayImportance::Trunk
-                    | HighwayImportance::Primary
-                    | HighwayImportance::Secondary
-                    | HighwayImportance::Tertiary,
-            ) | HighwayType::Link(
-                HighwayImportance::Motorway
-                    | HighwayImportance::Trunk
-                    | HighwayImportance::Primary
-                    | HighwayImportance::Secondary
-                    | HighwayImportance::Tertiary,
-            ) | HighwayType::Residential
-        )
+                | HighwayImportance::Trunk
+                | HighwayImportance::Primary
+                | HighwayImportance::Secondary
+                | HighwayImportance::Tertiary,
+            )
+            | HighwayType::Residential => Some(1),
+            _ => None,
+        }
     }
 
-    /// Highway type has shoulder(s) by default
+    /// Highway type with no `oneway=*` is a oneway,
+    /// as the carriageways of a motorway and its links are mapped as separate ways
     #[allow(clippy::unused_self)]
     #[must_use]
-    pub fn has_shoulder(&self, highway: HighwayType) -> bool {
+    pub fn is_oneway_by_default(&self, highway: HighwayType) -> bool {
         matches!(
             highway,
-            HighwayType::Classified(
-                HighwayImportance::Motorway
-                    | HighwayImportance::Trunk
-                    | HighwayImportance::Primary
-                    | HighwayImportance::Secondary,
-            ) | HighwayType::Link(
-                HighwayImportance::Motorway
-                    | HighwayImportance::Trunk
-                    | HighwayImportance::Primary
-                    | HighwayImportance::Secondary,
-            )
+            HighwayType::Classified(HighwayImportance::Motorway)
+                | HighwayType::Link(HighwayImportance::Motorway)
         )
     }
 }
@@ -121,12 +383,18 @@ impl Locale {
 /// Configuration to build locale, context about the place where an OSM way exists.
 #[derive(Default)]
 pub struct Config {
-    way_id: Option<u64>,
+    #[cfg_attr(not(feature = "country"), allow(dead_code))]
     iso_3166_1_alpha_2: Option<String>,
+    #[cfg_attr(not(feature = "country"), allow(dead_code))]
     iso_3166_1_alpha_3: Option<String>,
     iso_3166_2_subdivision: Option<String>,
+    #[cfg(feature = "country")]
     country: Option<Country>,
     driving_side: Option<DrivingSide>,
+    language: Option<String>,
+    units: Option<Units>,
+    width_table: Option<WidthTable>,
+    subdivisions: Option<Subdivisions>,
 }
 
 impl Config {
@@ -135,11 +403,28 @@ impl Config {
         Self::default()
     }
 
-    #[allow(clippy::missing_panics_doc, clippy::todo)]
-    #[must_use]
-    pub fn way_id(mut self, id: u64) -> Self {
-        self.way_id = Some(id);
-        todo!();
+    /// Assign the country, subdivision, and driving side of the areas enclosing an OSM way,
+    /// looked up with Overpass, see [`crate::overpass::get_region`]
+    ///
+    /// Anything already assigned is kept.
+    ///
+    /// # Errors
+    ///
+    /// May occur when processing a request.
+    #[cfg(feature = "overpass")]
+    pub async fn way_id(mut self, id: u64) -> Result<Self, crate::overpass::Error> {
+        let region = crate::overpass::get_region(&id).await?;
+        #[cfg(feature = "country")]
+        let has_country = self.country.is_some();
+        #[cfg(not(feature = "country"))]
+        let has_country = false;
+        if self.iso_3166_1_alpha_2.is_none() && self.iso_3166_1_alpha_3.is_none() && !has_country {
+            self = self.iso_3166_option(region.iso_3166.as_deref());
+        }
+        if self.driving_side.is_none() {
+            self.driving_side = region.driving_side;
+        }
+        Ok(self)
     }
 
     /// Assign ISO-3166
@@ -174,6 +459,34 @@ impl Config {
         self
     }
 
+    /// Assign the country or subdivision of a point from the built in outlines,
+    /// see [`Boundaries::builtin`].
+    ///
+    /// Anything already assigned is kept.
+    /// The outlines are read on every call, for many points see [`Config::coordinates_in`].
+    #[cfg(feature = "country")]
+    #[must_use]
+    pub fn coordinates(self, lat: f64, lon: f64) -> Self {
+        self.coordinates_in(&Boundaries::builtin(), lat, lon)
+    }
+
+    /// Assign the country or subdivision of a point from outlines, see [`Boundaries`].
+    ///
+    /// Anything already assigned is kept.
+    /// The driving side follows from the country, see [`Config::build`].
+    #[cfg(feature = "country")]
+    #[must_use]
+    pub fn coordinates_in(self, boundaries: &Boundaries, lat: f64, lon: f64) -> Self {
+        if self.iso_3166_1_alpha_2.is_some()
+            || self.iso_3166_1_alpha_3.is_some()
+            || self.country.is_some()
+        {
+            return self;
+        }
+        self.iso_3166_option(boundaries.get(lat, lon))
+    }
+
+    #[cfg(feature = "country")]
     #[must_use]
     pub fn country(mut self, country: Country) -> Self {
         self.country = Some(country);
@@ -186,9 +499,38 @@ impl Config {
         self
     }
 
+    /// Override the language of the country, as ISO 639-1, e.g. `fr`
+    #[must_use]
+    pub fn language(mut self, language: &str) -> Self {
+        self.language = Some(language.to_owned());
+        self
+    }
+
+    /// Override the units of the country, see [`Units`]
+    #[must_use]
+    pub fn units(mut self, units: Units) -> Self {
+        self.units = Some(units);
+        self
+    }
+
+    /// Override the built in width table, see [`WidthTable::builtin`]
+    #[must_use]
+    pub fn width_table(mut self, table: WidthTable) -> Self {
+        self.width_table = Some(table);
+        self
+    }
+
+    /// Override the built in rules of subdivisions, see [`Subdivisions::builtin`]
+    #[must_use]
+    pub fn subdivisions(mut self, subdivisions: Subdivisions) -> Self {
+        self.subdivisions = Some(subdivisions);
+        self
+    }
+
     #[must_use]
     pub fn build(&self) -> Locale {
         // TODO, more business logic
+        #[cfg(feature = "country")]
         let country = match (
             &self.iso_3166_1_alpha_2,
             &self.iso_3166_1_alpha_3,
@@ -202,16 +544,161 @@ impl Config {
             (None | Some(_), None | Some(_), _, Some(_c)) => unimplemented!(),
             (Some(_), Some(_), _, None) => unimplemented!(),
         };
+        #[cfg(feature = "country")]
+        let driving_side = self.driving_side.unwrap_or_else(|| {
+            country
+                .as_ref()
+                .map_or(DrivingSide::Right, DrivingSide::of_country)
+        });
+        #[cfg(not(feature = "country"))]
+        let driving_side = self.driving_side.unwrap_or(DrivingSide::Right);
+        #[cfg(feature = "country")]
+        let units = self.units.unwrap_or_else(|| {
+            country.as_ref().map_or(Units::Metric, |country| {
+                if IMPERIAL_UNITS.contains(&country.alpha2) {
+                    Units::Imperial
+                } else {
+                    Units::Metric
+                }
+            })
+        });
+        #[cfg(not(feature = "country"))]
+        let units = self.units.unwrap_or_default();
+        #[cfg(feature = "country")]
+        let language = self.language.clone().or_else(|| {
+            let country = country.as_ref()?;
+            LANGUAGES
+                .iter()
+                .find(|(alpha2, _)| *alpha2 == country.alpha2)
+                .map(|(_, language)| (*language).to_owned())
+        });
+        #[cfg(not(feature = "country"))]
+        let language = self.language.clone();
         Locale {
+            #[cfg(feature = "country")]
             country,
             iso_3166_2_subdivision: self.iso_3166_2_subdivision.clone(),
-            driving_side: self.driving_side.unwrap_or(DrivingSide::Right),
+            driving_side,
+            language,
+            units,
+            width_table: self.width_table.clone().unwrap_or_else(WidthTable::builtin),
+            subdivisions: self
+                .subdivisions
+                .clone()
+                .unwrap_or_else(Subdivisions::builtin),
         }
     }
 }
 
+/// Countries that sign distances in feet and inches, by ISO 3166-1 alpha-2
+#[cfg(feature = "country")]
+const IMPERIAL_UNITS: &[&str] = &["GB", "LR", "MM", "US"];
+
+/// The language of signs by ISO 3166-1 alpha-2 country, as ISO 639-1,
+/// for countries with one predominant language
+#[cfg(feature = "country")]
+const LANGUAGES: &[(&str, &str)] = &[
+    ("AR", "es"),
+    ("AT", "de"),
+    ("AU", "en"),
+    ("BR", "pt"),
+    ("CL", "es"),
+    ("CN", "zh"),
+    ("CO", "es"),
+    ("CZ", "cs"),
+    ("DE", "de"),
+    ("DK", "da"),
+    ("EE", "et"),
+    ("ES", "es"),
+    ("FR", "fr"),
+    ("GB", "en"),
+    ("GR", "el"),
+    ("HR", "hr"),
+    ("HU", "hu"),
+    ("IE", "en"),
+    ("IS", "is"),
+    ("IT", "it"),
+    ("JP", "ja"),
+    ("KR", "ko"),
+    ("LT", "lt"),
+    ("LV", "lv"),
+    ("MX", "es"),
+    ("NL", "nl"),
+    ("NO", "no"),
+    ("NZ", "en"),
+    ("PL", "pl"),
+    ("PT", "pt"),
+    ("RO", "ro"),
+    ("RS", "sr"),
+    ("RU", "ru"),
+    ("SE", "sv"),
+    ("SI", "sl"),
+    ("SK", "sk"),
+    ("TR", "tr"),
+    ("UA", "uk"),
+    ("US", "en"),
+];
+
+/// Locales built once and shared, for many ways in the same places.
+///
+/// ```
+/// use std::sync::Arc;
+/// use osm2lanes::locale::Cache;
+///
+/// let mut cache = Cache::default();
+/// let first = cache.get(Some("DE-NW"), None);
+/// let second = cache.get(Some("DE-NW"), None);
+/// assert!(Arc::ptr_eq(&first, &second));
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+pub struct Cache {
+    locales: HashMap<Option<DrivingSide>, HashMap<String, Arc<Locale>>>,
+}
+
+#[cfg(feature = "std")]
+impl Cache {
+    /// The locale for an ISO 3166 code, see [`Config::iso_3166`],
+    /// and optionally a driving side, see [`Config::driving_side`].
+    ///
+    /// The locale is built on first use.
+    ///
+    /// # Panics
+    ///
+    /// Cannot determine ISO3166 from code
+    pub fn get(
+        &mut self,
+        iso_3166: Option<&str>,
+        driving_side: Option<DrivingSide>,
+    ) -> Arc<Locale> {
+        let locales = self.locales.entry(driving_side).or_default();
+        let code = iso_3166.unwrap_or_default();
+        if let Some(locale) = locales.get(code) {
+            return Arc::clone(locale);
+        }
+        let mut config = Locale::builder().iso_3166_option(iso_3166);
+        if let Some(driving_side) = driving_side {
+            config = config.driving_side(driving_side);
+        }
+        let locale = Arc::new(config.build());
+        locales.insert(code.to_owned(), Arc::clone(&locale));
+        locale
+    }
+
+    /// Number of cached locales
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.locales.values().map(HashMap::len).sum()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
 /// Do vehicles travel on the right or left side of a road?
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum DrivingSide {
@@ -219,6 +706,18 @@ pub enum DrivingSide {
     Left,
 }
 
+/// Countries and territories that drive on the left, by ISO 3166-1 alpha-2
+///
+/// <https://en.wikipedia.org/wiki/Left-_and_right-hand_traffic>
+#[cfg(feature = "country")]
+const LEFT_HAND_TRAFFIC: &[&str] = &[
+    "AG", "AI", "AU", "BB", "BD", "BM", "BN", "BS", "BT", "BW", "CC", "CK", "CX", "CY", "DM", "FJ",
+    "FK", "GB", "GD", "GG", "GY", "HK", "ID", "IE", "IM", "IN", "JE", "JM", "JP", "KE", "KI", "KN",
+    "KY", "LC", "LK", "LS", "MO", "MS", "MT", "MU", "MV", "MW", "MY", "MZ", "NA", "NF", "NP", "NR",
+    "NU", "NZ", "PG", "PK", "PN", "SB", "SC", "SG", "SH", "SR", "SZ", "TC", "TH", "TK", "TL", "TO",
+    "TT", "TV", "TZ", "UG", "VC", "VG", "VI", "WS", "ZA", "ZM", "ZW",
+];
+
 impl DrivingSide {
     #[must_use]
     pub fn opposite(&self) -> Self {
@@ -227,9 +726,19 @@ impl DrivingSide {
             Self::Left => Self::Right,
         }
     }
+
+    /// The side that traffic drives on in the country
+    #[cfg(feature = "country")]
+    fn of_country(country: &Country) -> Self {
+        if LEFT_HAND_TRAFFIC.contains(&country.alpha2) {
+            Self::Left
+        } else {
+            Self::Right
+        }
+    }
 }
 
-impl std::str::FromStr for DrivingSide {
+impl core::str::FromStr for DrivingSide {
     type Err = String;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
@@ -249,11 +758,16 @@ impl ToString for DrivingSide {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "country"))]
 mod tests {
+    use std::sync::Arc;
+
     use celes::Country;
+    use osm_tag_schemes::{Access, HighwayImportance, HighwayType, ParkingOrientation};
 
-    use crate::locale::{DrivingSide, Locale};
+    use crate::locale::{Cache, DrivingSide, Locale, SubdivisionRules, Subdivisions, WidthTable};
+    use crate::metric::{Metre, Speed, Units};
+    use crate::road::{Designated, Direction};
 
     #[test]
     fn test_locale() {
@@ -264,4 +778,216 @@ mod tests {
         assert_eq!(locale.driving_side, DrivingSide::Right);
         assert_eq!(locale.country.unwrap(), Country::germany());
     }
+
+    #[test]
+    fn test_locale_driving_side() {
+        let gb = Locale::builder().iso_3166("GB").build();
+        assert_eq!(gb.driving_side, DrivingSide::Left);
+        let jp = Locale::builder().iso_3166("JPN").build();
+        assert_eq!(jp.driving_side, DrivingSide::Left);
+        let nl = Locale::builder().iso_3166("NL").build();
+        assert_eq!(nl.driving_side, DrivingSide::Right);
+        let gb_right = Locale::builder()
+            .iso_3166("GB")
+            .driving_side(DrivingSide::Right)
+            .build();
+        assert_eq!(gb_right.driving_side, DrivingSide::Right);
+        assert_eq!(Locale::builder().build().driving_side, DrivingSide::Right);
+    }
+
+    #[test]
+    fn test_locale_units() {
+        assert_eq!(
+            Locale::builder().iso_3166("US-CA").build().units,
+            Units::Imperial
+        );
+        assert_eq!(
+            Locale::builder().iso_3166("GB").build().units,
+            Units::Imperial
+        );
+        assert_eq!(
+            Locale::builder().iso_3166("NL").build().units,
+            Units::Metric
+        );
+        assert_eq!(Locale::builder().build().units, Units::Metric);
+        assert_eq!(
+            Locale::builder()
+                .iso_3166("US")
+                .units(Units::Metric)
+                .build()
+                .units,
+            Units::Metric
+        );
+    }
+
+    #[test]
+    fn test_locale_language() {
+        let de = Locale::builder().iso_3166("DE-NW").build();
+        assert_eq!(de.language.as_deref(), Some("de"));
+        // Belgium has several languages, so none is assumed
+        let be = Locale::builder().iso_3166("BE").build();
+        assert_eq!(be.language, None);
+        let be_fr = Locale::builder().iso_3166("BE").language("fr").build();
+        assert_eq!(be_fr.language.as_deref(), Some("fr"));
+    }
+
+    #[test]
+    fn test_locale_coordinates() {
+        let london = Locale::builder().coordinates(51.51, -0.13).build();
+        assert_eq!(
+            london.country.unwrap(),
+            Country::the_united_kingdom_of_great_britain_and_northern_ireland()
+        );
+        assert_eq!(london.driving_side, DrivingSide::Left);
+        let san_francisco = Locale::builder().coordinates(37.77, -122.42).build();
+        assert_eq!(
+            san_francisco.country.unwrap(),
+            Country::the_united_states_of_america()
+        );
+        assert_eq!(san_francisco.iso_3166_2_subdivision.as_deref(), Some("CA"));
+        let assigned = Locale::builder()
+            .iso_3166("NL")
+            .coordinates(51.51, -0.13)
+            .build();
+        assert_eq!(assigned.country.unwrap(), Country::the_netherlands());
+    }
+
+    #[test]
+    fn test_locale_subdivisions() {
+        let motorway = HighwayType::Classified(HighwayImportance::Motorway);
+        let california = Locale::builder().iso_3166("US-CA").build();
+        assert_eq!(
+            california.default_max_speed(motorway),
+            Some(Speed::Mph(65.0))
+        );
+        let new_york = Locale::builder().iso_3166("US-NY").build();
+        assert_eq!(new_york.default_max_speed(motorway), None);
+        let mut subdivisions = Subdivisions::builtin();
+        subdivisions.insert(
+            "US-NY",
+            SubdivisionRules::new()
+                .max_speed(motorway, Speed::Mph(55.0))
+                .parking_orientation(ParkingOrientation::Perpendicular),
+        );
+        let new_york = Locale::builder()
+            .iso_3166("US-NY")
+            .subdivisions(subdivisions)
+            .build();
+        assert_eq!(new_york.default_max_speed(motorway), Some(Speed::Mph(55.0)));
+        assert_eq!(
+            new_york.default_parking_orientation(),
+            ParkingOrientation::Perpendicular
+        );
+        assert_eq!(
+            california.default_parking_orientation(),
+            ParkingOrientation::Parallel
+        );
+    }
+
+    #[test]
+    fn test_legal_defaults() {
+        let be = Locale::builder().iso_3166("BE").build();
+        let nl = Locale::builder().iso_3166("NL").build();
+        assert!(be
+            .defaults()
+            .is_oneway_bicycle_exempt(HighwayType::Residential));
+        assert!(!be
+            .defaults()
+            .is_oneway_bicycle_exempt(HighwayType::Classified(HighwayImportance::Primary)));
+        assert!(!nl
+            .defaults()
+            .is_oneway_bicycle_exempt(HighwayType::Residential));
+        assert_eq!(
+            nl.defaults()
+                .bicycle_access(HighwayType::Residential, false),
+            Some(Access::Yes)
+        );
+        assert_eq!(
+            nl.defaults()
+                .bicycle_access(HighwayType::Classified(HighwayImportance::Primary), true),
+            Some(Access::No)
+        );
+        assert!(nl
+            .defaults()
+            .has_shoulder(HighwayType::Classified(HighwayImportance::Motorway)));
+    }
+
+    #[test]
+    fn test_travel_width_bidirectional_cycle_track() {
+        let nl = Locale::builder().iso_3166("NL").build();
+        let width = |locale: &Locale, direction| {
+            locale.travel_width(&Designated::Bicycle, direction, HighwayType::Residential)
+        };
+        assert_eq!(width(&nl, Some(Direction::Both)), Metre::new(3.0));
+        assert_eq!(width(&nl, Some(Direction::Forward)), Metre::new(2.0));
+        let default = Locale::builder().build();
+        assert_eq!(width(&default, Some(Direction::Both)), Metre::new(2.5));
+        assert_eq!(width(&default, None), Metre::new(2.0));
+    }
+
+    #[test]
+    fn test_min_travel_width() {
+        let nl = Locale::builder().iso_3166("NL").build();
+        let default = Locale::builder().build();
+        for designated in [Designated::Motor, Designated::Foot, Designated::Bicycle] {
+            for locale in [&nl, &default] {
+                assert!(
+                    locale.min_travel_width(&designated).val()
+                        <= locale
+                            .travel_width(&designated, None, HighwayType::Residential)
+                            .val()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_travel_width_motorway() {
+        let width = |locale: &Locale, highway| {
+            locale.travel_width(&Designated::Motor, Some(Direction::Forward), highway)
+        };
+        let motorway = HighwayType::Classified(HighwayImportance::Motorway);
+        let primary = HighwayType::Classified(HighwayImportance::Primary);
+        let gb = Locale::builder().iso_3166("GB").build();
+        assert_eq!(width(&gb, motorway), Metre::new(3.65));
+        assert_eq!(width(&gb, primary), Metre::new(3.0));
+        let default = Locale::builder().build();
+        assert_eq!(width(&default, motorway), Metre::new(3.75));
+        assert_eq!(width(&default, primary), Metre::new(3.5));
+    }
+
+    #[test]
+    fn test_travel_width_table() {
+        let width = |locale: &Locale, highway| {
+            locale.travel_width(&Designated::Motor, Some(Direction::Forward), highway)
+        };
+        let primary = HighwayType::Classified(HighwayImportance::Primary);
+        let nl = Locale::builder().iso_3166("NL").build();
+        assert_eq!(width(&nl, primary), Metre::new(3.35));
+        assert_eq!(width(&nl, HighwayType::Residential), Metre::new(3.1));
+        let us = Locale::builder().iso_3166("US").build();
+        assert_eq!(width(&us, primary), "12 ft".parse().unwrap());
+        assert_eq!(width(&us, HighwayType::Residential), Metre::new(3.5));
+
+        let mut table = WidthTable::builtin();
+        table.insert("US", Some("NY"), None, Metre::new(3.3));
+        let ny = Locale::builder()
+            .iso_3166("US-NY")
+            .width_table(table)
+            .build();
+        assert_eq!(width(&ny, primary), Metre::new(3.3));
+    }
+
+    #[test]
+    fn test_locale_cache() {
+        let mut cache = Cache::default();
+        let nl = cache.get(Some("NL"), None);
+        assert_eq!(nl.country.as_ref().unwrap(), &Country::the_netherlands());
+        assert!(Arc::ptr_eq(&nl, &cache.get(Some("NL"), None)));
+        let gb = cache.get(Some("GB"), Some(DrivingSide::Left));
+        assert_eq!(gb.driving_side, DrivingSide::Left);
+        assert!(!Arc::ptr_eq(&gb, &cache.get(Some("GB"), None)));
+        assert_eq!(cache.get(None, None).country, None);
+        assert_eq!(cache.len(), 4);
+    }
 }
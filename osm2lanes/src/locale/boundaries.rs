@@ -0,0 +1,186 @@
+use alloc::borrow::ToOwned;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// Country and subdivision outlines embedded in the crate
+const BUILTIN: &str = include_str!("boundaries.json");
+
+/// An outline of a country or subdivision, as longitude and latitude
+#[derive(Clone, Debug, PartialEq)]
+struct Boundary {
+    iso_3166: String,
+    polygon: Vec<(f64, f64)>,
+}
+
+impl Boundary {
+    /// Is the point inside the outline, by the even-odd rule
+    fn contains(&self, lat: f64, lon: f64) -> bool {
+        let mut inside = false;
+        let mut previous = match self.polygon.last() {
+            Some(previous) => previous,
+            None => return false,
+        };
+        for vertex in &self.polygon {
+            let ((x_1, y_1), (x_2, y_2)) = (previous, vertex);
+            if (y_1 > &lat) != (y_2 > &lat) && lon < (x_2 - x_1) * (lat - y_1) / (y_2 - y_1) + x_1 {
+                inside = !inside;
+            }
+            previous = vertex;
+        }
+        inside
+    }
+
+    /// A subdivision is more specific than a country
+    fn is_subdivision(&self) -> bool {
+        self.iso_3166.contains('-')
+    }
+}
+
+/// Outlines of countries and subdivisions, to find the ISO 3166 code of a point.
+///
+/// The built in outlines are low resolution and only cover some countries,
+/// so points near a border may be assigned to the wrong country.
+///
+/// ```
+/// use osm2lanes::locale::Boundaries;
+///
+/// let boundaries = Boundaries::builtin();
+/// assert_eq!(boundaries.get(52.37, 4.89), Some("NL"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Boundaries {
+    boundaries: Vec<Boundary>,
+}
+
+impl Boundaries {
+    /// The outlines embedded in the crate
+    ///
+    /// # Panics
+    ///
+    /// The embedded outlines are invalid
+    #[must_use]
+    pub fn builtin() -> Self {
+        #[allow(clippy::expect_used)]
+        Self::from_json(BUILTIN).expect("embedded boundaries")
+    }
+
+    /// Read outlines from a JSON array, e.g.
+    /// `[{"iso_3166": "US-CA", "polygon": [[-124.4, 42.0], [-120.0, 42.0], [-120.0, 39.0]]}]`
+    ///
+    /// The `iso_3166` is an ISO 3166-1 alpha-2 or ISO 3166-2 code,
+    /// and the `polygon` is an outline of longitude and latitude pairs.
+    /// A country or subdivision made up of several outlines is repeated.
+    ///
+    /// # Errors
+    ///
+    /// Invalid JSON or outlines
+    pub fn from_json(json: &str) -> Result<Self, BoundariesError> {
+        let value: serde_json::Value =
+            serde_json::from_str(json).map_err(|e| BoundariesError::Json(e.to_string()))?;
+        let boundaries = value
+            .as_array()
+            .ok_or_else(|| BoundariesError::Json("expected an array of outlines".to_owned()))?;
+        let boundaries = boundaries
+            .iter()
+            .enumerate()
+            .map(|(index, boundary)| {
+                let invalid = || BoundariesError::Boundary(index);
+                let iso_3166 = boundary
+                    .get("iso_3166")
+                    .and_then(serde_json::Value::as_str)
+                    .ok_or_else(invalid)?
+                    .to_owned();
+                let polygon = boundary
+                    .get("polygon")
+                    .and_then(serde_json::Value::as_array)
+                    .ok_or_else(invalid)?
+                    .iter()
+                    .map(|vertex| match vertex.as_array().map(Vec::as_slice) {
+                        Some([lon, lat]) => Ok((
+                            lon.as_f64().ok_or_else(invalid)?,
+                            lat.as_f64().ok_or_else(invalid)?,
+                        )),
+                        _ => Err(invalid()),
+                    })
+                    .collect::<Result<Vec<(f64, f64)>, BoundariesError>>()?;
+                if polygon.len() < 3 {
+                    return Err(invalid());
+                }
+                Ok(Boundary { iso_3166, polygon })
+            })
+            .collect::<Result<Vec<Boundary>, BoundariesError>>()?;
+        Ok(Self { boundaries })
+    }
+
+    /// The ISO 3166 code of a point, a subdivision where known, or else a country
+    #[must_use]
+    pub fn get(&self, lat: f64, lon: f64) -> Option<&str> {
+        self.boundaries
+            .iter()
+            .filter(|boundary| boundary.contains(lat, lon))
+            .max_by_key(|boundary| boundary.is_subdivision())
+            .map(|boundary| boundary.iso_3166.as_str())
+    }
+}
+
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug)]
+pub enum BoundariesError {
+    /// Invalid JSON, or not an array
+    Json(String),
+    /// Invalid outline, by index
+    Boundary(usize),
+}
+
+impl core::fmt::Display for BoundariesError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Json(e) => write!(f, "invalid boundaries: {e}"),
+            Self::Boundary(index) => write!(f, "invalid boundary {index}"),
+        }
+    }
+}
+
+impl std::error::Error for BoundariesError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_boundaries_builtin() {
+        let boundaries = Boundaries::builtin();
+        // Amsterdam
+        assert_eq!(boundaries.get(52.37, 4.89), Some("NL"));
+        // Brussels
+        assert_eq!(boundaries.get(50.85, 4.35), Some("BE"));
+        // Berlin
+        assert_eq!(boundaries.get(52.52, 13.40), Some("DE"));
+        // Paris
+        assert_eq!(boundaries.get(48.86, 2.35), Some("FR"));
+        // London and Belfast
+        assert_eq!(boundaries.get(51.51, -0.13), Some("GB"));
+        assert_eq!(boundaries.get(54.60, -5.93), Some("GB"));
+        // Dublin
+        assert_eq!(boundaries.get(53.35, -6.26), Some("IE"));
+        // New York and San Francisco
+        assert_eq!(boundaries.get(40.71, -74.0), Some("US"));
+        assert_eq!(boundaries.get(37.77, -122.42), Some("US-CA"));
+        // Atlantic Ocean
+        assert_eq!(boundaries.get(45.0, -30.0), None);
+    }
+
+    #[test]
+    fn test_boundaries_from_json() {
+        let boundaries = Boundaries::from_json(
+            r#"[{"iso_3166": "XX", "polygon": [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]}]"#,
+        )
+        .unwrap();
+        assert_eq!(boundaries.get(0.5, 0.5), Some("XX"));
+        assert_eq!(boundaries.get(0.5, 1.5), None);
+        assert!(matches!(
+            Boundaries::from_json(r#"[{"iso_3166": "XX", "polygon": [[0.0, 0.0]]}]"#),
+            Err(BoundariesError::Boundary(0))
+        ));
+    }
+}
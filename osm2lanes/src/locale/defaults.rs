@@ -0,0 +1,74 @@
+use osm_tag_schemes::{Access, HighwayImportance, HighwayType};
+
+use super::Locale;
+
+/// Rules of the jurisdiction of a locale, implied where the way is not tagged otherwise,
+/// see [`Locale::defaults`]
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Copy, Debug)]
+pub struct LegalDefaults<'locale> {
+    locale: &'locale Locale,
+}
+
+impl<'locale> LegalDefaults<'locale> {
+    pub(super) fn new(locale: &'locale Locale) -> Self {
+        Self { locale }
+    }
+
+    /// Access of pedestrians to the carriageway, not allowed on an expressway
+    #[must_use]
+    #[allow(clippy::unused_self)]
+    pub fn foot_access(&self, _highway: HighwayType, expressway: bool) -> Option<Access> {
+        expressway.then(|| Access::No)
+    }
+
+    /// Access of bicycles to the carriageway,
+    /// not allowed on an expressway, and always allowed on residential roads and living streets
+    #[must_use]
+    #[allow(clippy::unused_self)]
+    pub fn bicycle_access(&self, highway: HighwayType, expressway: bool) -> Option<Access> {
+        if expressway {
+            Some(Access::No)
+        } else if matches!(
+            highway,
+            HighwayType::Residential | HighwayType::LivingStreet
+        ) {
+            Some(Access::Yes)
+        } else {
+            None
+        }
+    }
+
+    /// Bicycles are exempt from a oneway, and may ride against the flow of traffic,
+    /// unless tagged `oneway:bicycle=yes`
+    #[must_use]
+    pub fn is_oneway_bicycle_exempt(&self, highway: HighwayType) -> bool {
+        // Guessed, Belgium opens oneway streets in built up areas to contraflow cycling,
+        // "beperkt eenrichtingsverkeer", taking residential roads and living streets as built up.
+        self.locale.is_belgium()
+            && matches!(
+                highway,
+                HighwayType::Residential | HighwayType::LivingStreet
+            )
+    }
+
+    /// Highway type has shoulder(s) by default, e.g. the emergency shoulders of a motorway
+    #[must_use]
+    #[allow(clippy::unused_self)]
+    pub fn has_shoulder(&self, highway: HighwayType) -> bool {
+        matches!(
+            highway,
+            HighwayType::Classified(
+                HighwayImportance::Motorway
+                    | HighwayImportance::Trunk
+                    | HighwayImportance::Primary
+                    | HighwayImportance::Secondary,
+            ) | HighwayType::Link(
+                HighwayImportance::Motorway
+                    | HighwayImportance::Trunk
+                    | HighwayImportance::Primary
+                    | HighwayImportance::Secondary,
+            )
+        )
+    }
+}
@@ -0,0 +1,163 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use osm_tag_schemes::{HighwayImportance, HighwayType, ParkingOrientation};
+
+use crate::metric::Speed;
+
+/// Rules of a subdivision that differ from the rest of its country,
+/// taking precedence over the defaults of the country
+///
+/// ```
+/// use osm2lanes::locale::SubdivisionRules;
+/// use osm2lanes::metric::Speed;
+/// use osm_tag_schemes::{HighwayImportance, HighwayType, ParkingOrientation};
+///
+/// let rules = SubdivisionRules::new()
+///     .max_speed(
+///         HighwayType::Classified(HighwayImportance::Motorway),
+///         Speed::Mph(75.0),
+///     )
+///     .parking_orientation(ParkingOrientation::Diagonal);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SubdivisionRules {
+    max_speeds: Vec<(HighwayType, Speed)>,
+    parking_orientation: Option<ParkingOrientation>,
+}
+
+impl SubdivisionRules {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Speed limit implied for the highway type, see [`super::Locale::default_max_speed`]
+    #[must_use]
+    pub fn max_speed(mut self, highway: HighwayType, speed: Speed) -> Self {
+        self.max_speeds.retain(|(h, _)| *h != highway);
+        self.max_speeds.push((highway, speed));
+        self
+    }
+
+    /// Orientation of parking where it is not tagged,
+    /// see [`super::Locale::default_parking_orientation`]
+    #[must_use]
+    pub fn parking_orientation(mut self, orientation: ParkingOrientation) -> Self {
+        self.parking_orientation = Some(orientation);
+        self
+    }
+
+    pub(super) fn get_max_speed(&self, highway: HighwayType) -> Option<Speed> {
+        self.max_speeds
+            .iter()
+            .find(|(h, _)| *h == highway)
+            .map(|(_, speed)| *speed)
+    }
+
+    pub(super) fn get_parking_orientation(&self) -> Option<ParkingOrientation> {
+        self.parking_orientation
+    }
+}
+
+/// Rules by subdivision, see [`SubdivisionRules`].
+///
+/// The built in rules can be extended or overridden, and are used by a locale,
+/// see [`super::Config::subdivisions`].
+///
+/// ```
+/// use osm2lanes::locale::{Locale, SubdivisionRules, Subdivisions};
+/// use osm_tag_schemes::ParkingOrientation;
+///
+/// let mut subdivisions = Subdivisions::builtin();
+/// subdivisions.insert(
+///     "DE-BE",
+///     SubdivisionRules::new().parking_orientation(ParkingOrientation::Diagonal),
+/// );
+/// let locale = Locale::builder()
+///     .iso_3166("DE-BE")
+///     .subdivisions(subdivisions)
+///     .build();
+/// assert_eq!(
+///     locale.default_parking_orientation(),
+///     ParkingOrientation::Diagonal
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Subdivisions {
+    rules: Vec<(String, SubdivisionRules)>,
+}
+
+// Speeds are never NaN
+impl Eq for Subdivisions {}
+
+impl Subdivisions {
+    /// The rules embedded in the crate
+    #[must_use]
+    pub fn builtin() -> Self {
+        let motorway = HighwayType::Classified(HighwayImportance::Motorway);
+        let mut subdivisions = Self::default();
+        // California Vehicle Code section 22349
+        subdivisions.insert(
+            "US-CA",
+            SubdivisionRules::new().max_speed(motorway, Speed::Mph(65.0)),
+        );
+        // Texas Transportation Code section 545.352
+        subdivisions.insert(
+            "US-TX",
+            SubdivisionRules::new().max_speed(motorway, Speed::Mph(70.0)),
+        );
+        subdivisions
+    }
+
+    /// Register the rules of a subdivision, by ISO 3166-2 code, e.g. `US-CA`,
+    /// replacing any earlier rules of the subdivision
+    pub fn insert(&mut self, iso_3166_2: &str, rules: SubdivisionRules) {
+        self.rules
+            .retain(|(code, _)| !code.eq_ignore_ascii_case(iso_3166_2));
+        self.rules.push((iso_3166_2.to_owned(), rules));
+    }
+
+    /// The rules of a subdivision,
+    /// by ISO 3166-1 alpha-2 country and the subdivision part of its ISO 3166-2 code
+    #[must_use]
+    pub fn get(&self, country: &str, subdivision: &str) -> Option<&SubdivisionRules> {
+        self.rules
+            .iter()
+            .find(|(code, _)| {
+                code.split_once('-').map_or(false, |(c, s)| {
+                    c.eq_ignore_ascii_case(country) && s.eq_ignore_ascii_case(subdivision)
+                })
+            })
+            .map(|(_, rules)| rules)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subdivisions() {
+        let motorway = HighwayType::Classified(HighwayImportance::Motorway);
+        let mut subdivisions = Subdivisions::builtin();
+        assert_eq!(
+            subdivisions
+                .get("US", "CA")
+                .and_then(|rules| rules.get_max_speed(motorway)),
+            Some(Speed::Mph(65.0))
+        );
+        assert!(subdivisions.get("US", "NY").is_none());
+        subdivisions.insert(
+            "us-ca",
+            SubdivisionRules::new().parking_orientation(ParkingOrientation::Diagonal),
+        );
+        let rules = subdivisions.get("US", "CA").unwrap();
+        assert_eq!(rules.get_max_speed(motorway), None);
+        assert_eq!(
+            rules.get_parking_orientation(),
+            Some(ParkingOrientation::Diagonal)
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+#[cfg(feature = "country")]
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use osm_tag_schemes::HighwayType;
+
+use crate::metric::Metre;
+
+/// Motor lane widths embedded in the crate
+#[cfg(feature = "country")]
+const BUILTIN: &str = include_str!("width_table.json");
+
+/// A row of the table, for a country, optionally a subdivision, and optionally some highway types
+#[derive(Clone, Debug, PartialEq)]
+struct Row {
+    country: String,
+    subdivision: Option<String>,
+    highways: Option<Vec<HighwayType>>,
+    width: Metre,
+}
+
+impl Row {
+    /// How closely the row matches, if at all,
+    /// where a subdivision is more specific than a highway type
+    fn specificity(
+        &self,
+        country: &str,
+        subdivision: Option<&str>,
+        highway: HighwayType,
+    ) -> Option<(bool, bool)> {
+        if !self.country.eq_ignore_ascii_case(country) {
+            return None;
+        }
+        let subdivision = match (&self.subdivision, subdivision) {
+            (None, _) => false,
+            (Some(row), Some(subdivision)) if row.eq_ignore_ascii_case(subdivision) => true,
+            (Some(_), _) => return None,
+        };
+        let highway = match &self.highways {
+            None => false,
+            Some(highways) if highways.contains(&highway) => true,
+            Some(_) => return None,
+        };
+        Some((subdivision, highway))
+    }
+}
+
+/// Width of a motor lane, by country, subdivision, and highway type,
+/// for lanes that are not on an expressway.
+///
+/// The built in table can be overridden, and is used by a locale, see [`super::Config::width_table`].
+///
+/// ```
+/// use osm2lanes::locale::{Locale, WidthTable};
+/// use osm2lanes::metric::Metre;
+///
+/// let mut table = WidthTable::builtin();
+/// table.insert("NL", None, None, Metre::new(3.25));
+/// let locale = Locale::builder().width_table(table).build();
+/// ```
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WidthTable {
+    rows: Vec<Row>,
+}
+
+// Widths are never NaN
+impl Eq for WidthTable {}
+
+impl WidthTable {
+    /// The table embedded in the crate, empty without the `country` feature
+    ///
+    /// # Panics
+    ///
+    /// The embedded table is invalid
+    #[must_use]
+    pub fn builtin() -> Self {
+        #[cfg(feature = "country")]
+        #[allow(clippy::expect_used)]
+        return Self::from_json(BUILTIN).expect("embedded width table");
+        #[cfg(not(feature = "country"))]
+        Self::default()
+    }
+
+    /// Read a table from a JSON array of rows, e.g.
+    /// `[{"country": "US", "subdivision": "CA", "highway": ["primary"], "width": "12 ft"}]`
+    ///
+    /// The `subdivision` and `highway` are optional,
+    /// the `width` is metres or a distance with a unit, see [`Metre`],
+    /// and any other fields, such as the `source` of the width, are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Invalid JSON or rows
+    #[cfg(feature = "country")]
+    pub fn from_json(json: &str) -> Result<Self, WidthTableError> {
+        let value: serde_json::Value =
+            serde_json::from_str(json).map_err(|e| WidthTableError::Json(e.to_string()))?;
+        let rows = value
+            .as_array()
+            .ok_or_else(|| WidthTableError::Json("expected an array of rows".to_owned()))?;
+        let rows = rows
+            .iter()
+            .enumerate()
+            .map(|(index, row)| {
+                let invalid = || WidthTableError::Row(index);
+                let country = row
+                    .get("country")
+                    .and_then(serde_json::Value::as_str)
+                    .ok_or_else(invalid)?
+                    .to_owned();
+                let subdivision = match row.get("subdivision") {
+                    None => None,
+                    Some(subdivision) => Some(subdivision.as_str().ok_or_else(invalid)?.to_owned()),
+                };
+                let highways = match row.get("highway") {
+                    None => None,
+                    Some(highways) => Some(
+                        highways
+                            .as_array()
+                            .ok_or_else(invalid)?
+                            .iter()
+                            .map(|highway| {
+                                let highway = highway.as_str().ok_or_else(invalid)?;
+                                highway
+                                    .parse()
+                                    .map_err(|_e| WidthTableError::Highway(highway.to_owned()))
+                            })
+                            .collect::<Result<Vec<HighwayType>, WidthTableError>>()?,
+                    ),
+                };
+                let width = match row.get("width") {
+                    Some(serde_json::Value::Number(width)) => {
+                        Metre::new(width.as_f64().ok_or_else(invalid)?)
+                    },
+                    Some(serde_json::Value::String(width)) => {
+                        width.parse().map_err(|_e| invalid())?
+                    },
+                    _ => return Err(invalid()),
+                };
+                Ok(Row {
+                    country,
+                    subdivision,
+                    highways,
+                    width,
+                })
+            })
+            .collect::<Result<Vec<Row>, WidthTableError>>()?;
+        Ok(Self { rows })
+    }
+
+    /// Override the width for a country, optionally only in a subdivision or on a highway type,
+    /// taking precedence over earlier rows that match as closely
+    pub fn insert(
+        &mut self,
+        country: &str,
+        subdivision: Option<&str>,
+        highway: Option<HighwayType>,
+        width: Metre,
+    ) {
+        self.rows.push(Row {
+            country: country.to_owned(),
+            subdivision: subdivision.map(ToOwned::to_owned),
+            highways: highway.map(|highway| alloc::vec![highway]),
+            width,
+        });
+    }
+
+    /// Width of the closest matching row,
+    /// by ISO 3166-1 alpha-2 country, ISO 3166-2 subdivision, and highway type
+    #[must_use]
+    pub fn get(
+        &self,
+        country: &str,
+        subdivision: Option<&str>,
+        highway: HighwayType,
+    ) -> Option<Metre> {
+        self.rows
+            .iter()
+            .filter_map(|row| {
+                row.specificity(country, subdivision, highway)
+                    .map(|specificity| (specificity, row.width))
+            })
+            // the last of equally close rows
+            .max_by_key(|(specificity, _)| *specificity)
+            .map(|(_, width)| width)
+    }
+}
+
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug)]
+pub enum WidthTableError {
+    /// Invalid JSON, or not an array
+    Json(String),
+    /// Invalid row, by index
+    Row(usize),
+    /// Unknown highway type
+    Highway(String),
+}
+
+impl core::fmt::Display for WidthTableError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Json(e) => write!(f, "invalid width table: {e}"),
+            Self::Row(index) => write!(f, "invalid width table row {index}"),
+            Self::Highway(highway) => write!(f, "unknown highway type '{highway}'"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for WidthTableError {}
+
+#[cfg(test)]
+mod tests {
+    use osm_tag_schemes::HighwayImportance;
+
+    use super::*;
+
+    #[test]
+    fn test_width_table_precedence() {
+        let primary = HighwayType::Classified(HighwayImportance::Primary);
+        let mut table = WidthTable::default();
+        table.insert("US", None, None, Metre::new(3.5));
+        table.insert("US", None, Some(primary), Metre::new(3.6));
+        table.insert("US", Some("CA"), None, Metre::new(3.7));
+        assert_eq!(table.get("US", None, primary), Some(Metre::new(3.6)));
+        assert_eq!(
+            table.get("US", None, HighwayType::Residential),
+            Some(Metre::new(3.5))
+        );
+        assert_eq!(table.get("US", Some("CA"), primary), Some(Metre::new(3.7)));
+        assert_eq!(table.get("NL", None, primary), None);
+        table.insert("US", None, None, Metre::new(3.4));
+        assert_eq!(
+            table.get("US", None, HighwayType::Residential),
+            Some(Metre::new(3.4))
+        );
+    }
+
+    #[cfg(feature = "country")]
+    #[test]
+    fn test_width_table_from_json() {
+        let primary = HighwayType::Classified(HighwayImportance::Primary);
+        let builtin = WidthTable::builtin();
+        assert_eq!(builtin.get("US", None, primary), "12 ft".parse().ok());
+        let table = WidthTable::from_json(
+            r#"[{"country": "DE", "subdivision": "BY", "highway": ["primary"], "width": 3.25}]"#,
+        )
+        .unwrap();
+        assert_eq!(table.get("DE", Some("BY"), primary), Some(Metre::new(3.25)));
+        assert_eq!(table.get("DE", None, primary), None);
+        assert!(matches!(
+            WidthTable::from_json(r#"[{"country": "DE", "highway": ["autobahn"], "width": 3.75}]"#),
+            Err(WidthTableError::Highway(_))
+        ));
+        assert!(matches!(
+            WidthTable::from_json(r#"[{"country": "DE"}]"#),
+            Err(WidthTableError::Row(0))
+        ));
+    }
+}
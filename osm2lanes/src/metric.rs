@@ -1,3 +1,19 @@
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::{String, ToString};
+
+/// System of units for distances in output, such as widths in tag values,
+/// speeds keep the unit they were given in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Units {
+    #[default]
+    Metric,
+    /// Feet and inches
+    Imperial,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
@@ -16,26 +32,41 @@ impl Metre {
     pub const fn val(&self) -> f64 {
         self.0
     }
+
+    /// Rounded to the nearest millimetre, without negative zero,
+    /// so that the serialized value is the same across platforms.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn canonical(self) -> Self {
+        Self(canonical(self.0))
+    }
 }
 
-impl std::ops::Add for Metre {
+/// Rounded to three decimal places, without negative zero
+#[cfg(feature = "std")]
+fn canonical(val: f64) -> f64 {
+    // adding positive zero turns negative zero into positive zero
+    (val * 1000.0_f64).round() / 1000.0_f64 + 0.0_f64
+}
+
+impl core::ops::Add for Metre {
     type Output = Self;
     fn add(self, other: Self) -> Self {
         Self(self.0 + other.0)
     }
 }
-impl std::ops::AddAssign for Metre {
+impl core::ops::AddAssign for Metre {
     fn add_assign(&mut self, other: Self) {
         *self = Self(self.0 + other.0);
     }
 }
-impl std::ops::Mul<Metre> for f64 {
+impl core::ops::Mul<Metre> for f64 {
     type Output = Metre;
     fn mul(self, other: Metre) -> Self::Output {
         Metre::new(self * other.val())
     }
 }
-impl std::iter::Sum for Metre {
+impl core::iter::Sum for Metre {
     fn sum<I>(iter: I) -> Self
     where
         I: Iterator<Item = Metre>,
@@ -44,6 +75,138 @@ impl std::iter::Sum for Metre {
     }
 }
 
+#[derive(Debug)]
+pub enum MetreError {
+    Empty,
+    Parse(core::num::ParseFloatError),
+    UnknownUnit(String),
+    OutOfRange,
+}
+
+impl core::fmt::Display for MetreError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "empty"),
+            Self::Parse(e) => e.fmt(f),
+            Self::UnknownUnit(unit) => write!(f, "unknown unit '{unit}'"),
+            Self::OutOfRange => write!(f, "out of range"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MetreError {}
+
+impl From<core::num::ParseFloatError> for MetreError {
+    fn from(e: core::num::ParseFloatError) -> Self {
+        MetreError::Parse(e)
+    }
+}
+
+impl Metre {
+    const INCH: f64 = 0.025_4_f64;
+    const INCHES_PER_FOOT: u32 = 12;
+
+    /// Tag value in metres, or for imperial units in feet and inches, e.g. `12'6"`.
+    ///
+    /// Distances that are not a whole number of inches remain in metres,
+    /// so that the value is not rounded.
+    #[must_use]
+    pub fn to_tag_value(&self, units: Units) -> String {
+        match units {
+            Units::Metric => self.0.to_string(),
+            Units::Imperial => {
+                let inches = self.0 / Self::INCH;
+                let rounded = inches.round();
+                if (inches - rounded).abs() > 1e-6_f64 {
+                    return self.0.to_string();
+                }
+                // a whole number of inches, distances are never negative
+                #[allow(
+                    clippy::as_conversions,
+                    clippy::cast_possible_truncation,
+                    clippy::cast_sign_loss
+                )]
+                let inches = rounded as u64;
+                let inches_per_foot = u64::from(Self::INCHES_PER_FOOT);
+                match (inches / inches_per_foot, inches % inches_per_foot) {
+                    (feet, 0) => format!("{}'", feet),
+                    (feet, inches) => format!("{}'{}\"", feet, inches),
+                }
+            },
+        }
+    }
+}
+
+/// A distance in metres by default, or with a unit,
+/// e.g. `2.5`, `2.5 m`, `250 cm`, `3 ft`, or `12'6"` in feet and inches
+///
+/// <https://wiki.openstreetmap.org/wiki/Map_features/Units#Explicit_specifications>
+impl core::str::FromStr for Metre {
+    type Err = MetreError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(MetreError::Empty);
+        }
+        let metres = if let Some((feet, inches)) = s.split_once('\'') {
+            let inches = match inches.trim() {
+                "" => 0.0_f64,
+                inches => inches
+                    .strip_suffix('"')
+                    .ok_or_else(|| MetreError::UnknownUnit(inches.to_owned()))?
+                    .trim()
+                    .parse::<f64>()?,
+            };
+            // by whole inches, so that e.g. `6'` is exactly 1.8288 metres
+            (feet.trim().parse::<f64>()? * f64::from(Self::INCHES_PER_FOOT) + inches) * Self::INCH
+        } else if let Some(inches) = s.strip_suffix('"') {
+            inches.trim().parse::<f64>()? * Self::INCH
+        } else {
+            let (value, unit) =
+                s.split_at(s.find(|c: char| c.is_ascii_alphabetic()).unwrap_or(s.len()));
+            let value = value.trim().parse::<f64>()?;
+            match unit {
+                "" | "m" => value,
+                "cm" => value * 0.01_f64,
+                "mm" => value * 0.001_f64,
+                "km" => value * 1000.0_f64,
+                "ft" => value * f64::from(Self::INCHES_PER_FOOT) * Self::INCH,
+                "in" => value * Self::INCH,
+                unit => return Err(MetreError::UnknownUnit(unit.to_owned())),
+            }
+        };
+        if !metres.is_finite() || metres < 0.0_f64 {
+            return Err(MetreError::OutOfRange);
+        }
+        Ok(Self(metres))
+    }
+}
+
+/// Mass in metric tonnes, used for weight limits.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tonne(f64);
+
+impl Tonne {
+    #[must_use]
+    pub const fn new(val: f64) -> Self {
+        Self(val)
+    }
+
+    #[must_use]
+    pub const fn val(&self) -> f64 {
+        self.0
+    }
+
+    /// Rounded to the nearest kilogram, see [`Metre::canonical`]
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn canonical(self) -> Self {
+        Self(canonical(self.0))
+    }
+}
+
 /// Vehicle Speed, used for speed limits and similar.
 // TODO: This part of the API may need to be revisited entirely
 // It is unclear whether a speed unit is needed per lane,
@@ -68,36 +231,50 @@ impl Speed {
             Self::Knots(val) => 1.852_f64 * val,
         }
     }
+
+    /// Rounded to three decimal places in the same unit, see [`Metre::canonical`]
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn canonical(self) -> Self {
+        match self {
+            Self::Kph(val) => Self::Kph(canonical(val)),
+            Self::Mph(val) => Self::Mph(canonical(val)),
+            Self::Knots(val) => Self::Knots(canonical(val)),
+        }
+    }
 }
 
 #[derive(Debug)]
 pub enum SpeedError {
     Empty,
-    Parse(std::num::ParseFloatError),
+    Parse(core::num::ParseFloatError),
     UnknownUnit(String),
+    UnknownValue(String),
     OutOfRange,
 }
 
-impl std::fmt::Display for SpeedError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for SpeedError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::Empty => write!(f, "empty"),
             Self::Parse(e) => e.fmt(f),
             Self::UnknownUnit(unit) => write!(f, "unknown unit '{unit}'"),
+            Self::UnknownValue(value) => write!(f, "unknown value '{value}'"),
             Self::OutOfRange => write!(f, "out of range"),
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for SpeedError {}
 
-impl From<std::num::ParseFloatError> for SpeedError {
-    fn from(e: std::num::ParseFloatError) -> Self {
+impl From<core::num::ParseFloatError> for SpeedError {
+    fn from(e: core::num::ParseFloatError) -> Self {
         SpeedError::Parse(e)
     }
 }
 
-impl std::str::FromStr for Speed {
+impl core::str::FromStr for Speed {
     type Err = SpeedError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if s.is_empty() {
@@ -116,8 +293,8 @@ impl std::str::FromStr for Speed {
     }
 }
 
-impl std::fmt::Display for Speed {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for Speed {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         match self {
             Self::Kph(v) => write!(f, "{}", v),
             Self::Mph(v) => write!(f, "{} mph", v),
@@ -147,7 +324,7 @@ impl<'de> serde::Deserialize<'de> for Speed {
 }
 
 mod speed {
-    use std::num::ParseFloatError;
+    use core::num::ParseFloatError;
 
     #[derive(Debug, PartialEq)]
     #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -167,7 +344,7 @@ mod speed {
         Knots,
     }
 
-    impl std::str::FromStr for SpeedStruct {
+    impl core::str::FromStr for SpeedStruct {
         type Err = ParseFloatError;
         fn from_str(s: &str) -> Result<Self, Self::Err> {
             Ok(Self {
@@ -209,7 +386,7 @@ mod speed {
     impl<'de> serde::de::Visitor<'de> for FloatOrStruct {
         type Value = SpeedStruct;
 
-        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
             formatter.write_str("number or map")
         }
 
@@ -328,7 +505,60 @@ mod speed {
 #[allow(clippy::similar_names)]
 #[cfg(test)]
 mod tests {
-    use crate::metric::Speed;
+    use crate::metric::{Metre, Speed, Tonne, Units};
+
+    #[test]
+    fn test_metre_canonical() {
+        assert_eq!(Metre::new(0.1 + 0.2).canonical(), Metre::new(0.3));
+        assert_eq!(Metre::new(3.500_4).canonical(), Metre::new(3.5));
+        assert!(Metre::new(-0.000_1).canonical().val().is_sign_positive());
+        assert_eq!(Tonne::new(7.499_99).canonical(), Tonne::new(7.5));
+        assert_eq!(Speed::Mph(29.999_999).canonical(), Speed::Mph(30.0));
+    }
+
+    #[test]
+    fn test_metre_from_str() {
+        let metres = |s: &str| s.parse::<Metre>().unwrap().canonical();
+        assert_eq!(metres("2.5"), Metre::new(2.5));
+        assert_eq!(metres("2.5 m"), Metre::new(2.5));
+        assert_eq!(metres("250 cm"), Metre::new(2.5));
+        assert_eq!(metres("3 ft"), Metre::new(0.914));
+        assert_eq!(metres("12'"), Metre::new(3.658));
+        assert_eq!(metres("12'6\""), Metre::new(3.81));
+        assert_eq!(metres("6\""), Metre::new(0.152));
+        assert!("wide".parse::<Metre>().is_err());
+        assert!("3 yd".parse::<Metre>().is_err());
+        assert!("-1".parse::<Metre>().is_err());
+    }
+
+    #[test]
+    fn test_metre_to_tag_value() {
+        let tag_value = |s: &str, units| s.parse::<Metre>().unwrap().to_tag_value(units);
+        assert_eq!(tag_value("2.5", Units::Metric), "2.5");
+        assert_eq!(tag_value("2.5", Units::Imperial), "2.5");
+        assert_eq!(tag_value("12 ft", Units::Imperial), "12'");
+        assert_eq!(tag_value("12'6\"", Units::Imperial), "12'6\"");
+        assert_eq!(tag_value("6\"", Units::Imperial), "0'6\"");
+        // rounded to whole inches before the feet are counted
+        assert_eq!(
+            Metre::new(1.828_8 - 1e-9).to_tag_value(Units::Imperial),
+            "6'"
+        );
+        assert_eq!(
+            Metre::new(1.803_4 + 1e-9).to_tag_value(Units::Imperial),
+            "5'11\""
+        );
+        for s in ["12'", "12'6\"", "6\"", "3 ft"] {
+            let metres = s.parse::<Metre>().unwrap();
+            assert_eq!(
+                metres
+                    .to_tag_value(Units::Imperial)
+                    .parse::<Metre>()
+                    .unwrap(),
+                metres
+            );
+        }
+    }
 
     #[test]
     fn test_speed() {
@@ -28,17 +28,33 @@ impl OverpassResponse {
             .iter()
             .find_map(|element| element.tags.get("driving_side"))
     }
+    fn region(&self) -> Region {
+        Region {
+            iso_3166: self
+                .iso3166_2()
+                .or_else(|| self.iso3166_1())
+                .map(ToOwned::to_owned),
+            driving_side: self.driving_side().and_then(|d| d.parse().ok()),
+        }
+    }
     fn locale(&self) -> Locale {
-        Locale::builder()
-            .driving_side(
-                self.driving_side()
-                    .map_or(DrivingSide::Right, |d| d.parse().unwrap()),
-            )
-            .iso_3166_option(self.iso3166_2().or_else(|| self.iso3166_1()))
-            .build()
+        let region = self.region();
+        let mut config = Locale::builder().iso_3166_option(region.iso_3166.as_deref());
+        if let Some(driving_side) = region.driving_side {
+            config = config.driving_side(driving_side);
+        }
+        config.build()
     }
 }
 
+/// The areas enclosing a way
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    /// ISO 3166-2 subdivision, or else ISO 3166-1 country
+    pub iso_3166: Option<String>,
+    pub driving_side: Option<DrivingSide>,
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize))]
 pub struct LatLon {
@@ -179,6 +195,38 @@ pub async fn get_way(id: &ElementId) -> Result<(Tags, LineString<f64>, Locale),
     ))
 }
 
+/// Get the Region enclosing a Way from Overpass
+///
+/// # Errors
+///
+/// May occur when processing a request.
+///
+pub async fn get_region(id: &ElementId) -> Result<Region, Error> {
+    let resp = reqwest::Client::new()
+        .get(format!(
+            r#"https://overpass-api.de/api/interpreter?data=[out:json][timeout:25];
+            way(id:{id});
+            >;
+            is_in->.enclosing;
+            (
+                area.enclosing["ISO3166-2"];
+                area.enclosing["ISO3166-1"];
+                area.enclosing["driving_side"];
+            );
+            out tags;"#
+        ))
+        .send()
+        .await?
+        .json::<OverpassResponse>()
+        .await?;
+    log::debug!("{:#?}", resp);
+
+    if resp.elements.is_empty() {
+        return Err(Error::Empty);
+    }
+    Ok(resp.region())
+}
+
 /// Get Tags and Geometries from Overpass.
 /// Given a longitude and latitude, find the nearest way within 100m by euclidean distance.
 ///
@@ -328,7 +376,8 @@ mod tests {
       }      
     "#;
 
-    use super::OverpassResponse;
+    use super::{OverpassResponse, Region};
+    use crate::locale::DrivingSide;
 
     #[test]
     fn element_from_response() {
@@ -337,4 +386,16 @@ mod tests {
         let element = result.elements.first().unwrap();
         assert!(element.geometry.is_some());
     }
+
+    #[test]
+    fn region_from_response() {
+        let result: OverpassResponse = serde_json::from_str(RESPONSE).unwrap();
+        assert_eq!(
+            result.region(),
+            Region {
+                iso_3166: Some("AU-WA".to_owned()),
+                driving_side: Some(DrivingSide::Left),
+            }
+        );
+    }
 }
@@ -0,0 +1,273 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::{Direction, Lane, Road};
+
+/// How a lane of one road connects to the lanes of the next road,
+/// with indices into `Road::lanes`.
+/// Separators are not matched.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LaneConnection {
+    /// The lane continues into the next road
+    Continues { from: usize, to: usize },
+    /// The lane ends before the next road
+    Ends { from: usize },
+    /// The lane begins on the next road
+    Begins { to: usize },
+}
+
+impl Road {
+    /// Match the lanes at the end of this road with the lanes at the start of the next road,
+    /// where the two ways share a node.
+    ///
+    /// If the next way is drawn in the opposite direction, set `next_reversed`,
+    /// such that its lanes are compared in the direction of this way.
+    ///
+    /// Lanes are matched by type, designation, and direction,
+    /// keeping as many lanes continuous as possible.
+    /// The connections are returned from left to right.
+    #[must_use]
+    pub fn connect(&self, next: &Road, next_reversed: bool) -> Vec<LaneConnection> {
+        let from: Vec<(usize, Lane)> = self
+            .lanes
+            .iter()
+            .enumerate()
+            .filter(|(_, lane)| !lane.is_separator())
+            .map(|(i, lane)| (i, lane.clone()))
+            .collect();
+        let mut to: Vec<(usize, Lane)> = next
+            .lanes
+            .iter()
+            .enumerate()
+            .filter(|(_, lane)| !lane.is_separator())
+            .map(|(i, lane)| {
+                if next_reversed {
+                    (i, reverse(lane))
+                } else {
+                    (i, lane.clone())
+                }
+            })
+            .collect();
+        if next_reversed {
+            to.reverse();
+        }
+
+        align(&from, &to, |(_, a), (_, b)| matches(a, b))
+            .into_iter()
+            .filter_map(|connection| {
+                Some(match connection {
+                    LaneConnection::Continues { from: i, to: j } => LaneConnection::Continues {
+                        from: from.get(i)?.0,
+                        to: to.get(j)?.0,
+                    },
+                    LaneConnection::Ends { from: i } => LaneConnection::Ends {
+                        from: from.get(i)?.0,
+                    },
+                    LaneConnection::Begins { to: j } => LaneConnection::Begins { to: to.get(j)?.0 },
+                })
+            })
+            .collect()
+    }
+
+    /// Reverse the lanes, as if the way were drawn in the opposite direction
+    pub(crate) fn reverse(&mut self) {
+        self.lanes = self.lanes.iter().rev().map(reverse).collect();
+    }
+}
+
+/// Align two sequences by their longest common subsequence of matching items,
+/// with indices into `from` and `to` in order,
+/// where items that are not matched end before the next items begin
+pub(crate) fn align<A, B, F>(from: &[A], to: &[B], matches: F) -> Vec<LaneConnection>
+where
+    F: Fn(&A, &B) -> bool,
+{
+    let mut lengths = vec![vec![0_usize; to.len() + 1]; from.len() + 1];
+    for (i, a) in from.iter().enumerate().rev() {
+        for (j, b) in to.iter().enumerate().rev() {
+            let length = if matches(a, b) {
+                get(&lengths, i + 1, j + 1) + 1
+            } else {
+                get(&lengths, i + 1, j).max(get(&lengths, i, j + 1))
+            };
+            if let Some(cell) = lengths.get_mut(i).and_then(|row| row.get_mut(j)) {
+                *cell = length;
+            }
+        }
+    }
+
+    let mut alignment = Vec::with_capacity(from.len().max(to.len()));
+    let (mut i, mut j) = (0, 0);
+    while let (Some(a), Some(b)) = (from.get(i), to.get(j)) {
+        if matches(a, b) && get(&lengths, i, j) == get(&lengths, i + 1, j + 1) + 1 {
+            alignment.push(LaneConnection::Continues { from: i, to: j });
+            i += 1;
+            j += 1;
+        } else if get(&lengths, i + 1, j) >= get(&lengths, i, j + 1) {
+            alignment.push(LaneConnection::Ends { from: i });
+            i += 1;
+        } else {
+            alignment.push(LaneConnection::Begins { to: j });
+            j += 1;
+        }
+    }
+    alignment.extend((i..from.len()).map(|from| LaneConnection::Ends { from }));
+    alignment.extend((j..to.len()).map(|to| LaneConnection::Begins { to }));
+    alignment
+}
+
+fn get(lengths: &[Vec<usize>], i: usize, j: usize) -> usize {
+    lengths
+        .get(i)
+        .and_then(|row| row.get(j))
+        .copied()
+        .unwrap_or(0)
+}
+
+/// Lanes that can continue into each other
+fn matches(a: &Lane, b: &Lane) -> bool {
+    match (a, b) {
+        (
+            Lane::Travel {
+                direction: a_direction,
+                designated: a_designated,
+                ..
+            },
+            Lane::Travel {
+                direction: b_direction,
+                designated: b_designated,
+                ..
+            },
+        ) => a_direction == b_direction && a_designated == b_designated,
+        (
+            Lane::Parking {
+                direction: a_direction,
+                designated: a_designated,
+                ..
+            },
+            Lane::Parking {
+                direction: b_direction,
+                designated: b_designated,
+                ..
+            },
+        ) => a_direction == b_direction && a_designated == b_designated,
+        (Lane::Shoulder { .. }, Lane::Shoulder { .. }) => true,
+        _ => false,
+    }
+}
+
+/// The lane as seen from the opposite direction of the way
+fn reverse(lane: &Lane) -> Lane {
+    let reverse_direction = |direction: Direction| match direction {
+        Direction::Forward => Direction::Backward,
+        Direction::Backward => Direction::Forward,
+        Direction::Both => Direction::Both,
+        Direction::Alternating => Direction::Alternating,
+        Direction::Reversible => Direction::Reversible,
+    };
+    match lane.clone() {
+        Lane::Travel {
+            direction,
+            designated,
+            shared,
+            delineation,
+            cycleway_lane,
+            width,
+            surface,
+            smoothness,
+            colour,
+            embedded_rails,
+            trolley_wire,
+            crossing,
+            max_speed,
+            restrictions,
+            destination,
+            turn,
+            access,
+        } => Lane::Travel {
+            direction: direction.map(reverse_direction),
+            designated,
+            shared,
+            delineation,
+            cycleway_lane,
+            width,
+            surface,
+            smoothness,
+            colour,
+            embedded_rails,
+            trolley_wire,
+            crossing,
+            max_speed,
+            restrictions,
+            destination,
+            turn,
+            access,
+        },
+        Lane::Parking {
+            direction,
+            designated,
+            width,
+            orientation,
+            position,
+            condition,
+            fee,
+        } => Lane::Parking {
+            direction: reverse_direction(direction),
+            designated,
+            width,
+            orientation,
+            position,
+            condition,
+            fee,
+        },
+        lane => lane.mirror(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use osm_tags::Tags;
+
+    use super::LaneConnection;
+    use crate::locale::Locale;
+    use crate::road::Road;
+    use crate::transform::{tags_to_lanes, TagsToLanesConfig};
+
+    fn road(tags: &[(&'static str, &'static str)]) -> Road {
+        tags_to_lanes(
+            &Tags::from_pairs(tags.iter().copied()).unwrap(),
+            &Locale::builder().build(),
+            &TagsToLanesConfig::new(false, false),
+        )
+        .unwrap()
+        .road
+    }
+
+    #[test]
+    fn test_connect() {
+        let two = road(&[("highway", "residential"), ("lanes", "2")]);
+        let three = road(&[
+            ("highway", "residential"),
+            ("lanes", "3"),
+            ("lanes:forward", "2"),
+            ("lanes:backward", "1"),
+        ]);
+        assert_eq!(
+            two.connect(&three, false),
+            vec![
+                LaneConnection::Continues { from: 0, to: 0 },
+                LaneConnection::Continues { from: 1, to: 1 },
+                LaneConnection::Begins { to: 2 },
+            ]
+        );
+        assert_eq!(
+            three.connect(&two, true),
+            vec![
+                LaneConnection::Continues { from: 0, to: 1 },
+                LaneConnection::Continues { from: 1, to: 0 },
+                LaneConnection::Ends { from: 2 },
+            ]
+        );
+    }
+}
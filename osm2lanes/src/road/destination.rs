@@ -0,0 +1,43 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use osm_tag_schemes::DestinationSymbol;
+
+/// Guidance signed for a lane,
+/// from `destination:lanes=*`, `destination:ref:lanes=*`, and `destination:symbol:lanes=*`
+///
+/// Multiple destinations of a lane are listed in signed order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Destination {
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub names: Vec<String>,
+    /// Language of the names, as ISO 639-1, if tagged with `destination:lang:<language>:lanes=*`
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub language: Option<String>,
+    /// Route references, e.g. `A 1`
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub refs: Vec<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub symbols: Vec<DestinationSymbol>,
+}
+
+impl Destination {
+    /// No destination is known
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty() && self.refs.is_empty() && self.symbols.is_empty()
+    }
+}
@@ -0,0 +1,407 @@
+use core::iter;
+use core::num::NonZeroUsize;
+
+use osm_tag_schemes::Placement;
+
+use super::{Designated, Direction, Lane, Road};
+use crate::locale::Locale;
+use crate::metric::Metre;
+
+/// Longest allowed miter, as a multiple of the offset, before the corner is clipped
+const MITER_LIMIT: f64 = 4.0_f64;
+
+/// A point in a planar coordinate system,
+/// with `x` towards the east and `y` towards the north, in metres
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point {
+    #[must_use]
+    pub const fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+}
+
+/// A distance across the road that changes linearly along the way
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transition {
+    pub start: Metre,
+    pub end: Metre,
+}
+
+/// Geometry of a single lane
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LaneGeometry {
+    /// Centerline of the lane, in the direction of the way
+    pub centerline: Vec<Point>,
+    /// Offset of the centerline from the way, positive to the left
+    pub offset: Transition,
+    pub width: Metre,
+}
+
+/// Geometry of every lane of a road, left to right, in the same order as `Road::lanes`
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Geometry {
+    pub lanes: Vec<LaneGeometry>,
+}
+
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GeometryError {
+    /// The way needs at least two distinct points
+    Degenerate,
+    /// The placement refers to a lane that does not exist
+    PlacementLane(Placement),
+}
+
+impl core::fmt::Display for GeometryError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Degenerate => write!(f, "way geometry has fewer than two distinct points"),
+            Self::PlacementLane(placement) => write!(f, "placement={placement} lane not found"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GeometryError {}
+
+impl Road {
+    /// Centerline of each lane, offset from the way geometry.
+    ///
+    /// The way is positioned in the cross-section according to `placement`,
+    /// or otherwise assumed to follow the centre of the cross-section.
+    /// As the lanes are ordered left to right relative to the direction of the way,
+    /// the driving side is already accounted for.
+    ///
+    /// # Errors
+    ///
+    /// If the way does not contain two distinct points,
+    /// or the placement refers to a lane that does not exist.
+    pub fn geometry(&self, way: &[Point], locale: &Locale) -> Result<Geometry, GeometryError> {
+        let way = dedup(way);
+        if way.len() < 2 {
+            return Err(GeometryError::Degenerate);
+        }
+        let highway = self.highway.r#type();
+        let reference = self.placement_transition(locale)?;
+        let mut left_edge = reference;
+        let lanes = self
+            .lanes
+            .iter()
+            .map(|lane| {
+                let width = lane.width(locale, highway);
+                let offset = Transition {
+                    start: Metre::new(left_edge.start.val() - 0.5_f64 * width.val()),
+                    end: Metre::new(left_edge.end.val() - 0.5_f64 * width.val()),
+                };
+                left_edge = Transition {
+                    start: Metre::new(left_edge.start.val() - width.val()),
+                    end: Metre::new(left_edge.end.val() - width.val()),
+                };
+                LaneGeometry {
+                    centerline: offset_polyline(&way, offset.start.val(), offset.end.val()),
+                    offset,
+                    width,
+                }
+            })
+            .collect();
+        Ok(Geometry { lanes })
+    }
+
+    /// Distance from the left edge of the cross-section to the way,
+    /// at the start and at the end of the way.
+    ///
+    /// These only differ for `placement=transition`
+    /// with `placement:start=*` and `placement:end=*`,
+    /// otherwise an unknown start or end is assumed to be the centre of the cross-section.
+    ///
+    /// # Errors
+    ///
+    /// If the placement refers to a lane that does not exist.
+    pub fn placement_transition(&self, locale: &Locale) -> Result<Transition, GeometryError> {
+        let highway = self.highway.r#type();
+        // left and right edge of each lane, from the left edge of the cross-section
+        let mut edge = 0.0_f64;
+        let edges: Vec<(f64, f64)> = self
+            .lanes
+            .iter()
+            .map(|lane| {
+                let left = edge;
+                edge += lane.width(locale, highway).val();
+                (left, edge)
+            })
+            .collect();
+        let centre = 0.5_f64 * edge;
+
+        // Lanes counted by placement, in the same way as `lanes=*`
+        let counted = |direction: Option<Direction>| {
+            self.lanes
+                .iter()
+                .zip(edges.iter())
+                .filter(move |(lane, _)| match lane {
+                    Lane::Travel {
+                        designated: Designated::Motor | Designated::Bus,
+                        direction: lane_direction,
+                        ..
+                    } => direction.is_none() || *lane_direction == direction,
+                    _ => false,
+                })
+                .map(|(_, edges)| *edges)
+                .collect::<Vec<(f64, f64)>>()
+        };
+        let forward = || counted(Some(Direction::Forward));
+        // Looking in the backward direction, left and right are swapped
+        let backward = || {
+            counted(Some(Direction::Backward))
+                .into_iter()
+                .rev()
+                .map(|(left, right)| (right, left))
+                .collect::<Vec<(f64, f64)>>()
+        };
+        let both = || counted(None);
+
+        let scheme = match &self.placement {
+            Some(scheme) => scheme,
+            None => {
+                return Ok(Transition {
+                    start: Metre::new(centre),
+                    end: Metre::new(centre),
+                })
+            },
+        };
+        let placement = if let Some(placement) = scheme.forward {
+            Some((placement, forward()))
+        } else if let Some(placement) = scheme.backward {
+            Some((placement, backward()))
+        } else {
+            scheme.both.map(|placement| (placement, both()))
+        };
+        let (start, end) = match placement {
+            Some((Placement::Transition, _)) | None => {
+                let lanes = both();
+                let start = match scheme.start {
+                    Some(start) => position(start, &lanes)?,
+                    None => None,
+                };
+                let end = match scheme.end {
+                    Some(end) => position(end, &lanes)?,
+                    None => None,
+                };
+                (start, end)
+            },
+            Some((placement, lanes)) => {
+                let fixed = position(placement, &lanes)?;
+                (fixed, fixed)
+            },
+        };
+        Ok(Transition {
+            start: Metre::new(start.unwrap_or(centre)),
+            end: Metre::new(end.unwrap_or(centre)),
+        })
+    }
+}
+
+/// Position of the placement given the left and right edges of the counted lanes,
+/// `None` if the placement does not refer to a fixed lane
+fn position(placement: Placement, lanes: &[(f64, f64)]) -> Result<Option<f64>, GeometryError> {
+    let lane = |n: NonZeroUsize| {
+        lanes
+            .get(n.get() - 1)
+            .copied()
+            .ok_or(GeometryError::PlacementLane(placement))
+    };
+    Ok(match placement {
+        Placement::Transition => None,
+        Placement::LeftOf(n) => Some(lane(n)?.0),
+        Placement::MiddleOf(n) => {
+            let (left, right) = lane(n)?;
+            Some(0.5_f64 * (left + right))
+        },
+        Placement::RightOf(n) => Some(lane(n)?.1),
+    })
+}
+
+/// Remove consecutive duplicate points, which have no direction
+fn dedup(points: &[Point]) -> Vec<Point> {
+    let mut deduped: Vec<Point> = Vec::with_capacity(points.len());
+    for point in points {
+        if deduped.last() != Some(point) {
+            deduped.push(*point);
+        }
+    }
+    deduped
+}
+
+/// Unit normal to the left of the segment from `a` to `b`
+fn left_normal(a: Point, b: Point) -> Point {
+    let dx = b.x - a.x;
+    let dy = b.y - a.y;
+    let length = dx.hypot(dy);
+    Point::new(-dy / length, dx / length)
+}
+
+/// Offset a polyline of distinct points to the left, with mitered joins.
+/// The offset changes linearly with the distance along the polyline.
+fn offset_polyline(points: &[Point], start: f64, end: f64) -> Vec<Point> {
+    let normals: Vec<Point> = points
+        .windows(2)
+        .map(|w| match w {
+            [a, b] => left_normal(*a, *b),
+            _ => unreachable!(),
+        })
+        .collect();
+    let mut distance = 0.0_f64;
+    let distances: Vec<f64> = iter::once(0.0_f64)
+        .chain(points.windows(2).map(|w| {
+            if let [a, b] = w {
+                distance += (b.x - a.x).hypot(b.y - a.y);
+            }
+            distance
+        }))
+        .collect();
+    let length = distance;
+    points
+        .iter()
+        .zip(distances)
+        .enumerate()
+        .map(|(i, (point, distance))| {
+            let before = i.checked_sub(1).and_then(|i| normals.get(i));
+            let after = normals.get(i);
+            let normal = match (before, after) {
+                (Some(before), Some(after)) => miter(*before, *after),
+                (Some(normal), None) | (None, Some(normal)) => *normal,
+                (None, None) => unreachable!(),
+            };
+            let offset = start + (end - start) * distance / length;
+            Point::new(point.x + offset * normal.x, point.y + offset * normal.y)
+        })
+        .collect()
+}
+
+/// Scaled bisector of two unit normals,
+/// such that offsetting along it keeps both adjacent segments at the offset distance
+fn miter(before: Point, after: Point) -> Point {
+    let x = before.x + after.x;
+    let y = before.y + after.y;
+    let length = x.hypot(y);
+    if length < f64::EPSILON {
+        // The way doubles back on itself
+        return after;
+    }
+    let (x, y) = (x / length, y / length);
+    // cosine of half the angle between the segments
+    let cos = x * before.x + y * before.y;
+    let scale = (1.0_f64 / cos).min(MITER_LIMIT);
+    Point::new(x * scale, y * scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use osm_tags::Tags;
+
+    use super::{offset_polyline, Point};
+    use crate::locale::Locale;
+    use crate::transform::{tags_to_lanes, TagsToLanesConfig};
+
+    fn assert_near(actual: &[Point], expected: &[Point]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, b) in actual.iter().zip(expected) {
+            assert!((a.x - b.x).abs() < 1e-9_f64 && (a.y - b.y).abs() < 1e-9_f64);
+        }
+    }
+
+    #[test]
+    fn test_offset_straight() {
+        let line = offset_polyline(&[Point::new(0.0, 0.0), Point::new(10.0, 0.0)], 2.0, 2.0);
+        assert_near(&line, &[Point::new(0.0, 2.0), Point::new(10.0, 2.0)]);
+    }
+
+    #[test]
+    fn test_offset_corner() {
+        let line = offset_polyline(
+            &[
+                Point::new(0.0, 0.0),
+                Point::new(10.0, 0.0),
+                Point::new(10.0, 10.0),
+            ],
+            -1.0,
+            -1.0,
+        );
+        assert_near(
+            &line,
+            &[
+                Point::new(0.0, -1.0),
+                Point::new(11.0, -1.0),
+                Point::new(11.0, 10.0),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_placement() {
+        let locale = Locale::builder().build();
+        let config = TagsToLanesConfig::new(false, false);
+        let way = [Point::new(0.0, 0.0), Point::new(10.0, 0.0)];
+
+        let tags =
+            Tags::from_pairs([("highway", "primary"), ("lanes", "2"), ("shoulder", "no")]).unwrap();
+        let road = tags_to_lanes(&tags, &locale, &config).unwrap().road;
+        let offsets: Vec<f64> = road
+            .geometry(&way, &locale)
+            .unwrap()
+            .lanes
+            .iter()
+            .map(|lane| lane.offset.start.val())
+            .collect();
+        assert_eq!(offsets, vec![1.75_f64, -1.75_f64]);
+
+        let tags = Tags::from_pairs([
+            ("highway", "primary"),
+            ("lanes", "2"),
+            ("shoulder", "no"),
+            ("placement:forward", "right_of:1"),
+        ])
+        .unwrap();
+        let road = tags_to_lanes(&tags, &locale, &config).unwrap().road;
+        let offsets: Vec<f64> = road
+            .geometry(&way, &locale)
+            .unwrap()
+            .lanes
+            .iter()
+            .map(|lane| lane.offset.start.val())
+            .collect();
+        assert_eq!(offsets, vec![5.25_f64, 1.75_f64]);
+    }
+
+    #[test]
+    fn test_transition() {
+        let line = offset_polyline(&[Point::new(0.0, 0.0), Point::new(10.0, 0.0)], 0.0, 2.0);
+        assert_near(&line, &[Point::new(0.0, 0.0), Point::new(10.0, 2.0)]);
+
+        let locale = Locale::builder().build();
+        let tags = Tags::from_pairs([
+            ("highway", "primary"),
+            ("lanes", "2"),
+            ("shoulder", "no"),
+            ("placement", "transition"),
+            ("placement:start", "left_of:1"),
+            ("placement:end", "middle_of:2"),
+        ])
+        .unwrap();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, false))
+            .unwrap()
+            .road;
+        let transition = road.placement_transition(&locale).unwrap();
+        assert!(transition.start.val().abs() < 1e-9_f64);
+        assert!((transition.end.val() - 5.25_f64).abs() < 1e-9_f64);
+    }
+}
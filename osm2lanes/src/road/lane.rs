@@ -1,7 +1,17 @@
-use osm_tag_schemes::{Access as AccessTagValue, HighwayType};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use osm_tag_schemes::{
+    Access as AccessTagValue, Conditional, Crossing, CyclewayLane, EmbeddedRails, HighwayType,
+    ParkingCondition, ParkingOrientation, ParkingPosition, Smoothness, Surface, TrolleyWire,
+    TurnDirection,
+};
 
 use crate::locale::Locale;
 use crate::metric::{Metre, Speed};
+use crate::road::destination::Destination;
+use crate::road::restrictions::Restrictions;
 use crate::road::separator::{Markings, Semantic};
 
 /// A single lane
@@ -14,10 +24,44 @@ pub enum Lane {
         #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
         direction: Option<Direction>,
         designated: Designated,
+        /// Another mode sharing the lane, see `cycleway=shared_lane` and `cycleway=share_busway`
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        shared: Option<Designated>,
+        /// Separation of the lane from the carriageway, see `cycleway=lane` and `cycleway=track`
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        delineation: Option<Delineation>,
+        /// Legal status of a painted cycle lane, see `cycleway:right:lane=advisory`
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        cycleway_lane: Option<CyclewayLane>,
         #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
         width: Option<Metre>,
         #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        surface: Option<Surface>,
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        smoothness: Option<Smoothness>,
+        /// Colour of the lane surface, see `cycleway:right:colour=red`
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        colour: Option<String>,
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        embedded_rails: Option<EmbeddedRails>,
+        /// Overhead wires for trolleybuses, see `trolley_wire=yes`
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        trolley_wire: Option<TrolleyWire>,
+        /// The lane crosses a road, see `footway=crossing` and `cycleway=crossing`
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        crossing: Option<Crossing>,
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
         max_speed: Option<Speed>,
+        /// Restrictions specific to this lane, from `maxheight:lanes=*` and similar
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        restrictions: Option<Restrictions>,
+        /// Signed guidance for this lane, from `destination:lanes=*` and similar,
+        /// boxed as few lanes have any
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        destination: Option<Box<Destination>>,
+        /// Turn markings of this lane, in order, from `turn:lanes=*`
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        turn: Option<Vec<TurnDirection>>,
         #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
         access: Option<AccessByType>,
     },
@@ -26,10 +70,24 @@ pub enum Lane {
         designated: Designated,
         #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
         width: Option<Metre>,
+        /// How cars are parked, from `parking:lane:<side>=*`
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        orientation: Option<ParkingOrientation>,
+        /// Where cars are parked relative to the carriageway
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        position: Option<ParkingPosition>,
+        /// Who may park, and how, from `parking:condition:<side>=*`
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        condition: Option<ParkingCondition>,
+        /// Whether there is a fee to park, from `parking:lane:<side>:fee=*`
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        fee: Option<bool>,
     },
     Shoulder {
         #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
         width: Option<Metre>,
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        access: Option<AccessByType>,
     },
     Separator {
         #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
@@ -53,12 +111,27 @@ impl Lane {
                 .map(|m| m.width(locale))
                 .unwrap_or_default(),
             Lane::Travel {
-                width, designated, ..
-            } => width.unwrap_or_else(|| locale.travel_width(designated, highway)),
-            // TODO: parking different from travel?
+                width,
+                designated,
+                direction,
+                ..
+            } => width.unwrap_or_else(|| locale.travel_width(designated, *direction, highway)),
             Lane::Parking {
-                width, designated, ..
-            } => width.unwrap_or_else(|| locale.travel_width(designated, highway)),
+                width,
+                orientation,
+                position,
+                ..
+            } => width.unwrap_or_else(|| {
+                let width = locale.parking_width(
+                    orientation.unwrap_or_else(|| locale.default_parking_orientation()),
+                );
+                // only the half of the car off the kerb is on the carriageway
+                if *position == Some(ParkingPosition::HalfOnKerb) {
+                    0.5_f64 * width
+                } else {
+                    width
+                }
+            }),
             Lane::Shoulder { width, .. } => width.unwrap_or(Lane::DEFAULT_WIDTH),
         }
     }
@@ -79,6 +152,33 @@ impl Lane {
             _ => self,
         }
     }
+
+    /// Round widths, speeds, and restrictions for canonical output, see [`Metre::canonical`].
+    #[cfg(feature = "std")]
+    pub fn canonicalize(&mut self) {
+        match self {
+            Self::Travel {
+                width,
+                max_speed,
+                restrictions,
+                ..
+            } => {
+                *width = width.map(Metre::canonical);
+                *max_speed = max_speed.map(Speed::canonical);
+                if let Some(restrictions) = restrictions {
+                    restrictions.canonicalize();
+                }
+            },
+            Self::Parking { width, .. } | Self::Shoulder { width, .. } => {
+                *width = width.map(Metre::canonical);
+            },
+            Self::Separator { markings, .. } => {
+                if let Some(markings) = markings {
+                    markings.canonicalize();
+                }
+            },
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -88,6 +188,10 @@ pub enum Direction {
     Forward,
     Backward,
     Both,
+    /// Both directions, but only one at a time, e.g. controlled by traffic signals
+    Alternating,
+    /// One direction at a time, changed over the day, e.g. for the rush hour
+    Reversible,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -103,6 +207,16 @@ pub enum Designated {
     Bus,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Delineation {
+    /// Marked with paint only
+    Painted,
+    /// Physically separated, by a kerb or a verge
+    Physical,
+}
+
 /// Display lane detail as printable characters
 pub trait Printable {
     fn as_ascii(&self) -> char;
@@ -164,6 +278,8 @@ impl Printable for Direction {
             Self::Forward => '^',
             Self::Backward => 'v',
             Self::Both => '|',
+            Self::Alternating => '~',
+            Self::Reversible => '=',
         }
     }
     fn as_utf8(&self) -> char {
@@ -171,6 +287,8 @@ impl Printable for Direction {
             Self::Forward => '↑',
             Self::Backward => '↓',
             Self::Both => '↕',
+            Self::Alternating => '⇅',
+            Self::Reversible => '⇵',
         }
     }
 }
@@ -192,8 +310,20 @@ pub struct AccessByType {
     pub(crate) taxi: Option<AccessAndDirection>,
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub(crate) bus: Option<AccessAndDirection>,
+    /// High-occupancy vehicles
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub(crate) hov: Option<AccessAndDirection>,
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub(crate) motor: Option<AccessAndDirection>,
+    /// Emergency vehicles, e.g. on a motorway shoulder
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub(crate) emergency: Option<AccessAndDirection>,
+    /// Bus access that only applies under a condition, e.g. a bus lane at peak hours
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub(crate) bus_conditional: Vec<Conditional<AccessTagValue>>,
 }
 
 /// Access for a given user
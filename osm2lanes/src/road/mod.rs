@@ -1,17 +1,52 @@
-use osm_tag_schemes::{Highway, Lit, Smoothness, TrackType};
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use osm_tag_schemes::{
+    CycleStreet, DualCarriageway, Expressway, Highway, Lit, PassingPlaces, PlacementScheme,
+    PriorityRoad, Smoothness, Surface, TrackType,
+};
 
 use crate::locale::Locale;
 use crate::metric::Metre;
 
+mod connection;
+pub(crate) use connection::align;
+pub use connection::LaneConnection;
+
+#[cfg(feature = "std")]
+mod geometry;
+#[cfg(feature = "std")]
+pub use geometry::{Geometry, GeometryError, LaneGeometry, Point, Transition};
+
+mod destination;
+pub use destination::Destination;
+
 mod lane;
-pub use lane::{AccessAndDirection, AccessByType, Designated, Direction, Lane, Printable};
+pub use lane::{
+    AccessAndDirection, AccessByType, Delineation, Designated, Direction, Lane, Printable,
+};
+
+mod restrictions;
+pub use restrictions::Restrictions;
+
+mod separately_mapped;
+pub use separately_mapped::SeparatelyMapped;
 
 mod separator;
 pub use separator::{Color, Marking, Markings, Semantic, Style};
 
+mod versioning;
+pub use versioning::FormatVersion;
+#[cfg(feature = "serde")]
+pub use versioning::FormatVersionError;
+
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Road {
+    /// Version of the serialized format, see [`Road::from_versioned_json`] to load older documents
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub format_version: FormatVersion,
+
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub name: Option<String>,
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
@@ -24,12 +59,47 @@ pub struct Road {
     pub lit: Option<Lit>,
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub tracktype: Option<TrackType>,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub attributes: RoadAttributes,
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-    pub smoothness: Option<Smoothness>,
+    pub priority_road: Option<PriorityRoad>,
+    /// Opposing traffic can pass on a single track road, see `passing_places=*`
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub passing_places: Option<PassingPlaces>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub expressway: Option<Expressway>,
+    /// The way is one of a pair of oneway carriageways, with the median on the inside
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub dual_carriageway: Option<DualCarriageway>,
+    /// Bicycles have priority and motor vehicles are guests, see `cyclestreet=yes`
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub cycle_street: Option<CycleStreet>,
+    /// Restrictions applying to the whole way, see [`Restrictions`]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub restrictions: Option<Restrictions>,
+
+    /// Position of the way in the cross-section
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub placement: Option<PlacementScheme>,
+    /// Infrastructure alongside the road that is not part of the lanes, see [`SeparatelyMapped`]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub separately_mapped: Option<SeparatelyMapped>,
 
     pub lanes: Vec<Lane>,
 }
 
+/// Pavement of the whole way, from `surface=*` and `smoothness=*`,
+/// see the lanes for any per lane surface from `surface:lanes=*`
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoadAttributes {
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub surface: Option<Surface>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub smoothness: Option<Smoothness>,
+}
+
 impl Road {
     #[must_use]
     pub fn has_separators(&self) -> bool {
@@ -38,6 +108,21 @@ impl Road {
 }
 
 impl Road {
+    /// Normalise the road for canonical output,
+    /// such that serializing it can be byte-compared across runs, platforms, and implementations.
+    ///
+    /// Lanes are always ordered left to right and tags are always sorted by key,
+    /// this additionally rounds all widths, speeds, and restrictions, see [`Metre::canonical`].
+    #[cfg(feature = "std")]
+    pub fn canonicalize(&mut self) {
+        if let Some(restrictions) = &mut self.restrictions {
+            restrictions.canonicalize();
+        }
+        for lane in &mut self.lanes {
+            lane.canonicalize();
+        }
+    }
+
     /// Width in metres
     #[must_use]
     pub fn width(&self, locale: &Locale) -> Metre {
@@ -47,3 +132,83 @@ impl Road {
             .sum::<Metre>()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[cfg(all(feature = "serde", feature = "std"))]
+    #[test]
+    fn test_canonical_serialization() {
+        use alloc::vec;
+
+        use osm_tag_schemes::{Highway, HighwayImportance, HighwayType};
+
+        use super::{
+            Color, Designated, Direction, FormatVersion, Lane, Marking, Markings, Restrictions,
+            Road, RoadAttributes, Style,
+        };
+        use crate::metric::{Metre, Speed, Tonne};
+
+        let road = || Road {
+            format_version: FormatVersion::CURRENT,
+            name: None,
+            r#ref: None,
+            highway: Highway::active(HighwayType::Classified(HighwayImportance::Secondary)),
+            lit: None,
+            tracktype: None,
+            attributes: RoadAttributes::default(),
+            priority_road: None,
+            passing_places: None,
+            expressway: None,
+            dual_carriageway: None,
+            cycle_street: None,
+            restrictions: Some(Restrictions {
+                max_height: Some(Metre::new(4.000_04)),
+                max_weight: Some(Tonne::new(7.499_99)),
+                max_length: None,
+            }),
+            placement: None,
+            separately_mapped: None,
+            lanes: vec![
+                Lane::Travel {
+                    direction: Some(Direction::Forward),
+                    designated: Designated::Motor,
+                    shared: None,
+                    delineation: None,
+                    cycleway_lane: None,
+                    width: Some(Metre::new(3.500_4)),
+                    surface: None,
+                    smoothness: None,
+                    colour: None,
+                    embedded_rails: None,
+                    trolley_wire: None,
+                    crossing: None,
+                    max_speed: Some(Speed::Mph(29.999_999)),
+                    restrictions: Some(Restrictions {
+                        max_height: None,
+                        max_weight: None,
+                        max_length: Some(Metre::new(-0.000_1)),
+                    }),
+                    destination: None,
+                    turn: None,
+                    access: None,
+                },
+                Lane::Separator {
+                    semantic: None,
+                    markings: Some(Markings::new(vec![Marking {
+                        style: Style::SolidLine,
+                        width: Some(Metre::new(0.1 + 0.2)),
+                        color: Some(Color::White),
+                    }])),
+                },
+            ],
+        };
+        let serialize = || {
+            let mut road = road();
+            road.canonicalize();
+            serde_json::to_string_pretty(&road).unwrap()
+        };
+        let first = serialize();
+        assert_eq!(first, serialize());
+        assert_eq!(first, include_str!("canonical.json").trim_end());
+    }
+}
@@ -0,0 +1,30 @@
+use crate::metric::{Metre, Tonne};
+
+/// Physical restrictions on vehicles,
+/// from `maxheight=*`, `maxweight=*`, and `maxlength=*`
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Restrictions {
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub max_height: Option<Metre>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub max_weight: Option<Tonne>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub max_length: Option<Metre>,
+}
+
+impl Restrictions {
+    /// No restriction is known
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.max_height.is_none() && self.max_weight.is_none() && self.max_length.is_none()
+    }
+
+    /// Round the limits for canonical output, see [`Metre::canonical`]
+    #[cfg(feature = "std")]
+    pub fn canonicalize(&mut self) {
+        self.max_height = self.max_height.map(Metre::canonical);
+        self.max_weight = self.max_weight.map(Tonne::canonical);
+        self.max_length = self.max_length.map(Metre::canonical);
+    }
+}
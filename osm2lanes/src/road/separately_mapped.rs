@@ -0,0 +1,28 @@
+use alloc::vec::Vec;
+
+use crate::road::Designated;
+
+/// Modes with infrastructure mapped as separate ways alongside the road,
+/// such as `cycleway:right=separate`, by side of the way
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SeparatelyMapped {
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub left: Vec<Designated>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub right: Vec<Designated>,
+}
+
+impl SeparatelyMapped {
+    /// Nothing is mapped separately
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.left.is_empty() && self.right.is_empty()
+    }
+}
@@ -1,3 +1,5 @@
+use alloc::vec::Vec;
+
 use super::Printable;
 use crate::locale::Locale;
 use crate::metric::Metre;
@@ -43,15 +45,29 @@ impl Markings {
             .map(|marking| marking.width.unwrap_or(Marking::DEFAULT_WIDTH))
             .sum::<Metre>()
     }
+
+    /// Round widths for canonical output, see [`Metre::canonical`].
+    #[cfg(feature = "std")]
+    pub fn canonicalize(&mut self) {
+        for marking in &mut self.0 {
+            marking.width = marking.width.map(Metre::canonical);
+        }
+    }
 }
 
-impl std::ops::Deref for Markings {
+impl core::ops::Deref for Markings {
     type Target = Vec<Marking>;
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
 
+impl core::ops::DerefMut for Markings {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Marking {
@@ -91,6 +107,11 @@ pub enum Style {
     // up and down are left to right
     KerbUp,
     KerbDown,
+    // physical devices, rather than paint
+    RumbleStrip,
+    BottsDots,
+    RaisedSeparator,
+    Studs,
 }
 
 impl Style {
@@ -105,6 +126,10 @@ impl Style {
             Self::KerbDown => '\\',
             Self::KerbUp => '/',
             Self::NoFill => ' ',
+            Self::RumbleStrip => '≋',
+            Self::BottsDots => '∘',
+            Self::RaisedSeparator => '▮',
+            Self::Studs => '•',
         }
     }
     /// Opposite marking style
@@ -118,8 +143,21 @@ impl Style {
             Self::KerbDown => Self::KerbUp,
             Self::KerbUp => Self::KerbDown,
             Self::NoFill => Self::NoFill,
+            Self::RumbleStrip => Self::RumbleStrip,
+            Self::BottsDots => Self::BottsDots,
+            Self::RaisedSeparator => Self::RaisedSeparator,
+            Self::Studs => Self::Studs,
         }
     }
+
+    /// The marking is a physical device rather than paint
+    #[must_use]
+    pub const fn is_physical(&self) -> bool {
+        matches!(
+            self,
+            Self::RumbleStrip | Self::BottsDots | Self::RaisedSeparator | Self::Studs
+        )
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
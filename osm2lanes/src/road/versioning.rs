@@ -0,0 +1,126 @@
+#[cfg(feature = "serde")]
+use alloc::string::String;
+
+#[cfg(feature = "serde")]
+use super::Road;
+
+/// Version of the serialized format of a [`Road`](super::Road) and its lanes,
+/// increased when a change would stop older documents from deserializing
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct FormatVersion(u32);
+
+impl FormatVersion {
+    /// Documents serialized before the format version was introduced
+    pub const UNVERSIONED: Self = Self(0);
+    /// The format written by this release
+    pub const CURRENT: Self = Self(1);
+
+    #[must_use]
+    pub const fn get(self) -> u32 {
+        self.0
+    }
+}
+
+impl Default for FormatVersion {
+    fn default() -> Self {
+        Self::CURRENT
+    }
+}
+
+impl core::fmt::Display for FormatVersion {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "v{}", self.0)
+    }
+}
+
+/// Error loading a versioned document, see [`Road::from_versioned_json`]
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+pub enum FormatVersionError {
+    /// The document is not valid JSON, or does not match the format after upgrading
+    Json(serde_json::Error),
+    /// The document was written by a newer release
+    Unsupported(u64),
+}
+
+#[cfg(feature = "serde")]
+impl core::fmt::Display for FormatVersionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Json(e) => write!(f, "invalid road document: {e}"),
+            Self::Unsupported(version) => write!(
+                f,
+                "format version v{version} is newer than the supported {}",
+                FormatVersion::CURRENT
+            ),
+        }
+    }
+}
+
+#[cfg(all(feature = "serde", feature = "std"))]
+impl std::error::Error for FormatVersionError {}
+
+#[cfg(feature = "serde")]
+impl From<serde_json::Error> for FormatVersionError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Json(e)
+    }
+}
+
+/// Upgrade a document by one version, the index is the version upgraded from
+#[cfg(feature = "serde")]
+type Migration = fn(&mut serde_json::Map<String, serde_json::Value>);
+
+#[cfg(feature = "serde")]
+const MIGRATIONS: [Migration; 1] = [
+    // v0 to v1: the version field is added, the rest of the format is unchanged
+    |_road| {},
+];
+
+#[cfg(feature = "serde")]
+impl Road {
+    /// Deserialize a road from JSON written by this or any previous release,
+    /// upgrading the document one format version at a time.
+    /// A document without a `format_version` predates versioning.
+    ///
+    /// # Errors
+    ///
+    /// The document is invalid or written by a newer release,
+    /// see [`FormatVersionError`].
+    ///
+    /// ```
+    /// use osm2lanes::road::{FormatVersion, Road};
+    /// let road = Road::from_versioned_json(r#"{"highway":"primary","lanes":[]}"#).unwrap();
+    /// assert_eq!(road.format_version, FormatVersion::CURRENT);
+    /// assert!(Road::from_versioned_json(r#"{"format_version":999,"highway":"primary","lanes":[]}"#).is_err());
+    /// ```
+    pub fn from_versioned_json(json: &str) -> Result<Self, FormatVersionError> {
+        let mut value: serde_json::Value = serde_json::from_str(json)?;
+        if let Some(road) = value.as_object_mut() {
+            let version = match road.get("format_version") {
+                None => u64::from(FormatVersion::UNVERSIONED.get()),
+                Some(version) => version.as_u64().ok_or_else(|| {
+                    FormatVersionError::Json(serde::de::Error::custom(
+                        "format_version is not an unsigned integer",
+                    ))
+                })?,
+            };
+            if version > u64::from(FormatVersion::CURRENT.get()) {
+                return Err(FormatVersionError::Unsupported(version));
+            }
+            for migration in MIGRATIONS
+                .iter()
+                .skip(usize::try_from(version).unwrap_or(0))
+            {
+                migration(road);
+            }
+            road.insert(
+                String::from("format_version"),
+                serde_json::Value::from(FormatVersion::CURRENT.get()),
+            );
+        }
+        Ok(serde_json::from_value(value)?)
+    }
+}
@@ -2,7 +2,7 @@ use osm_tag_schemes::{Highway, HighwayType};
 use osm_tags::Tags;
 
 use crate::locale::DrivingSide;
-use crate::road::{Lane, Road};
+use crate::road::{FormatVersion, Lane, Road, RoadAttributes};
 
 #[derive(Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -68,12 +68,24 @@ impl TestCase {
         match &self.expected {
             Expected::Road(road) => road.clone(),
             Expected::Output(lanes) => Road {
+                format_version: FormatVersion::CURRENT,
                 name: None,
                 r#ref: None,
-                highway: Highway::active(HighwayType::UnknownRoad),
+                highway: Highway::from_tags(&self.tags)
+                    .ok()
+                    .flatten()
+                    .unwrap_or_else(|| Highway::active(HighwayType::UnknownRoad)),
                 lit: None,
                 tracktype: None,
-                smoothness: None,
+                attributes: RoadAttributes::default(),
+                priority_road: None,
+                passing_places: None,
+                expressway: None,
+                dual_carriageway: None,
+                cycle_street: None,
+                restrictions: None,
+                placement: None,
+                separately_mapped: None,
                 lanes: lanes.clone(),
             },
         }
@@ -152,11 +164,18 @@ pub fn get_tests() -> Vec<TestCase> {
 mod tests {
 
     use assert_json_diff::assert_json_eq;
+    use osm_tag_schemes::{
+        Crossing, CyclewayLane, EmbeddedRails, ParkingCondition, ParkingOrientation,
+        ParkingPosition, Smoothness, Surface, TrolleyWire, TurnDirection,
+    };
 
     use super::*;
     use crate::locale::{DrivingSide, Locale};
     use crate::metric::{Metre, Speed};
-    use crate::road::{AccessByType, Color, Lane, Marking, Markings, Printable, Road, Semantic};
+    use crate::road::{
+        AccessByType, Color, Delineation, Designated, Destination, Lane, Marking, Markings,
+        Printable, Restrictions, Road, Semantic,
+    };
     use crate::transform::{
         lanes_to_tags, tags_to_lanes, LanesToTagsConfig, RoadError, RoadFromTags, RoadWarnings,
         TagsToLanesConfig,
@@ -178,6 +197,12 @@ mod tests {
         }
     }
 
+    impl<T: EqExpected> EqExpected for Box<T> {
+        fn eq_exp(&self, expected: &Self) -> bool {
+            self.as_ref().eq_exp(expected)
+        }
+    }
+
     impl EqExpected for Road {
         fn eq_exp(&self, expected: &Self) -> bool {
             if self.lanes.len() != expected.lanes.len() {
@@ -210,23 +235,59 @@ mod tests {
                 (
                     Lane::Travel {
                         designated: actual_designated,
+                        shared: actual_shared,
+                        delineation: actual_delineation,
+                        cycleway_lane: actual_cycleway_lane,
                         direction: actual_direction,
                         width: actual_width,
+                        surface: actual_surface,
+                        smoothness: actual_smoothness,
+                        colour: actual_colour,
+                        embedded_rails: actual_embedded_rails,
+                        trolley_wire: actual_trolley_wire,
+                        crossing: actual_crossing,
                         max_speed: actual_max_speed,
+                        restrictions: actual_restrictions,
+                        destination: actual_destination,
+                        turn: actual_turn,
                         access: actual_access,
                     },
                     Lane::Travel {
                         designated: expected_designated,
+                        shared: expected_shared,
+                        delineation: expected_delineation,
+                        cycleway_lane: expected_cycleway_lane,
                         direction: expected_direction,
                         width: expected_width,
+                        surface: expected_surface,
+                        smoothness: expected_smoothness,
+                        colour: expected_colour,
+                        embedded_rails: expected_embedded_rails,
+                        trolley_wire: expected_trolley_wire,
+                        crossing: expected_crossing,
                         max_speed: expected_max_speed,
+                        restrictions: expected_restrictions,
+                        destination: expected_destination,
+                        turn: expected_turn,
                         access: expected_access,
                     },
                 ) => {
                     actual_designated == expected_designated
                         && actual_direction == expected_direction
+                        && actual_shared.eq_exp(&expected_shared)
+                        && actual_delineation.eq_exp(&expected_delineation)
+                        && actual_cycleway_lane.eq_exp(&expected_cycleway_lane)
                         && actual_width.eq_exp(&expected_width)
+                        && actual_surface.eq_exp(&expected_surface)
+                        && actual_smoothness.eq_exp(&expected_smoothness)
+                        && actual_colour.eq_exp(&expected_colour)
+                        && actual_embedded_rails.eq_exp(&expected_embedded_rails)
+                        && actual_trolley_wire.eq_exp(&expected_trolley_wire)
+                        && actual_crossing.eq_exp(&expected_crossing)
                         && actual_max_speed.eq_exp(&expected_max_speed)
+                        && actual_restrictions.eq_exp(&expected_restrictions)
+                        && actual_destination.eq_exp(&expected_destination)
+                        && actual_turn.eq_exp(&expected_turn)
                         && actual_access.eq_exp(&expected_access)
                 },
                 (
@@ -234,25 +295,39 @@ mod tests {
                         designated: actual_designated,
                         direction: actual_direction,
                         width: actual_width,
+                        orientation: actual_orientation,
+                        position: actual_position,
+                        condition: actual_condition,
+                        fee: actual_fee,
                     },
                     Lane::Parking {
                         designated: expected_designated,
                         direction: expected_direction,
                         width: expected_width,
+                        orientation: expected_orientation,
+                        position: expected_position,
+                        condition: expected_condition,
+                        fee: expected_fee,
                     },
                 ) => {
                     actual_designated == expected_designated
                         && actual_direction == expected_direction
                         && actual_width.eq_exp(&expected_width)
+                        && actual_orientation.eq_exp(&expected_orientation)
+                        && actual_position.eq_exp(&expected_position)
+                        && actual_condition.eq_exp(&expected_condition)
+                        && actual_fee.eq_exp(&expected_fee)
                 },
                 (
                     Lane::Shoulder {
                         width: actual_width,
+                        access: actual_access,
                     },
                     Lane::Shoulder {
                         width: expected_width,
+                        access: expected_access,
                     },
-                ) => actual_width.eq_exp(&expected_width),
+                ) => actual_width.eq_exp(&expected_width) && actual_access.eq_exp(&expected_access),
                 (actual, expected) => actual == expected,
             }
         }
@@ -286,18 +361,114 @@ mod tests {
         }
     }
 
+    impl EqExpected for Crossing {
+        fn eq_exp(&self, expected: &Self) -> bool {
+            self == expected
+        }
+    }
+
+    impl EqExpected for Restrictions {
+        fn eq_exp(&self, expected: &Self) -> bool {
+            self == expected
+        }
+    }
+
+    impl EqExpected for Destination {
+        fn eq_exp(&self, expected: &Self) -> bool {
+            self == expected
+        }
+    }
+
     impl EqExpected for Speed {
         fn eq_exp(&self, expected: &Self) -> bool {
             self == expected
         }
     }
 
+    impl EqExpected for Surface {
+        fn eq_exp(&self, expected: &Self) -> bool {
+            self == expected
+        }
+    }
+
+    impl EqExpected for String {
+        fn eq_exp(&self, expected: &Self) -> bool {
+            self == expected
+        }
+    }
+
+    impl EqExpected for Smoothness {
+        fn eq_exp(&self, expected: &Self) -> bool {
+            self == expected
+        }
+    }
+
+    impl EqExpected for EmbeddedRails {
+        fn eq_exp(&self, expected: &Self) -> bool {
+            self == expected
+        }
+    }
+
+    impl EqExpected for TrolleyWire {
+        fn eq_exp(&self, expected: &Self) -> bool {
+            self == expected
+        }
+    }
+
     impl EqExpected for AccessByType {
         fn eq_exp(&self, expected: &Self) -> bool {
             self == expected
         }
     }
 
+    impl EqExpected for Designated {
+        fn eq_exp(&self, expected: &Self) -> bool {
+            self == expected
+        }
+    }
+
+    impl EqExpected for CyclewayLane {
+        fn eq_exp(&self, expected: &Self) -> bool {
+            self == expected
+        }
+    }
+
+    impl EqExpected for ParkingOrientation {
+        fn eq_exp(&self, expected: &Self) -> bool {
+            self == expected
+        }
+    }
+
+    impl EqExpected for Vec<TurnDirection> {
+        fn eq_exp(&self, expected: &Self) -> bool {
+            self == expected
+        }
+    }
+
+    impl EqExpected for ParkingCondition {
+        fn eq_exp(&self, expected: &Self) -> bool {
+            self == expected
+        }
+    }
+
+    impl EqExpected for bool {
+        fn eq_exp(&self, expected: &Self) -> bool {
+            self == expected
+        }
+    }
+
+    impl EqExpected for ParkingPosition {
+        fn eq_exp(&self, expected: &Self) -> bool {
+            self == expected
+        }
+    }
+
+    impl EqExpected for Delineation {
+        fn eq_exp(&self, expected: &Self) -> bool {
+            self == expected
+        }
+    }
+
     impl EqExpected for Color {
         fn eq_exp(&self, expected: &Self) -> bool {
             self == expected
@@ -350,12 +521,21 @@ mod tests {
 
         fn expected_road(&self) -> Road {
             Road {
+                format_version: FormatVersion::CURRENT,
                 name: None,
                 r#ref: None,
                 highway: Highway::from_tags(&self.tags).unwrap().unwrap(),
                 lit: None,
                 tracktype: None,
-                smoothness: None,
+                attributes: RoadAttributes::default(),
+                priority_road: None,
+                passing_places: None,
+                expressway: None,
+                dual_carriageway: None,
+                cycle_street: None,
+                restrictions: None,
+                placement: None,
+                separately_mapped: None,
                 lanes: self
                     .lanes()
                     .iter()
@@ -371,12 +551,21 @@ mod tests {
         fn into_filtered_road(self, test: &TestCase) -> (Road, RoadWarnings) {
             (
                 Road {
+                    format_version: FormatVersion::CURRENT,
                     name: None,
                     r#ref: None,
                     highway: self.road.highway,
                     lit: None,
                     tracktype: None,
-                    smoothness: None,
+                    attributes: RoadAttributes::default(),
+                    priority_road: None,
+                    passing_places: None,
+                    expressway: None,
+                    dual_carriageway: None,
+                    cycle_street: None,
+                    restrictions: None,
+                    placement: None,
+                    separately_mapped: None,
                     lanes: self
                         .road
                         .lanes
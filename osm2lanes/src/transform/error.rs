@@ -1,6 +1,11 @@
+use alloc::format;
+use alloc::vec::Vec;
+
 use osm_tags::DuplicateKeyError;
 
-use super::TagsToLanesMsg;
+use super::{LaneWidth, TagsToLanesMsg, WayContext};
+#[cfg(feature = "std")]
+use crate::metric::Metre;
 use crate::road::Road;
 
 #[derive(Clone, Debug, Default)]
@@ -26,10 +31,20 @@ impl RoadWarnings {
     pub fn push(&mut self, msg: TagsToLanesMsg) {
         self.0.push(msg);
     }
+
+    pub fn append(&mut self, mut other: Self) {
+        self.0.append(&mut other.0);
+    }
+
+    pub(in crate::transform) fn set_context(&mut self, context: &WayContext) {
+        for msg in &mut self.0 {
+            msg.set_context(context);
+        }
+    }
 }
 
-impl std::fmt::Display for RoadWarnings {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for RoadWarnings {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(
             f,
             "{}",
@@ -62,10 +77,23 @@ pub enum RoadError {
     RoundTrip,
 }
 
+impl RoadError {
+    /// Messages carry the context,
+    /// `WayNotRoad` and `RoundTrip` have no message to attach it to
+    pub(in crate::transform) fn set_context(&mut self, context: &WayContext) {
+        match self {
+            RoadError::Msg(msg) => msg.set_context(context),
+            RoadError::Warnings(warnings) => warnings.set_context(context),
+            RoadError::WayNotRoad | RoadError::RoundTrip => {},
+        }
+    }
+}
+
+#[cfg(feature = "std")]
 impl std::error::Error for RoadError {}
 
-impl std::fmt::Display for RoadError {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for RoadError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         match self {
             RoadError::WayNotRoad => write!(f, "way is not road"),
             RoadError::Msg(msg) => msg.fmt(f),
@@ -78,7 +106,7 @@ impl std::fmt::Display for RoadError {
 impl From<DuplicateKeyError> for RoadError {
     #[track_caller]
     fn from(e: DuplicateKeyError) -> Self {
-        e.into()
+        TagsToLanesMsg::from(e).into()
     }
 }
 
@@ -99,4 +127,24 @@ impl From<RoadWarnings> for RoadError {
 pub struct RoadFromTags {
     pub road: Road,
     pub warnings: RoadWarnings,
+    /// How the width of each of the lanes of the road was determined,
+    /// `None` for a lane without a width, e.g. a separator
+    pub widths: Vec<Option<LaneWidth>>,
+}
+
+impl RoadFromTags {
+    /// Reverse the lanes with their widths, as if the way were drawn in the opposite direction
+    pub(crate) fn reverse(&mut self) {
+        self.road.reverse();
+        self.widths.reverse();
+    }
+
+    /// Normalise for canonical output, see [`Road::canonicalize`]
+    #[cfg(feature = "std")]
+    pub fn canonicalize(&mut self) {
+        self.road.canonicalize();
+        for lane_width in self.widths.iter_mut().flatten() {
+            lane_width.width = lane_width.width.map(Metre::canonical);
+        }
+    }
 }
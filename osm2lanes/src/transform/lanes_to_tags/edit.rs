@@ -0,0 +1,140 @@
+use osm_tags::{Tags, TagsRef};
+
+use super::{lanes_to_tags_merge, Config, LanesToTagsMsg};
+use crate::locale::Locale;
+use crate::metric::Metre;
+use crate::road::{Designated, Lane, Road};
+
+/// A change to a single lane of a road, lanes are indexed from left to right
+#[derive(Clone, Debug, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum LaneEdit {
+    /// Set the width, `None` to use the default width
+    SetWidth { index: usize, width: Option<Metre> },
+    /// Change the designation of a travel or parking lane
+    SetDesignated {
+        index: usize,
+        designated: Designated,
+    },
+    /// Insert a lane before `index`, or at the end if `index` is the number of lanes
+    Insert { index: usize, lane: Lane },
+    /// Remove the lane
+    Remove { index: usize },
+}
+
+impl LaneEdit {
+    fn apply(&self, road: &mut Road) -> Result<(), LanesToTagsMsg> {
+        match self {
+            Self::SetWidth { index, width } => match lane_mut(road, *index)? {
+                Lane::Travel { width: w, .. }
+                | Lane::Parking { width: w, .. }
+                | Lane::Shoulder { width: w, .. } => *w = *width,
+                Lane::Separator { .. } => {
+                    return Err(LanesToTagsMsg::invalid_edit("width of separator"))
+                },
+            },
+            Self::SetDesignated { index, designated } => match lane_mut(road, *index)? {
+                Lane::Travel { designated: d, .. } | Lane::Parking { designated: d, .. } => {
+                    *d = *designated;
+                },
+                Lane::Shoulder { .. } | Lane::Separator { .. } => {
+                    return Err(LanesToTagsMsg::invalid_edit("designation of lane"))
+                },
+            },
+            Self::Insert { index, lane } => {
+                if *index > road.lanes.len() {
+                    return Err(LanesToTagsMsg::invalid_edit("lane index out of range"));
+                }
+                road.lanes.insert(*index, lane.clone());
+            },
+            Self::Remove { index } => {
+                if *index >= road.lanes.len() {
+                    return Err(LanesToTagsMsg::invalid_edit("lane index out of range"));
+                }
+                road.lanes.remove(*index);
+            },
+        }
+        Ok(())
+    }
+}
+
+fn lane_mut(road: &mut Road, index: usize) -> Result<&mut Lane, LanesToTagsMsg> {
+    road.lanes
+        .get_mut(index)
+        .ok_or_else(|| LanesToTagsMsg::invalid_edit("lane index out of range"))
+}
+
+/// Apply an edit to the road described by `original`,
+/// returning the edited road and the tags describing it.
+///
+/// Tags that are not described by the lanes are kept from `original`.
+///
+/// # Errors
+///
+/// If the edit does not apply to the road, or see [`super::lanes_to_tags`]
+pub fn apply_lane_edit(
+    original: &TagsRef<'_>,
+    road: &Road,
+    edit: &LaneEdit,
+    locale: &Locale,
+    config: &Config,
+) -> Result<(Road, Tags), LanesToTagsMsg> {
+    let mut road = road.clone();
+    edit.apply(&mut road)?;
+    let tags = lanes_to_tags_merge(&road, original, locale, config)?;
+    Ok((road, tags))
+}
+
+#[cfg(test)]
+mod tests {
+    use osm_tags::Tags;
+
+    use super::{apply_lane_edit, LaneEdit};
+    use crate::locale::Locale;
+    use crate::road::{Designated, Lane};
+    use crate::transform::{tags_to_lanes, LanesToTagsConfig, TagsToLanesConfig};
+
+    #[test]
+    fn test_apply_lane_edit() {
+        let tags = Tags::from_pairs([
+            ("highway", "secondary"),
+            ("name", "Main Street"),
+            ("lanes", "2"),
+            ("shoulder", "no"),
+            ("sidewalk", "no"),
+            ("cycleway:right", "lane"),
+        ])
+        .unwrap();
+        let locale = Locale::builder().build();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::default())
+            .unwrap()
+            .road;
+        let index = road.lanes.iter().position(Lane::is_bicycle).unwrap();
+        let (edited, edited_tags) = apply_lane_edit(
+            &tags,
+            &road,
+            &LaneEdit::SetDesignated {
+                index,
+                designated: Designated::Bus,
+            },
+            &locale,
+            &LanesToTagsConfig::new(false),
+        )
+        .unwrap();
+        assert_eq!(edited.lanes.len(), road.lanes.len());
+        assert_eq!(edited_tags.get("name"), Some("Main Street"));
+        assert_eq!(edited_tags.get("cycleway:right"), None);
+        assert_eq!(edited_tags.get("busway:right"), Some("lane"));
+
+        assert!(apply_lane_edit(
+            &tags,
+            &road,
+            &LaneEdit::Remove {
+                index: road.lanes.len()
+            },
+            &locale,
+            &LanesToTagsConfig::new(false),
+        )
+        .is_err());
+    }
+}
@@ -1,14 +1,26 @@
 #![allow(clippy::module_name_repetitions)] // TODO: fix upstream
 
-use celes::Country;
-use osm_tag_schemes::Access;
-use osm_tags::Tags;
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 
+use osm_tag_schemes::{
+    keys, Access, Conditional, Crossing, EmbeddedRails, Expressway, HighwayType,
+    ParkingOrientation, RumbleStrips, Separation, Surface, TurnDirection,
+};
+use osm_tags::{TagKey, Tags, TagsRef};
+
+pub use self::edit::{apply_lane_edit, LaneEdit};
 pub use self::error::LanesToTagsMsg;
-use super::{tags_to_lanes, TagsToLanesConfig};
+use super::tags::{CYCLEWAY, SHOULDER, SIDEWALK};
+use super::{reverse_way, tags_to_lanes, TagsToLanesConfig};
 use crate::locale::{DrivingSide, Locale};
-use crate::metric::Speed;
-use crate::road::{AccessByType, Color, Designated, Direction, Lane, Marking, Road};
+use crate::metric::{Metre, Speed, Units};
+use crate::road::{
+    AccessAndDirection, AccessByType, Color, Delineation, Designated, Destination, Direction, Lane,
+    Marking, Restrictions, Road, RoadAttributes, Semantic, SeparatelyMapped, Style,
+};
 
 #[non_exhaustive]
 pub struct Config {
@@ -43,8 +55,12 @@ impl Lane {
     }
 }
 
+mod edit;
+
 mod error {
-    use std::panic::Location;
+    use alloc::borrow::ToOwned;
+    use alloc::string::String;
+    use core::panic::Location;
 
     use osm_tags::DuplicateKeyError;
 
@@ -67,11 +83,12 @@ mod error {
         Unimplemented(String),
         TagsDuplicateKey(DuplicateKeyError),
         Roundtrip(Option<RoadError>),
+        InvalidEdit(String),
     }
 
-    impl std::fmt::Display for LanesToTagsMsg {
+    impl core::fmt::Display for LanesToTagsMsg {
         #[allow(clippy::panic_in_result_fn)]
-        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
             match &self.issue {
                 LanesToTagsIssue::Unimplemented(description) => {
                     write!(f, "unimplemented: '{}' - {}", description, self.location)
@@ -81,10 +98,14 @@ mod error {
                 LanesToTagsIssue::Roundtrip(Some(e)) => {
                     write!(f, "roundtrip: {} - {}", e, self.location)
                 },
+                LanesToTagsIssue::InvalidEdit(description) => {
+                    write!(f, "invalid edit: '{}' - {}", description, self.location)
+                },
             }
         }
     }
 
+    #[cfg(feature = "std")]
     impl std::error::Error for LanesToTagsMsg {}
 
     impl LanesToTagsMsg {
@@ -105,6 +126,15 @@ mod error {
                 issue: LanesToTagsIssue::Roundtrip(None),
             }
         }
+
+        #[must_use]
+        #[track_caller]
+        pub fn invalid_edit(description: &str) -> Self {
+            LanesToTagsMsg {
+                location: Location::caller(),
+                issue: LanesToTagsIssue::InvalidEdit(description.to_owned()),
+            }
+        }
     }
 
     impl From<DuplicateKeyError> for LanesToTagsMsg {
@@ -148,10 +178,36 @@ pub fn lanes_to_tags(
         .iter()
         .any(|lane| lane.is_motor() || lane.is_bus())
     {
-        tags.checked_insert("highway", "path")?;
+        if !set_pedestrian_street(road, &mut tags)?
+            && !set_crossing(&road.lanes, &mut tags)?
+            && !set_cycleway_way(&road.lanes, &mut tags)?
+        {
+            // Keep the classification of ways without vehicle lanes, e.g. `highway=steps`
+            let highway = match road.highway.r#type() {
+                r#type @ (HighwayType::Bridleway
+                | HighwayType::Corridor
+                | HighwayType::Cycleway
+                | HighwayType::Footway
+                | HighwayType::Path
+                | HighwayType::Pedestrian
+                | HighwayType::Steps) => r#type,
+                _ => HighwayType::Path,
+            };
+            tags.checked_insert("highway", highway.to_string())?;
+        }
         return Ok(tags);
     }
 
+    // A oneway against the direction of the way is written as the way drawn the other way around
+    let mut motor_lanes = road.lanes.iter().filter(|lane| lane.is_motor()).peekable();
+    if motor_lanes.peek().is_some()
+        && motor_lanes.all(|lane| lane.direction() == Some(Direction::Backward))
+    {
+        let mut reversed = road.clone();
+        reversed.reverse();
+        return Ok(reverse_way(&lanes_to_tags(&reversed, locale, config)?)?);
+    }
+
     tags.checked_insert("highway", road.highway.r#type().to_string())?;
     if road.highway.is_construction() {
         return Err(LanesToTagsMsg::unimplemented("construction=*"));
@@ -159,19 +215,61 @@ pub fn lanes_to_tags(
     if road.highway.is_proposed() {
         return Err(LanesToTagsMsg::unimplemented("construction=*"));
     }
+    if let Some(priority_road) = road.priority_road {
+        tags.checked_insert("priority_road", <&str>::from(priority_road))?;
+    }
+    if let Some(passing_places) = road.passing_places {
+        tags.checked_insert("passing_places", <&str>::from(passing_places))?;
+    }
+    if let Some(expressway) = road.expressway {
+        tags.checked_insert("expressway", <&str>::from(expressway))?;
+    }
+    if let Some(dual_carriageway) = road.dual_carriageway {
+        tags.checked_insert("dual_carriageway", <&str>::from(dual_carriageway))?;
+    }
+    if let Some(cycle_street) = road.cycle_street {
+        tags.checked_insert(cycle_street.key(), "yes")?;
+    }
 
     let lanes = &road.lanes;
 
-    let lane_count = set_lanes(lanes, &mut tags)?;
-    let oneway = set_oneway(lanes, &mut tags, locale, lane_count)?;
+    set_lanes(lanes, &mut tags)?;
+    let oneway = set_oneway(lanes, &mut tags)?;
 
-    set_shoulder(lanes, &mut tags)?;
-    set_pedestrian(lanes, &mut tags)?;
+    set_shoulder(lanes, locale.units, &mut tags)?;
+    set_pedestrian(
+        lanes,
+        road.separately_mapped.as_ref(),
+        locale.units,
+        &mut tags,
+    )?;
     set_parking(lanes, &mut tags)?;
-    set_cycleway(lanes, &mut tags, oneway, locale)?;
+    set_cycleway(
+        lanes,
+        &mut tags,
+        oneway,
+        road.cycle_street.is_some(),
+        locale,
+    )?;
+    set_separately_mapped(road.separately_mapped.as_ref(), &mut tags)?;
+    set_cycleway_lanes(lanes, &mut tags, oneway)?;
+    set_cycleway_buffers(lanes, locale.units, &mut tags)?;
     set_busway(lanes, &mut tags, oneway)?;
+    set_bus_conditional(lanes, &mut tags)?;
+    set_taxi_and_hov(lanes, &mut tags)?;
+    set_access(lanes, &mut tags)?;
+    set_surface(road.attributes, lanes, &mut tags)?;
+    set_widths(road, locale, &mut tags)?;
+    set_embedded_rails(lanes, &mut tags)?;
+    set_trolley_wire(lanes, &mut tags)?;
+    set_restrictions(road.restrictions, lanes, locale.units, &mut tags)?;
+    set_destinations(lanes, &mut tags)?;
+    set_turns(lanes, &mut tags, oneway)?;
+    set_physical_devices(lanes, &mut tags)?;
 
-    let max_speed = get_max_speed(lanes, &mut tags)?;
+    let implied_max_speed = set_shared_space(road, locale, &mut tags)?
+        .or_else(|| locale.default_max_speed(road.highway.r#type()));
+    let max_speed = get_max_speed(lanes, implied_max_speed, &mut tags)?;
 
     locale_additions(max_speed, locale, &mut tags)?;
 
@@ -180,7 +278,84 @@ pub fn lanes_to_tags(
     Ok(tags)
 }
 
-fn set_lanes(lanes: &[Lane], tags: &mut Tags) -> Result<usize, LanesToTagsMsg> {
+/// Keys written by [`lanes_to_tags`], including any subkeys
+const MANAGED_KEYS: &[&str] = &[
+    "highway",
+    "lanes",
+    "oneway",
+    "turn:lanes",
+    "shoulder",
+    "sidewalk",
+    "parking:lane",
+    "parking:left",
+    "parking:right",
+    "parking:both",
+    "parking:condition",
+    "cycleway",
+    "busway",
+    "bus:lanes",
+    "taxi:lanes",
+    "hov:lanes",
+    "maxspeed",
+    "motorroad",
+    "surface",
+    "smoothness",
+    "width",
+    "embedded_rails",
+    "trolley_wire",
+    "priority_road",
+    "passing_places",
+    "expressway",
+    "dual_carriageway",
+    "shared_space",
+    "cyclestreet",
+    "bicycle_road",
+    "maxheight",
+    "maxweight",
+    "maxlength",
+    "destination",
+    "rumble_strips",
+    "separation",
+    "footway",
+    "crossing",
+    "foot",
+    "bicycle",
+    "vehicle",
+    "motor_vehicle",
+    "segregated",
+];
+
+fn is_managed(key: &str) -> bool {
+    MANAGED_KEYS.iter().any(|managed| {
+        key.strip_prefix(managed)
+            .map_or(false, |rest| rest.is_empty() || rest.starts_with(':'))
+    })
+}
+
+/// Convert Lanes back to Tags, keeping the original tags that are not described by the lanes
+///
+/// Tags that [`lanes_to_tags`] writes replace the original,
+/// such that the difference to `original` is minimal.
+///
+/// # Errors
+///
+/// See [`lanes_to_tags`]
+pub fn lanes_to_tags_merge(
+    road: &Road,
+    original: &TagsRef<'_>,
+    locale: &Locale,
+    config: &Config,
+) -> Result<Tags, LanesToTagsMsg> {
+    let mut tags = lanes_to_tags(road, locale, config)?;
+    for (key, val) in original.to_str_pairs() {
+        if !is_managed(key) {
+            tags.checked_insert(key.to_owned(), val)?;
+        }
+    }
+    Ok(tags)
+}
+
+fn set_lanes(lanes: &[Lane], tags: &mut TagsRef<'_>) -> Result<(), LanesToTagsMsg> {
     let lane_count = lanes
         .iter()
         .filter(|lane| {
@@ -194,16 +369,36 @@ fn set_lanes(lanes: &[Lane], tags: &mut Tags) -> Result<usize, LanesToTagsMsg> {
         })
         .count();
     tags.checked_insert("lanes", lane_count.to_string())?;
-    Ok(lane_count)
+    Ok(())
 }
 
 /// Returns oneway
-fn set_oneway(
-    lanes: &[Lane],
-    tags: &mut Tags,
-    locale: &Locale,
-    lane_count: usize,
-) -> Result<bool, LanesToTagsMsg> {
+fn set_oneway(lanes: &[Lane], tags: &mut TagsRef<'_>) -> Result<bool, LanesToTagsMsg> {
+    let mut motor_lanes = lanes.iter().filter(|lane| lane.is_motor());
+    if let (
+        Some(Lane::Travel {
+            direction: Some(Direction::Alternating),
+            ..
+        }),
+        None,
+    ) = (motor_lanes.next(), motor_lanes.next())
+    {
+        tags.checked_insert("oneway", "alternating")?;
+        return Ok(false);
+    }
+    if lanes.iter().filter(|lane| lane.is_motor()).all(|lane| {
+        matches!(
+            lane,
+            Lane::Travel {
+                direction: Some(Direction::Reversible),
+                ..
+            }
+        )
+    }) && lanes.iter().any(Lane::is_motor)
+    {
+        tags.checked_insert("oneway", "reversible")?;
+        return Ok(true);
+    }
     if lanes.iter().filter(|lane| lane.is_motor()).all(|lane| {
         matches!(
             lane,
@@ -258,18 +453,16 @@ fn set_oneway(
             )
         }) {
             tags.checked_insert("lanes:both_ways", "1")?;
-            if lane_count >= 3 {
-                tags.checked_insert(
-                    "turn:lanes:both_ways",
-                    locale.driving_side.opposite().to_string(),
-                )?;
-            }
         }
         Ok(false)
     }
 }
 
-fn set_shoulder(lanes: &[Lane], tags: &mut Tags) -> Result<(), LanesToTagsMsg> {
+fn set_shoulder(
+    lanes: &[Lane],
+    units: Units,
+    tags: &mut TagsRef<'_>,
+) -> Result<(), LanesToTagsMsg> {
     match (
         lanes.first().unwrap().is_shoulder(),
         lanes.last().unwrap().is_shoulder(),
@@ -286,40 +479,126 @@ fn set_shoulder(lanes: &[Lane], tags: &mut Tags) -> Result<(), LanesToTagsMsg> {
         },
         (true, true) => tags.checked_insert("shoulder", "both")?,
     }
+    for (side, lane) in [("left", lanes.first()), ("right", lanes.last())] {
+        if let Some(Lane::Shoulder { width, access }) = lane {
+            if let Some(width) = width {
+                tags.checked_insert(SHOULDER + side + "width", width.to_tag_value(units))?;
+            }
+            if let Some(access) = access {
+                for (mode, access) in [
+                    ("foot", &access.foot),
+                    ("bicycle", &access.bicycle),
+                    ("emergency", &access.emergency),
+                ] {
+                    if let Some(access) = access {
+                        tags.checked_insert(
+                            SHOULDER + side + "access" + mode,
+                            <&str>::from(access.access.clone()),
+                        )?;
+                    }
+                }
+            }
+        }
+    }
     Ok(())
 }
 
-fn set_pedestrian(lanes: &[Lane], tags: &mut Tags) -> Result<(), LanesToTagsMsg> {
+/// `sidewalk=*`, or `sidewalk:left=*` and `sidewalk:right=*` when mapped separately on one side
+fn set_pedestrian(
+    lanes: &[Lane],
+    separately_mapped: Option<&SeparatelyMapped>,
+    units: Units,
+    tags: &mut TagsRef<'_>,
+) -> Result<(), LanesToTagsMsg> {
+    let sidewalk = |lane: Option<&Lane>, separate: Option<&Vec<Designated>>| {
+        if lane.map_or(false, Lane::is_foot) {
+            "yes"
+        } else if separate.map_or(false, |separate| separate.contains(&Designated::Foot)) {
+            "separate"
+        } else {
+            "no"
+        }
+    };
     match (
-        lanes.first().unwrap().is_foot(),
-        lanes.last().unwrap().is_foot(),
+        sidewalk(
+            lanes.first(),
+            separately_mapped.map(|separately_mapped| &separately_mapped.left),
+        ),
+        sidewalk(
+            lanes.last(),
+            separately_mapped.map(|separately_mapped| &separately_mapped.right),
+        ),
     ) {
-        (false, false) => {
-            // TODO do we want to always be explicit about this?
-            tags.checked_insert("sidewalk", "no")?;
+        // TODO do we want to always be explicit about this?
+        ("no", "no") => tags.checked_insert("sidewalk", "no")?,
+        ("yes", "no") => tags.checked_insert("sidewalk", "left")?,
+        ("no", "yes") => tags.checked_insert("sidewalk", "right")?,
+        ("yes", "yes") => tags.checked_insert("sidewalk", "both")?,
+        ("separate", "separate") => tags.checked_insert("sidewalk", "separate")?,
+        (left, right) => {
+            tags.checked_insert(SIDEWALK + "left", left)?;
+            tags.checked_insert(SIDEWALK + "right", right)?;
         },
-        (true, false) => tags.checked_insert("sidewalk", "left")?,
-        (false, true) => tags.checked_insert("sidewalk", "right")?,
-        (true, true) => tags.checked_insert("sidewalk", "both")?,
+    }
+    for (side, lane) in [("left", lanes.first()), ("right", lanes.last())] {
+        if let Some(Lane::Travel {
+            designated: Designated::Foot,
+            shared,
+            width,
+            surface,
+            smoothness,
+            access,
+            ..
+        }) = lane
+        {
+            if shared == &Some(Designated::Bicycle) {
+                let bicycle = access
+                    .as_ref()
+                    .and_then(|access| access.bicycle.as_ref())
+                    .map_or("yes", |bicycle| <&str>::from(bicycle.access.clone()));
+                tags.checked_insert(SIDEWALK + side + "bicycle", bicycle)?;
+            }
+            if let Some(width) = width {
+                tags.checked_insert(SIDEWALK + side + "width", width.to_tag_value(units))?;
+            }
+            if let Some(surface) = surface {
+                tags.checked_insert(SIDEWALK + side + "surface", <&str>::from(surface))?;
+            }
+            if let Some(smoothness) = smoothness {
+                tags.checked_insert(SIDEWALK + side + "smoothness", <&str>::from(smoothness))?;
+            }
+        }
     }
     Ok(())
 }
 
-fn set_parking(lanes: &[Lane], tags: &mut Tags) -> Result<(), LanesToTagsMsg> {
+fn set_parking(lanes: &[Lane], tags: &mut TagsRef<'_>) -> Result<(), LanesToTagsMsg> {
+    let orientation = |lane: &Lane| match lane {
+        Lane::Parking { orientation, .. } => {
+            Some(orientation.unwrap_or(ParkingOrientation::Parallel))
+        },
+        _ => None,
+    };
     match (
         lanes
             .iter()
             .take_while(|lane| !lane.is_motor())
-            .any(|lane| matches!(lane, Lane::Parking { .. })),
+            .find_map(orientation),
         lanes
             .iter()
             .skip_while(|lane| !lane.is_motor())
-            .any(|lane| matches!(lane, Lane::Parking { .. })),
+            .find_map(orientation),
     ) {
-        (false, false) => {},
-        (true, false) => tags.checked_insert("parking:lane:left", "parallel")?,
-        (false, true) => tags.checked_insert("parking:lane:right", "parallel")?,
-        (true, true) => tags.checked_insert("parking:lane:both", "parallel")?,
+        (None, None) => {},
+        (Some(left), None) => tags.checked_insert("parking:lane:left", <&str>::from(left))?,
+        (None, Some(right)) => tags.checked_insert("parking:lane:right", <&str>::from(right))?,
+        (Some(left), Some(right)) if left == right => {
+            tags.checked_insert("parking:lane:both", <&str>::from(left))?;
+        },
+        (Some(left), Some(right)) => {
+            tags.checked_insert("parking:lane:left", <&str>::from(left))?;
+            tags.checked_insert("parking:lane:right", <&str>::from(right))?;
+        },
     }
 
     if let Some(Lane::Separator { markings, .. }) = lanes.first() {
@@ -337,8 +616,9 @@ fn set_parking(lanes: &[Lane], tags: &mut Tags) -> Result<(), LanesToTagsMsg> {
 
 fn set_cycleway(
     lanes: &[Lane],
-    tags: &mut Tags,
+    tags: &mut TagsRef<'_>,
     oneway: bool,
+    cycle_street: bool,
     locale: &Locale,
 ) -> Result<(), LanesToTagsMsg> {
     let left_cycle_lane: Option<&Lane> = lanes
@@ -350,11 +630,36 @@ fn set_cycleway(
         .rev()
         .take_while(|lane| !lane.is_motor())
         .find(|lane| lane.is_bicycle());
-    match (left_cycle_lane.is_some(), right_cycle_lane.is_some()) {
-        (false, false) => {},
-        (true, false) => tags.checked_insert("cycleway:left", "lane")?,
-        (false, true) => tags.checked_insert("cycleway:right", "lane")?,
-        (true, true) => tags.checked_insert("cycleway:both", "lane")?,
+    let variant = |lane: &Lane| match lane {
+        Lane::Travel {
+            delineation: Some(Delineation::Physical),
+            ..
+        } => "track",
+        _ => "lane",
+    };
+    match (left_cycle_lane.map(variant), right_cycle_lane.map(variant)) {
+        (None, None) => {},
+        (Some(left), Some(right)) if left == right => {
+            tags.checked_insert("cycleway:both", left)?;
+        },
+        (left, right) => {
+            if let Some(left) = left {
+                tags.checked_insert("cycleway:left", left)?;
+            }
+            if let Some(right) = right {
+                tags.checked_insert("cycleway:right", right)?;
+            }
+        },
+    }
+
+    // On a cycle street the lanes are shared without `cycleway=shared_lane`
+    if !cycle_street {
+        set_shared(
+            lanes,
+            tags,
+            left_cycle_lane.is_none(),
+            right_cycle_lane.is_none(),
+        )?;
     }
 
     // if the way has oneway=yes and you are allowed to cycle against that oneway flow
@@ -383,6 +688,12 @@ fn set_cycleway(
             tags.checked_insert("cycleway:left:oneway", "-1")?;
         },
         Some(Direction::Both) => tags.checked_insert("cycleway:left:oneway", "no")?,
+        Some(Direction::Alternating) => {
+            tags.checked_insert("cycleway:left:oneway", "alternating")?;
+        },
+        Some(Direction::Reversible) => {
+            tags.checked_insert("cycleway:left:oneway", "reversible")?;
+        },
         None => {},
     }
     match right_cycle_lane.and_then(Lane::direction) {
@@ -393,20 +704,19 @@ fn set_cycleway(
             tags.checked_insert("cycleway:right:oneway", "-1")?;
         },
         Some(Direction::Both) => tags.checked_insert("cycleway:right:oneway", "no")?,
+        Some(Direction::Alternating) => {
+            tags.checked_insert("cycleway:right:oneway", "alternating")?;
+        },
+        Some(Direction::Reversible) => {
+            tags.checked_insert("cycleway:right:oneway", "reversible")?;
+        },
         None => {},
     }
 
-    if let Some(Lane::Travel {
-        width: Some(width), ..
-    }) = left_cycle_lane
-    {
-        tags.checked_insert("cycleway:left:width", width.val().to_string())?;
-    }
-    if let Some(Lane::Travel {
-        width: Some(width), ..
-    }) = right_cycle_lane
-    {
-        tags.checked_insert("cycleway:right:width", width.val().to_string())?;
+    for (side, lane) in [("left", left_cycle_lane), ("right", right_cycle_lane)] {
+        if let Some(lane) = lane {
+            set_cycleway_attributes(lane, side, locale.units, tags)?;
+        }
     }
 
     // Handle shared lanes
@@ -427,7 +737,226 @@ fn set_cycleway(
     Ok(())
 }
 
-fn set_busway(lanes: &[Lane], tags: &mut Tags, oneway: bool) -> Result<(), LanesToTagsMsg> {
+/// `cycleway:SIDE:lane=*`, `cycleway:SIDE:width=*`, `cycleway:SIDE:surface=*`
+/// and `cycleway:SIDE:colour=*`
+fn set_cycleway_attributes(
+    lane: &Lane,
+    side: &str,
+    units: Units,
+    tags: &mut TagsRef<'_>,
+) -> Result<(), LanesToTagsMsg> {
+    if let Lane::Travel {
+        cycleway_lane,
+        width,
+        surface,
+        colour,
+        ..
+    } = lane
+    {
+        if let Some(cycleway_lane) = cycleway_lane {
+            tags.checked_insert(CYCLEWAY + side + "lane", <&str>::from(cycleway_lane))?;
+        }
+        if let Some(width) = width {
+            tags.checked_insert(CYCLEWAY + side + "width", width.to_tag_value(units))?;
+        }
+        if let Some(surface) = surface {
+            tags.checked_insert(CYCLEWAY + side + "surface", <&str>::from(surface))?;
+        }
+        if let Some(colour) = colour {
+            tags.checked_insert(CYCLEWAY + side + "colour", colour.as_str())?;
+        }
+    }
+    Ok(())
+}
+
+/// `cycleway:SIDE=separate` for cycleways mapped as separate ways
+fn set_separately_mapped(
+    separately_mapped: Option<&SeparatelyMapped>,
+    tags: &mut TagsRef<'_>,
+) -> Result<(), LanesToTagsMsg> {
+    let (left, right) = separately_mapped.map_or((false, false), |separately_mapped| {
+        (
+            separately_mapped.left.contains(&Designated::Bicycle),
+            separately_mapped.right.contains(&Designated::Bicycle),
+        )
+    });
+    match (left, right) {
+        (false, false) => {},
+        (true, false) => tags.checked_insert("cycleway:left", "separate")?,
+        (false, true) => tags.checked_insert("cycleway:right", "separate")?,
+        (true, true) => tags.checked_insert("cycleway:both", "separate")?,
+    }
+    Ok(())
+}
+
+/// `cycleway:lanes=*` for the cycle lanes between the motor lanes,
+/// or `cycleway:lanes:forward=*` and `cycleway:lanes:backward=*` if not oneway
+fn set_cycleway_lanes(
+    lanes: &[Lane],
+    tags: &mut TagsRef<'_>,
+    oneway: bool,
+) -> Result<(), LanesToTagsMsg> {
+    let is_carriageway = |lane: &Lane| lane.is_motor() || lane.is_bus();
+    let (first, last) = match (
+        lanes.iter().position(|lane| is_carriageway(lane)),
+        lanes.iter().rposition(|lane| is_carriageway(lane)),
+    ) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return Ok(()),
+    };
+    let carriageway = lanes.get(first..=last).unwrap_or_default();
+    if !carriageway.iter().any(Lane::is_bicycle) {
+        return Ok(());
+    }
+    // Left to right in the direction of travel
+    let value = |lanes: &mut dyn Iterator<Item = &Lane>| -> Option<String> {
+        let values: Vec<&str> = lanes
+            .filter_map(|lane| {
+                if lane.is_bicycle() {
+                    Some("lane")
+                } else if is_carriageway(lane) {
+                    Some("none")
+                } else {
+                    None
+                }
+            })
+            .collect();
+        values.contains(&"lane").then(|| values.join("|"))
+    };
+    if oneway {
+        if let Some(value) = value(&mut carriageway.iter()) {
+            tags.checked_insert("cycleway:lanes", value)?;
+        }
+        return Ok(());
+    }
+    if carriageway.iter().any(|lane| {
+        matches!(lane, Lane::Travel { .. })
+            && !matches!(
+                lane.direction(),
+                Some(Direction::Forward | Direction::Backward)
+            )
+    }) {
+        return Err(LanesToTagsMsg::unimplemented(
+            "cycle lanes between the motor lanes of both directions",
+        ));
+    }
+    let direction = |direction: Direction| move |lane: &&Lane| lane.direction() == Some(direction);
+    if let Some(value) = value(&mut carriageway.iter().filter(direction(Direction::Forward))) {
+        tags.checked_insert("cycleway:lanes:forward", value)?;
+    }
+    if let Some(value) = value(
+        &mut carriageway
+            .iter()
+            .rev()
+            .filter(direction(Direction::Backward)),
+    ) {
+        tags.checked_insert("cycleway:lanes:backward", value)?;
+    }
+    Ok(())
+}
+
+/// `cycleway:SIDE:buffer=*` towards the centre of the road,
+/// and `cycleway:SIDE:buffer:SIDE=*` towards the edge,
+/// from the buffer separators next to the outermost cycle lanes
+fn set_cycleway_buffers(
+    lanes: &[Lane],
+    units: Units,
+    tags: &mut TagsRef<'_>,
+) -> Result<(), LanesToTagsMsg> {
+    let buffer = |lane: Option<&Lane>| match lane {
+        Some(Lane::Separator {
+            semantic: Some(Semantic::Buffer),
+            markings: Some(markings),
+        }) => markings
+            .iter()
+            .find(|marking| marking.style == Style::NoFill)
+            .and_then(|marking| marking.width),
+        _ => None,
+    };
+    if let Some(index) = lanes
+        .iter()
+        .take_while(|lane| !lane.is_motor())
+        .position(Lane::is_bicycle)
+    {
+        if let Some(width) = buffer(lanes.get(index.saturating_add(1))) {
+            tags.checked_insert("cycleway:left:buffer", width.to_tag_value(units))?;
+        }
+        if let Some(width) = index
+            .checked_sub(1)
+            .and_then(|index| buffer(lanes.get(index)))
+        {
+            tags.checked_insert("cycleway:left:buffer:left", width.to_tag_value(units))?;
+        }
+    }
+    if let Some(index) = lanes
+        .iter()
+        .rev()
+        .take_while(|lane| !lane.is_motor())
+        .position(Lane::is_bicycle)
+        .and_then(|index| lanes.len().checked_sub(index.saturating_add(1)))
+    {
+        if let Some(width) = index
+            .checked_sub(1)
+            .and_then(|index| buffer(lanes.get(index)))
+        {
+            tags.checked_insert("cycleway:right:buffer", width.to_tag_value(units))?;
+        }
+        if let Some(width) = buffer(lanes.get(index.saturating_add(1))) {
+            tags.checked_insert("cycleway:right:buffer:right", width.to_tag_value(units))?;
+        }
+    }
+    Ok(())
+}
+
+/// `cycleway:SIDE=shared_lane` for sharrows, or `cycleway:SIDE=share_busway`,
+/// on the sides without a cycle lane
+fn set_shared(
+    lanes: &[Lane],
+    tags: &mut TagsRef<'_>,
+    no_left_cycle_lane: bool,
+    no_right_cycle_lane: bool,
+) -> Result<(), LanesToTagsMsg> {
+    let shared = |lane: &Lane| match lane {
+        Lane::Travel {
+            designated: Designated::Motor,
+            shared: Some(Designated::Bicycle),
+            ..
+        } => Some("shared_lane"),
+        Lane::Travel {
+            designated: Designated::Bus,
+            shared: Some(Designated::Bicycle),
+            ..
+        } => Some("share_busway"),
+        _ => None,
+    };
+    let is_shareable = |lane: &&Lane| lane.is_motor() || lane.is_bus();
+    let left_shared = lanes
+        .iter()
+        .find(is_shareable)
+        .and_then(shared)
+        .filter(|_| no_left_cycle_lane);
+    let right_shared = lanes
+        .iter()
+        .rev()
+        .find(is_shareable)
+        .and_then(shared)
+        .filter(|_| no_right_cycle_lane);
+    match (left_shared, right_shared) {
+        (None, None) => {},
+        (Some(left), None) => tags.checked_insert("cycleway:left", left)?,
+        (None, Some(right)) => tags.checked_insert("cycleway:right", right)?,
+        (Some(left), Some(right)) if left == right => {
+            tags.checked_insert("cycleway:both", left)?;
+        },
+        (Some(left), Some(right)) => {
+            tags.checked_insert("cycleway:left", left)?;
+            tags.checked_insert("cycleway:right", right)?;
+        },
+    }
+    Ok(())
+}
+
+fn set_busway(lanes: &[Lane], tags: &mut TagsRef<'_>, oneway: bool) -> Result<(), LanesToTagsMsg> {
     let left_bus_lane = lanes
         .iter()
         .take_while(|lane| !lane.is_motor())
@@ -437,12 +966,30 @@ fn set_busway(lanes: &[Lane], tags: &mut Tags, oneway: bool) -> Result<(), Lanes
         .rev()
         .take_while(|lane| !lane.is_motor())
         .find(|lane| lane.is_bus());
-    if left_bus_lane.is_none() && right_bus_lane.is_none() && lanes.iter().any(Lane::is_bus) {
+    // Only the lanes counted in `lanes=*`
+    let carriageway: Vec<&Lane> = lanes
+        .iter()
+        .filter(|lane| lane.is_motor() || lane.is_bus())
+        .collect();
+    let bus_access = |lane: &Lane| -> Option<&'static str> {
+        lane.access()
+            .and_then(|access| access.bus.as_ref())
+            .map(|bus| bus.access.clone().into())
+    };
+    if (left_bus_lane.is_none() && right_bus_lane.is_none() && lanes.iter().any(Lane::is_bus))
+        || carriageway.iter().any(|lane| bus_access(lane).is_some())
+    {
         tags.checked_insert(
             "bus:lanes",
-            lanes
+            carriageway
                 .iter()
-                .map(|lane| if lane.is_bus() { "designated" } else { "" })
+                .map(|lane| {
+                    if lane.is_bus() {
+                        "designated"
+                    } else {
+                        bus_access(lane).unwrap_or("")
+                    }
+                })
                 .collect::<Vec<_>>()
                 .as_slice()
                 .join("|"),
@@ -459,45 +1006,748 @@ fn set_busway(lanes: &[Lane], tags: &mut Tags, oneway: bool) -> Result<(), Lanes
             (None, None) => {},
             (Some(left), None) => tags.checked_insert("busway:left", value(left))?,
             (None, Some(right)) => tags.checked_insert("busway:right", value(right))?,
-            (Some(_left), Some(_right)) => tags.checked_insert("busway:both", "lane")?,
+            (Some(left), Some(right)) if value(left) == value(right) => {
+                tags.checked_insert("busway:both", value(left))?;
+            },
+            (Some(left), Some(right)) => {
+                tags.checked_insert("busway:left", value(left))?;
+                tags.checked_insert("busway:right", value(right))?;
+            },
+        }
+    }
+    Ok(())
+}
+
+/// `bus:lanes:conditional=*` for the lanes counted in `lanes=*`,
+/// with the restrictions in the order their conditions first appear
+fn set_bus_conditional(lanes: &[Lane], tags: &mut TagsRef<'_>) -> Result<(), LanesToTagsMsg> {
+    let carriageway: Vec<&[Conditional<Access>]> = lanes
+        .iter()
+        .filter(|lane| lane.is_motor() || lane.is_bus())
+        .map(|lane| {
+            lane.access()
+                .map_or(&[][..], |access| access.bus_conditional.as_slice())
+        })
+        .collect();
+    let mut conditions: Vec<&str> = Vec::new();
+    for conditional in carriageway.iter().copied().flatten() {
+        if !conditions.contains(&conditional.condition.as_str()) {
+            conditions.push(&conditional.condition);
+        }
+    }
+    if conditions.is_empty() {
+        return Ok(());
+    }
+    let restrictions: Vec<String> = conditions
+        .into_iter()
+        .map(|condition| {
+            let values: Vec<&str> = carriageway
+                .iter()
+                .map(|lane| {
+                    lane.iter()
+                        .find(|conditional| conditional.condition == condition)
+                        .map_or("", |conditional| conditional.value.clone().into())
+                })
+                .collect();
+            format!("{} @ ({})", values.join("|"), condition)
+        })
+        .collect();
+    tags.checked_insert("bus:lanes:conditional", restrictions.join("; "))?;
+    Ok(())
+}
+
+/// `taxi:lanes=*` and `hov:lanes=*` for the access of the lanes counted in `lanes=*`
+fn set_taxi_and_hov(lanes: &[Lane], tags: &mut TagsRef<'_>) -> Result<(), LanesToTagsMsg> {
+    let carriageway: Vec<&Lane> = lanes
+        .iter()
+        .filter(|lane| lane.is_motor() || lane.is_bus())
+        .collect();
+    let lanes_access = |access: fn(&AccessByType) -> Option<&AccessAndDirection>| {
+        let values: Vec<Option<&'static str>> = carriageway
+            .iter()
+            .map(|lane| {
+                lane.access()
+                    .and_then(access)
+                    .map(|access| access.access.clone().into())
+            })
+            .collect();
+        values.iter().any(Option::is_some).then(|| {
+            values
+                .iter()
+                .map(|value| value.unwrap_or(""))
+                .collect::<Vec<_>>()
+                .as_slice()
+                .join("|")
+        })
+    };
+    if let Some(taxi) = lanes_access(|access| access.taxi.as_ref()) {
+        tags.checked_insert("taxi:lanes", taxi)?;
+    }
+    if let Some(hov) = lanes_access(|access| access.hov.as_ref()) {
+        tags.checked_insert("hov:lanes", hov)?;
+    }
+    Ok(())
+}
+
+/// `foot=*`, `bicycle=*`, and `motor_vehicle=*` if all lanes used by that mode agree,
+/// otherwise `vehicle:lanes=*` or `motor_vehicle:lanes=*` for the lanes counted in `lanes=*`
+fn set_access(lanes: &[Lane], tags: &mut TagsRef<'_>) -> Result<(), LanesToTagsMsg> {
+    let way_access =
+        |designated: &[Designated], access: fn(&AccessByType) -> Option<&AccessAndDirection>| {
+            let mut values = lanes
+                .iter()
+                .filter(|lane| match lane {
+                    Lane::Travel {
+                        designated: lane_designated,
+                        ..
+                    } => designated.contains(lane_designated),
+                    _ => false,
+                })
+                .map(|lane| lane_access(lane, access));
+            let first = values.next().flatten()?;
+            values
+                .all(|value| value == Some(first))
+                .then(|| <&str>::from(first.clone()))
+        };
+    if let Some(foot) = way_access(&[Designated::Foot], |access| access.foot.as_ref()) {
+        tags.checked_insert("foot", foot)?;
+    }
+    let bicycle = way_access(
+        &[Designated::Bicycle, Designated::Motor, Designated::Bus],
+        |access| access.bicycle.as_ref(),
+    );
+    if let Some(bicycle) = bicycle {
+        tags.checked_insert("bicycle", bicycle)?;
+    }
+    if let Some(motor_vehicle) = way_access(&[Designated::Motor, Designated::Bus], |access| {
+        access.motor.as_ref()
+    }) {
+        tags.checked_insert("motor_vehicle", motor_vehicle)?;
+        return Ok(());
+    }
+
+    let carriageway: Vec<&Lane> = lanes
+        .iter()
+        .filter(|lane| lane.is_motor() || lane.is_bus())
+        .collect();
+    let lanes_access = |access: fn(&AccessByType) -> Option<&AccessAndDirection>| {
+        carriageway
+            .iter()
+            .map(|lane| lane_access(lane, access))
+            .collect::<Vec<_>>()
+    };
+    let motor_vehicle = lanes_access(|access| access.motor.as_ref());
+    if motor_vehicle.iter().all(Option::is_none) {
+        return Ok(());
+    }
+    let key =
+        if bicycle.is_none() && lanes_access(|access| access.bicycle.as_ref()) == motor_vehicle {
+            "vehicle:lanes"
+        } else {
+            "motor_vehicle:lanes"
+        };
+    tags.checked_insert(
+        key,
+        motor_vehicle
+            .iter()
+            .map(|access| access.map_or("", |access| access.clone().into()))
+            .collect::<Vec<_>>()
+            .as_slice()
+            .join("|"),
+    )?;
+    Ok(())
+}
+
+/// The access of a lane for a mode, if it applies in all directions of the lane
+fn lane_access(
+    lane: &Lane,
+    access: fn(&AccessByType) -> Option<&AccessAndDirection>,
+) -> Option<&Access> {
+    match lane.access().and_then(access) {
+        Some(AccessAndDirection {
+            access,
+            direction: None,
+        }) => Some(access),
+        _ => None,
+    }
+}
+
+/// `smoothness=*` and `surface=*` of the way,
+/// with `surface:lanes=*` for the lanes counted in `lanes=*` that differ from the way
+fn set_surface(
+    attributes: RoadAttributes,
+    lanes: &[Lane],
+    tags: &mut TagsRef<'_>,
+) -> Result<(), LanesToTagsMsg> {
+    if let Some(smoothness) = attributes.smoothness {
+        tags.checked_insert("smoothness", <&str>::from(smoothness))?;
+    }
+    let surfaces: Vec<Option<Surface>> = lanes
+        .iter()
+        .filter_map(|lane| match lane {
+            Lane::Travel {
+                designated: Designated::Motor | Designated::Bus,
+                surface,
+                ..
+            } => Some(*surface),
+            _ => None,
+        })
+        .collect();
+    match surfaces.first() {
+        Some(first) if surfaces.iter().all(|surface| surface == first) => {
+            if let Some(surface) = first.or(attributes.surface) {
+                tags.checked_insert("surface", <&str>::from(surface))?;
+            }
+        },
+        Some(_) => {
+            // Lanes with the surface of the way are left empty
+            if let Some(surface) = attributes.surface {
+                tags.checked_insert("surface", <&str>::from(surface))?;
+            }
+            tags.checked_insert(
+                "surface:lanes",
+                surfaces
+                    .iter()
+                    .map(|surface| match surface {
+                        Some(surface) if Some(*surface) != attributes.surface => {
+                            <&str>::from(surface)
+                        },
+                        _ => "",
+                    })
+                    .collect::<Vec<_>>()
+                    .as_slice()
+                    .join("|"),
+            )?;
+        },
+        None => {
+            if let Some(surface) = attributes.surface {
+                tags.checked_insert("surface", <&str>::from(surface))?;
+            }
+        },
+    }
+    Ok(())
+}
+
+/// `width:lanes=*` if any lane counted in `lanes=*` is not the width implied by the locale,
+/// where lanes of the implied width are left empty
+fn set_widths(road: &Road, locale: &Locale, tags: &mut TagsRef<'_>) -> Result<(), LanesToTagsMsg> {
+    let highway = road.highway.r#type();
+    let widths: Vec<Option<Metre>> = road
+        .lanes
+        .iter()
+        .filter_map(|lane| match lane {
+            Lane::Travel {
+                designated: designated @ (Designated::Motor | Designated::Bus),
+                width,
+                ..
+            } => {
+                let implied = if road.expressway == Some(Expressway::Yes) {
+                    locale.expressway_travel_width(designated, highway)
+                } else {
+                    locale.travel_width(designated, None, highway)
+                };
+                Some(width.filter(|width| *width != implied))
+            },
+            _ => None,
+        })
+        .collect();
+    if widths.iter().any(Option::is_some) {
+        tags.checked_insert(
+            "width:lanes",
+            widths
+                .iter()
+                .map(|width| {
+                    width
+                        .map(|width| width.to_tag_value(locale.units))
+                        .unwrap_or_default()
+                })
+                .collect::<Vec<_>>()
+                .as_slice()
+                .join("|"),
+        )?;
+    }
+    Ok(())
+}
+
+/// `embedded_rails=*` if the lanes counted in `lanes=*` contain rails,
+/// and `embedded_rails:lanes=*` if only some of them do
+fn set_embedded_rails(lanes: &[Lane], tags: &mut TagsRef<'_>) -> Result<(), LanesToTagsMsg> {
+    let rails: Vec<Option<EmbeddedRails>> = lanes
+        .iter()
+        .filter_map(|lane| match lane {
+            Lane::Travel {
+                designated: Designated::Motor | Designated::Bus,
+                embedded_rails,
+                ..
+            } => Some(*embedded_rails),
+            _ => None,
+        })
+        .collect();
+    if let Some(first) = rails.iter().flatten().next() {
+        tags.checked_insert("embedded_rails", <&str>::from(first))?;
+        if rails.iter().any(|lane_rails| lane_rails != &Some(*first)) {
+            tags.checked_insert(
+                "embedded_rails:lanes",
+                rails
+                    .iter()
+                    .map(|lane_rails| lane_rails.map_or("", <&str>::from))
+                    .collect::<Vec<_>>()
+                    .as_slice()
+                    .join("|"),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// `trolley_wire=*` if there are overhead wires above any lane
+fn set_trolley_wire(lanes: &[Lane], tags: &mut TagsRef<'_>) -> Result<(), LanesToTagsMsg> {
+    let wire = lanes.iter().find_map(|lane| match lane {
+        Lane::Travel {
+            trolley_wire: Some(trolley_wire),
+            ..
+        } => Some(*trolley_wire),
+        _ => None,
+    });
+    if let Some(wire) = wire {
+        tags.checked_insert("trolley_wire", <&str>::from(wire))?;
+    }
+    Ok(())
+}
+
+/// `footway=crossing` or `cycleway=crossing` if a lane crosses a road,
+/// returns whether the lanes are a crossing
+fn set_crossing(lanes: &[Lane], tags: &mut TagsRef<'_>) -> Result<bool, LanesToTagsMsg> {
+    let crossing = lanes.iter().find_map(|lane| match lane {
+        Lane::Travel {
+            designated,
+            crossing: Some(crossing),
+            ..
+        } => Some((designated, crossing)),
+        _ => None,
+    });
+    let (designated, crossing) = match crossing {
+        Some(crossing) => crossing,
+        None => return Ok(false),
+    };
+    match designated {
+        Designated::Foot => {
+            tags.checked_insert("highway", "footway")?;
+            tags.checked_insert("footway", "crossing")?;
+        },
+        Designated::Bicycle => {
+            tags.checked_insert("highway", "cycleway")?;
+            tags.checked_insert("cycleway", "crossing")?;
+        },
+        Designated::Motor | Designated::Bus => return Ok(false),
+    }
+    if crossing != &Crossing::Yes {
+        tags.checked_insert("crossing", <&str>::from(crossing))?;
+    }
+    Ok(true)
+}
+
+/// `highway=pedestrian`, with the `vehicle=*` access of its single foot lane,
+/// returns false if the road is not a pedestrian street
+fn set_pedestrian_street(road: &Road, tags: &mut TagsRef<'_>) -> Result<bool, LanesToTagsMsg> {
+    if road.highway.r#type() != HighwayType::Pedestrian {
+        return Ok(false);
+    }
+    tags.checked_insert("highway", "pedestrian")?;
+    let vehicle = road.lanes.iter().find_map(|lane| match lane {
+        Lane::Travel {
+            designated: Designated::Foot,
+            access: Some(access),
+            ..
+        } => access.motor.as_ref(),
+        _ => None,
+    });
+    if let Some(vehicle) = vehicle {
+        if vehicle.access != Access::No {
+            tags.checked_insert("vehicle", <&str>::from(vehicle.access.clone()))?;
+        }
+    }
+    Ok(true)
+}
+
+/// `highway=cycleway`, with a `foot=*` that is either `segregated=*` or shared,
+/// returns false if there is no cycle lane
+fn set_cycleway_way(lanes: &[Lane], tags: &mut TagsRef<'_>) -> Result<bool, LanesToTagsMsg> {
+    let (direction, access) = match lanes.iter().find_map(|lane| match lane {
+        Lane::Travel {
+            designated: Designated::Bicycle,
+            direction,
+            access,
+            ..
+        } => Some((direction, access)),
+        _ => None,
+    }) {
+        Some(cycle_lane) => cycle_lane,
+        None => return Ok(false),
+    };
+    tags.checked_insert("highway", "cycleway")?;
+    if direction == &Some(Direction::Forward) {
+        tags.checked_insert("oneway", "yes")?;
+    }
+    let footway = lanes.iter().find_map(|lane| match lane {
+        Lane::Travel {
+            designated: Designated::Foot,
+            access,
+            ..
+        } => Some(access),
+        _ => None,
+    });
+    let (foot, segregated) = match footway {
+        Some(access) => (access.as_ref().and_then(|a| a.foot.as_ref()), true),
+        None => (access.as_ref().and_then(|a| a.foot.as_ref()), false),
+    };
+    if let Some(foot) = foot {
+        tags.checked_insert("foot", <&str>::from(foot.access.clone()))?;
+        tags.checked_insert("segregated", if segregated { "yes" } else { "no" })?;
+    }
+    Ok(true)
+}
+
+/// `maxheight=*`, `maxweight=*`, and `maxlength=*` from the road,
+/// and their `:lanes=*` variants if any of the lanes counted in `lanes=*` has its own
+fn set_restrictions(
+    road_restrictions: Option<Restrictions>,
+    lanes: &[Lane],
+    units: Units,
+    tags: &mut TagsRef<'_>,
+) -> Result<(), LanesToTagsMsg> {
+    if let Some(restrictions) = road_restrictions {
+        if let Some(max_height) = restrictions.max_height {
+            tags.checked_insert("maxheight", max_height.to_tag_value(units))?;
         }
+        if let Some(max_weight) = restrictions.max_weight {
+            tags.checked_insert("maxweight", max_weight.val().to_string())?;
+        }
+        if let Some(max_length) = restrictions.max_length {
+            tags.checked_insert("maxlength", max_length.to_tag_value(units))?;
+        }
+    }
+
+    let lane_restrictions: Vec<Restrictions> = lanes
+        .iter()
+        .filter_map(|lane| match lane {
+            Lane::Travel {
+                designated: Designated::Motor | Designated::Bus,
+                restrictions,
+                ..
+            } => Some(restrictions.unwrap_or_default()),
+            _ => None,
+        })
+        .collect();
+    let mut set_lanes = |key: &'static str, value: &dyn Fn(&Restrictions) -> Option<String>| {
+        if lane_restrictions.iter().any(|r| value(r).is_some()) {
+            tags.checked_insert(
+                key,
+                lane_restrictions
+                    .iter()
+                    .map(|r| value(r).unwrap_or_default())
+                    .collect::<Vec<_>>()
+                    .as_slice()
+                    .join("|"),
+            )
+        } else {
+            Ok(())
+        }
+    };
+    set_lanes("maxheight:lanes", &|r| {
+        r.max_height.map(|m| m.to_tag_value(units))
+    })?;
+    set_lanes("maxweight:lanes", &|r| {
+        r.max_weight.map(|t| t.val().to_string())
+    })?;
+    set_lanes("maxlength:lanes", &|r| {
+        r.max_length.map(|m| m.to_tag_value(units))
+    })?;
+    Ok(())
+}
+
+/// `rumble_strips=*` and `separation=*`, from the physical devices of the separators
+fn set_physical_devices(lanes: &[Lane], tags: &mut TagsRef<'_>) -> Result<(), LanesToTagsMsg> {
+    fn separator_styles<'lanes>(
+        lanes: &'lanes [Lane],
+        semantics: &'lanes [Semantic],
+    ) -> impl Iterator<Item = Style> + 'lanes {
+        lanes
+            .iter()
+            .filter_map(|lane| match lane {
+                Lane::Separator {
+                    semantic: Some(semantic),
+                    markings: Some(markings),
+                } if semantics.contains(semantic) => Some(markings),
+                _ => None,
+            })
+            .flat_map(|markings| markings.iter().map(|marking| marking.style))
+    }
+    let has_rumble_strip = |semantic: Semantic| {
+        separator_styles(lanes, &[semantic]).any(|style| style == Style::RumbleStrip)
+    };
+    let rumble_strips = match (
+        has_rumble_strip(Semantic::Centre),
+        has_rumble_strip(Semantic::Shoulder),
+    ) {
+        (false, false) => None,
+        (true, false) => Some(RumbleStrips::Centre),
+        (false, true) => Some(RumbleStrips::Edge),
+        (true, true) => Some(RumbleStrips::Yes),
+    };
+    if let Some(rumble_strips) = rumble_strips {
+        tags.checked_insert("rumble_strips", <&str>::from(rumble_strips))?;
+    }
+
+    let separation =
+        separator_styles(lanes, &[Semantic::Lane, Semantic::Modal]).find_map(|style| match style {
+            Style::RumbleStrip => Some(Separation::RumbleStrip),
+            Style::BottsDots => Some(Separation::BottsDots),
+            Style::RaisedSeparator => Some(Separation::RaisedSeparator),
+            Style::Studs => Some(Separation::Studs),
+            _ => None,
+        });
+    if let Some(separation) = separation {
+        tags.checked_insert("separation", <&str>::from(separation))?;
     }
     Ok(())
 }
 
-fn get_max_speed(lanes: &[Lane], tags: &mut Tags) -> Result<Option<Speed>, LanesToTagsMsg> {
-    let max_speeds: Vec<Speed> = lanes
+/// `destination:lanes=*`, or `destination:lang:<language>:lanes=*` for names in a language,
+/// `destination:ref:lanes=*`, and `destination:symbol:lanes=*`
+fn set_destinations(lanes: &[Lane], tags: &mut TagsRef<'_>) -> Result<(), LanesToTagsMsg> {
+    let destinations: Vec<Option<&Destination>> = lanes
         .iter()
         .filter_map(|lane| match lane {
-            Lane::Travel { max_speed, .. } => *max_speed,
+            Lane::Travel {
+                designated: Designated::Motor | Designated::Bus,
+                destination,
+                ..
+            } => Some(destination.as_deref()),
             _ => None,
         })
         .collect();
-    if let Some(max_speed) = max_speeds.first() {
-        // Check if all are the same
-        // See benches/benchmark_all_same.rs
-        if max_speeds.windows(2).all(|w| {
-            let speeds: &[Speed; 2] = w.try_into().unwrap();
-            speeds[0] == speeds[1]
-        }) {
-            tags.checked_insert("maxspeed", max_speed.to_string())?;
-            Ok(Some(*max_speed))
+    let mut set_lanes = |key: TagKey, values: &dyn Fn(&Destination) -> Vec<&str>| {
+        let lanes: Vec<String> = destinations
+            .iter()
+            .map(|destination| destination.map(values).unwrap_or_default().join(";"))
+            .collect();
+        if lanes.iter().any(|lane| !lane.is_empty()) {
+            tags.checked_insert(key, lanes.as_slice().join("|"))
         } else {
-            Err(LanesToTagsMsg::unimplemented(
-                "different max speeds per lane",
-            ))
+            Ok(())
         }
+    };
+    let mut languages: Vec<Option<&str>> = destinations
+        .iter()
+        .flatten()
+        .map(|destination| destination.language.as_deref())
+        .collect();
+    languages.sort_unstable();
+    languages.dedup();
+    for language in languages {
+        let key = match language {
+            None => keys::DESTINATION_LANES,
+            Some(language) => keys::DESTINATION_LANG + language + "lanes",
+        };
+        set_lanes(key, &|d| {
+            if d.language.as_deref() == language {
+                d.names.iter().map(String::as_str).collect()
+            } else {
+                Vec::new()
+            }
+        })?;
+    }
+    set_lanes(keys::DESTINATION_REF_LANES, &|d| {
+        d.refs.iter().map(String::as_str).collect()
+    })?;
+    set_lanes(keys::DESTINATION_SYMBOL_LANES, &|d| {
+        d.symbols.iter().copied().map(<&str>::from).collect()
+    })?;
+    Ok(())
+}
+
+/// `turn:lanes=*` for oneway roads, otherwise `turn:lanes:forward=*`, `turn:lanes:backward=*`,
+/// and `turn:lanes:both_ways=*`, each left to right in the direction of travel
+fn set_turns(lanes: &[Lane], tags: &mut TagsRef<'_>, oneway: bool) -> Result<(), LanesToTagsMsg> {
+    let turns = |direction: Direction| -> Vec<Option<&Vec<TurnDirection>>> {
+        lanes
+            .iter()
+            .filter_map(|lane| match lane {
+                Lane::Travel {
+                    designated: Designated::Motor | Designated::Bus,
+                    direction: Some(lane_direction),
+                    turn,
+                    ..
+                } if *lane_direction == direction => Some(turn.as_ref()),
+                _ => None,
+            })
+            .collect()
+    };
+    let mut set_lanes = |key: &'static str, turns: &[Option<&Vec<TurnDirection>>]| {
+        if turns.iter().any(Option::is_some) {
+            let lanes: Vec<String> = turns
+                .iter()
+                .map(|turn| match turn {
+                    Some(turn) if !turn.is_empty() => turn
+                        .iter()
+                        .copied()
+                        .map(<&str>::from)
+                        .collect::<Vec<_>>()
+                        .join(";"),
+                    _ => "none".to_owned(),
+                })
+                .collect();
+            tags.checked_insert(key, lanes.as_slice().join("|"))
+        } else {
+            Ok(())
+        }
+    };
+    if oneway {
+        set_lanes("turn:lanes", &turns(Direction::Forward))?;
     } else {
-        Ok(None)
+        set_lanes("turn:lanes:forward", &turns(Direction::Forward))?;
+        // Backward lanes are left to right for traffic travelling backward
+        let mut backward = turns(Direction::Backward);
+        backward.reverse();
+        set_lanes("turn:lanes:backward", &backward)?;
+        set_lanes("turn:lanes:both_ways", &turns(Direction::Both))?;
+    }
+    Ok(())
+}
+
+/// `shared_space=yes` for vehicle lanes shared with pedestrians, unless implied by `highway=living_street`,
+/// returns the speed limit implied by either
+fn set_shared_space(
+    road: &Road,
+    locale: &Locale,
+    tags: &mut TagsRef<'_>,
+) -> Result<Option<Speed>, LanesToTagsMsg> {
+    if !road.lanes.iter().any(|lane| {
+        matches!(
+            lane,
+            Lane::Travel {
+                designated: Designated::Motor | Designated::Bus,
+                shared: Some(Designated::Foot),
+                ..
+            }
+        )
+    }) {
+        return Ok(None);
+    }
+    if road.highway.r#type() != HighwayType::LivingStreet {
+        tags.checked_insert("shared_space", "yes")?;
+    }
+    Ok(Some(locale.living_street_max_speed()))
+}
+
+/// The speed if the same for all, `None` if empty
+fn all_same(max_speeds: &[Speed]) -> Result<Option<Speed>, ()> {
+    match max_speeds.first() {
+        // Check if all are the same
+        // See benches/benchmark_all_same.rs
+        Some(max_speed) => {
+            if max_speeds.windows(2).all(|w| {
+                let speeds: &[Speed; 2] = w.try_into().unwrap();
+                speeds[0] == speeds[1]
+            }) {
+                Ok(Some(*max_speed))
+            } else {
+                Err(())
+            }
+        },
+        None => Ok(None),
+    }
+}
+
+/// `maxspeed=*` if the same for all lanes, unless implied,
+/// otherwise `maxspeed:forward=*` and `maxspeed:backward=*` if the same for all lanes in each direction,
+/// otherwise `maxspeed:lanes=*` if only the lanes counted in `lanes=*` have a speed limit
+fn get_max_speed(
+    lanes: &[Lane],
+    implied: Option<Speed>,
+    tags: &mut TagsRef<'_>,
+) -> Result<Option<Speed>, LanesToTagsMsg> {
+    let max_speeds = |filter: fn(Option<Direction>) -> bool| -> Vec<Speed> {
+        lanes
+            .iter()
+            .filter_map(|lane| match lane {
+                Lane::Travel {
+                    max_speed,
+                    direction,
+                    ..
+                } if filter(*direction) => *max_speed,
+                _ => None,
+            })
+            .collect()
+    };
+    if let Ok(max_speed) = all_same(&max_speeds(|_| true)) {
+        if let Some(max_speed) = max_speed {
+            if implied != Some(max_speed) {
+                tags.checked_insert("maxspeed", max_speed.to_string())?;
+            }
+        }
+        return Ok(max_speed);
+    }
+    let other = max_speeds(|direction| {
+        !matches!(direction, Some(Direction::Forward | Direction::Backward))
+    });
+    match (
+        all_same(&max_speeds(|direction| {
+            direction == Some(Direction::Forward)
+        })),
+        all_same(&max_speeds(|direction| {
+            direction == Some(Direction::Backward)
+        })),
+    ) {
+        (Ok(Some(forward)), Ok(Some(backward))) if other.is_empty() => {
+            tags.checked_insert("maxspeed:forward", forward.to_string())?;
+            tags.checked_insert("maxspeed:backward", backward.to_string())?;
+            Ok(None)
+        },
+        _ => {
+            let (carriageway, non_motorized): (Vec<&Lane>, Vec<&Lane>) = lanes
+                .iter()
+                .partition(|lane| lane.is_motor() || lane.is_bus());
+            if non_motorized.iter().any(|lane| {
+                matches!(
+                    lane,
+                    Lane::Travel {
+                        max_speed: Some(_),
+                        ..
+                    }
+                )
+            }) {
+                return Err(LanesToTagsMsg::unimplemented(
+                    "different max speeds per lane",
+                ));
+            }
+            tags.checked_insert(
+                "maxspeed:lanes",
+                carriageway
+                    .iter()
+                    .map(|lane| match lane {
+                        Lane::Travel {
+                            max_speed: Some(max_speed),
+                            ..
+                        } => max_speed.to_string(),
+                        _ => String::new(),
+                    })
+                    .collect::<Vec<_>>()
+                    .as_slice()
+                    .join("|"),
+            )?;
+            Ok(None)
+        },
     }
 }
 
 fn locale_additions(
     max_speed: Option<Speed>,
     locale: &Locale,
-    tags: &mut Tags,
+    tags: &mut TagsRef<'_>,
 ) -> Result<(), LanesToTagsMsg> {
-    if max_speed == Some(Speed::Kph(100.0)) && locale.country == Some(Country::the_netherlands()) {
+    if max_speed == Some(Speed::Kph(100.0)) && locale.is_netherlands() {
         tags.checked_insert("motorroad", "yes")?;
     }
     Ok(())
@@ -505,7 +1755,7 @@ fn locale_additions(
 
 fn check_roundtrip(
     config: &Config,
-    tags: &Tags,
+    tags: &TagsRef<'_>,
     locale: &Locale,
     lanes: &[Lane],
 ) -> Result<(), LanesToTagsMsg> {
@@ -524,3 +1774,35 @@ fn check_roundtrip(
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use osm_tags::Tags;
+
+    use super::{lanes_to_tags, Config};
+    use crate::locale::Locale;
+    use crate::transform::{tags_to_lanes, TagsToLanesConfig};
+
+    #[test]
+    fn test_highway_roundtrip() {
+        let locale = Locale::builder().build();
+        for (pairs, highway) in [
+            (
+                &[("highway", "residential"), ("lanes", "2")][..],
+                "residential",
+            ),
+            (&[("highway", "primary"), ("lanes", "2")][..], "primary"),
+            (&[("highway", "steps")][..], "steps"),
+            (&[("highway", "footway")][..], "footway"),
+            (&[("highway", "pedestrian")][..], "pedestrian"),
+            (&[("highway", "path")][..], "path"),
+        ] {
+            let tags = Tags::from_pairs(pairs.iter().copied()).unwrap();
+            let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::default())
+                .unwrap()
+                .road;
+            let output = lanes_to_tags(&road, &locale, &Config::new(false)).unwrap();
+            assert_eq!(output.get("highway"), Some(highway), "{}", tags.to_string());
+        }
+    }
+}
@@ -1,4 +1,8 @@
-use osm_tags::TagKey;
+use alloc::borrow::ToOwned;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use osm_tags::{DuplicateKeyError, TagKey, Tags, TagsRef};
 
 use crate::locale::DrivingSide;
 use crate::road::{Designated, Direction, Lane};
@@ -7,10 +11,20 @@ mod error;
 pub use error::{RoadError, RoadFromTags, RoadWarnings};
 
 mod tags_to_lanes;
-pub use tags_to_lanes::{tags_to_lanes, Config as TagsToLanesConfig, Infer, TagsToLanesMsg};
+#[cfg(feature = "rayon")]
+pub use tags_to_lanes::tags_to_lanes_par;
+pub use tags_to_lanes::{
+    tags_to_lanes, tags_to_lanes_by_locale, tags_to_lanes_with_sidepaths, tags_to_lanes_with_trace,
+    Config as TagsToLanesConfig, Incremental, Infer, LaneDifference, LaneWidth, LocaleComparison,
+    Sidepath, SidepathSide, TagsToLanesMsg, Trace, TraceChange, TraceHtml, TraceLane, TraceStep,
+    Transformer, WayContext, WidthConstraint,
+};
 
 mod lanes_to_tags;
-pub use lanes_to_tags::{lanes_to_tags, Config as LanesToTagsConfig, LanesToTagsMsg};
+pub use lanes_to_tags::{
+    apply_lane_edit, lanes_to_tags, lanes_to_tags_merge, Config as LanesToTagsConfig, LaneEdit,
+    LanesToTagsMsg,
+};
 
 pub mod tags {
     use osm_tags::TagKey;
@@ -18,9 +32,10 @@ pub mod tags {
     pub const CYCLEWAY: TagKey = TagKey::from_static("cycleway");
     pub const SIDEWALK: TagKey = TagKey::from_static("sidewalk");
     pub const SHOULDER: TagKey = TagKey::from_static("shoulder");
+    pub const PARKING: TagKey = TagKey::from_static("parking");
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum WaySide {
     Both,
     Right,
@@ -28,7 +43,7 @@ enum WaySide {
 }
 
 impl WaySide {
-    fn as_str(&self) -> &'static str {
+    fn as_str(self) -> &'static str {
         match self {
             Self::Both => "both",
             Self::Right => "right",
@@ -125,3 +140,33 @@ impl Lane {
         }
     }
 }
+
+/// The tags of the same oneway drawn in the opposite direction
+///
+/// Swaps `left` and `right`, and `forward` and `backward`, within the keys,
+/// the side in the values of `sidewalk=*`, `shoulder=*` and `footway=*`,
+/// and `yes` and `-1` in the values of `oneway=*` and its subkeys.
+/// The values of `*:lanes=*` are kept, as on a oneway they are in the direction of travel.
+/// Reversing twice gives the original tags.
+fn reverse_way(tags: &TagsRef<'_>) -> Result<Tags, DuplicateKeyError> {
+    Tags::from_pairs(tags.iter().map(|(key, val)| {
+        let parts: Vec<&str> = key
+            .split(':')
+            .map(|part| match part {
+                "left" => "right",
+                "right" => "left",
+                "forward" => "backward",
+                "backward" => "forward",
+                part => part,
+            })
+            .collect();
+        let val = match (parts.first(), parts.last(), val) {
+            (Some(&("sidewalk" | "shoulder" | "footway")), _, "left") => "right",
+            (Some(&("sidewalk" | "shoulder" | "footway")), _, "right") => "left",
+            (Some(&"oneway"), _, "yes") | (_, Some(&"oneway"), "yes") => "-1",
+            (Some(&"oneway"), _, "-1") | (_, Some(&"oneway"), "-1") => "yes",
+            (_, _, val) => val,
+        };
+        (parts.join(":"), val)
+    }))
+}
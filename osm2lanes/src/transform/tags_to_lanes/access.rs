@@ -0,0 +1,159 @@
+use core::str::FromStr;
+
+use osm_tag_schemes::{keys, Access, LaneDependent};
+use osm_tags::{TagKey, TagsRef};
+
+use super::infer::Infer;
+use super::road::{LaneBuilder, LaneType, RoadBuilder};
+use super::{TagsToLanesMsg, TagsTyped};
+use crate::locale::Locale;
+use crate::road::{AccessAndDirection, Designated};
+use crate::transform::RoadWarnings;
+
+/// Access of the way by transport mode, each inheriting from its parent in the access hierarchy
+/// <https://wiki.openstreetmap.org/wiki/Key:access#Transport_mode_restrictions>
+#[derive(Debug)]
+struct Resolved {
+    foot: Option<Access>,
+    bicycle: Option<Access>,
+    motor: Option<Access>,
+}
+
+impl Resolved {
+    fn from_tags(tags: &TagsRef<'_>, warnings: &mut RoadWarnings) -> Self {
+        let mut get = |key: &TagKey, parent: &Option<Access>| -> Option<Access> {
+            tags.get_enum(key, warnings).or_else(|| parent.clone())
+        };
+        let access = get(&keys::ACCESS, &None);
+        let foot = get(&keys::FOOT, &access);
+        let vehicle = get(&keys::VEHICLE, &access);
+        let bicycle = get(&keys::BICYCLE, &vehicle);
+        let motor = get(&keys::MOTOR_VEHICLE, &vehicle);
+        Self {
+            foot,
+            bicycle,
+            motor,
+        }
+    }
+
+    /// Set the access of the modes of transport that use the lane, by its designation
+    fn apply(&self, lane: &mut LaneBuilder) -> Result<(), TagsToLanesMsg> {
+        fn set(
+            inferred: &mut Infer<AccessAndDirection>,
+            access: &Option<Access>,
+        ) -> Result<(), TagsToLanesMsg> {
+            // Access tagged for the lane itself, e.g. `bicycle:lanes=*`, takes precedence
+            if matches!(inferred, Infer::Direct(_)) {
+                return Ok(());
+            }
+            if let Some(access) = access {
+                inferred.set(Infer::Direct(AccessAndDirection {
+                    access: access.clone(),
+                    direction: None,
+                }))?;
+            }
+            Ok(())
+        }
+
+        match (lane.r#type.some(), lane.designated.some()) {
+            (Some(LaneType::Travel), Some(Designated::Foot)) => {
+                set(&mut lane.access.foot, &self.foot)?;
+            },
+            (Some(LaneType::Travel), Some(Designated::Bicycle)) => {
+                set(&mut lane.access.bicycle, &self.bicycle)?;
+            },
+            (Some(LaneType::Travel), Some(Designated::Motor | Designated::Bus)) => {
+                set(&mut lane.access.bicycle, &self.bicycle)?;
+                set(&mut lane.access.motor, &self.motor)?;
+            },
+            (Some(LaneType::Shoulder), _) => {
+                set(&mut lane.access.foot, &self.foot)?;
+                set(&mut lane.access.bicycle, &self.bicycle)?;
+            },
+            _ => {},
+        }
+        Ok(())
+    }
+}
+
+/// Access from `access=*`, `vehicle=*`, `motor_vehicle=*`, `bicycle=*`, and `foot=*`, resolved for the modes of transport using each lane.
+///
+/// Access tagged for the lane itself, or implied by the highway, takes precedence.
+pub(super) fn access(
+    tags: &TagsRef<'_>,
+    locale: &Locale,
+    road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
+) -> Result<(), TagsToLanesMsg> {
+    let resolved = Resolved::from_tags(tags, warnings);
+    for lane in road.lanes_ltr_mut(locale) {
+        resolved.apply(lane)?;
+    }
+    Ok(())
+}
+
+/// Access of a single lane, from a value of `access:lanes=*`,
+/// where an empty value is the access of the way
+#[derive(Debug, PartialEq, Eq)]
+struct LaneAccess(Option<Access>);
+
+impl FromStr for LaneAccess {
+    type Err = <Access as FromStr>::Err;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            Ok(Self(None))
+        } else {
+            s.parse().map(|access| Self(Some(access)))
+        }
+    }
+}
+
+type Mode = fn(&mut LaneBuilder) -> &mut Infer<AccessAndDirection>;
+
+/// Access of individual lanes counted in `lanes=*`,
+/// from `access:lanes=*`, `vehicle:lanes=*`, and `motor_vehicle:lanes=*`,
+/// and their `:forward` and `:backward` variants.
+///
+/// The more specific modes of transport are applied last, taking precedence.
+pub(super) fn lane_access(
+    tags: &TagsRef<'_>,
+    locale: &Locale,
+    road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
+) {
+    const VEHICLE: &[Mode] = &[
+        |lane| &mut lane.access.bicycle,
+        |lane| &mut lane.access.motor,
+    ];
+    const MOTOR_VEHICLE: &[Mode] = &[|lane| &mut lane.access.motor];
+
+    for (key, modes) in [
+        (keys::ACCESS_LANES, VEHICLE),
+        (keys::VEHICLE_LANES, VEHICLE),
+        (keys::MOTOR_VEHICLE_LANES, MOTOR_VEHICLE),
+    ] {
+        match LaneDependent::<LaneAccess>::from_tags(tags, &key) {
+            Ok(None) => {},
+            Ok(Some(lanes)) => {
+                if let Some(lanes) = road.lane_dependent_mut(lanes, locale) {
+                    for (lane, LaneAccess(access)) in lanes {
+                        if let Some(access) = access {
+                            for mode in modes {
+                                *mode(lane) = Infer::Direct(AccessAndDirection {
+                                    access: access.clone(),
+                                    direction: None,
+                                });
+                            }
+                        }
+                    }
+                } else {
+                    warnings.push(TagsToLanesMsg::unsupported(
+                        "lane count mismatch",
+                        tags.subset(&[&key + "forward", &key + "backward", key]),
+                    ));
+                }
+            },
+            Err(e) => warnings.push(e.into()),
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use alloc::vec::Vec;
+
+use osm_tags::TagsRef;
+
+use super::{Config, Transformer};
+use crate::locale::Locale;
+use crate::road::Lane;
+use crate::transform::{RoadError, RoadFromTags};
+
+/// The same tags transformed in each of a list of locales,
+/// see [`tags_to_lanes_by_locale`]
+#[derive(Clone, Debug)]
+pub struct LocaleComparison {
+    /// The result for each locale, in the order of the locales
+    pub results: Vec<Result<RoadFromTags, RoadError>>,
+}
+
+/// A lane position where the locales disagree
+#[derive(Clone, Debug, PartialEq)]
+pub struct LaneDifference<'road> {
+    /// Index of the lane from left to right
+    pub index: usize,
+    /// The lane for each locale, `None` if the road has fewer lanes or could not be transformed
+    pub lanes: Vec<Option<&'road Lane>>,
+}
+
+impl LocaleComparison {
+    /// Every locale results in the same road, or every locale fails
+    #[must_use]
+    pub fn is_same(&self) -> bool {
+        let mut roads = self.results.iter().map(|result| {
+            result
+                .as_ref()
+                .ok()
+                .map(|road_from_tags| &road_from_tags.road)
+        });
+        match roads.next() {
+            None => true,
+            Some(first) => roads.all(|road| road == first),
+        }
+    }
+
+    /// Lanes that differ between the locales.
+    ///
+    /// Lanes are compared by position from left to right,
+    /// so locales driving on different sides mostly differ.
+    #[must_use]
+    pub fn lane_differences(&self) -> Vec<LaneDifference<'_>> {
+        let lanes: Vec<&[Lane]> = self
+            .results
+            .iter()
+            .map(|result| {
+                result
+                    .as_ref()
+                    .map_or(&[][..], |road_from_tags| &road_from_tags.road.lanes)
+            })
+            .collect();
+        let count = lanes.iter().map(|lanes| lanes.len()).max().unwrap_or(0);
+        (0..count)
+            .filter_map(|index| {
+                let lanes: Vec<Option<&Lane>> =
+                    lanes.iter().map(|lanes| lanes.get(index)).collect();
+                let first = lanes.first().copied().flatten();
+                if lanes.iter().all(|lane| *lane == first) {
+                    None
+                } else {
+                    Some(LaneDifference { index, lanes })
+                }
+            })
+            .collect()
+    }
+}
+
+/// Transform the same tags in each of the locales,
+/// like [`tags_to_lanes`](super::tags_to_lanes),
+/// to compare how the locale defaults change the interpretation.
+///
+/// ```
+/// use osm2lanes::locale::{DrivingSide, Locale};
+/// use osm2lanes::transform::{tags_to_lanes_by_locale, TagsToLanesConfig};
+/// use osm_tags::Tags;
+///
+/// let tags = Tags::from_pairs([("highway", "secondary"), ("sidewalk", "right")]).unwrap();
+/// let locales = [
+///     Locale::builder().driving_side(DrivingSide::Right).build(),
+///     Locale::builder().driving_side(DrivingSide::Left).build(),
+/// ];
+/// let comparison = tags_to_lanes_by_locale(&tags, &locales, &TagsToLanesConfig::default());
+/// assert_eq!(comparison.results.len(), 2);
+/// assert!(!comparison.is_same());
+/// assert!(!comparison.lane_differences().is_empty());
+/// ```
+#[must_use]
+pub fn tags_to_lanes_by_locale(
+    tags: &TagsRef<'_>,
+    locales: &[Locale],
+    config: &Config,
+) -> LocaleComparison {
+    let mut transformer = Transformer::new(config.clone());
+    LocaleComparison {
+        results: locales
+            .iter()
+            .map(|locale| transformer.transform(tags, locale))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use osm_tags::Tags;
+
+    use super::{tags_to_lanes_by_locale, LocaleComparison};
+    use crate::locale::{DrivingSide, Locale};
+    use crate::metric::Metre;
+    use crate::road::Lane;
+    use crate::transform::{RoadError, TagsToLanesConfig};
+
+    fn compare(tags: &Tags, locales: &[Locale]) -> LocaleComparison {
+        tags_to_lanes_by_locale(tags, locales, &TagsToLanesConfig::default())
+    }
+
+    #[test]
+    fn test_same() {
+        let tags = Tags::from_pairs([("highway", "secondary"), ("lanes", "2")]).unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let comparison = compare(&tags, &[locale.clone(), locale]);
+        assert!(comparison.is_same());
+        assert!(comparison.lane_differences().is_empty());
+
+        let comparison = compare(&tags, &[]);
+        assert!(comparison.is_same());
+        assert!(comparison.lane_differences().is_empty());
+    }
+
+    #[test]
+    fn test_lanes_differ() {
+        let tags = Tags::from_pairs([("highway", "secondary"), ("sidewalk", "right")]).unwrap();
+        let comparison = compare(
+            &tags,
+            &[
+                Locale::builder().driving_side(DrivingSide::Right).build(),
+                Locale::builder().driving_side(DrivingSide::Left).build(),
+            ],
+        );
+        assert!(!comparison.is_same());
+        let differences = comparison.lane_differences();
+        assert!(!differences.is_empty());
+        for difference in &differences {
+            assert_eq!(difference.lanes.len(), 2);
+            assert_ne!(difference.lanes.first(), difference.lanes.get(1));
+        }
+    }
+
+    #[test]
+    fn test_attribute_differs() {
+        let tags = Tags::from_pairs([("highway", "secondary"), ("lanes", "2")]).unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let mut comparison = compare(&tags, &[locale.clone(), locale]);
+        let lanes = &mut comparison
+            .results
+            .get_mut(1)
+            .unwrap()
+            .as_mut()
+            .unwrap()
+            .road
+            .lanes;
+        let index = lanes
+            .iter()
+            .position(|lane| matches!(lane, Lane::Travel { .. }))
+            .unwrap();
+        if let Some(Lane::Travel { width, .. }) = lanes.get_mut(index) {
+            *width = Some(Metre::new(3.0));
+        }
+
+        assert!(!comparison.is_same());
+        let differences = comparison.lane_differences();
+        assert_eq!(differences.len(), 1);
+        let difference = differences.first().unwrap();
+        assert_eq!(difference.index, index);
+        assert_ne!(difference.lanes.first(), difference.lanes.get(1));
+    }
+
+    #[test]
+    fn test_lane_count_and_error_differ() {
+        let tags = Tags::from_pairs([("highway", "secondary"), ("lanes", "2")]).unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let mut comparison = compare(&tags, &[locale.clone(), locale.clone(), locale]);
+        let count = comparison
+            .results
+            .first()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .road
+            .lanes
+            .len();
+        comparison
+            .results
+            .get_mut(1)
+            .unwrap()
+            .as_mut()
+            .unwrap()
+            .road
+            .lanes
+            .pop();
+        *comparison.results.get_mut(2).unwrap() = Err(RoadError::WayNotRoad);
+
+        assert!(!comparison.is_same());
+        let differences = comparison.lane_differences();
+        assert_eq!(differences.len(), count);
+        let last = differences.last().unwrap();
+        assert_eq!(last.index, count - 1);
+        assert!(last.lanes.first().unwrap().is_some());
+        assert_eq!(last.lanes.get(1..), Some(&[None, None][..]));
+
+        // Every locale failing is the same
+        let comparison = LocaleComparison {
+            results: vec![Err(RoadError::WayNotRoad), Err(RoadError::RoundTrip)],
+        };
+        assert!(comparison.is_same());
+        assert!(comparison.lane_differences().is_empty());
+    }
+}
@@ -1,7 +1,7 @@
 use osm_tag_schemes::Highway;
-use osm_tags::{TagKey, Tags};
+use osm_tags::{TagKey, TagsRef};
 
-use super::{Infer, Oneway, TagsNumeric};
+use super::{Infer, Oneway, TagsTyped};
 use crate::locale::Locale;
 use crate::transform::tags_to_lanes::modes::BusLaneCount;
 use crate::transform::{RoadWarnings, TagsToLanesMsg};
@@ -21,14 +21,15 @@ impl Counts {
     /// Parses and validates the `lanes` scheme (which excludes parking lanes, bike lanes, etc.).
     /// See <https://wiki.openstreetmap.org/wiki/Key:lanes>.
     ///
-    /// Validates `lanes[:{forward,both_ways,backward}]=*` and `centre_turn_lane=yes`.
+    /// Validates `lanes[:{forward,both_ways,backward}]=*` and `centre_turn_lane=yes`,
+    /// warning if `lanes:forward`, `lanes:backward` and `lanes:both_ways` do not add up to `lanes`.
     #[allow(
         clippy::integer_arithmetic,
         clippy::integer_division,
         clippy::too_many_lines
     )]
     pub(super) fn new(
-        tags: &Tags,
+        tags: &TagsRef<'_>,
         oneway: Oneway,
         highway: &Highway,
         centre_turn_lane: &CentreTurnLaneScheme, // TODO prefer TurnLanesScheme
@@ -38,6 +39,24 @@ impl Counts {
     ) -> Self {
         let lanes = LanesDirectionScheme::from_tags(tags, oneway, locale, warnings);
 
+        // A single lane, used in one direction at a time
+        if oneway == Oneway::Alternating {
+            if lanes.total.map_or(false, |total| total != 1)
+                || lanes.forward.is_some()
+                || lanes.backward.is_some()
+                || lanes.both_ways.is_some()
+            {
+                warnings.push(TagsToLanesMsg::ambiguous_tags(tags.subset([
+                    "oneway",
+                    "lanes",
+                    "lanes:forward",
+                    "lanes:backward",
+                    "lanes:both_ways",
+                ])));
+            }
+            return Self::One;
+        }
+
         let centre_turn_lane = match (lanes.both_ways, centre_turn_lane.some()) {
             (Some(()), None | Some(true)) => Infer::Direct(true),
             (None, Some(true)) => Infer::Calculated(true),
@@ -45,7 +64,7 @@ impl Counts {
             (None, None) => Infer::Default(false),
             (Some(()), Some(false)) => {
                 warnings.push(TagsToLanesMsg::ambiguous_tags(
-                    tags.subset(&[LANES + "both_ways", CENTRE_TURN_LANE]),
+                    tags.subset(&[LANES_BOTH_WAYS, CENTRE_TURN_LANE]),
                 ));
                 Infer::Default(true)
             },
@@ -100,8 +119,10 @@ impl Counts {
                     centre_turn_lane,
                 }
             } else {
-                // Assume 1 lane, but guess 1 normal lane plus bus lanes.
-                let assumed_forward = 1; // TODO depends on highway tag
+                // Assume the default lanes of the highway, plus bus lanes.
+                let assumed_forward = locale
+                    .default_directional_lanes(highway.r#type())
+                    .unwrap_or(1);
                 Self::Directional {
                     forward: Infer::Default(assumed_forward + bus.forward),
                     backward: Infer::Default(0),
@@ -110,16 +131,19 @@ impl Counts {
             }
         } else {
             // Twoway
+            let mismatch = || {
+                TagsToLanesMsg::ambiguous_tags(tags.subset(&[
+                    LANES,
+                    LANES_FORWARD,
+                    LANES_BACKWARD,
+                    LANES_BOTH_WAYS,
+                    CENTRE_TURN_LANE,
+                ]))
+            };
             match (lanes.total, lanes.forward, lanes.backward) {
                 (Some(l), Some(f), Some(b)) => {
                     if l != f + b + both_ways {
-                        warnings.push(TagsToLanesMsg::ambiguous_tags(tags.subset([
-                            "lanes",
-                            "lanes:forward",
-                            "lanes:backward",
-                            "lanes:both_ways",
-                            "center_turn_lanes",
-                        ])));
+                        warnings.push(mismatch());
                     }
                     Self::Directional {
                         forward: Infer::Direct(f),
@@ -132,20 +156,37 @@ impl Counts {
                     backward: Infer::Direct(b),
                     centre_turn_lane,
                 },
+                // The remaining lanes are in the other direction,
+                // unless there are more lanes in the one direction than in total
                 (Some(l), Some(f), None) => Self::Directional {
                     forward: Infer::Direct(f),
-                    backward: Infer::Calculated(l - f - both_ways),
+                    backward: l.checked_sub(f + both_ways).map_or_else(
+                        || {
+                            warnings.push(mismatch());
+                            Infer::Default(0)
+                        },
+                        Infer::Calculated,
+                    ),
                     centre_turn_lane,
                 },
                 (Some(l), None, Some(b)) => Self::Directional {
-                    forward: Infer::Calculated(l - b - both_ways),
+                    forward: l.checked_sub(b + both_ways).map_or_else(
+                        || {
+                            warnings.push(mismatch());
+                            Infer::Default(0)
+                        },
+                        Infer::Calculated,
+                    ),
                     backward: Infer::Direct(b),
                     centre_turn_lane,
                 },
                 // Alleyways or narrow unmarked roads, usually:
                 (Some(1), None, None) => Self::One,
                 (Some(l), None, None) => {
-                    if l % 2 == 0 && centre_turn_lane.some().unwrap_or(false) {
+                    if l % 2 == 0
+                        && lanes.both_ways.is_none()
+                        && centre_turn_lane.some().unwrap_or(false)
+                    {
                         // Only tagged with lanes and deprecated center_turn_lane tag.
                         // Assume the center_turn_lane is in addition to evenly divided lanes.
                         Self::Directional {
@@ -162,32 +203,35 @@ impl Counts {
                         let half = (remaining_lanes + 1) / 2; // usize division rounded up.
                         Self::Directional {
                             forward: Infer::Default(half + bus.forward),
-                            backward: Infer::Default(
-                                remaining_lanes - half - both_ways + bus.backward,
-                            ),
+                            backward: Infer::Default(remaining_lanes - half + bus.backward),
                             centre_turn_lane,
                         }
                     }
                 },
                 (None, None, None) => {
-                    if locale.has_split_lanes(highway.r#type())
-                        || bus.forward > 0
-                        || bus.backward > 0
-                    {
-                        Self::Directional {
+                    match (
+                        locale.default_directional_lanes(highway.r#type()),
+                        bus.forward > 0 || bus.backward > 0,
+                    ) {
+                        (Some(assumed), _) => Self::Directional {
+                            forward: Infer::Default(assumed + bus.forward),
+                            backward: Infer::Default(assumed + bus.backward),
+                            centre_turn_lane,
+                        },
+                        (None, true) => Self::Directional {
                             forward: Infer::Default(1 + bus.forward),
                             backward: Infer::Default(1 + bus.backward),
                             centre_turn_lane,
-                        }
-                    } else {
-                        Self::One
+                        },
+                        (None, false) => Self::One,
                     }
                 },
                 (None, _, _) => {
-                    if locale.has_split_lanes(highway.r#type()) {
-                        // Without the "lanes" tag, assume one normal lane in each dir, plus bus lanes.
-                        let forward = Infer::from(lanes.forward).or_default(1 + bus.forward);
-                        let backward = Infer::from(lanes.backward).or_default(1 + bus.forward);
+                    if let Some(assumed) = locale.default_directional_lanes(highway.r#type()) {
+                        // Without the "lanes" tag, assume the default lanes in each dir, plus bus lanes.
+                        let forward = Infer::from(lanes.forward).or_default(assumed + bus.forward);
+                        let backward =
+                            Infer::from(lanes.backward).or_default(assumed + bus.backward);
                         // TODO lanes.downgrade(&[forward, backward, bothways]);
                         Self::Directional {
                             forward,
@@ -204,6 +248,9 @@ impl Counts {
 }
 
 const LANES: TagKey = TagKey::from_static("lanes");
+const LANES_FORWARD: TagKey = TagKey::from_static("lanes:forward");
+const LANES_BACKWARD: TagKey = TagKey::from_static("lanes:backward");
+const LANES_BOTH_WAYS: TagKey = TagKey::from_static("lanes:both_ways");
 
 /// `lanes` and directional `lanes:*` scheme, see <https://wiki.openstreetmap.org/wiki/Key:lanes>
 pub(in crate::transform::tags_to_lanes) struct LanesDirectionScheme {
@@ -214,20 +261,20 @@ pub(in crate::transform::tags_to_lanes) struct LanesDirectionScheme {
 }
 impl LanesDirectionScheme {
     pub(crate) fn from_tags(
-        tags: &Tags,
+        tags: &TagsRef<'_>,
         _oneway: Oneway,
         _locale: &Locale,
         warnings: &mut RoadWarnings,
     ) -> Self {
         let both_ways = tags
-            .get_parsed(&(LANES + "both_ways"), warnings)
+            .get_parsed(&LANES_BOTH_WAYS, warnings)
             .filter(|&v: &usize| {
                 if v == 1 {
                     true
                 } else {
                     warnings.push(TagsToLanesMsg::unsupported(
                         "lanes:both_ways must be 1",
-                        tags.subset(&[LANES + "both_ways"]),
+                        tags.subset(&[LANES_BOTH_WAYS]),
                     ));
                     false
                 }
@@ -235,8 +282,8 @@ impl LanesDirectionScheme {
             .map(|_v| {});
         Self {
             total: tags.get_parsed(&LANES, warnings),
-            forward: tags.get_parsed(&(LANES + "forward"), warnings),
-            backward: tags.get_parsed(&(LANES + "backward"), warnings),
+            forward: tags.get_parsed(&LANES_FORWARD, warnings),
+            backward: tags.get_parsed(&LANES_BACKWARD, warnings),
             both_ways,
         }
     }
@@ -248,7 +295,7 @@ impl CentreTurnLaneScheme {
     /// Parses and validates the `centre_turn_lane` tag and emits a deprecation warning.
     /// See <https://wiki.openstreetmap.org/wiki/Key:centre_turn_lane>.
     pub(crate) fn from_tags(
-        tags: &Tags,
+        tags: &TagsRef<'_>,
         _oneway: Oneway,
         _locale: &Locale,
         warnings: &mut RoadWarnings,
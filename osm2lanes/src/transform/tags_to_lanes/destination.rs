@@ -0,0 +1,117 @@
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use osm_tag_schemes::{keys, DestinationSymbol};
+use osm_tags::{TagKey, TagsRef};
+
+use super::road::RoadBuilder;
+use super::TagsToLanesMsg;
+use crate::locale::Locale;
+use crate::road::Destination;
+use crate::transform::RoadWarnings;
+
+/// The values of each lane of a `:lanes` key,
+/// where a lane may have multiple values separated by `;`
+fn lane_values<'tag>(
+    tags: &'tag TagsRef<'_>,
+    key: &TagKey,
+    road: &RoadBuilder,
+    warnings: &mut RoadWarnings,
+) -> Option<Vec<Vec<&'tag str>>> {
+    let value = tags.get(key)?;
+    let lanes: Vec<Vec<&str>> = value
+        .split('|')
+        .map(|lane| {
+            lane.split(';')
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .collect()
+        })
+        .collect();
+    if lanes.len() != road.len() {
+        warnings.push(TagsToLanesMsg::unsupported(
+            "lane count mismatch",
+            tags.subset([key]),
+        ));
+        return None;
+    }
+    Some(lanes)
+}
+
+fn set(
+    tags: &TagsRef<'_>,
+    key: &TagKey,
+    set: fn(&mut Destination, Vec<&str>, &mut bool),
+    locale: &Locale,
+    road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
+) {
+    if let Some(lanes) = lane_values(tags, key, road, warnings) {
+        let mut unknown = false;
+        for (lane, values) in road.lanes_ltr_mut(locale).zip(lanes) {
+            set(&mut lane.destination, values, &mut unknown);
+        }
+        if unknown {
+            warnings.push(TagsToLanesMsg::unsupported_tags(tags.subset([key])));
+        }
+    }
+}
+
+/// Guidance on individual lanes,
+/// from `destination:lanes=*`, `destination:ref:lanes=*`, and `destination:symbol:lanes=*`,
+/// or `destination:lang:<language>:lanes=*` in the language of the locale
+pub(super) fn lane_destinations(
+    tags: &TagsRef<'_>,
+    locale: &Locale,
+    road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
+) {
+    let names = |destination: &mut Destination, names: Vec<&str>, _unknown: &mut bool| {
+        destination.names = names.into_iter().map(ToString::to_string).collect();
+    };
+    if tags.get(&keys::DESTINATION_LANES).is_some() {
+        set(
+            tags,
+            &keys::DESTINATION_LANES,
+            names,
+            locale,
+            road,
+            warnings,
+        );
+    } else if let Some(language) = &locale.language {
+        let key = keys::DESTINATION_LANG + language.as_str() + "lanes";
+        set(tags, &key, names, locale, road, warnings);
+        for lane in road.lanes_ltr_mut(locale) {
+            if !lane.destination.names.is_empty() {
+                lane.destination.language = Some(language.clone());
+            }
+        }
+    }
+    set(
+        tags,
+        &keys::DESTINATION_REF_LANES,
+        |destination, refs, _unknown| {
+            destination.refs = refs.into_iter().map(ToString::to_string).collect();
+        },
+        locale,
+        road,
+        warnings,
+    );
+    set(
+        tags,
+        &keys::DESTINATION_SYMBOL_LANES,
+        |destination, symbols, unknown| {
+            destination.symbols = symbols
+                .into_iter()
+                .filter_map(|symbol| {
+                    let parsed = symbol.parse::<DestinationSymbol>().ok();
+                    *unknown |= parsed.is_none();
+                    parsed
+                })
+                .collect();
+        },
+        locale,
+        road,
+        warnings,
+    );
+}
@@ -0,0 +1,62 @@
+use alloc::vec::Vec;
+
+use osm_tag_schemes::{keys, EmbeddedRails, Schemes};
+use osm_tags::TagsRef;
+
+use super::infer::Infer;
+use super::road::RoadBuilder;
+use super::TagsToLanesMsg;
+use crate::locale::Locale;
+use crate::transform::RoadWarnings;
+
+/// Lanes containing rails, from `embedded_rails:lanes=*`,
+/// otherwise all lanes when the way has `embedded_rails=*`.
+///
+/// An empty or `no` value in `embedded_rails:lanes=*` is a lane without rails.
+pub(super) fn embedded_rails(
+    tags: &TagsRef<'_>,
+    generic_schemes: &Schemes,
+    locale: &Locale,
+    road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
+) {
+    if let Some(value) = tags.get(&keys::EMBEDDED_RAILS_LANES) {
+        let rails = value
+            .split('|')
+            .map(|rails| match rails {
+                "" | "no" => Ok(None),
+                rails => rails.parse::<EmbeddedRails>().map(Some),
+            })
+            .collect::<Result<Vec<Option<EmbeddedRails>>, _>>();
+        match rails {
+            Ok(rails) if rails.len() == road.len() => {
+                for (lane, rails) in road.lanes_ltr_mut(locale).zip(rails) {
+                    lane.embedded_rails = Infer::direct(rails);
+                }
+            },
+            Ok(_rails) => warnings.push(TagsToLanesMsg::unsupported(
+                "lane count mismatch",
+                tags.subset(&[keys::EMBEDDED_RAILS_LANES]),
+            )),
+            Err(_parse_error) => {
+                warnings.push(TagsToLanesMsg::unsupported_tag(
+                    keys::EMBEDDED_RAILS_LANES,
+                    value,
+                ));
+            },
+        }
+        return;
+    }
+
+    match generic_schemes.embedded_rails {
+        Ok(Some(rails)) => {
+            for lane in road.lanes_ltr_mut(locale) {
+                lane.embedded_rails = Infer::Direct(rails);
+            }
+        },
+        Ok(None) => {},
+        Err(_) => warnings.push(TagsToLanesMsg::unsupported_tags(
+            tags.subset(&[keys::EMBEDDED_RAILS]),
+        )),
+    }
+}
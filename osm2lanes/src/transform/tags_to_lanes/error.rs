@@ -1,6 +1,9 @@
-use std::panic::Location;
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::string::String;
+use core::panic::Location;
 
-use osm_tags::{DuplicateKeyError, TagKey, Tags};
+use osm_tags::{DuplicateKeyError, TagKey, Tags, TagsDiff};
 
 use crate::transform::tags_to_lanes::LaneBuilder;
 
@@ -10,21 +13,70 @@ use crate::transform::tags_to_lanes::LaneBuilder;
 /// use osm2lanes::transform::TagsToLanesMsg;
 /// let _ = TagsToLanesMsg::deprecated_tag("foo", "bar");
 /// let _ = TagsToLanesMsg::unsupported_tag("foo", "bar");
+/// let _ = TagsToLanesMsg::invalid_value("width", "wide", "a distance");
 /// let _ = TagsToLanesMsg::unsupported_str(String::from("foo=bar because x and y"));
 /// let _ = TagsToLanesMsg::ambiguous_str("foo=bar because x and y");
 /// ```
 #[derive(Clone, Debug)]
 pub struct TagsToLanesMsg {
     location: &'static Location<'static>,
+    context: Option<WayContext>,
     pub(in crate::transform) issue: TagsToLanesIssue,
 }
 
+/// Identifies the way being transformed in messages,
+/// e.g. to make the logs of a batch run actionable
+///
+/// ```
+/// use osm2lanes::transform::WayContext;
+/// let context = WayContext::way(4_000_000).source("planet.osm.pbf");
+/// assert_eq!("way 4000000 in planet.osm.pbf", context.to_string());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WayContext {
+    pub way_id: Option<u64>,
+    /// Name of the source of the way, such as a file name
+    pub source: Option<String>,
+}
+
+impl WayContext {
+    #[must_use]
+    pub fn way(way_id: u64) -> Self {
+        Self {
+            way_id: Some(way_id),
+            source: None,
+        }
+    }
+
+    #[must_use]
+    pub fn source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+}
+
+impl core::fmt::Display for WayContext {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match (self.way_id, &self.source) {
+            (Some(way_id), Some(source)) => write!(f, "way {} in {}", way_id, source),
+            (Some(way_id), None) => write!(f, "way {}", way_id),
+            (None, Some(source)) => write!(f, "{}", source),
+            (None, None) => write!(f, "unknown way"),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub(crate) enum TagsToLanesIssue {
-    /// Deprecated OSM tags, with suggested alternative
+    /// Deprecated OSM tags, with the changes to the suggested alternative
     Deprecated {
         deprecated_tags: Tags,
-        suggested_tags: Option<Tags>,
+        replacement: Option<TagsDiff>,
+    },
+    /// Tag values that were normalized, e.g. `oneway=Yes` to `oneway=yes`
+    Normalized {
+        original: Tags,
+        normalized: Tags,
     },
     /// Tag combination that is unsupported, and may never be supported
     Unsupported {
@@ -43,13 +95,19 @@ pub(crate) enum TagsToLanesIssue {
     },
     /// Locale not used
     SeparatorLocaleUnused {
-        inside: LaneBuilder,
-        outside: LaneBuilder,
+        inside: Box<LaneBuilder>,
+        outside: Box<LaneBuilder>,
     },
     /// Locale not used
     SeparatorUnknown {
-        inside: LaneBuilder,
-        outside: LaneBuilder,
+        inside: Box<LaneBuilder>,
+        outside: Box<LaneBuilder>,
+    },
+    /// Tag value that is not of the expected type, e.g. `width=wide`
+    InvalidValue {
+        key: TagKey,
+        value: String,
+        expected: &'static str,
     },
     /// Internal errors
     TagsDuplicateKey(DuplicateKeyError),
@@ -59,12 +117,14 @@ pub(crate) enum TagsToLanesIssue {
 impl TagsToLanesMsg {
     #[must_use]
     #[track_caller]
+    #[allow(clippy::needless_pass_by_value)]
     pub fn deprecated(deprecated: Tags, suggested: Tags) -> Self {
         TagsToLanesMsg {
             location: Location::caller(),
+            context: None,
             issue: TagsToLanesIssue::Deprecated {
+                replacement: Some(deprecated.diff(&suggested)),
                 deprecated_tags: deprecated,
-                suggested_tags: Some(suggested),
             },
         }
     }
@@ -74,9 +134,10 @@ impl TagsToLanesMsg {
     pub fn deprecated_tags(tags: Tags) -> Self {
         TagsToLanesMsg {
             location: Location::caller(),
+            context: None,
             issue: TagsToLanesIssue::Deprecated {
                 deprecated_tags: tags,
-                suggested_tags: None,
+                replacement: None,
             },
         }
     }
@@ -86,9 +147,23 @@ impl TagsToLanesMsg {
     pub fn deprecated_tag<K: Into<TagKey>>(key: K, val: &str) -> Self {
         TagsToLanesMsg {
             location: Location::caller(),
+            context: None,
             issue: TagsToLanesIssue::Deprecated {
                 deprecated_tags: Tags::from_pair(key, val),
-                suggested_tags: None,
+                replacement: None,
+            },
+        }
+    }
+
+    #[must_use]
+    #[track_caller]
+    pub fn normalized(original: Tags, normalized: Tags) -> Self {
+        TagsToLanesMsg {
+            location: Location::caller(),
+            context: None,
+            issue: TagsToLanesIssue::Normalized {
+                original,
+                normalized,
             },
         }
     }
@@ -98,6 +173,7 @@ impl TagsToLanesMsg {
     pub fn unsupported(description: impl Into<String>, tags: Tags) -> Self {
         TagsToLanesMsg {
             location: Location::caller(),
+            context: None,
             issue: TagsToLanesIssue::Unsupported {
                 description: Some(description.into()),
                 tags: Some(tags),
@@ -110,6 +186,7 @@ impl TagsToLanesMsg {
     pub fn unsupported_tags(tags: Tags) -> Self {
         TagsToLanesMsg {
             location: Location::caller(),
+            context: None,
             issue: TagsToLanesIssue::Unsupported {
                 description: None,
                 tags: Some(tags),
@@ -122,6 +199,7 @@ impl TagsToLanesMsg {
     pub fn unsupported_tag<K: Into<TagKey>>(key: K, val: &str) -> Self {
         TagsToLanesMsg {
             location: Location::caller(),
+            context: None,
             issue: TagsToLanesIssue::Unsupported {
                 description: None,
                 tags: Some(Tags::from_pair(key, val)),
@@ -134,6 +212,7 @@ impl TagsToLanesMsg {
     pub fn unsupported_str(description: impl Into<String>) -> Self {
         TagsToLanesMsg {
             location: Location::caller(),
+            context: None,
             issue: TagsToLanesIssue::Unsupported {
                 description: Some(description.into()),
                 tags: None,
@@ -141,11 +220,26 @@ impl TagsToLanesMsg {
         }
     }
 
+    #[must_use]
+    #[track_caller]
+    pub fn invalid_value<K: Into<TagKey>>(key: K, val: &str, expected: &'static str) -> Self {
+        TagsToLanesMsg {
+            location: Location::caller(),
+            context: None,
+            issue: TagsToLanesIssue::InvalidValue {
+                key: key.into(),
+                value: val.to_owned(),
+                expected,
+            },
+        }
+    }
+
     #[must_use]
     #[track_caller]
     pub fn unimplemented(description: impl Into<String>, tags: Tags) -> Self {
         TagsToLanesMsg {
             location: Location::caller(),
+            context: None,
             issue: TagsToLanesIssue::Unimplemented {
                 description: Some(description.into()),
                 tags: Some(tags),
@@ -158,6 +252,7 @@ impl TagsToLanesMsg {
     pub fn unimplemented_tag<K: Into<TagKey>>(key: K, val: &str) -> Self {
         TagsToLanesMsg {
             location: Location::caller(),
+            context: None,
             issue: TagsToLanesIssue::Unimplemented {
                 description: None,
                 tags: Some(Tags::from_pair(key, val)),
@@ -170,6 +265,7 @@ impl TagsToLanesMsg {
     pub fn unimplemented_tags(tags: Tags) -> Self {
         TagsToLanesMsg {
             location: Location::caller(),
+            context: None,
             issue: TagsToLanesIssue::Unimplemented {
                 description: None,
                 tags: Some(tags),
@@ -182,6 +278,7 @@ impl TagsToLanesMsg {
     pub fn ambiguous_tag<K: Into<TagKey>>(key: K, val: &str) -> Self {
         TagsToLanesMsg {
             location: Location::caller(),
+            context: None,
             issue: TagsToLanesIssue::Ambiguous {
                 description: None,
                 tags: Some(Tags::from_pair(key, val)),
@@ -194,6 +291,7 @@ impl TagsToLanesMsg {
     pub fn ambiguous_tags(tags: Tags) -> Self {
         TagsToLanesMsg {
             location: Location::caller(),
+            context: None,
             issue: TagsToLanesIssue::Ambiguous {
                 description: None,
                 tags: Some(tags),
@@ -206,6 +304,7 @@ impl TagsToLanesMsg {
     pub fn ambiguous_str(description: impl Into<String>) -> Self {
         TagsToLanesMsg {
             location: Location::caller(),
+            context: None,
             issue: TagsToLanesIssue::Ambiguous {
                 description: Some(description.into()),
                 tags: None,
@@ -218,7 +317,11 @@ impl TagsToLanesMsg {
     pub fn separator_locale_unused(inside: LaneBuilder, outside: LaneBuilder) -> Self {
         TagsToLanesMsg {
             location: Location::caller(),
-            issue: TagsToLanesIssue::SeparatorLocaleUnused { inside, outside },
+            context: None,
+            issue: TagsToLanesIssue::SeparatorLocaleUnused {
+                inside: Box::new(inside),
+                outside: Box::new(outside),
+            },
         }
     }
 
@@ -227,7 +330,11 @@ impl TagsToLanesMsg {
     pub fn separator_unknown(inside: LaneBuilder, outside: LaneBuilder) -> Self {
         TagsToLanesMsg {
             location: Location::caller(),
-            issue: TagsToLanesIssue::SeparatorUnknown { inside, outside },
+            context: None,
+            issue: TagsToLanesIssue::SeparatorUnknown {
+                inside: Box::new(inside),
+                outside: Box::new(outside),
+            },
         }
     }
 
@@ -236,35 +343,81 @@ impl TagsToLanesMsg {
     pub fn internal(e: &'static str) -> Self {
         TagsToLanesMsg {
             location: Location::caller(),
+            context: None,
             issue: TagsToLanesIssue::Internal(e),
         }
     }
 }
 
+impl TagsToLanesMsg {
+    /// The way the message is about, if the caller provided it
+    #[must_use]
+    pub fn context(&self) -> Option<&WayContext> {
+        self.context.as_ref()
+    }
+
+    /// The changes that replace deprecated tags, if the message suggests any
+    ///
+    /// ```
+    /// use osm2lanes::transform::TagsToLanesMsg;
+    /// use osm_tags::Tags;
+    ///
+    /// let msg = TagsToLanesMsg::deprecated(
+    ///     Tags::from_pair("parking:lane:both", "parallel"),
+    ///     Tags::from_pair("parking:both", "lane"),
+    /// );
+    /// let replacement = msg.replacement().unwrap();
+    /// assert_eq!(replacement.removed.to_vec(), ["parking:lane:both=parallel"]);
+    /// assert_eq!(replacement.added.to_vec(), ["parking:both=lane"]);
+    /// ```
+    #[must_use]
+    pub fn replacement(&self) -> Option<&TagsDiff> {
+        match &self.issue {
+            TagsToLanesIssue::Deprecated { replacement, .. } => replacement.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub(in crate::transform) fn set_context(&mut self, context: &WayContext) {
+        self.context = Some(context.clone());
+    }
+}
+
 impl From<DuplicateKeyError> for TagsToLanesMsg {
     #[track_caller]
     fn from(e: DuplicateKeyError) -> Self {
         TagsToLanesMsg {
             location: Location::caller(),
+            context: None,
             issue: TagsToLanesIssue::TagsDuplicateKey(e),
         }
     }
 }
 
-impl std::fmt::Display for TagsToLanesMsg {
+impl core::fmt::Display for TagsToLanesMsg {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.fmt_issue(f)?;
+        if let Some(context) = &self.context {
+            write!(f, " ({})", context)?;
+        }
+        Ok(())
+    }
+}
+
+impl TagsToLanesMsg {
     #[allow(clippy::panic_in_result_fn)]
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    fn fmt_issue(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         match &self.issue {
             TagsToLanesIssue::Deprecated {
                 deprecated_tags,
-                suggested_tags,
+                replacement,
             } => {
-                if let Some(suggested_tags) = suggested_tags {
+                if let Some(replacement) = replacement {
                     write!(
                         f,
-                        "deprecated: replace '{}' with '{}' - {}",
+                        "deprecated: '{}', {} - {}",
                         deprecated_tags.to_vec().as_slice().join(" "),
-                        suggested_tags.to_vec().as_slice().join(" "),
+                        replacement,
                         self.location,
                     )
                 } else {
@@ -276,6 +429,16 @@ impl std::fmt::Display for TagsToLanesMsg {
                     )
                 }
             },
+            TagsToLanesIssue::Normalized {
+                original,
+                normalized,
+            } => write!(
+                f,
+                "normalized: '{}' to '{}' - {}",
+                original.to_vec().as_slice().join(" "),
+                normalized.to_vec().as_slice().join(" "),
+                self.location,
+            ),
             TagsToLanesIssue::Unsupported { description, tags }
             | TagsToLanesIssue::Unimplemented { description, tags }
             | TagsToLanesIssue::Ambiguous { description, tags } => {
@@ -322,12 +485,22 @@ impl std::fmt::Display for TagsToLanesMsg {
                     inside, outside, self.location
                 )
             },
+            TagsToLanesIssue::InvalidValue {
+                key,
+                value,
+                expected,
+            } => write!(
+                f,
+                "invalid: '{}={}', expected {} - {}",
+                key, value, expected, self.location
+            ),
             TagsToLanesIssue::TagsDuplicateKey(e) => write!(f, "{} - {}", e, self.location),
             TagsToLanesIssue::Internal(e) => write!(f, "{} - {}", e, self.location),
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for TagsToLanesMsg {}
 
 #[cfg(feature = "serde")]
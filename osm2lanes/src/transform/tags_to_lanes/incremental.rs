@@ -0,0 +1,560 @@
+use alloc::borrow::ToOwned;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use osm_tag_schemes::Schemes;
+use osm_tags::{KeySet, Tags, TagsRef};
+
+use super::oneway::Oneway;
+use super::road::{LaneBuffers, RoadBuilder};
+use super::{finish, prepare, start, Config, Stage, Start, TagSchemes};
+use crate::locale::Locale;
+use crate::transform::error::{RoadError, RoadWarnings};
+use crate::transform::{reverse_way, RoadFromTags};
+
+/// Transformation of tags to lanes for interactive editing,
+/// updated as the tags change one at a time.
+///
+/// The road is kept as it was before each stage of the transformation,
+/// such that a change only recomputes the stages that read the changed key.
+/// Any other change falls back to a full transformation.
+///
+/// ```
+/// use osm2lanes::locale::Locale;
+/// use osm2lanes::transform::{Incremental, TagsToLanesConfig};
+/// use osm_tags::Tags;
+///
+/// let locale = Locale::builder().build();
+/// let tags = Tags::from_pairs([("highway", "secondary"), ("cycleway:right", "lane")]).unwrap();
+/// let mut incremental = Incremental::new(tags, &locale, TagsToLanesConfig::default());
+/// let result = incremental.set("cycleway:right:width", Some("2"), &locale);
+/// assert!(result.is_ok());
+/// ```
+pub struct Incremental {
+    tags: Tags,
+    config: Config,
+    state: Option<State>,
+    result: Result<RoadFromTags, RoadError>,
+}
+
+struct State {
+    /// The tags read by the stages, if their reads are tracked
+    tags: Option<Tags>,
+    crate_schemes: TagSchemes,
+    /// The road before each stage, followed by the road after the last stage
+    snapshots: Vec<(RoadBuilder, RoadWarnings)>,
+}
+
+impl Incremental {
+    #[must_use]
+    pub fn new(tags: Tags, locale: &Locale, config: Config) -> Self {
+        let mut incremental = Self {
+            tags,
+            config,
+            state: None,
+            result: Err(RoadError::WayNotRoad),
+        };
+        incremental.full(locale);
+        incremental
+    }
+
+    #[must_use]
+    pub fn tags(&self) -> &TagsRef<'_> {
+        &self.tags
+    }
+
+    /// The result of the transformation of the current tags
+    pub fn result(&self) -> &Result<RoadFromTags, RoadError> {
+        &self.result
+    }
+
+    /// Set the value of a tag, or remove it if `val` is `None`,
+    /// and update the result.
+    ///
+    /// The locale must be the same for every call.
+    pub fn set(
+        &mut self,
+        key: &str,
+        val: Option<&str>,
+        locale: &Locale,
+    ) -> &Result<RoadFromTags, RoadError> {
+        let tags = Tags::from_pairs(
+            self.tags
+                .iter()
+                .filter(|(k, _v)| *k != key)
+                .chain(val.map(|val| (key, val)))
+                .map(|(k, v)| (k.to_owned(), v.to_owned())),
+        );
+        match tags {
+            Ok(tags) => self.tags = tags,
+            Err(e) => {
+                self.state = None;
+                self.result = self.config.contextualise(Err(e.into()));
+                return &self.result;
+            },
+        }
+
+        match (first_stage(key), self.state.take()) {
+            (Some(index), Some(mut state)) if index < state.snapshots.len() => {
+                state.snapshots.truncate(index + 1);
+                self.run(locale, Some(state));
+            },
+            _ => self.full(locale),
+        }
+        &self.result
+    }
+
+    fn full(&mut self, locale: &Locale) {
+        self.run(locale, None);
+    }
+
+    /// Resume the transformation from the last snapshot,
+    /// or start it again without a state
+    fn run(&mut self, locale: &Locale, mut state: Option<State>) {
+        let result = self.read_tags().and_then(|read| {
+            let tags = read.tags.as_ref().unwrap_or(&self.tags);
+            let state = match &mut state {
+                Some(state) => {
+                    if let Some(previous) = &state.tags {
+                        keep_reads(previous, tags);
+                    }
+                    state
+                },
+                None => {
+                    let Start {
+                        warnings,
+                        generic_schemes: _,
+                        crate_schemes,
+                        road,
+                    } = start(
+                        tags,
+                        locale,
+                        LaneBuffers::default(),
+                        RoadWarnings::default(),
+                    )?;
+                    state.insert(State {
+                        tags: None,
+                        crate_schemes,
+                        snapshots: vec![(road, warnings)],
+                    })
+                },
+            };
+            let result = resume(tags, read.warnings, locale, &self.config, state);
+            if self.config.warn_unread_tags {
+                state.tags = read.tags;
+            }
+            let mut result = result?;
+            if read.reversed {
+                result.reverse();
+            }
+            Ok(result)
+        });
+        self.state = state;
+        self.result = self.config.contextualise(result);
+    }
+
+    /// The tags as read by [`super::tags_to_lanes`]
+    fn read_tags(&self) -> Result<ReadTags, RoadError> {
+        let mut warnings = RoadWarnings::default();
+        let prepared = prepare(&self.tags, &self.config, &mut warnings);
+        let tags = prepared.as_ref().unwrap_or(&self.tags);
+        if tags.is(&Oneway::KEY, "-1") {
+            let mut reversed = reverse_way(tags)?;
+            if self.config.warn_unread_tags {
+                reversed = reversed.with_read_tracking();
+            }
+            return Ok(ReadTags {
+                tags: Some(reversed),
+                reversed: true,
+                warnings,
+            });
+        }
+        Ok(ReadTags {
+            tags: prepared,
+            reversed: false,
+            warnings,
+        })
+    }
+}
+
+/// The tags read by the stages
+struct ReadTags {
+    /// Normalized, with their reads tracked, or drawn the other way around for `oneway=-1`,
+    /// if they differ from the input
+    tags: Option<Tags>,
+    reversed: bool,
+    /// Warnings from preparing the tags, such as the normalized values
+    warnings: RoadWarnings,
+}
+
+/// Mark the unchanged tags that were read in the previous transformation as read,
+/// as the stages that read them are skipped
+fn keep_reads(previous: &TagsRef<'_>, tags: &TagsRef<'_>) {
+    let diff = previous.diff(tags);
+    for (key, _val) in previous.iter() {
+        let changed = diff.removed.iter().any(|(removed, _val)| removed == key)
+            || diff
+                .changed
+                .iter()
+                .any(|(changed, _, _)| changed.as_str() == key);
+        let read = previous.unread().all(|(unread, _val)| unread != key);
+        if !changed && read {
+            let _read = tags.get(key);
+        }
+    }
+}
+
+/// Run the stages after the last snapshot, keeping a snapshot after each stage,
+/// with the warnings from preparing the tags before the warnings of the stages
+fn resume(
+    tags: &TagsRef<'_>,
+    mut warnings: RoadWarnings,
+    locale: &Locale,
+    config: &Config,
+    state: &mut State,
+) -> Result<RoadFromTags, RoadError> {
+    let (mut road, mut stage_warnings) = state
+        .snapshots
+        .last()
+        .cloned()
+        .ok_or(RoadError::WayNotRoad)?;
+    for stage in Stage::ALL.into_iter().skip(state.snapshots.len() - 1) {
+        stage.run(
+            tags,
+            locale,
+            &state.crate_schemes,
+            &mut road,
+            &mut stage_warnings,
+        )?;
+        state.snapshots.push((road.clone(), stage_warnings.clone()));
+    }
+    warnings.append(stage_warnings);
+    finish(
+        road,
+        Schemes::from_tags(tags),
+        tags,
+        locale,
+        config,
+        warnings,
+        &mut LaneBuffers::default(),
+    )
+}
+
+/// Keys first read by a stage, and not read by [`start`] nor by an earlier stage,
+/// with `None` for keys only read after the stages, by the road attributes or separators.
+/// A change to any other key falls back to a full transformation.
+///
+/// Every key is checked against [`super::tags_to_lanes`] in `test_incremental_resume_keys`,
+/// and against the reads of the stages in `test_incremental_resume_reads`.
+const RESUME: [(&str, Option<Stage>); 40] = [
+    ("footway", Some(Stage::NonMotorized)),
+    ("cycleway", Some(Stage::NonMotorized)),
+    ("crossing", Some(Stage::NonMotorized)),
+    ("crossing_ref", Some(Stage::NonMotorized)),
+    ("cycleway:left", Some(Stage::Bicycle)),
+    ("cycleway:right", Some(Stage::Bicycle)),
+    ("cycleway:both", Some(Stage::Bicycle)),
+    ("cycleway:lanes", Some(Stage::Bicycle)),
+    ("cycleway:left:width", Some(Stage::Bicycle)),
+    ("cycleway:right:width", Some(Stage::Bicycle)),
+    ("cycleway:both:width", Some(Stage::Bicycle)),
+    ("cycleway:left:buffer", Some(Stage::Bicycle)),
+    ("cycleway:right:buffer", Some(Stage::Bicycle)),
+    ("cycleway:both:buffer", Some(Stage::Bicycle)),
+    ("cycleway:left:oneway", Some(Stage::Bicycle)),
+    ("cycleway:right:oneway", Some(Stage::Bicycle)),
+    ("cycleway:both:oneway", Some(Stage::Bicycle)),
+    ("oneway:bicycle", Some(Stage::Bicycle)),
+    ("parking:lane", Some(Stage::Parking)),
+    ("parking:lane:left", Some(Stage::Parking)),
+    ("parking:lane:right", Some(Stage::Parking)),
+    ("parking:lane:both", Some(Stage::Parking)),
+    ("parking:condition:left", Some(Stage::Parking)),
+    ("parking:condition:right", Some(Stage::Parking)),
+    ("parking:condition:both", Some(Stage::Parking)),
+    ("parking:left", Some(Stage::Parking)),
+    ("parking:right", Some(Stage::Parking)),
+    ("parking:both", Some(Stage::Parking)),
+    ("sidewalk", Some(Stage::FootAndShoulder)),
+    ("sidewalk:left", Some(Stage::FootAndShoulder)),
+    ("sidewalk:right", Some(Stage::FootAndShoulder)),
+    ("sidewalk:both", Some(Stage::FootAndShoulder)),
+    ("shoulder", Some(Stage::FootAndShoulder)),
+    ("shoulder:left", Some(Stage::FootAndShoulder)),
+    ("shoulder:right", Some(Stage::FootAndShoulder)),
+    ("shoulder:both", Some(Stage::FootAndShoulder)),
+    ("maxheight", None),
+    ("maxweight", None),
+    ("maxlength", None),
+    ("motorroad", None),
+];
+
+/// The keys of [`RESUME`], in the same order
+const RESUME_KEYS: KeySet<40> = KeySet::new(resume_keys(RESUME));
+
+#[allow(clippy::indexing_slicing)]
+const fn resume_keys<const N: usize>(
+    resume: [(&'static str, Option<Stage>); N],
+) -> [&'static str; N] {
+    let mut keys = [""; N];
+    let mut index = 0;
+    while index < N {
+        keys[index] = resume[index].0;
+        index += 1;
+    }
+    keys
+}
+
+/// Index of the first stage that reads the key,
+/// or the number of stages if only the road attributes or separators read the key,
+/// `None` if the key may be read before the stages
+fn first_stage(key: &str) -> Option<usize> {
+    let (_key, stage) = RESUME.get(RESUME_KEYS.get(key)?)?;
+    match stage {
+        Some(stage) => Stage::ALL.iter().position(|s| s == stage),
+        None => Some(Stage::ALL.len()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use osm_tags::Tags;
+
+    use super::{Incremental, RESUME};
+    use crate::locale::Locale;
+    use crate::transform::error::RoadWarnings;
+    use crate::transform::tags_to_lanes::road::LaneBuffers;
+    use crate::transform::tags_to_lanes::{start, Stage, Start};
+    use crate::transform::{tags_to_lanes, TagsToLanesConfig};
+
+    const BASES: [&[(&str, &str)]; 6] = [
+        &[("highway", "secondary"), ("lanes", "2")],
+        &[
+            ("highway", "secondary"),
+            ("oneway", "yes"),
+            ("lanes", "2"),
+            ("cycleway:right", "lane"),
+            ("parking:lane:both", "parallel"),
+            ("sidewalk", "both"),
+        ],
+        &[
+            ("highway", "residential"),
+            ("busway", "lane"),
+            ("cycleway:both", "track"),
+            ("shoulder", "no"),
+        ],
+        &[
+            ("highway", "secondary"),
+            ("oneway", "-1"),
+            ("cycleway:left", "lane"),
+        ],
+        &[("highway", "footway"), ("footway", "sidewalk")],
+        &[("highway", "cycleway"), ("foot", "designated")],
+    ];
+
+    const VALS: [Option<&str>; 10] = [
+        None,
+        Some("yes"),
+        Some("no"),
+        Some("lane"),
+        Some("track"),
+        Some("opposite_lane"),
+        Some("both"),
+        Some("separate"),
+        Some("parallel"),
+        Some("2"),
+    ];
+
+    /// Each key of the table resumes with the same result as a full transformation
+    #[test]
+    fn test_incremental_resume_keys() {
+        let locale = Locale::builder().build();
+        for config in [
+            TagsToLanesConfig::default(),
+            TagsToLanesConfig::default().with_unread_tags_warning(),
+        ] {
+            for base in BASES {
+                let base = Tags::from_pairs(base.iter().copied()).unwrap();
+                for (key, _stage) in RESUME {
+                    for val in VALS {
+                        let mut incremental =
+                            Incremental::new(base.clone(), &locale, config.clone());
+                        incremental.set(key, val, &locale);
+                        let result = incremental.result();
+                        let expected = tags_to_lanes(incremental.tags(), &locale, &config);
+                        let context =
+                            format!("{}: {key}={val:?}", base.to_string().replace('\n', " "));
+                        match (result, &expected) {
+                            (Ok(result), Ok(expected)) => {
+                                assert_eq!(result.road, expected.road, "{context}");
+                                assert_eq!(
+                                    result.warnings.to_string(),
+                                    expected.warnings.to_string(),
+                                    "{context}"
+                                );
+                            },
+                            (Err(error), Err(expected)) => {
+                                assert_eq!(error.to_string(), expected.to_string(), "{context}");
+                            },
+                            _ => panic!("{context}: {result:?} != {expected:?}"),
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Each key of the table is not read before its stage
+    #[test]
+    fn test_incremental_resume_reads() {
+        let locale = Locale::builder().build();
+        for base in BASES {
+            for (key, stage) in RESUME {
+                for val in VALS.into_iter().flatten() {
+                    let tags = Tags::from_pairs(
+                        base.iter()
+                            .copied()
+                            .filter(|(k, _v)| *k != key)
+                            .chain([(key, val)]),
+                    )
+                    .unwrap()
+                    .with_read_tracking();
+                    let read = || tags.unread().all(|(k, _v)| k != key);
+                    let context = format!("{base:?}: {key}={val}");
+                    let Start {
+                        crate_schemes,
+                        mut road,
+                        ..
+                    } = match start(
+                        &tags,
+                        &locale,
+                        LaneBuffers::default(),
+                        RoadWarnings::default(),
+                    ) {
+                        Ok(start) => start,
+                        Err(_) => continue,
+                    };
+                    assert!(!read(), "read before the stages, {context}");
+                    let mut warnings = RoadWarnings::default();
+                    for earlier in Stage::ALL.into_iter().take_while(|s| Some(*s) != stage) {
+                        if earlier
+                            .run(&tags, &locale, &crate_schemes, &mut road, &mut warnings)
+                            .is_err()
+                        {
+                            break;
+                        }
+                        assert!(!read(), "read by {}, {context}", earlier.name());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_incremental() {
+        let locale = Locale::builder().build();
+        let tags = Tags::from_pairs([
+            ("highway", "secondary"),
+            ("lanes", "2"),
+            ("cycleway:right", "lane"),
+        ])
+        .unwrap();
+        let mut incremental = Incremental::new(tags, &locale, TagsToLanesConfig::default());
+        for (key, val) in [
+            ("cycleway:right:width", Some("2.5")),
+            ("sidewalk", Some("both")),
+            ("name", Some("Main Street")),
+            ("lanes", Some("4")),
+            ("cycleway:right", None),
+        ] {
+            let road = incremental
+                .set(key, val, &locale)
+                .as_ref()
+                .unwrap()
+                .road
+                .clone();
+            let expected =
+                tags_to_lanes(incremental.tags(), &locale, &TagsToLanesConfig::default())
+                    .unwrap()
+                    .road;
+            assert_eq!(road, expected, "{key}");
+        }
+    }
+
+    #[test]
+    fn test_incremental_parking_condition() {
+        let locale = Locale::builder().build();
+        let tags = Tags::from_pairs([
+            ("highway", "residential"),
+            ("parking:lane:both", "parallel"),
+            ("parking:condition:both", "free"),
+        ])
+        .unwrap();
+        let mut incremental = Incremental::new(tags, &locale, TagsToLanesConfig::default());
+        let road = incremental
+            .set("parking:condition:both", Some("residents"), &locale)
+            .as_ref()
+            .unwrap()
+            .road
+            .clone();
+        let expected = tags_to_lanes(incremental.tags(), &locale, &TagsToLanesConfig::default())
+            .unwrap()
+            .road;
+        assert_eq!(road, expected);
+    }
+
+    #[test]
+    fn test_incremental_oneway_reversed() {
+        let locale = Locale::builder().build();
+        let tags =
+            Tags::from_pairs([("highway", "secondary"), ("oneway", "-1"), ("lanes", "2")]).unwrap();
+        let mut incremental = Incremental::new(tags, &locale, TagsToLanesConfig::default());
+        for (key, val) in [
+            ("cycleway:right", Some("lane")),
+            ("sidewalk", Some("both")),
+            ("lanes", Some("3")),
+        ] {
+            let road = incremental
+                .set(key, val, &locale)
+                .as_ref()
+                .unwrap()
+                .road
+                .clone();
+            let expected =
+                tags_to_lanes(incremental.tags(), &locale, &TagsToLanesConfig::default())
+                    .unwrap()
+                    .road;
+            assert_eq!(road, expected, "{key}");
+        }
+    }
+
+    #[test]
+    fn test_incremental_prepared() {
+        let locale = Locale::builder().build();
+        let config = TagsToLanesConfig::default()
+            .with_normalized_tags()
+            .with_unread_tags_warning();
+        let tags = Tags::from_pairs([
+            ("highway", "Secondary"),
+            ("lanes", "2"),
+            ("cycleway:right", "lane"),
+            ("cylceway:left", "lane"),
+        ])
+        .unwrap();
+        let mut incremental = Incremental::new(tags, &locale, config.clone());
+        for (key, val) in [
+            ("cycleway:right", Some("Track")),
+            ("cycleway:right:width", Some("2")),
+            ("name", Some("Main Street")),
+        ] {
+            let result = incremental.set(key, val, &locale).as_ref().unwrap().clone();
+            let expected = tags_to_lanes(incremental.tags(), &locale, &config).unwrap();
+            assert_eq!(result.road, expected.road, "{key}");
+            assert_eq!(
+                result.warnings.to_string(),
+                expected.warnings.to_string(),
+                "{key}"
+            );
+        }
+    }
+}
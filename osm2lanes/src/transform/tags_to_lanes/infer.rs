@@ -3,12 +3,13 @@ use super::TagsToLanesMsg;
 #[derive(Debug)]
 pub struct InferConflict;
 
-impl std::fmt::Display for InferConflict {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for InferConflict {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "inferred values conflict")
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for InferConflict {}
 
 impl From<InferConflict> for TagsToLanesMsg {
@@ -20,6 +21,8 @@ impl From<InferConflict> for TagsToLanesMsg {
 // TODO: implement try when this is closed: https://github.com/rust-lang/rust/issues/84277
 /// A value with various levels of inference
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Infer<T> {
     None,
     Default(T),
@@ -0,0 +1,183 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+
+use osm_tag_schemes::{keys, HighwayType, LaneDependent};
+use osm_tags::{TagKey, TagsRef};
+
+use super::infer::Infer;
+use super::road::{LaneBuilder, RoadBuilder};
+use super::TagsToLanesMsg;
+use crate::locale::Locale;
+use crate::metric::{Speed, SpeedError};
+use crate::road::Direction;
+use crate::transform::RoadWarnings;
+
+/// Implicit speed limits by country and zone, e.g. `maxspeed=DE:urban`,
+/// where `None` is a zone without a general speed limit
+///
+/// <https://wiki.openstreetmap.org/wiki/Key:maxspeed#Implicit_maxspeed_values>
+const ZONES: &[(&str, &str, Option<Speed>)] = &[
+    ("AT", "urban", Some(Speed::Kph(50.0))),
+    ("AT", "rural", Some(Speed::Kph(100.0))),
+    ("AT", "trunk", Some(Speed::Kph(100.0))),
+    ("AT", "motorway", Some(Speed::Kph(130.0))),
+    ("CH", "urban", Some(Speed::Kph(50.0))),
+    ("CH", "rural", Some(Speed::Kph(80.0))),
+    ("CH", "trunk", Some(Speed::Kph(100.0))),
+    ("CH", "motorway", Some(Speed::Kph(120.0))),
+    ("DE", "urban", Some(Speed::Kph(50.0))),
+    ("DE", "rural", Some(Speed::Kph(100.0))),
+    ("DE", "bicycle_road", Some(Speed::Kph(30.0))),
+    ("DE", "motorway", None),
+    ("FR", "urban", Some(Speed::Kph(50.0))),
+    ("FR", "rural", Some(Speed::Kph(80.0))),
+    ("FR", "trunk", Some(Speed::Kph(110.0))),
+    ("FR", "motorway", Some(Speed::Kph(130.0))),
+    ("GB", "nsl_restricted", Some(Speed::Mph(30.0))),
+    ("GB", "nsl_single", Some(Speed::Mph(60.0))),
+    ("GB", "nsl_dual", Some(Speed::Mph(70.0))),
+    ("GB", "motorway", Some(Speed::Mph(70.0))),
+    ("NL", "urban", Some(Speed::Kph(50.0))),
+    ("NL", "rural", Some(Speed::Kph(80.0))),
+    ("NL", "trunk", Some(Speed::Kph(100.0))),
+    ("NL", "motorway", Some(Speed::Kph(130.0))),
+];
+
+/// Speed limit of a zone, e.g. `DE:urban`, `DE:zone30`, or `GB:zone:20`
+fn zone(country: &str, zone: &str, locale: &Locale) -> Result<Option<Speed>, SpeedError> {
+    let unknown = || SpeedError::UnknownValue(format!("{country}:{zone}"));
+    if zone == "living_street" {
+        return Ok(Some(locale.living_street_max_speed()));
+    }
+    if let Some(limit) = zone
+        .strip_prefix("zone:")
+        .or_else(|| zone.strip_prefix("zone"))
+    {
+        let limit: f64 = limit.parse().map_err(|_e| unknown())?;
+        return Ok(Some(if matches!(country, "GB" | "US") {
+            Speed::Mph(limit)
+        } else {
+            Speed::Kph(limit)
+        }));
+    }
+    ZONES
+        .iter()
+        .find(|(c, z, _)| *c == country && *z == zone)
+        .map(|(_, _, speed)| *speed)
+        .ok_or_else(unknown)
+}
+
+/// The speed limit from a value of `maxspeed=*`, `None` if there is no fixed limit
+fn parse(value: &str, locale: &Locale) -> Result<Option<Speed>, SpeedError> {
+    match value {
+        "none" | "signals" | "variable" => Ok(None),
+        "walk" => Ok(Some(locale.living_street_max_speed())),
+        _ => match value.split_once(':') {
+            Some((country, zone_value))
+                if country.len() == 2 && country.chars().all(|c| c.is_ascii_uppercase()) =>
+            {
+                zone(country, zone_value, locale)
+            },
+            _ => value.parse().map(Some),
+        },
+    }
+}
+
+/// The speed limit of a key, warning if the value is not understood
+fn get(
+    tags: &TagsRef<'_>,
+    key: &TagKey,
+    locale: &Locale,
+    warnings: &mut RoadWarnings,
+) -> Option<Speed> {
+    match parse(tags.get(key)?, locale) {
+        Ok(max_speed) => max_speed,
+        Err(e) => {
+            warnings.push(TagsToLanesMsg::unsupported(
+                e.to_string(),
+                tags.subset([key]),
+            ));
+            None
+        },
+    }
+}
+
+/// Speed limit of the way from `maxspeed=*`,
+/// or the speed limit implied by the locale for the highway type if untagged
+pub(super) fn way_max_speed(
+    tags: &TagsRef<'_>,
+    highway: HighwayType,
+    locale: &Locale,
+    warnings: &mut RoadWarnings,
+) -> Infer<Speed> {
+    if tags.get(&keys::MAXSPEED).is_none() {
+        return locale
+            .default_max_speed(highway)
+            .map_or(Infer::None, Infer::Default);
+    }
+    Infer::direct(get(tags, &keys::MAXSPEED, locale, warnings))
+}
+
+/// Speed limits in a direction of travel, from `maxspeed:forward=*` and `maxspeed:backward=*`,
+/// overriding `maxspeed=*` for the lanes in that direction
+pub(super) fn directional_max_speed(
+    tags: &TagsRef<'_>,
+    locale: &Locale,
+    road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
+) {
+    let is_direction =
+        |direction| move |lane: &&mut LaneBuilder| lane.direction.some() == Some(direction);
+    if let Some(max_speed) = get(tags, &keys::MAXSPEED_FORWARD, locale, warnings) {
+        for lane in road
+            .forward_ltr_mut(locale)
+            .filter(is_direction(Direction::Forward))
+        {
+            lane.max_speed = Infer::Direct(max_speed);
+        }
+    }
+    if let Some(max_speed) = get(tags, &keys::MAXSPEED_BACKWARD, locale, warnings) {
+        for lane in road
+            .backward_ltr_mut(locale)
+            .filter(is_direction(Direction::Backward))
+        {
+            lane.max_speed = Infer::Direct(max_speed);
+        }
+    }
+}
+
+/// Speed limits of individual lanes counted in `lanes=*`, from `maxspeed:lanes=*`,
+/// and its `:forward` and `:backward` variants, where an empty value is the speed limit of the way
+pub(super) fn lane_max_speed(
+    tags: &TagsRef<'_>,
+    locale: &Locale,
+    road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
+) {
+    let key = keys::MAXSPEED_LANES;
+    match LaneDependent::<String>::from_tags(tags, &key) {
+        Ok(None) => {},
+        Ok(Some(lanes)) => {
+            if let Some(lanes) = road.lane_dependent_mut(lanes, locale) {
+                for (lane, value) in lanes {
+                    if value.is_empty() {
+                        continue;
+                    }
+                    match parse(&value, locale) {
+                        Ok(max_speed) => lane.max_speed = Infer::direct(max_speed),
+                        Err(e) => warnings.push(TagsToLanesMsg::unsupported(
+                            e.to_string(),
+                            tags.subset(&[&key + "forward", &key + "backward", key.clone()]),
+                        )),
+                    }
+                }
+            } else {
+                warnings.push(TagsToLanesMsg::unsupported(
+                    "lane count mismatch",
+                    tags.subset(&[&key + "forward", &key + "backward", key]),
+                ));
+            }
+        },
+        Err(e) => warnings.push(e.into()),
+    }
+}
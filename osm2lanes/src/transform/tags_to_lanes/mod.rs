@@ -1,71 +1,187 @@
 #![allow(clippy::module_name_repetitions)] // TODO: fix upstream
 
-use std::borrow::Borrow;
-use std::hash::Hash;
+use alloc::borrow::ToOwned;
+use core::str::FromStr;
 
-use osm_tag_schemes::Schemes;
-use osm_tags::Tags;
+use osm_tag_schemes::{keys, PassingPlaces, Schemes};
+use osm_tags::{Tags, TagsRef};
 
 use crate::locale::Locale;
-use crate::road::Road;
+use crate::metric::{Metre, Speed};
+use crate::road::{Direction, FormatVersion, Lane, Road, RoadAttributes};
 use crate::transform::error::{RoadError, RoadWarnings};
-use crate::transform::RoadFromTags;
+use crate::transform::{reverse_way, RoadFromTags};
 
 mod error;
-pub use error::TagsToLanesMsg;
+pub use error::{TagsToLanesMsg, WayContext};
 
 mod counts;
 
 mod modes;
-use modes::BuswayScheme;
+use modes::{BuswayScheme, LanesBusScheme};
 
 mod separator;
 
 mod road;
 use osm_tags::TagKey;
-use road::{LaneBuilder, LaneBuilderError, LaneType, RoadBuilder};
+use road::{LaneBuffers, LaneBuilder, LaneBuilderError, LaneType, RoadBuilder};
 
 mod unsupported;
 use unsupported::unsupported;
 
+mod unread;
+
+mod normalize;
+
+mod surface;
+
+mod embedded_rails;
+
+mod trolley_wire;
+
+mod traffic_sign;
+
+mod access;
+
+mod restrictions;
+
+mod max_speed;
+
+mod width;
+pub use width::{LaneWidth, WidthConstraint};
+
+mod destination;
+
+mod turn;
+
 mod infer;
 pub use infer::Infer;
 
 mod oneway;
 use oneway::Oneway;
 
-trait TagsNumeric {
+mod trace;
+pub use trace::{Trace, TraceChange, TraceHtml, TraceLane, TraceStep};
+
+mod incremental;
+pub use incremental::Incremental;
+
+mod sidepath;
+pub use sidepath::{tags_to_lanes_with_sidepaths, Sidepath, SidepathSide};
+
+mod compare;
+pub use compare::{tags_to_lanes_by_locale, LaneDifference, LocaleComparison};
+
+/// Typed values of tags,
+/// warning with the key, the value, and the expected type if the value is invalid
+trait TagsTyped {
+    /// The value parsed by `parse`, described as `expected` if invalid
+    fn get_typed<Q, T, O>(
+        &self,
+        key: &Q,
+        expected: &'static str,
+        parse: impl FnOnce(&str) -> Option<T>,
+        warnings: &mut RoadWarnings,
+    ) -> Option<T>
+    where
+        Q: AsRef<str> + ?Sized + ToOwned<Owned = O>,
+        O: Into<TagKey>;
+
+    /// The value parsed as `T`, e.g. a count of lanes
     fn get_parsed<Q, T, O>(&self, key: &Q, warnings: &mut RoadWarnings) -> Option<T>
     where
-        TagKey: Borrow<Q>,
-        Q: Ord + Hash + Eq + ?Sized + ToOwned<Owned = O>,
+        Q: AsRef<str> + ?Sized + ToOwned<Owned = O>,
+        O: Into<TagKey>,
+        T: FromStr,
+    {
+        let expected = core::any::type_name::<T>()
+            .rsplit("::")
+            .next()
+            .unwrap_or("value");
+        self.get_typed(key, expected, |val| val.parse().ok(), warnings)
+    }
+
+    /// The value `yes` or `no`
+    fn get_bool<Q, O>(&self, key: &Q, warnings: &mut RoadWarnings) -> Option<bool>
+    where
+        Q: AsRef<str> + ?Sized + ToOwned<Owned = O>,
         O: Into<TagKey>,
-        T: std::str::FromStr;
+    {
+        self.get_typed(
+            key,
+            "yes or no",
+            |val| match val {
+                "yes" => Some(true),
+                "no" => Some(false),
+                _ => None,
+            },
+            warnings,
+        )
+    }
+
+    /// The value as a distance, see [`Metre`]
+    fn get_metre<Q, O>(&self, key: &Q, warnings: &mut RoadWarnings) -> Option<Metre>
+    where
+        Q: AsRef<str> + ?Sized + ToOwned<Owned = O>,
+        O: Into<TagKey>,
+    {
+        self.get_typed(key, "a distance", |val| val.parse().ok(), warnings)
+    }
+
+    /// The value as a speed, see [`Speed`]
+    fn get_speed<Q, O>(&self, key: &Q, warnings: &mut RoadWarnings) -> Option<Speed>
+    where
+        Q: AsRef<str> + ?Sized + ToOwned<Owned = O>,
+        O: Into<TagKey>,
+    {
+        self.get_typed(key, "a speed", |val| val.parse().ok(), warnings)
+    }
+
+    /// A known value of a tag scheme, e.g. [`osm_tag_schemes::Access`]
+    fn get_enum<T, Q, O>(&self, key: &Q, warnings: &mut RoadWarnings) -> Option<T>
+    where
+        Q: AsRef<str> + ?Sized + ToOwned<Owned = O>,
+        O: Into<TagKey>,
+        T: FromStr,
+    {
+        self.get_typed(key, "a known value", |val| val.parse().ok(), warnings)
+    }
 }
 
-impl TagsNumeric for Tags {
-    fn get_parsed<Q, T, O>(&self, key: &Q, warnings: &mut RoadWarnings) -> Option<T>
+impl TagsTyped for TagsRef<'_> {
+    fn get_typed<Q, T, O>(
+        &self,
+        key: &Q,
+        expected: &'static str,
+        parse: impl FnOnce(&str) -> Option<T>,
+        warnings: &mut RoadWarnings,
+    ) -> Option<T>
     where
-        TagKey: Borrow<Q>,
-        Q: Ord + Hash + Eq + ?Sized + ToOwned<Owned = O>,
+        Q: AsRef<str> + ?Sized + ToOwned<Owned = O>,
         O: Into<TagKey>,
-        T: std::str::FromStr,
     {
-        self.get(key).and_then(|val| {
-            if let Ok(w) = val.parse::<T>() {
-                Some(w)
-            } else {
-                warnings.push(TagsToLanesMsg::unsupported_tag(key.to_owned(), val));
-                None
-            }
-        })
+        let val = self.get(key)?;
+        let parsed = parse(val);
+        if parsed.is_none() {
+            warnings.push(TagsToLanesMsg::invalid_value(key.to_owned(), val, expected));
+        }
+        parsed
     }
 }
 
+#[derive(Clone)]
 #[non_exhaustive]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Config {
     pub error_on_warnings: bool,
     pub include_separators: bool,
+    /// Attached to every warning and error message
+    pub context: Option<WayContext>,
+    /// Warn on tags that describe lanes but were never read, such as typos
+    pub warn_unread_tags: bool,
+    /// Normalize the case and whitespace of values before reading them, such as `oneway=Yes `,
+    /// warning with the values that were normalized
+    pub normalize_tags: bool,
 }
 
 impl Config {
@@ -74,6 +190,80 @@ impl Config {
         Self {
             error_on_warnings,
             include_separators,
+            context: None,
+            warn_unread_tags: false,
+            normalize_tags: false,
+        }
+    }
+
+    /// ```
+    /// use osm2lanes::locale::Locale;
+    /// use osm2lanes::transform::{tags_to_lanes, TagsToLanesConfig, WayContext};
+    /// use osm_tags::Tags;
+    ///
+    /// let tags = Tags::from_pairs([("highway", "secondary"), ("sidewalk", "yes")]).unwrap();
+    /// let config = TagsToLanesConfig::default().with_context(WayContext::way(1));
+    /// let road = tags_to_lanes(&tags, &Locale::builder().build(), &config).unwrap();
+    /// assert!(!road.warnings.is_empty());
+    /// for msg in road.warnings.as_slice() {
+    ///     assert_eq!(msg.context(), Some(&WayContext::way(1)));
+    /// }
+    /// ```
+    #[must_use]
+    pub fn with_context(mut self, context: WayContext) -> Self {
+        self.context = Some(context);
+        self
+    }
+
+    /// ```
+    /// use osm2lanes::locale::Locale;
+    /// use osm2lanes::transform::{tags_to_lanes, TagsToLanesConfig};
+    /// use osm_tags::Tags;
+    ///
+    /// let tags = Tags::from_pairs([("highway", "secondary"), ("cylceway", "lane")]).unwrap();
+    /// let config = TagsToLanesConfig::new(false, false).with_unread_tags_warning();
+    /// let road = tags_to_lanes(&tags, &Locale::builder().build(), &config).unwrap();
+    /// assert_eq!(road.warnings.as_slice().len(), 1, "{}", road.warnings);
+    /// ```
+    #[must_use]
+    pub fn with_unread_tags_warning(mut self) -> Self {
+        self.warn_unread_tags = true;
+        self
+    }
+
+    /// ```
+    /// use osm2lanes::locale::Locale;
+    /// use osm2lanes::transform::{tags_to_lanes, TagsToLanesConfig};
+    /// use osm_tags::Tags;
+    ///
+    /// let tags = Tags::from_pairs([("highway", "Secondary "), ("oneway", "Yes")]).unwrap();
+    /// let config = TagsToLanesConfig::new(false, false).with_normalized_tags();
+    /// let road = tags_to_lanes(&tags, &Locale::builder().build(), &config).unwrap();
+    /// assert_eq!(road.warnings.as_slice().len(), 1);
+    /// ```
+    #[must_use]
+    pub fn with_normalized_tags(mut self) -> Self {
+        self.normalize_tags = true;
+        self
+    }
+
+    /// Attach the context, if any, to the messages of a result
+    fn contextualise(
+        &self,
+        result: Result<RoadFromTags, RoadError>,
+    ) -> Result<RoadFromTags, RoadError> {
+        match &self.context {
+            None => result,
+            Some(context) => match result {
+                Ok(mut road_from_tags) => {
+                    road_from_tags.warnings.set_context(context);
+                    Ok(road_from_tags)
+                },
+                Err(mut e) => {
+                    e.set_context(context);
+                    Err(e)
+                },
+            },
         }
     }
 }
@@ -83,6 +273,9 @@ impl Default for Config {
         Self {
             error_on_warnings: false,
             include_separators: true,
+            context: None,
+            warn_unread_tags: false,
+            normalize_tags: false,
         }
     }
 }
@@ -90,17 +283,23 @@ impl Default for Config {
 pub(in crate::transform::tags_to_lanes) struct TagSchemes {
     oneway: Oneway,
     busway: BuswayScheme,
+    lanes_bus: LanesBusScheme,
 }
 
 impl TagSchemes {
     pub(crate) fn from_tags(
-        tags: &Tags,
+        tags: &TagsRef<'_>,
         locale: &Locale,
         warnings: &mut RoadWarnings,
     ) -> Result<Self, TagsToLanesMsg> {
         let oneway = Oneway::from_tags(tags, locale, warnings)?;
         let busway = BuswayScheme::from_tags(tags, oneway, locale, warnings)?;
-        Ok(Self { oneway, busway })
+        let lanes_bus = LanesBusScheme::from_tags(tags, oneway, &busway, warnings);
+        Ok(Self {
+            oneway,
+            busway,
+            lanes_bus,
+        })
     }
 }
 
@@ -118,13 +317,278 @@ impl TagSchemes {
 /// If the issue may be recoverable, a warning is preferred.
 /// A config option allows all warnings to be treated as errors.
 ///
+/// The tags may borrow their keys and values, such as those of a way read from a PBF file,
+/// which are then not copied, see [`TagsRef::from_borrowed_pairs`].
+///
+/// ```
+/// use osm2lanes::locale::Locale;
+/// use osm2lanes::transform::{tags_to_lanes, TagsToLanesConfig};
+/// use osm_tags::{Tags, TagsRef};
+///
+/// let pairs = [("highway", "secondary"), ("lanes", "3")];
+/// let locale = Locale::builder().build();
+/// let config = TagsToLanesConfig::default();
+/// let borrowed = tags_to_lanes(&TagsRef::from_borrowed_pairs(pairs).unwrap(), &locale, &config).unwrap();
+/// let owned = tags_to_lanes(&Tags::from_pairs(pairs).unwrap(), &locale, &config).unwrap();
+/// assert_eq!(borrowed.road, owned.road);
+/// ```
 pub fn tags_to_lanes(
-    tags: &Tags,
+    tags: &TagsRef<'_>,
     locale: &Locale,
     config: &Config,
+) -> Result<RoadFromTags, RoadError> {
+    transform(tags, locale, config, &mut LaneBuffers::default(), None)
+}
+
+/// Like [`tags_to_lanes`],
+/// but also return a [`Trace`] of the intermediate decisions for debugging.
+///
+/// The trace contains every stage that completed, even if the transformation fails.
+pub fn tags_to_lanes_with_trace(
+    tags: &TagsRef<'_>,
+    locale: &Locale,
+    config: &Config,
+) -> (Result<RoadFromTags, RoadError>, Trace) {
+    let mut trace = Trace::default();
+    let result = transform(
+        tags,
+        locale,
+        config,
+        &mut LaneBuffers::default(),
+        Some(&mut trace),
+    );
+    (result, trace)
+}
+
+/// Transforms many ways with the same config, like [`tags_to_lanes`],
+/// reusing the intermediate storage from one way to the next.
+///
+/// ```
+/// use osm2lanes::locale::Locale;
+/// use osm2lanes::transform::{TagsToLanesConfig, Transformer};
+/// use osm_tags::Tags;
+///
+/// let locale = Locale::builder().build();
+/// let mut transformer = Transformer::new(TagsToLanesConfig::default());
+/// for lanes in ["1", "2", "3"] {
+///     let tags = Tags::from_pairs([("highway", "secondary"), ("lanes", lanes)]).unwrap();
+///     assert!(transformer.transform(&tags, &locale).is_ok());
+/// }
+/// ```
+pub struct Transformer {
+    config: Config,
+    buffers: LaneBuffers,
+    tags: Tags,
+}
+
+impl Transformer {
+    #[must_use]
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            buffers: LaneBuffers::default(),
+            tags: Tags::default(),
+        }
+    }
+
+    /// See [`tags_to_lanes`]
+    ///
+    /// # Errors
+    ///
+    /// See [`tags_to_lanes`]
+    pub fn transform(
+        &mut self,
+        tags: &TagsRef<'_>,
+        locale: &Locale,
+    ) -> Result<RoadFromTags, RoadError> {
+        transform(tags, locale, &self.config, &mut self.buffers, None)
+    }
+
+    /// Like [`Transformer::transform`], from borrowed pairs of tags,
+    /// such as those of a way read from a PBF file,
+    /// without copying their keys and values,
+    /// reusing the storage of the tags from one way to the next, see [`TagsRef::assign_pairs`]
+    ///
+    /// ```
+    /// use osm2lanes::locale::Locale;
+    /// use osm2lanes::transform::{TagsToLanesConfig, Transformer};
+    ///
+    /// let locale = Locale::builder().build();
+    /// let mut transformer = Transformer::new(TagsToLanesConfig::default());
+    /// for lanes in ["1", "2", "3"] {
+    ///     let pairs = [("highway", "secondary"), ("lanes", lanes)];
+    ///     assert!(transformer.transform_pairs(pairs, &locale).is_ok());
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If a key is duplicated, otherwise see [`tags_to_lanes`]
+    pub fn transform_pairs<'any, I>(
+        &mut self,
+        pairs: I,
+        locale: &Locale,
+    ) -> Result<RoadFromTags, RoadError>
+    where
+        I: IntoIterator<Item = (&'any str, &'any str)>,
+    {
+        let tags = core::mem::take(&mut self.tags).assign_pairs(pairs)?;
+        let result = transform(&tags, locale, &self.config, &mut self.buffers, None);
+        // Release the borrowed pairs, keeping their storage for the next way
+        self.tags = tags.assign_pairs([]).unwrap_or_default();
+        result
+    }
+}
+
+/// Transforms many ways in parallel, like [`tags_to_lanes`],
+/// reusing a [`Transformer`] on each thread.
+///
+/// The results are in the same order as `ways`.
+///
+/// ```
+/// use osm2lanes::locale::Locale;
+/// use osm2lanes::transform::{tags_to_lanes_par, TagsToLanesConfig};
+/// use osm_tags::Tags;
+///
+/// let ways: Vec<Tags> = ["1", "2", "3"]
+///     .into_iter()
+///     .map(|lanes| Tags::from_pairs([("highway", "secondary"), ("lanes", lanes)]).unwrap())
+///     .collect();
+/// let locale = Locale::builder().build();
+/// let results = tags_to_lanes_par(&ways, &locale, &TagsToLanesConfig::default());
+/// assert_eq!(results.len(), ways.len());
+/// ```
+#[cfg(feature = "rayon")]
+#[must_use]
+pub fn tags_to_lanes_par(
+    ways: &[Tags],
+    locale: &Locale,
+    config: &Config,
+) -> Vec<Result<RoadFromTags, RoadError>> {
+    use rayon::prelude::*;
+
+    ways.par_iter()
+        .map_init(
+            || Transformer::new(config.clone()),
+            |transformer, tags| transformer.transform(tags, locale),
+        )
+        .collect()
+}
+
+fn transform(
+    tags: &TagsRef<'_>,
+    locale: &Locale,
+    config: &Config,
+    buffers: &mut LaneBuffers,
+    trace: Option<&mut Trace>,
 ) -> Result<RoadFromTags, RoadError> {
     let mut warnings = RoadWarnings::default();
+    let prepared = prepare(tags, config, &mut warnings);
+    let tags = prepared.as_ref().unwrap_or(tags);
+    config.contextualise(transform_way(
+        tags, locale, config, buffers, trace, warnings,
+    ))
+}
+
+/// The tags to read, if they differ from the input, normalized or with their reads tracked
+fn prepare<'tags>(
+    tags: &TagsRef<'tags>,
+    config: &Config,
+    warnings: &mut RoadWarnings,
+) -> Option<TagsRef<'tags>> {
+    if !config.normalize_tags && !config.warn_unread_tags {
+        return None;
+    }
+    let mut tags = tags.clone();
+    if config.normalize_tags {
+        normalize::normalize(&mut tags, warnings);
+    }
+    if config.warn_unread_tags {
+        tags = tags.with_read_tracking();
+    }
+    Some(tags)
+}
+
+fn transform_way(
+    tags: &TagsRef<'_>,
+    locale: &Locale,
+    config: &Config,
+    buffers: &mut LaneBuffers,
+    mut trace: Option<&mut Trace>,
+    warnings: RoadWarnings,
+) -> Result<RoadFromTags, RoadError> {
+    // Traffic against the direction of the way is read from the way drawn the other way around
+    if tags.is(&Oneway::KEY, "-1") {
+        let mut reversed = reverse_way(tags)?;
+        if config.warn_unread_tags {
+            reversed = reversed.with_read_tracking();
+        }
+        let mut result = transform_way(&reversed, locale, config, buffers, trace, warnings)?;
+        result.reverse();
+        return Ok(result);
+    }
+
+    let Start {
+        mut warnings,
+        generic_schemes,
+        crate_schemes,
+        mut road,
+    } = start(tags, locale, core::mem::take(buffers), warnings)?;
+    if let Some(trace) = trace.as_deref_mut() {
+        trace.record(
+            "highway",
+            "highway classification, oneway, and motor vehicle lane counts",
+            &road,
+            locale,
+            &warnings,
+        );
+    }
+
+    for stage in Stage::ALL {
+        stage.run(tags, locale, &crate_schemes, &mut road, &mut warnings)?;
+        if let Some(trace) = trace.as_deref_mut() {
+            trace.record(stage.name(), stage.description(), &road, locale, &warnings);
+        }
+    }
+
+    let previous = trace.is_some().then(|| road.clone());
+    let result = finish(
+        road,
+        generic_schemes,
+        tags,
+        locale,
+        config,
+        warnings,
+        buffers,
+    );
+    if let (Some(trace), Some(previous), Ok(road_from_tags)) = (trace, previous, &result) {
+        trace.record_lanes(
+            "separators",
+            "separators between lanes, and the final lanes",
+            previous,
+            &road_from_tags.road.lanes,
+            tags,
+            locale,
+            &road_from_tags.warnings,
+        );
+    }
+    result
+}
+
+/// The state before the stages
+struct Start<'tag> {
+    warnings: RoadWarnings,
+    generic_schemes: Schemes<'tag>,
+    crate_schemes: TagSchemes,
+    road: RoadBuilder,
+}
 
+fn start<'tag>(
+    tags: &'tag TagsRef<'_>,
+    locale: &Locale,
+    buffers: LaneBuffers,
+    mut warnings: RoadWarnings,
+) -> Result<Start<'tag>, RoadError> {
     // Early return if we find unimplemented or unsupported tags.
     unsupported(tags, locale, &mut warnings)?;
 
@@ -139,44 +603,372 @@ pub fn tags_to_lanes(
         &crate_schemes,
         tags,
         locale,
+        buffers,
         &mut warnings,
     )?;
+    surface::surface_lanes(tags, locale, &mut road, &mut warnings);
+    embedded_rails::embedded_rails(tags, &generic_schemes, locale, &mut road, &mut warnings);
+    traffic_sign::traffic_sign(tags, locale, &mut road, &mut warnings);
+    max_speed::directional_max_speed(tags, locale, &mut road, &mut warnings);
+    max_speed::lane_max_speed(tags, locale, &mut road, &mut warnings);
+    width::lane_widths(tags, locale, &mut road, &mut warnings);
+    width::way_width(tags, locale, &mut road, &mut warnings);
+    restrictions::lane_restrictions(tags, locale, &mut road, &mut warnings);
+    destination::lane_destinations(tags, locale, &mut road, &mut warnings);
+    turn::lane_turns(tags, locale, &mut road, &mut warnings);
+    access::lane_access(tags, locale, &mut road, &mut warnings);
+
+    Ok(Start {
+        warnings,
+        generic_schemes,
+        crate_schemes,
+        road,
+    })
+}
 
-    modes::non_motorized(tags, locale, &mut road, &mut warnings)?;
+/// Stages applied to the road builder in order, after it is created for the highway
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Stage {
+    NonMotorized,
+    Bus,
+    Bicycle,
+    Parking,
+    FootAndShoulder,
+}
 
-    modes::bus(
-        &crate_schemes.busway,
-        tags,
-        locale,
-        &mut road,
-        &mut warnings,
-    )?;
+impl Stage {
+    const ALL: [Self; 5] = [
+        Self::NonMotorized,
+        Self::Bus,
+        Self::Bicycle,
+        Self::Parking,
+        Self::FootAndShoulder,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::NonMotorized => "non_motorized",
+            Self::Bus => "bus",
+            Self::Bicycle => "bicycle",
+            Self::Parking => "parking",
+            Self::FootAndShoulder => "foot_and_shoulder",
+        }
+    }
 
-    modes::bicycle(tags, locale, &mut road, &mut warnings)?;
+    fn description(self) -> &'static str {
+        match self {
+            Self::NonMotorized => "roads without motor vehicle lanes",
+            Self::Bus => "bus, taxi and high-occupancy vehicle lanes, and busways",
+            Self::Bicycle => "cycle lanes and tracks",
+            Self::Parking => "parking lanes",
+            Self::FootAndShoulder => "sidewalks and shoulders",
+        }
+    }
 
-    modes::parking(tags, locale, &mut road)?;
+    fn run(
+        self,
+        tags: &TagsRef<'_>,
+        locale: &Locale,
+        crate_schemes: &TagSchemes,
+        road: &mut RoadBuilder,
+        warnings: &mut RoadWarnings,
+    ) -> Result<(), RoadError> {
+        match self {
+            Self::NonMotorized => modes::non_motorized(tags, locale, road, warnings)?,
+            Self::Bus => {
+                modes::bus(
+                    &crate_schemes.busway,
+                    &crate_schemes.lanes_bus,
+                    tags,
+                    locale,
+                    road,
+                    warnings,
+                )?;
+                modes::taxi_and_hov(tags, locale, road, warnings)?;
+            },
+            Self::Bicycle => modes::bicycle(tags, locale, road, warnings)?,
+            Self::Parking => modes::parking(tags, locale, road, warnings)?,
+            Self::FootAndShoulder => modes::foot_and_shoulder(tags, locale, road, warnings)?,
+        }
+        Ok(())
+    }
+}
 
-    modes::foot_and_shoulder(tags, locale, &mut road, &mut warnings)?;
+/// Passing places only apply to a single track road,
+/// a single motor lane used in both directions
+fn passing_places(
+    passing_places: Option<PassingPlaces>,
+    lanes: &[Lane],
+    tags: &TagsRef<'_>,
+    warnings: &mut RoadWarnings,
+) -> Option<PassingPlaces> {
+    let mut motor_lanes = lanes.iter().filter(|lane| lane.is_motor());
+    let single_track = matches!(
+        (motor_lanes.next(), motor_lanes.next()),
+        (
+            Some(Lane::Travel {
+                direction: Some(Direction::Both),
+                ..
+            }),
+            None,
+        )
+    );
+    if passing_places == Some(PassingPlaces::Yes) && !single_track {
+        warnings.push(TagsToLanesMsg::unsupported(
+            "passing places without a single track",
+            tags.subset(&[keys::PASSING_PLACES]),
+        ));
+        return None;
+    }
+    passing_places
+}
 
-    let (lanes, highway, _oneway) =
+/// Build the lanes and the road, after the stages
+fn finish(
+    mut road: RoadBuilder,
+    generic_schemes: Schemes,
+    tags: &TagsRef<'_>,
+    locale: &Locale,
+    config: &Config,
+    mut warnings: RoadWarnings,
+    buffers: &mut LaneBuffers,
+) -> Result<RoadFromTags, RoadError> {
+    // Only the first of multiple values is used
+    for (key, val) in &generic_schemes.ambiguous {
+        warnings.push(TagsToLanesMsg::ambiguous_tag(key.clone(), val));
+    }
+    // The bus lanes are known after the stages
+    trolley_wire::trolley_wire(tags, &generic_schemes, locale, &mut road, &mut warnings);
+    // The lanes of each mode are known after the stages
+    access::access(tags, locale, &mut road, &mut warnings)?;
+    let separately_mapped = core::mem::take(&mut road.separately_mapped);
+    let (mut lanes, widths, highway, _oneway, lane_buffers) =
         road.into_ltr(tags, locale, config.include_separators, &mut warnings)?;
+    *buffers = lane_buffers;
+    if config.include_separators {
+        separator::physical_devices(&mut lanes, tags, locale, &mut warnings);
+    }
+
+    let passing_places = passing_places(
+        generic_schemes.passing_places.unwrap_or(None),
+        &lanes,
+        tags,
+        &mut warnings,
+    );
+
+    let restrictions = restrictions::road_restrictions(tags, &mut warnings);
 
-    let road_from_tags = RoadFromTags {
+    let mut road_from_tags = RoadFromTags {
         road: Road {
+            format_version: FormatVersion::CURRENT,
             name: generic_schemes.name,
             r#ref: generic_schemes.r#ref,
             highway,
             lit: generic_schemes.lit.unwrap_or(None),
             tracktype: generic_schemes.tracktype.unwrap_or(None),
-            smoothness: generic_schemes.smoothness.unwrap_or(None),
+            attributes: RoadAttributes {
+                surface: generic_schemes.surface.unwrap_or(None),
+                smoothness: generic_schemes.smoothness.unwrap_or(None),
+            },
+            priority_road: generic_schemes.priority_road.unwrap_or(None),
+            passing_places,
+            expressway: generic_schemes.expressway.unwrap_or(None),
+            dual_carriageway: generic_schemes.dual_carriageway.unwrap_or(None),
+            cycle_street: generic_schemes.cycle_street.unwrap_or(None),
+            restrictions,
+            placement: generic_schemes.placement.unwrap_or(None),
+            separately_mapped: (!separately_mapped.is_empty()).then(|| separately_mapped),
             lanes,
         },
         warnings,
+        widths,
     };
 
+    if config.warn_unread_tags {
+        unread::unread_tags(tags, &mut road_from_tags.warnings);
+    }
+
     if config.error_on_warnings && !road_from_tags.warnings.is_empty() {
         return Err(road_from_tags.warnings.into());
     }
 
     Ok(road_from_tags)
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use osm_tag_schemes::Access;
+    use osm_tags::Tags;
+
+    use super::error::TagsToLanesIssue;
+    use super::TagsTyped;
+    use crate::metric::{Metre, Speed};
+    use crate::transform::RoadWarnings;
+
+    /// Results in the order of the ways, the same as transforming each way in turn
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_tags_to_lanes_par() {
+        use crate::locale::Locale;
+        use crate::transform::{tags_to_lanes, tags_to_lanes_par, TagsToLanesConfig};
+
+        let ways: Vec<Tags> = (0..64_usize)
+            .map(|i| {
+                let lanes = (i % 5 + 1).to_string();
+                let highway = ["secondary", "residential", "lava", "footway"]
+                    .get(i % 4)
+                    .copied()
+                    .unwrap();
+                if i % 7 == 0 {
+                    // Not a road
+                    Tags::from_pair("lanes", lanes)
+                } else {
+                    Tags::from_pairs([("highway", highway), ("lanes", lanes.as_str())]).unwrap()
+                }
+            })
+            .collect();
+        let locale = Locale::builder().build();
+        let config = TagsToLanesConfig::default();
+        let results = tags_to_lanes_par(&ways, &locale, &config);
+        assert_eq!(results.len(), ways.len());
+        for (tags, result) in ways.iter().zip(&results) {
+            match (result, tags_to_lanes(tags, &locale, &config)) {
+                (Ok(result), Ok(expected)) => {
+                    assert_eq!(result.road, expected.road, "{}", tags.to_string());
+                    assert_eq!(
+                        result.warnings.to_string(),
+                        expected.warnings.to_string(),
+                        "{}",
+                        tags.to_string()
+                    );
+                },
+                (Err(error), Err(expected)) => {
+                    assert_eq!(
+                        error.to_string(),
+                        expected.to_string(),
+                        "{}",
+                        tags.to_string()
+                    );
+                },
+                (result, expected) => panic!("{:?} != {:?}", result, expected),
+            }
+        }
+        assert!(results.iter().any(Result::is_ok));
+        assert!(results.iter().any(Result::is_err));
+    }
+
+    /// Reusing the storage of a transformer, the same as transforming each way in turn
+    #[test]
+    fn test_transformer() {
+        use crate::locale::Locale;
+        use crate::transform::{
+            tags_to_lanes, RoadError, TagsToLanesConfig, TagsToLanesMsg, Transformer,
+        };
+
+        let locale = Locale::builder().build();
+        let config = TagsToLanesConfig::default();
+        let mut transformer = Transformer::new(config.clone());
+        for pairs in [
+            &[
+                ("highway", "primary"),
+                ("lanes", "4"),
+                ("cycleway:both", "lane"),
+            ][..],
+            &[("highway", "service")][..],
+            &[("lanes", "2")][..],
+            &[
+                ("highway", "residential"),
+                ("lanes", "2"),
+                ("highway", "service"),
+            ][..],
+            &[
+                ("sidewalk", "both"),
+                ("highway", "residential"),
+                ("oneway", "yes"),
+            ][..],
+        ] {
+            let result = transformer.transform_pairs(pairs.iter().copied(), &locale);
+            match (result, Tags::from_pairs(pairs.iter().copied())) {
+                (Ok(result), Ok(tags)) => {
+                    let expected = tags_to_lanes(&tags, &locale, &config).unwrap();
+                    assert_eq!(result.road, expected.road);
+                    assert_eq!(
+                        transformer.transform(&tags, &locale).unwrap().road,
+                        expected.road
+                    );
+                },
+                (Err(error), Ok(tags)) => {
+                    let expected = tags_to_lanes(&tags, &locale, &config).unwrap_err();
+                    assert_eq!(error.to_string(), expected.to_string());
+                },
+                (Err(error), Err(duplicate)) => {
+                    assert!(matches!(
+                        error,
+                        RoadError::Msg(TagsToLanesMsg {
+                            issue: TagsToLanesIssue::TagsDuplicateKey(key),
+                            ..
+                        }) if key.to_string() == duplicate.to_string()
+                    ));
+                },
+                (Ok(_), Err(_)) => panic!("duplicate key was not an error"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_tags_typed() {
+        let tags = Tags::from_pairs([
+            ("lanes", "2"),
+            ("segregated", "yes"),
+            ("width", "3.5"),
+            ("maxspeed", "30 mph"),
+            ("foot", "designated"),
+            ("motor_vehicle", "maybe"),
+        ])
+        .unwrap();
+        let mut warnings = RoadWarnings::default();
+        assert_eq!(tags.get_parsed("lanes", &mut warnings), Some(2_usize));
+        assert_eq!(tags.get_bool("segregated", &mut warnings), Some(true));
+        assert_eq!(
+            tags.get_metre("width", &mut warnings),
+            Some(Metre::new(3.5))
+        );
+        assert_eq!(
+            tags.get_speed("maxspeed", &mut warnings),
+            Some(Speed::Mph(30.0))
+        );
+        assert_eq!(
+            tags.get_enum("foot", &mut warnings),
+            Some(Access::Designated)
+        );
+        assert_eq!(tags.get_bool("oneway", &mut warnings), None);
+        assert!(warnings.is_empty(), "{:?}", warnings);
+
+        assert_eq!(
+            tags.get_enum::<Access, _, _>("motor_vehicle", &mut warnings),
+            None
+        );
+        assert_eq!(tags.get_bool("lanes", &mut warnings), None);
+        let issues: Vec<_> = warnings
+            .as_slice()
+            .iter()
+            .map(|msg| match &msg.issue {
+                TagsToLanesIssue::InvalidValue {
+                    key,
+                    value,
+                    expected,
+                } => (key.as_str(), value.as_str(), *expected),
+                issue => panic!("unexpected issue {:?}", issue),
+            })
+            .collect();
+        assert_eq!(
+            issues,
+            [
+                ("motor_vehicle", "maybe", "a known value"),
+                ("lanes", "2", "yes or no"),
+            ]
+        );
+    }
+}
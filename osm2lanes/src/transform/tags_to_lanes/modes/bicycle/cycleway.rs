@@ -1,16 +1,20 @@
-use std::borrow::Borrow;
-use std::fmt::Display;
-use std::hash::Hash;
+use alloc::borrow::ToOwned;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Display;
+use core::str::FromStr;
 
-use osm_tags::{TagKey, Tags};
+use osm_tag_schemes::keys::ONEWAY_BICYCLE;
+use osm_tags::{TagKey, Tags, TagsRef};
 
-use crate::locale::Locale;
+use crate::locale::{DrivingSide, Locale};
 use crate::metric::Metre;
 use crate::road::Direction;
 use crate::transform::tags::CYCLEWAY;
 use crate::transform::tags_to_lanes::oneway::Oneway;
-use crate::transform::tags_to_lanes::road::Width;
-use crate::transform::tags_to_lanes::{Infer, TagsNumeric, TagsToLanesMsg};
+use crate::transform::tags_to_lanes::road::{Buffer, Width};
+use crate::transform::tags_to_lanes::{Infer, TagsToLanesMsg, TagsTyped};
 use crate::transform::{RoadWarnings, WaySide};
 
 #[derive(Debug)]
@@ -31,22 +35,30 @@ impl From<VariantError> for TagsToLanesMsg {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(in crate::transform::tags_to_lanes) enum Variant {
     SharedMotor,
-    // SharedBus,
+    /// Sharrows on the motor lane
+    SharedLane,
+    /// Bicycles allowed on the bus lane
+    SharedBus,
     // OptionalLane,
     Lane,
     Track,
+    /// Mapped as a separate way alongside the road
+    Separate,
 }
 
 impl Display for Variant {
     #[allow(clippy::todo, clippy::panic_in_result_fn)]
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(
             f,
             "{}",
             match self {
                 Self::SharedMotor => todo!(),
+                Self::SharedLane => "shared_lane",
+                Self::SharedBus => "share_busway",
                 Self::Lane => "lane",
                 Self::Track => "track",
+                Self::Separate => "separate",
             }
         )
     }
@@ -70,30 +82,33 @@ impl<T> OptionNo<T> {
 
 pub(in crate::transform::tags_to_lanes) struct Opposite;
 
+/// The variant of a key, where the first of multiple `;` separated values takes precedence,
+/// warning that the others are ignored, e.g. `lane` of `cycleway=lane;track`
 pub(in crate::transform::tags_to_lanes) fn get_variant<Q, O>(
-    tags: &Tags,
+    tags: &TagsRef<'_>,
     k: &Q,
+    warnings: &mut RoadWarnings,
 ) -> Result<OptionNo<(Variant, Option<Opposite>)>, VariantError>
 where
-    TagKey: Borrow<Q>,
-    Q: Ord + Hash + Eq + ?Sized + ToOwned<Owned = O>,
+    Q: AsRef<str> + ?Sized + ToOwned<Owned = O>,
     O: Into<TagKey>,
 {
-    match tags.get(k) {
+    let values = tags.get_values(k);
+    if let (Some(val), true) = (tags.get(k), values.len() > 1) {
+        warnings.push(TagsToLanesMsg::ambiguous_tag(k.to_owned(), val));
+    }
+    match values.first().copied().or_else(|| tags.get(k)) {
         Some("lane") => Ok(OptionNo::Some((Variant::Lane, None))),
         Some("track") => Ok(OptionNo::Some((Variant::Track, None))),
         Some("opposite_lane") => Ok(OptionNo::Some((Variant::Lane, Some(Opposite)))),
         Some("opposite_track") => Ok(OptionNo::Some((Variant::Track, Some(Opposite)))),
         Some("opposite") => Ok(OptionNo::Some((Variant::SharedMotor, Some(Opposite)))),
+        Some("shared_lane") => Ok(OptionNo::Some((Variant::SharedLane, None))),
+        Some("share_busway") => Ok(OptionNo::Some((Variant::SharedBus, None))),
+        Some("opposite_share_busway") => Ok(OptionNo::Some((Variant::SharedBus, Some(Opposite)))),
+        Some("separate") => Ok(OptionNo::Some((Variant::Separate, None))),
         Some("no") => Ok(OptionNo::No),
-        Some(
-            v @ ("shared_lane"
-            | "share_busway"
-            | "opposite_share_busway"
-            | "shared"
-            | "shoulder"
-            | "separate"),
-        ) => Err(VariantError::UnimplementedVariant(
+        Some(v @ ("shared" | "shoulder")) => Err(VariantError::UnimplementedVariant(
             k.to_owned().into(),
             v.to_owned(),
         )),
@@ -114,13 +129,145 @@ where
 ///     `Err` if the variant is not known
 type VariantWithMetadata = Result<(OptionNo<(Variant, Option<Opposite>)>, TagKey), VariantError>;
 
-fn cycleway_variant(tags: &Tags, side: Option<WaySide>) -> VariantWithMetadata {
-    let key = if let Some(side) = side {
-        CYCLEWAY + side.as_str()
-    } else {
-        CYCLEWAY
+/// Buffer width for `cycleway:SIDE:buffer=yes`
+const DEFAULT_BUFFER_WIDTH: Metre = Metre::new(0.5_f64);
+
+// Sided keys are precomputed, such that they are not concatenated for every way
+const CYCLEWAY_BOTH: TagKey = TagKey::from_static("cycleway:both");
+const CYCLEWAY_BOTH_WIDTH: TagKey = TagKey::from_static("cycleway:both:width");
+const CYCLEWAY_LEFT: TagKey = TagKey::from_static("cycleway:left");
+const CYCLEWAY_RIGHT: TagKey = TagKey::from_static("cycleway:right");
+const CYCLEWAY_LEFT_WIDTH: TagKey = TagKey::from_static("cycleway:left:width");
+const CYCLEWAY_RIGHT_WIDTH: TagKey = TagKey::from_static("cycleway:right:width");
+const CYCLEWAY_LEFT_ONEWAY: TagKey = TagKey::from_static("cycleway:left:oneway");
+const CYCLEWAY_RIGHT_ONEWAY: TagKey = TagKey::from_static("cycleway:right:oneway");
+
+/// `cycleway:{side}:width`
+fn width_key(side: DrivingSide) -> TagKey {
+    match side {
+        DrivingSide::Left => CYCLEWAY_LEFT_WIDTH,
+        DrivingSide::Right => CYCLEWAY_RIGHT_WIDTH,
+    }
+}
+
+/// Target width from `cycleway:{side}:width`, falling back to `cycleway:both:width`
+fn width(tags: &TagsRef<'_>, side: DrivingSide, warnings: &mut RoadWarnings) -> Option<Width> {
+    tags.get_metre(&width_key(side), warnings)
+        .or_else(|| tags.get_metre(&CYCLEWAY_BOTH_WIDTH, warnings))
+        .map(|width: Metre| Width {
+            target: Infer::Direct(width),
+            ..Default::default()
+        })
+}
+
+/// `cycleway:{side}:oneway`
+fn oneway_key(side: DrivingSide) -> TagKey {
+    match side {
+        DrivingSide::Left => CYCLEWAY_LEFT_ONEWAY,
+        DrivingSide::Right => CYCLEWAY_RIGHT_ONEWAY,
+    }
+}
+
+/// Direction of travel on the cycleway of the given side of the way,
+/// from `cycleway:{side}:oneway=*`, falling back to `oneway:bicycle=*`
+/// and then to the default for that side of the road
+fn direction(
+    tags: &TagsRef<'_>,
+    side: DrivingSide,
+    default: Direction,
+    warnings: &mut RoadWarnings,
+) -> Direction {
+    let key = oneway_key(side);
+    match tags.get(&key) {
+        Some("yes") => Direction::Forward,
+        Some("-1") => Direction::Backward,
+        Some("no") => Direction::Both,
+        Some(value) => {
+            warnings.push(TagsToLanesMsg::unsupported_tag(key, value));
+            default
+        },
+        None => match tags.get(&ONEWAY_BICYCLE) {
+            Some("yes") => Direction::Forward,
+            Some("-1") => Direction::Backward,
+            Some("no") => Direction::Both,
+            _ => default,
+        },
+    }
+}
+
+/// The most specific of `cycleway:{side}:{attribute}`, `cycleway:both:{attribute}`
+/// and `cycleway:{attribute}`
+pub(in crate::transform::tags_to_lanes) fn attribute<T: FromStr>(
+    tags: &TagsRef<'_>,
+    side: DrivingSide,
+    attribute: &str,
+    warnings: &mut RoadWarnings,
+) -> Option<T> {
+    let key = [
+        CYCLEWAY + side.tag() + attribute,
+        CYCLEWAY_BOTH + attribute,
+        CYCLEWAY + attribute,
+    ]
+    .into_iter()
+    .find(|key| tags.get(key).is_some())?;
+    tags.get_parsed(&key, warnings)
+}
+
+/// Width of a buffer, `Some(Infer::None)` if `=no` and `None` if untagged
+fn buffer_width(
+    tags: &TagsRef<'_>,
+    key: &TagKey,
+    warnings: &mut RoadWarnings,
+) -> Option<Infer<Metre>> {
+    match tags.get(key) {
+        None => None,
+        Some("no") => Some(Infer::None),
+        Some("yes") => Some(Infer::Default(DEFAULT_BUFFER_WIDTH)),
+        Some(_) => Some(Infer::direct(tags.get_metre(key, warnings))),
+    }
+}
+
+/// Painted buffers of the cycle lane on the given side of the way,
+/// from `cycleway:{side}:buffer=*` and `cycleway:{side}:buffer:{left,right}=*`,
+/// falling back to `cycleway:both:buffer*=*`.
+///
+/// Without a `:left` or `:right` suffix, the buffer is towards the centre of the road.
+pub(in crate::transform::tags_to_lanes) fn buffer(
+    tags: &TagsRef<'_>,
+    side: DrivingSide,
+    warnings: &mut RoadWarnings,
+) -> Buffer {
+    let mut sided = |suffix: Option<DrivingSide>| {
+        [CYCLEWAY + side.tag(), CYCLEWAY_BOTH]
+            .into_iter()
+            .find_map(|key| {
+                let key = key + "buffer";
+                let key = match suffix {
+                    Some(suffix) => key + suffix.tag(),
+                    None => key,
+                };
+                buffer_width(tags, &key, warnings)
+            })
+    };
+    let inside = sided(Some(side.opposite()))
+        .or_else(|| sided(None))
+        .unwrap_or(Infer::None);
+    let outside = sided(Some(side)).unwrap_or(Infer::None);
+    Buffer { inside, outside }
+}
+
+fn cycleway_variant(
+    tags: &TagsRef<'_>,
+    side: Option<WaySide>,
+    warnings: &mut RoadWarnings,
+) -> VariantWithMetadata {
+    let key = match side {
+        None => CYCLEWAY,
+        Some(WaySide::Both) => CYCLEWAY_BOTH,
+        Some(WaySide::Left) => CYCLEWAY_LEFT,
+        Some(WaySide::Right) => CYCLEWAY_RIGHT,
     };
-    let variant = get_variant(tags, &key)?;
+    let variant = get_variant(tags, &key, warnings)?;
     Ok((variant, key))
 }
 
@@ -139,6 +286,53 @@ pub(in crate::transform::tags_to_lanes) enum Location {
     Both { forward: Way, backward: Way },
 }
 
+impl Location {
+    /// The forward and backward ways
+    fn split(self) -> (Option<Way>, Option<Way>) {
+        match self {
+            Self::None => (None, None),
+            Self::Forward(way) => (Some(way), None),
+            Self::Backward(way) => (None, Some(way)),
+            Self::Both { forward, backward } => (Some(forward), Some(backward)),
+        }
+    }
+
+    fn join(forward: Option<Way>, backward: Option<Way>) -> Self {
+        match (forward, backward) {
+            (None, None) => Self::None,
+            (Some(forward), None) => Self::Forward(forward),
+            (None, Some(backward)) => Self::Backward(backward),
+            (Some(forward), Some(backward)) => Self::Both { forward, backward },
+        }
+    }
+}
+
+/// The way of a side from the first, most specific, of the schemes covering the side,
+/// warning about less specific schemes that disagree
+fn resolve(
+    candidates: Vec<(Option<Way>, Vec<TagKey>)>,
+    tags: &TagsRef<'_>,
+    keys: &mut Vec<TagKey>,
+    warnings: &mut RoadWarnings,
+) -> Option<Way> {
+    let mut candidates = candidates.into_iter();
+    let (way, way_keys) = candidates.next()?;
+    for (other, other_keys) in candidates {
+        if way.as_ref().map(|way| way.variant) != other.as_ref().map(|other| other.variant) {
+            warnings.push(TagsToLanesMsg::unsupported(
+                "conflicting cycleway tags, the most specific is used",
+                tags.subset(way_keys.iter().chain(other_keys.iter())),
+            ));
+        }
+    }
+    for key in way_keys {
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+    way
+}
+
 /// Bicycle lane or track scheme
 #[derive(Debug, PartialEq)]
 pub(in crate::transform::tags_to_lanes) struct Scheme {
@@ -147,8 +341,16 @@ pub(in crate::transform::tags_to_lanes) struct Scheme {
 }
 
 impl Scheme {
+    /// Combine the `cycleway=*`, `cycleway:both=*` and `cycleway:SIDE=*` schemes
+    ///
+    /// Conflicting tags are resolved per side, in order of precedence:
+    /// 1. `cycleway:left=*` or `cycleway:right=*`
+    /// 2. `cycleway:both=*`
+    /// 3. `cycleway=*`
+    ///
+    /// with a warning for each less specific tag that is overridden.
     pub(in crate::transform::tags_to_lanes) fn from_tags(
-        tags: &Tags,
+        tags: &TagsRef<'_>,
         locale: &Locale,
         road_oneway: Oneway,
         warnings: &mut RoadWarnings,
@@ -161,98 +363,30 @@ impl Scheme {
         let scheme_cycleway_backward =
             Self::from_tags_cycleway_backward(tags, locale, road_oneway, warnings)?;
 
-        match (
-            scheme_cycleway,
-            scheme_cycleway_both,
-            (scheme_cycleway_forward, scheme_cycleway_backward),
-        ) {
-            (
-                Some(scheme_cycleway),
-                scheme_cycleway_other_1,
-                (scheme_cycleway_other_2, scheme_cycleway_other_3),
-            )
-            | (
-                scheme_cycleway_other_1,
-                Some(scheme_cycleway),
-                (scheme_cycleway_other_2, scheme_cycleway_other_3),
-            ) => {
-                if let Some(scheme_cycleway_other_1) = scheme_cycleway_other_1 {
-                    warnings.push(TagsToLanesMsg::unsupported_tags(
-                        tags.subset(
-                            scheme_cycleway
-                                .keys
-                                .iter()
-                                .chain(scheme_cycleway_other_1.keys.iter()),
-                        ),
-                    ));
-                }
-                if let Some(scheme_cycleway_other_2) = scheme_cycleway_other_2 {
-                    warnings.push(TagsToLanesMsg::unsupported_tags(
-                        tags.subset(
-                            scheme_cycleway
-                                .keys
-                                .iter()
-                                .chain(scheme_cycleway_other_2.keys.iter()),
-                        ),
-                    ));
-                }
-                if let Some(scheme_cycleway_other_3) = scheme_cycleway_other_3 {
-                    warnings.push(TagsToLanesMsg::unsupported_tags(
-                        tags.subset(
-                            scheme_cycleway
-                                .keys
-                                .iter()
-                                .chain(scheme_cycleway_other_3.keys.iter()),
-                        ),
-                    ));
-                }
-                Ok(scheme_cycleway)
-            },
-            (
-                None,
-                None,
-                (Some(scheme_cycleway_direction), None) | (None, Some(scheme_cycleway_direction)),
-            ) => Ok(scheme_cycleway_direction),
-            (None, None, (Some(scheme_cycleway_forward), Some(scheme_cycleway_backward))) => {
-                match (scheme_cycleway_forward, scheme_cycleway_backward) {
-                    (
-                        scheme_cycleway_forward,
-                        Self {
-                            location: Location::None,
-                            ..
-                        },
-                    ) => Ok(scheme_cycleway_forward),
-                    (
-                        Self {
-                            location: Location::None,
-                            ..
-                        },
-                        scheme_cycleway_backward,
-                    ) => Ok(scheme_cycleway_backward),
-                    (
-                        Self {
-                            location: Location::Forward(forward),
-                            keys: mut forward_keys,
-                        },
-                        Self {
-                            location: Location::Backward(backward),
-                            keys: mut backward_keys,
-                        },
-                    ) => {
-                        forward_keys.append(&mut backward_keys);
-                        Ok(Self {
-                            location: Location::Both { forward, backward },
-                            keys: forward_keys,
-                        })
-                    },
-                    _ => panic!("cannot join cycleways"),
-                }
-            },
-            (None, None, (None, None)) => Ok(Self {
-                location: Location::None,
-                keys: vec![],
-            }),
+        // Candidates of each side, most specific first
+        let mut forward_candidates = Vec::new();
+        let mut backward_candidates = Vec::new();
+        if let Some(scheme) = scheme_cycleway_forward {
+            forward_candidates.push((scheme.location.split().0, scheme.keys));
         }
+        if let Some(scheme) = scheme_cycleway_backward {
+            backward_candidates.push((scheme.location.split().1, scheme.keys));
+        }
+        for scheme in [scheme_cycleway_both, scheme_cycleway]
+            .into_iter()
+            .flatten()
+        {
+            let (forward, backward) = scheme.location.split();
+            forward_candidates.push((forward, scheme.keys.clone()));
+            backward_candidates.push((backward, scheme.keys));
+        }
+        let mut keys = Vec::new();
+        let forward = resolve(forward_candidates, tags, &mut keys, warnings);
+        let backward = resolve(backward_candidates, tags, &mut keys, warnings);
+        Ok(Self {
+            location: Location::join(forward, backward),
+            keys,
+        })
     }
 
     /// Handle `cycleway=*` tags
@@ -260,12 +394,12 @@ impl Scheme {
     /// `None` if unknown
     #[allow(clippy::unnecessary_wraps, clippy::panic_in_result_fn)]
     pub(in crate::transform::tags_to_lanes) fn from_tags_cycleway(
-        tags: &Tags,
+        tags: &TagsRef<'_>,
         locale: &Locale,
         road_oneway: Oneway,
         warnings: &mut RoadWarnings,
     ) -> Result<Option<Self>, TagsToLanesMsg> {
-        match cycleway_variant(tags, None) {
+        match cycleway_variant(tags, None, warnings) {
             Ok((OptionNo::Some((variant, opposite)), key)) => {
                 if road_oneway.into() {
                     if opposite.is_none() {
@@ -286,10 +420,7 @@ impl Scheme {
                                         CYCLEWAY + locale.driving_side.opposite().tag(),
                                         variant.to_string(),
                                     ),
-                                    (
-                                        CYCLEWAY + locale.driving_side.opposite().tag() + "oneway",
-                                        "-1".to_owned(),
-                                    ),
+                                    (oneway_key(locale.driving_side.opposite()), "-1".to_owned()),
                                 ])
                                 .unwrap(),
                             ));
@@ -343,12 +474,12 @@ impl Scheme {
     /// `None` if unknown
     #[allow(clippy::unnecessary_wraps, clippy::panic_in_result_fn)]
     pub(in crate::transform::tags_to_lanes) fn from_tags_cycleway_both(
-        tags: &Tags,
-        _locale: &Locale,
+        tags: &TagsRef<'_>,
+        locale: &Locale,
         _road_oneway: Oneway,
         warnings: &mut RoadWarnings,
     ) -> Option<Self> {
-        match cycleway_variant(tags, Some(WaySide::Both)) {
+        match cycleway_variant(tags, Some(WaySide::Both), warnings) {
             Ok((OptionNo::Some((variant, opposite)), key)) => {
                 if let Some(Opposite) = opposite {
                     warnings.push(TagsToLanesMsg::unsupported_tags(
@@ -360,12 +491,12 @@ impl Scheme {
                         forward: Way {
                             variant,
                             direction: Direction::Forward,
-                            width: None,
+                            width: width(tags, locale.driving_side, warnings),
                         },
                         backward: Way {
                             variant,
                             direction: Direction::Backward,
-                            width: None,
+                            width: width(tags, locale.driving_side.opposite(), warnings),
                         },
                     },
                     keys: vec![key],
@@ -386,42 +517,23 @@ impl Scheme {
     /// Handle `cycleway:FORWARD=*` tags
     /// `Location::None` if `=no`
     /// `None` if unknown
-    #[allow(clippy::unnecessary_wraps, clippy::panic_in_result_fn)]
+    #[allow(clippy::unnecessary_wraps)]
     pub(in crate::transform::tags_to_lanes) fn from_tags_cycleway_forward(
-        tags: &Tags,
+        tags: &TagsRef<'_>,
         locale: &Locale,
         _road_oneway: Oneway,
         warnings: &mut RoadWarnings,
     ) -> Result<Option<Self>, TagsToLanesMsg> {
-        match cycleway_variant(tags, Some(locale.driving_side.into())) {
-            Ok((OptionNo::Some((variant, _opposite)), key)) => {
-                let width = tags
-                    .get_parsed(&(CYCLEWAY + locale.driving_side.tag() + "width"), warnings)
-                    .map(|w| Width {
-                        target: Infer::Direct(Metre::new(w)),
-                        ..Default::default()
-                    });
-                if tags.is(&(CYCLEWAY + locale.driving_side.tag() + "oneway"), "no")
-                    || tags.is("oneway:bicycle", "no")
-                {
-                    return Ok(Some(Self {
-                        location: Location::Forward(Way {
-                            variant,
-                            direction: Direction::Both,
-                            width,
-                        }),
-                        keys: vec![key],
-                    }));
-                }
-                Ok(Some(Self {
-                    location: Location::Forward(Way {
-                        variant,
-                        direction: Direction::Forward,
-                        width,
-                    }),
-                    keys: vec![key],
-                }))
-            },
+        let side = locale.driving_side;
+        match cycleway_variant(tags, Some(side.into()), warnings) {
+            Ok((OptionNo::Some((variant, _opposite)), root_key)) => Ok(Some(Self {
+                location: Location::Forward(Way {
+                    variant,
+                    direction: direction(tags, side, Direction::Forward, warnings),
+                    width: width(tags, side, warnings),
+                }),
+                keys: vec![root_key, width_key(side), oneway_key(side), ONEWAY_BICYCLE],
+            })),
             Ok((OptionNo::No, key)) => Ok(Some(Self {
                 location: Location::None,
                 keys: vec![key],
@@ -437,68 +549,36 @@ impl Scheme {
     /// Handle `cycleway:BACKWARD=*` tags
     /// `Location::None` if `=no`
     /// `None` if unknown
-    #[allow(clippy::unnecessary_wraps, clippy::panic_in_result_fn)]
+    #[allow(clippy::unnecessary_wraps)]
     pub(in crate::transform::tags_to_lanes) fn from_tags_cycleway_backward(
-        tags: &Tags,
+        tags: &TagsRef<'_>,
         locale: &Locale,
         road_oneway: Oneway,
         warnings: &mut RoadWarnings,
     ) -> Result<Option<Self>, TagsToLanesMsg> {
-        match cycleway_variant(tags, Some(locale.driving_side.opposite().into())) {
+        let side = locale.driving_side.opposite();
+        match cycleway_variant(tags, Some(side.into()), warnings) {
             Ok((OptionNo::Some((variant, _opposite)), root_key)) => {
-                let width_key = CYCLEWAY + locale.driving_side.opposite().tag() + "width";
-                let width = tags.get_parsed(&width_key, warnings).map(|w| Width {
-                    target: Infer::Direct(Metre::new(w)),
-                    ..Default::default()
-                });
-                let oneway_key = CYCLEWAY + locale.driving_side.opposite().tag() + "oneway";
-                Ok(Some(if tags.is(&oneway_key, "yes") {
-                    Self {
-                        location: Location::Backward(Way {
-                            variant,
-                            direction: Direction::Forward,
-                            width,
-                        }),
-                        keys: vec![root_key, width_key, oneway_key],
-                    }
-                } else if tags.is(&oneway_key, "-1") {
-                    Self {
-                        location: Location::Backward(Way {
-                            variant,
-                            direction: Direction::Backward,
-                            width,
-                        }),
-                        keys: vec![root_key, width_key, oneway_key],
-                    }
-                } else if tags.is(&oneway_key, "no") || tags.is("oneway:bicycle", "no") {
-                    Self {
-                        location: Location::Backward(Way {
-                            variant,
-                            direction: Direction::Both,
-                            width,
-                        }),
-                        keys: vec![root_key, width_key, oneway_key],
-                    }
-                } else if road_oneway.into() {
+                let default = if road_oneway.into() {
                     // A oneway road with a cycleway on the wrong side
-                    Self {
-                        location: Location::Backward(Way {
-                            variant,
-                            direction: Direction::Forward,
-                            width,
-                        }),
-                        keys: vec![root_key, width_key, oneway_key, Oneway::KEY],
-                    }
+                    Direction::Forward
                 } else {
                     // A contraflow bicycle lane
-                    Self {
-                        location: Location::Backward(Way {
-                            variant,
-                            direction: Direction::Backward,
-                            width,
-                        }),
-                        keys: vec![root_key, width_key, oneway_key, Oneway::KEY],
-                    }
+                    Direction::Backward
+                };
+                Ok(Some(Self {
+                    location: Location::Backward(Way {
+                        variant,
+                        direction: direction(tags, side, default, warnings),
+                        width: width(tags, side, warnings),
+                    }),
+                    keys: vec![
+                        root_key,
+                        width_key(side),
+                        oneway_key(side),
+                        ONEWAY_BICYCLE,
+                        Oneway::KEY,
+                    ],
                 }))
             },
             Ok((OptionNo::No, key)) => Ok(Some(Self {
@@ -518,12 +598,15 @@ impl Scheme {
 mod tests {
     use osm_tags::Tags;
 
-    use super::Scheme;
-    use crate::locale::Locale;
+    use super::{oneway_key, Scheme};
+    use crate::locale::{DrivingSide, Locale};
+    use crate::metric::Metre;
     use crate::road::Direction;
+    use crate::transform::tags::CYCLEWAY;
     use crate::transform::tags_to_lanes::error::TagsToLanesIssue;
     use crate::transform::tags_to_lanes::modes::bicycle::cycleway::{Location, Variant, Way};
     use crate::transform::tags_to_lanes::oneway::Oneway;
+    use crate::transform::tags_to_lanes::Infer;
     use crate::transform::RoadWarnings;
 
     #[test]
@@ -682,6 +765,160 @@ mod tests {
         );
     }
 
+    #[test]
+    fn oneway_shared_lane() {
+        let mut warnings = RoadWarnings::default();
+        let scheme = Scheme::from_tags(
+            &Tags::from_pair("cycleway", "shared_lane"),
+            &Locale::builder().build(),
+            Oneway::Yes,
+            &mut warnings,
+        )
+        .unwrap();
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert_eq!(
+            scheme.location,
+            Location::Forward(Way {
+                variant: Variant::SharedLane,
+                direction: Direction::Forward,
+                width: None,
+            })
+        );
+    }
+
+    // `cycleway:SIDE:oneway=*` on either side of a oneway or two-way road
+    #[test]
+    fn side_oneway() {
+        for driving_side in [DrivingSide::Right, DrivingSide::Left] {
+            let locale = Locale::builder().driving_side(driving_side).build();
+            for road_oneway in [Oneway::Yes, Oneway::No] {
+                for (side, forward_side) in [(driving_side, true), (driving_side.opposite(), false)]
+                {
+                    for (value, direction) in [
+                        ("yes", Direction::Forward),
+                        ("-1", Direction::Backward),
+                        ("no", Direction::Both),
+                    ] {
+                        let tags = Tags::from_pairs([
+                            (CYCLEWAY + side.tag(), "lane"),
+                            (oneway_key(side), value),
+                        ])
+                        .unwrap();
+                        let mut warnings = RoadWarnings::default();
+                        let scheme =
+                            Scheme::from_tags(&tags, &locale, road_oneway, &mut warnings).unwrap();
+                        assert!(warnings.is_empty(), "{:?}", warnings);
+                        let way = Way {
+                            variant: Variant::Lane,
+                            direction,
+                            width: None,
+                        };
+                        assert_eq!(
+                            scheme.location,
+                            if forward_side {
+                                Location::Forward(way)
+                            } else {
+                                Location::Backward(way)
+                            },
+                            "{:?}",
+                            tags
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    // Without `cycleway:SIDE:oneway=*`, from `oneway:bicycle=*` or the road
+    #[test]
+    fn oneway_bicycle() {
+        let locale = Locale::builder().driving_side(DrivingSide::Left).build();
+        for (road_oneway, oneway_bicycle, direction) in [
+            (Oneway::Yes, None, Direction::Forward),
+            (Oneway::No, None, Direction::Backward),
+            (Oneway::Yes, Some("no"), Direction::Both),
+            (Oneway::No, Some("yes"), Direction::Forward),
+        ] {
+            let mut tags = Tags::from_pair("cycleway:right", "lane");
+            if let Some(oneway_bicycle) = oneway_bicycle {
+                tags.checked_insert("oneway:bicycle", oneway_bicycle)
+                    .unwrap();
+            }
+            let mut warnings = RoadWarnings::default();
+            let scheme = Scheme::from_tags(&tags, &locale, road_oneway, &mut warnings).unwrap();
+            assert!(warnings.is_empty(), "{:?}", warnings);
+            assert_eq!(
+                scheme.location,
+                Location::Backward(Way {
+                    variant: Variant::Lane,
+                    direction,
+                    width: None,
+                }),
+                "{:?}",
+                tags
+            );
+        }
+    }
+
+    #[test]
+    fn separate() {
+        let mut warnings = RoadWarnings::default();
+        let scheme = Scheme::from_tags(
+            &Tags::from_pair("cycleway:right", "separate"),
+            &Locale::builder().build(),
+            Oneway::No,
+            &mut warnings,
+        )
+        .unwrap();
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert_eq!(
+            scheme.location,
+            Location::Forward(Way {
+                variant: Variant::Separate,
+                direction: Direction::Forward,
+                width: None,
+            })
+        );
+    }
+
+    #[test]
+    fn oneway_opposite_share_busway() {
+        let mut warnings = RoadWarnings::default();
+        let scheme = Scheme::from_tags(
+            &Tags::from_pair("cycleway", "opposite_share_busway"),
+            &Locale::builder().build(),
+            Oneway::Yes,
+            &mut warnings,
+        )
+        .unwrap();
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert_eq!(
+            scheme.location,
+            Location::Backward(Way {
+                variant: Variant::SharedBus,
+                direction: Direction::Backward,
+                width: None,
+            })
+        );
+    }
+
+    #[test]
+    fn buffer() {
+        let mut warnings = RoadWarnings::default();
+        let buffer = super::buffer(
+            &Tags::from_pairs([
+                ("cycleway:right:buffer", "0.6"),
+                ("cycleway:both:buffer:right", "yes"),
+            ])
+            .unwrap(),
+            DrivingSide::Right,
+            &mut warnings,
+        );
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert_eq!(buffer.inside, Infer::Direct(Metre::new(0.6_f64)));
+        assert_eq!(buffer.outside, Infer::Default(super::DEFAULT_BUFFER_WIDTH));
+    }
+
     // cycleway=opposite only applies to oneway
     #[test]
     fn err_opposite_twoway() {
@@ -706,12 +943,41 @@ mod tests {
         assert!(!warnings.is_empty(), "{:?}", scheme);
     }
 
+    #[test]
+    fn warn_multiple_values() {
+        let tags = Tags::from_pair("cycleway:right", "lane;track");
+        let mut warnings = RoadWarnings::default();
+        let scheme =
+            Scheme::from_tags(&tags, &Locale::builder().build(), Oneway::No, &mut warnings)
+                .unwrap();
+        assert_eq!(warnings.as_slice().len(), 1);
+        if let TagsToLanesIssue::Ambiguous {
+            tags: Some(ambiguous_tags),
+            ..
+        } = &warnings.as_slice().get(0).unwrap().issue
+        {
+            assert_eq!(ambiguous_tags.to_str_pairs(), tags.to_str_pairs());
+        } else {
+            panic!("wrong TagsToLanesIssue")
+        }
+        // The first value takes precedence
+        assert_eq!(
+            scheme.location,
+            Location::Forward(Way {
+                variant: Variant::Lane,
+                direction: Direction::Forward,
+                width: None,
+            })
+        );
+    }
+
     #[test]
     fn warn_no_lane() {
         let tags = Tags::from_pairs([("cycleway", "no"), ("cycleway:left", "lane")]).unwrap();
         let mut warnings = RoadWarnings::default();
-        let _scheme =
-            Scheme::from_tags(&tags, &Locale::builder().build(), Oneway::No, &mut warnings);
+        let scheme =
+            Scheme::from_tags(&tags, &Locale::builder().build(), Oneway::No, &mut warnings)
+                .unwrap();
         assert_eq!(warnings.as_slice().len(), 1);
         if let TagsToLanesIssue::Unsupported {
             tags: Some(unsupported_tags),
@@ -722,14 +988,24 @@ mod tests {
         } else {
             panic!("wrong TagsToLanesIssue")
         }
+        // The side overrides the bare key
+        assert_eq!(
+            scheme.location,
+            Location::Backward(Way {
+                variant: Variant::Lane,
+                direction: Direction::Backward,
+                width: None,
+            })
+        );
     }
 
     #[test]
     fn warn_track_no() {
         let tags = Tags::from_pairs([("cycleway", "track"), ("cycleway:left", "no")]).unwrap();
         let mut warnings = RoadWarnings::default();
-        let _scheme =
-            Scheme::from_tags(&tags, &Locale::builder().build(), Oneway::No, &mut warnings);
+        let scheme =
+            Scheme::from_tags(&tags, &Locale::builder().build(), Oneway::No, &mut warnings)
+                .unwrap();
         assert_eq!(warnings.as_slice().len(), 1);
         if let TagsToLanesIssue::Unsupported {
             tags: Some(unsupported_tags),
@@ -740,15 +1016,25 @@ mod tests {
         } else {
             panic!("wrong TagsToLanesIssue")
         }
+        // The side overrides the bare key
+        assert_eq!(
+            scheme.location,
+            Location::Forward(Way {
+                variant: Variant::Track,
+                direction: Direction::Forward,
+                width: None,
+            })
+        );
     }
 
     #[test]
-    fn err_lane_track() {
+    fn warn_lane_track() {
         let tags =
             Tags::from_pairs([("cycleway:both", "lane"), ("cycleway:right", "track")]).unwrap();
         let mut warnings = RoadWarnings::default();
-        let _scheme =
-            Scheme::from_tags(&tags, &Locale::builder().build(), Oneway::No, &mut warnings);
+        let scheme =
+            Scheme::from_tags(&tags, &Locale::builder().build(), Oneway::No, &mut warnings)
+                .unwrap();
         assert_eq!(warnings.as_slice().len(), 1);
         if let TagsToLanesIssue::Unsupported {
             tags: Some(unsupported_tags),
@@ -759,5 +1045,21 @@ mod tests {
         } else {
             panic!("wrong TagsToLanesIssue")
         }
+        // The side overrides both sides
+        assert_eq!(
+            scheme.location,
+            Location::Both {
+                forward: Way {
+                    variant: Variant::Track,
+                    direction: Direction::Forward,
+                    width: None,
+                },
+                backward: Way {
+                    variant: Variant::Lane,
+                    direction: Direction::Backward,
+                    width: None,
+                },
+            }
+        );
     }
 }
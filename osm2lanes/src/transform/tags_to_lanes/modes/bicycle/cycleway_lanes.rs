@@ -1,20 +1,234 @@
-use osm_tag_schemes::LaneDependentAccess;
-use osm_tags::{TagKey, Tags};
+use alloc::vec::Vec;
 
-use crate::locale::Locale;
-use crate::transform::{RoadWarnings, TagsToLanesMsg};
+use osm_tag_schemes::{keys, Access, LaneAccess, LaneCycleway, LaneDependent, LaneDependentAccess};
+use osm_tags::TagsRef;
 
-pub(in crate::transform::tags_to_lanes) struct Scheme(Option<LaneDependentAccess>);
+use super::cycleway::{self, Variant};
+use crate::locale::{DrivingSide, Locale};
+use crate::road::{AccessAndDirection, Direction};
+use crate::transform::tags_to_lanes::road::Buffer;
+use crate::transform::tags_to_lanes::{Infer, LaneBuilder, RoadBuilder, TagsToLanesMsg};
+use crate::transform::RoadWarnings;
+
+/// A single lane of `cycleway:lanes=*` or `bicycle:lanes=*`
+#[derive(Debug, PartialEq, Eq)]
+enum Entry {
+    /// A cycle lane, which is not counted in `lanes=*`
+    Cycle,
+    /// A motor or bus lane shared with bicycles
+    Shared(Variant),
+    /// Access for bicycles to the lane
+    Access(Access),
+    None,
+}
+
+impl From<LaneCycleway> for Entry {
+    fn from(cycleway: LaneCycleway) -> Self {
+        match cycleway {
+            LaneCycleway::Lane => Self::Cycle,
+            LaneCycleway::SharedLane => Self::Shared(Variant::SharedLane),
+            LaneCycleway::ShareBusway => Self::Shared(Variant::SharedBus),
+            LaneCycleway::None | LaneCycleway::No => Self::None,
+        }
+    }
+}
+
+impl From<LaneAccess> for Entry {
+    fn from(access: LaneAccess) -> Self {
+        match access {
+            LaneAccess::Designated => Self::Cycle,
+            LaneAccess::Yes => Self::Access(Access::Yes),
+            LaneAccess::No => Self::Access(Access::No),
+            LaneAccess::None => Self::None,
+        }
+    }
+}
+
+/// Per lane cycling infrastructure, from `cycleway:lanes=*` or `bicycle:lanes=*`
+///
+/// The lanes of each direction are left to right in the direction of travel.
+#[derive(Debug, Default)]
+pub(in crate::transform::tags_to_lanes) struct Scheme {
+    forward: Option<Vec<Entry>>,
+    backward: Option<Vec<Entry>>,
+}
 
 impl Scheme {
     pub(crate) fn from_tags(
-        tags: &Tags,
-        _locale: &Locale,
-        _warnings: &mut RoadWarnings,
+        tags: &TagsRef<'_>,
+        locale: &Locale,
+        road: &RoadBuilder,
+        warnings: &mut RoadWarnings,
     ) -> Result<Self, TagsToLanesMsg> {
-        Ok(Self(LaneDependentAccess::from_tags(
-            tags,
-            &TagKey::from_static("cycleway:lanes"),
-        )?))
+        match (
+            LaneDependent::<LaneCycleway>::from_tags(tags, &keys::CYCLEWAY_LANES)?,
+            LaneDependentAccess::from_tags(tags, &keys::BICYCLE_LANES)?,
+        ) {
+            (None, None) => Ok(Self::default()),
+            (Some(cycleway), None) => Ok(Self::from_lanes(cycleway, locale, road, warnings)),
+            (None, Some(bicycle)) => Ok(Self::from_lanes(bicycle, locale, road, warnings)),
+            (Some(_), Some(_)) => Err(TagsToLanesMsg::unsupported(
+                "more than one bicycle lanes scheme used",
+                tags.subset(&[keys::CYCLEWAY_LANES, keys::BICYCLE_LANES]),
+            )),
+        }
+    }
+
+    fn from_lanes<T: Into<Entry>>(
+        lanes: LaneDependent<T>,
+        locale: &Locale,
+        road: &RoadBuilder,
+        warnings: &mut RoadWarnings,
+    ) -> Self {
+        let entries = |lanes: Vec<T>| -> Vec<Entry> { lanes.into_iter().map(Into::into).collect() };
+        match lanes {
+            LaneDependent::Forward(forward) => Self {
+                forward: Some(entries(forward)),
+                backward: None,
+            },
+            LaneDependent::Backward(backward) => Self {
+                forward: None,
+                backward: Some(entries(backward)),
+            },
+            LaneDependent::ForwardBackward { forward, backward } => Self {
+                forward: Some(entries(forward)),
+                backward: Some(entries(backward)),
+            },
+            LaneDependent::LeftToRight(lanes) => {
+                let mut lanes = entries(lanes);
+                if road.backward_len() == 0 {
+                    return Self {
+                        forward: Some(lanes),
+                        backward: None,
+                    };
+                }
+                // Without knowing which direction a cycle lane belongs to,
+                // the lanes can only be split between the directions by the lane counts
+                if lanes.contains(&Entry::Cycle) || lanes.len() != road.len() {
+                    warnings.push(TagsToLanesMsg::unsupported_str(
+                        "cycle lanes in lanes of both directions, use :lanes:forward and :lanes:backward",
+                    ));
+                    return Self::default();
+                }
+                // The lanes on the left are in the opposite direction of travel
+                let (mut left, right) = match locale.driving_side {
+                    DrivingSide::Right => {
+                        let right = lanes.split_off(road.backward_len());
+                        (lanes, right)
+                    },
+                    DrivingSide::Left => {
+                        let right = lanes.split_off(road.forward_len());
+                        (lanes, right)
+                    },
+                };
+                left.reverse();
+                match locale.driving_side {
+                    DrivingSide::Right => Self {
+                        forward: Some(right),
+                        backward: Some(left),
+                    },
+                    DrivingSide::Left => Self {
+                        forward: Some(left),
+                        backward: Some(right),
+                    },
+                }
+            },
+        }
+    }
+
+    /// Whether the scheme adds cycle lanes
+    pub(crate) fn has_cycle_lanes(&self) -> bool {
+        self.forward
+            .iter()
+            .chain(self.backward.iter())
+            .any(|lanes| lanes.contains(&Entry::Cycle))
+    }
+
+    /// Insert the cycle lanes and apply the sharing and access to the existing lanes
+    pub(crate) fn apply(
+        self,
+        tags: &TagsRef<'_>,
+        locale: &Locale,
+        road: &mut RoadBuilder,
+        warnings: &mut RoadWarnings,
+    ) -> Result<(), TagsToLanesMsg> {
+        if let Some(forward) = self.forward {
+            apply_direction(forward, Direction::Forward, tags, locale, road, warnings)?;
+        }
+        if let Some(backward) = self.backward {
+            apply_direction(backward, Direction::Backward, tags, locale, road, warnings)?;
+        }
+        Ok(())
+    }
+}
+
+fn apply_direction(
+    mut entries: Vec<Entry>,
+    direction: Direction,
+    tags: &TagsRef<'_>,
+    locale: &Locale,
+    road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
+) -> Result<(), TagsToLanesMsg> {
+    let len = match direction {
+        Direction::Backward => road.backward_len(),
+        _ => road.forward_len(),
+    };
+    if entries
+        .iter()
+        .filter(|entry| **entry != Entry::Cycle)
+        .count()
+        != len
+    {
+        warnings.push(TagsToLanesMsg::unsupported(
+            "lane count mismatch",
+            tags.subset(&[
+                keys::CYCLEWAY_LANES,
+                keys::CYCLEWAY_LANES + "forward",
+                keys::CYCLEWAY_LANES + "backward",
+                keys::BICYCLE_LANES,
+                keys::BICYCLE_LANES + "forward",
+                keys::BICYCLE_LANES + "backward",
+            ]),
+        ));
+        return Ok(());
+    }
+    // From the centre outwards
+    if locale.driving_side == DrivingSide::Left {
+        entries.reverse();
+    }
+    for (index, entry) in entries.into_iter().enumerate() {
+        if entry == Entry::Cycle {
+            let lane = LaneBuilder::cycle(
+                cycleway::Way {
+                    variant: Variant::Lane,
+                    direction,
+                    width: None,
+                },
+                Buffer::default(),
+            );
+            match direction {
+                Direction::Backward => road.insert_backward(index, lane),
+                _ => road.insert_forward(index, lane),
+            }
+            continue;
+        }
+        let lane = match direction {
+            Direction::Backward => road.backward_mut(index),
+            _ => road.forward_mut(index),
+        };
+        match entry {
+            Entry::Shared(variant) => super::share(lane, variant)?,
+            Entry::Access(access) => {
+                if let Some(lane) = lane {
+                    lane.access.bicycle = Infer::Direct(AccessAndDirection {
+                        access,
+                        direction: None,
+                    });
+                }
+            },
+            Entry::Cycle | Entry::None => {},
+        }
     }
+    Ok(())
 }
@@ -1,9 +1,14 @@
-use osm_tag_schemes::Access;
-use osm_tags::Tags;
+use alloc::string::String;
 
-use crate::locale::Locale;
+use osm_tag_schemes::keys::{self, HIGHWAY};
+use osm_tag_schemes::{Access, CycleStreet, CyclewayLane, Surface};
+use osm_tags::TagsRef;
+
+use crate::locale::{DrivingSide, Locale};
 use crate::road::{AccessAndDirection, Designated, Direction};
-use crate::transform::tags_to_lanes::road::LaneType;
+use crate::transform::tags::CYCLEWAY;
+use crate::transform::tags_to_lanes::oneway::Oneway;
+use crate::transform::tags_to_lanes::road::{Buffer, LaneType};
 use crate::transform::tags_to_lanes::{Infer, LaneBuilder, RoadBuilder, TagsToLanesMsg};
 use crate::transform::RoadWarnings;
 
@@ -12,55 +17,232 @@ pub(in crate::transform::tags_to_lanes) mod cycleway;
 mod cycleway_lanes;
 
 impl LaneBuilder {
-    fn cycle(way: cycleway::Way) -> Self {
+    fn cycle(way: cycleway::Way, buffer: Buffer) -> Self {
         Self {
             r#type: Infer::Direct(LaneType::Travel),
             direction: Infer::Direct(way.direction),
             designated: Infer::Direct(Designated::Bicycle),
             width: way.width.unwrap_or_default(),
+            buffer,
             cycleway_variant: Some(way.variant),
             ..Default::default()
         }
     }
+    /// A cycle lane or track on the given side of the way,
+    /// with the `lane=*`, `surface=*` and `colour=*` of `cycleway:<side>:*`
+    fn cycleway(
+        tags: &TagsRef<'_>,
+        side: DrivingSide,
+        way: cycleway::Way,
+        buffer: Buffer,
+        warnings: &mut RoadWarnings,
+    ) -> Self {
+        let variant = way.variant;
+        let mut cycle = Self::cycle(way, buffer);
+        if variant == cycleway::Variant::Lane {
+            if let Some(lane) = cycleway::attribute::<CyclewayLane>(tags, side, "lane", warnings) {
+                cycle.cycleway_lane = Infer::Direct(lane);
+            }
+        }
+        if let Some(surface) = cycleway::attribute::<Surface>(tags, side, "surface", warnings) {
+            cycle.surface = Infer::Direct(surface);
+        }
+        if let Some(colour) = cycleway::attribute::<String>(tags, side, "colour", warnings) {
+            cycle.colour = Infer::Direct(colour);
+        }
+        cycle
+    }
 }
 
 pub(in crate::transform::tags_to_lanes) fn bicycle(
-    tags: &Tags,
+    tags: &TagsRef<'_>,
     locale: &Locale,
     road: &mut RoadBuilder,
     warnings: &mut RoadWarnings,
 ) -> Result<(), TagsToLanesMsg> {
+    // The way is itself a cycle crossing, see `non_motorized`
+    if matches!(tags.get(&HIGHWAY), Some("cycleway" | "path")) && tags.is(&CYCLEWAY, "crossing") {
+        return Ok(());
+    }
     let cycleway_scheme = cycleway::Scheme::from_tags(tags, locale, road.oneway, warnings)?;
+    #[cfg(feature = "log")]
     log::trace!("cycleway=* scheme: {cycleway_scheme:?}");
-    let _cycleway_lanes_scheme = cycleway_lanes::Scheme::from_tags(tags, locale, warnings)?;
-    log::trace!("cycleway:lanes=* scheme: {cycleway_scheme:?}");
+    let cycleway_lanes_scheme = cycleway_lanes::Scheme::from_tags(tags, locale, road, warnings)?;
+    #[cfg(feature = "log")]
+    log::trace!("cycleway:lanes=* scheme: {cycleway_lanes_scheme:?}");
+    // The lanes scheme counts the existing lanes, so is applied before adding the outer cycle lanes
+    if cycleway_lanes_scheme.has_cycle_lanes()
+        && cycleway_scheme.location != cycleway::Location::None
+    {
+        warnings.push(TagsToLanesMsg::ambiguous_tags(tags.subset(&[
+            CYCLEWAY,
+            CYCLEWAY + "both",
+            CYCLEWAY + "left",
+            CYCLEWAY + "right",
+            keys::CYCLEWAY_LANES,
+            keys::BICYCLE_LANES,
+        ])));
+    } else {
+        cycleway_lanes_scheme.apply(tags, locale, road, warnings)?;
+    }
+    oneway_bicycle(tags, locale, road);
+    let forward_buffer = cycleway::buffer(tags, locale.driving_side, warnings);
+    let backward_buffer = cycleway::buffer(tags, locale.driving_side.opposite(), warnings);
     match cycleway_scheme.location {
         cycleway::Location::None => {},
         cycleway::Location::Forward(way) => {
-            if let cycleway::Variant::Lane | cycleway::Variant::Track = way.variant {
-                road.push_forward_outside(LaneBuilder::cycle(way));
+            forward(tags, locale, road, way, forward_buffer, warnings)?;
+        },
+        cycleway::Location::Backward(way) => {
+            backward(tags, locale, road, way, backward_buffer, warnings)?;
+        },
+        cycleway::Location::Both {
+            forward: forward_way,
+            backward: backward_way,
+        } => {
+            forward(tags, locale, road, forward_way, forward_buffer, warnings)?;
+            backward(tags, locale, road, backward_way, backward_buffer, warnings)?;
+        },
+    }
+    cycle_street(tags, locale, road, warnings);
+    Ok(())
+}
+
+/// Bicycles may ride both ways in the outside motor lane of a oneway where the locale exempts them,
+/// see [`crate::locale::LegalDefaults::is_oneway_bicycle_exempt`]
+fn oneway_bicycle(tags: &TagsRef<'_>, locale: &Locale, road: &mut RoadBuilder) {
+    if road.oneway != Oneway::Yes
+        || tags.is(&keys::ONEWAY_BICYCLE, "yes")
+        || !locale
+            .defaults()
+            .is_oneway_bicycle_exempt(road.highway.r#type())
+    {
+        return;
+    }
+    if let Some(lane) = road.forward_outside_mut() {
+        if lane.designated.some() == Some(Designated::Motor)
+            && !matches!(lane.access.bicycle, Infer::Direct(_))
+        {
+            lane.access.bicycle = Infer::Default(AccessAndDirection {
+                access: Access::Yes,
+                direction: Some(Direction::Both),
+            });
+        }
+    }
+}
+
+/// On a cycle street every motor lane is shared with bicycles,
+/// see `cyclestreet=yes` and `bicycle_road=yes`
+fn cycle_street(
+    tags: &TagsRef<'_>,
+    locale: &Locale,
+    road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
+) {
+    match CycleStreet::from_tags(tags) {
+        Ok(Some(_cycle_street)) => {
+            for lane in road.lanes_ltr_mut(locale) {
+                if lane.r#type.some() == Some(LaneType::Travel)
+                    && lane.designated.some() == Some(Designated::Motor)
+                {
+                    lane.shared = Infer::Direct(Designated::Bicycle);
+                }
+            }
+        },
+        Ok(None) => {},
+        Err(_unknown) => warnings.push(TagsToLanesMsg::unsupported_tags(
+            tags.subset(&[keys::CYCLESTREET, keys::BICYCLE_ROAD]),
+        )),
+    }
+}
+
+/// Share the motor or bus lane with bicycles,
+/// see `cycleway=shared_lane` and `cycleway=share_busway`
+fn share(lane: Option<&mut LaneBuilder>, variant: cycleway::Variant) -> Result<(), TagsToLanesMsg> {
+    let designated = if variant == cycleway::Variant::SharedBus {
+        Designated::Bus
+    } else {
+        Designated::Motor
+    };
+    match lane {
+        Some(lane) if lane.designated.some() == Some(designated) => {
+            lane.shared = Infer::Direct(Designated::Bicycle);
+            Ok(())
+        },
+        _ => Err(TagsToLanesMsg::unsupported_str(match designated {
+            Designated::Bus => "no bus lane to share with bicycles",
+            _ => "no motor lane to share with bicycles",
+        })),
+    }
+}
+
+fn forward(
+    tags: &TagsRef<'_>,
+    locale: &Locale,
+    road: &mut RoadBuilder,
+    way: cycleway::Way,
+    buffer: Buffer,
+    warnings: &mut RoadWarnings,
+) -> Result<(), TagsToLanesMsg> {
+    match way.variant {
+        cycleway::Variant::Lane | cycleway::Variant::Track => {
+            road.push_forward_outside(LaneBuilder::cycleway(
+                tags,
+                locale.driving_side,
+                way,
+                buffer,
+                warnings,
+            ));
+        },
+        cycleway::Variant::SharedLane | cycleway::Variant::SharedBus => {
+            share(road.forward_outside_mut(), way.variant)?;
+        },
+        cycleway::Variant::Separate => {
+            road.push_separately_mapped(locale.driving_side, Designated::Bicycle);
+        },
+        // TODO: Do nothing if forward sharing the lane? What if we are on a bus-only road?
+        cycleway::Variant::SharedMotor => {},
+    }
+    Ok(())
+}
+
+fn backward(
+    tags: &TagsRef<'_>,
+    locale: &Locale,
+    road: &mut RoadBuilder,
+    way: cycleway::Way,
+    buffer: Buffer,
+    warnings: &mut RoadWarnings,
+) -> Result<(), TagsToLanesMsg> {
+    match way.variant {
+        cycleway::Variant::Lane | cycleway::Variant::Track => {
+            road.push_backward_outside(LaneBuilder::cycleway(
+                tags,
+                locale.driving_side.opposite(),
+                way,
+                buffer,
+                warnings,
+            ));
+        },
+        cycleway::Variant::SharedLane | cycleway::Variant::SharedBus => {
+            // Without backward lanes, the forward lane on the backward side is shared
+            if road.backward_len() > 0 {
+                share(road.backward_outside_mut(), way.variant)?;
+            } else {
+                share(road.forward_inside_mut(), way.variant)?;
             }
-            // TODO: Do nothing if forward sharing the lane? What if we are on a bus-only road?
         },
-        cycleway::Location::Backward(way) => match way.variant {
-            cycleway::Variant::Lane | cycleway::Variant::Track => {
-                road.push_backward_outside(LaneBuilder::cycle(way));
-            },
-            cycleway::Variant::SharedMotor => {
-                road.forward_outside_mut()
-                    .ok_or_else(|| {
-                        TagsToLanesMsg::unsupported_str("no forward lanes for cycleway")
-                    })?
-                    .access
-                    .bicycle = Infer::Direct(AccessAndDirection {
-                    access: Access::Yes,
-                    direction: Some(Direction::Both),
-                });
-            },
+        cycleway::Variant::Separate => {
+            road.push_separately_mapped(locale.driving_side.opposite(), Designated::Bicycle);
         },
-        cycleway::Location::Both { forward, backward } => {
-            road.push_forward_outside(LaneBuilder::cycle(forward));
-            road.push_backward_outside(LaneBuilder::cycle(backward));
+        cycleway::Variant::SharedMotor => {
+            road.forward_outside_mut()
+                .ok_or_else(|| TagsToLanesMsg::unsupported_str("no forward lanes for cycleway"))?
+                .access
+                .bicycle = Infer::Direct(AccessAndDirection {
+                access: Access::Yes,
+                direction: Some(Direction::Both),
+            });
         },
     }
     Ok(())
@@ -1,7 +1,6 @@
-use std::borrow::Borrow;
-use std::hash::Hash;
+use alloc::borrow::ToOwned;
 
-use osm_tags::{TagKey, Tags};
+use osm_tags::{TagKey, Tags, TagsRef};
 
 use crate::locale::Locale;
 use crate::road::Direction;
@@ -11,31 +10,35 @@ use crate::transform::{RoadWarnings, TagsToLanesMsg};
 const BUSWAY: TagKey = TagKey::from_static("busway");
 const ONEWAY: TagKey = TagKey::from_static("oneway");
 
-#[derive(Debug, PartialEq, Eq)]
-pub(in crate::transform::tags_to_lanes) enum Variant {
-    None,
-    Forward,
-    Backward,
-    Both,
+/// Inferred busway scheme, the direction of the bus lane on each side of the way
+///
+/// On a oneway road, the bus lane on the backward side travels forward unless `=opposite_lane`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(in crate::transform::tags_to_lanes) struct Scheme {
+    forward_side: Option<Direction>,
+    backward_side: Option<Direction>,
 }
 
-/// Inferred busway scheme for forward lane and backward lane existing
-#[derive(Debug)]
-pub(in crate::transform::tags_to_lanes) struct Scheme(Variant);
-
 impl Scheme {
-    pub(crate) fn forward(&self) -> bool {
-        match self.0 {
-            Variant::None | Variant::Backward => false,
-            Variant::Forward | Variant::Both => true,
-        }
+    /// Number of bus lanes travelling forward
+    pub(crate) fn forward(&self) -> usize {
+        self.count(Direction::Forward)
     }
 
-    pub(crate) fn backward(&self) -> bool {
-        match self.0 {
-            Variant::None | Variant::Forward => false,
-            Variant::Backward | Variant::Both => true,
-        }
+    /// Number of bus lanes travelling backward
+    pub(crate) fn backward(&self) -> usize {
+        self.count(Direction::Backward)
+    }
+
+    fn count(&self, direction: Direction) -> usize {
+        [self.forward_side, self.backward_side]
+            .into_iter()
+            .filter(|side| *side == Some(direction))
+            .count()
+    }
+
+    pub(super) fn is_none(&self) -> bool {
+        self.forward_side.is_none() && self.backward_side.is_none()
     }
 }
 
@@ -45,10 +48,9 @@ enum Lane {
     Opposite,
 }
 
-fn get_bus_lane<Q, O>(tags: &Tags, key: &Q, warnings: &mut RoadWarnings) -> Lane
+fn get_bus_lane<Q, O>(tags: &TagsRef<'_>, key: &Q, warnings: &mut RoadWarnings) -> Lane
 where
-    TagKey: Borrow<Q>,
-    Q: Ord + Hash + Eq + ?Sized + ToOwned<Owned = O>,
+    Q: AsRef<str> + ?Sized + ToOwned<Owned = O>,
     O: Into<TagKey>,
 {
     match tags.get(key) {
@@ -65,75 +67,68 @@ where
 impl Scheme {
     #[allow(clippy::unnecessary_wraps)]
     pub(in crate::transform::tags_to_lanes) fn from_tags(
-        tags: &Tags,
+        tags: &TagsRef<'_>,
         road_oneway: Oneway,
         locale: &Locale,
         warnings: &mut RoadWarnings,
     ) -> Result<Self, TagsToLanesMsg> {
-        let bus_oneway: Oneway = match tags.get(&(ONEWAY + "bus")) {
-            Some("yes") => Oneway::Yes,
-            Some("no") => Oneway::No,
-            None => road_oneway,
-            Some(v) => {
-                warnings.push(TagsToLanesMsg::unsupported_tag(ONEWAY + "bus", v));
-                road_oneway
-            },
+        let bus_oneway = bus_oneway(tags, road_oneway, warnings);
+        // The direction of a bus lane on the backward side, without `=opposite_lane`
+        let backward_side_direction = if bus_oneway == Oneway::Yes {
+            Direction::Forward
+        } else {
+            Direction::Backward
         };
 
-        let busway_root: Lane = get_bus_lane(tags, &BUSWAY, warnings);
-        let busway_root: Variant = match (busway_root, bus_oneway) {
-            (Lane::None, _) => Variant::None,
-            (Lane::Lane, Oneway::No) => Variant::Both,
-            (Lane::Lane, Oneway::Yes) => Variant::Forward,
-            (Lane::Opposite, Oneway::No) => {
-                warnings.push(TagsToLanesMsg::unsupported_tags(tags.subset(&[
-                    BUSWAY,
-                    ONEWAY,
-                    ONEWAY + "bus",
-                ])));
-                Variant::None
-            },
-            (Lane::Opposite, Oneway::Yes) => Variant::Backward,
-        };
+        let busway_root = Self::from_tags_busway(tags, bus_oneway, locale, warnings);
 
         let busway_both_key = BUSWAY + "both";
         let busway_both: Lane = get_bus_lane(tags, &busway_both_key, warnings);
-        let busway_both: Variant = match busway_both {
-            Lane::None => Variant::None,
-            Lane::Lane => Variant::Both,
+        let busway_both = match busway_both {
+            Lane::None => Scheme::default(),
+            Lane::Lane => Scheme {
+                forward_side: Some(Direction::Forward),
+                backward_side: Some(backward_side_direction),
+            },
             Lane::Opposite => {
                 warnings.push(TagsToLanesMsg::unsupported_tags(
                     tags.subset([&busway_both_key]),
                 ));
-                Variant::None
+                Scheme::default()
             },
         };
 
         let busway_forward_key = BUSWAY + locale.driving_side.tag();
-        let busway_forward: Lane = get_bus_lane(tags, &busway_forward_key, warnings);
-        if let Lane::Opposite = busway_forward {
-            warnings.push(TagsToLanesMsg::unsupported_tags(
-                tags.subset([&busway_forward_key]),
-            ));
-        }
+        let busway_forward = match get_bus_lane(tags, &busway_forward_key, warnings) {
+            Lane::None => None,
+            Lane::Lane => Some(Direction::Forward),
+            Lane::Opposite => {
+                warnings.push(TagsToLanesMsg::unsupported_tags(
+                    tags.subset([&busway_forward_key]),
+                ));
+                None
+            },
+        };
         let busway_backward_key = BUSWAY + locale.driving_side.opposite().tag();
-        let busway_backward: Lane = get_bus_lane(tags, &busway_backward_key, warnings);
-        let busway_forward_backward = match (busway_forward, busway_backward) {
-            (Lane::None | Lane::Opposite, Lane::None) => Variant::None,
-            (Lane::Lane, Lane::None) => Variant::Forward,
-            (Lane::None | Lane::Opposite, Lane::Lane | Lane::Opposite) => Variant::Backward,
-            (Lane::Lane, Lane::Lane | Lane::Opposite) => Variant::Both,
+        let busway_backward = match get_bus_lane(tags, &busway_backward_key, warnings) {
+            Lane::None => None,
+            Lane::Lane => Some(backward_side_direction),
+            Lane::Opposite => Some(Direction::Backward),
+        };
+        let busway_forward_backward = Scheme {
+            forward_side: busway_forward,
+            backward_side: busway_backward,
         };
 
-        if let Variant::Both = busway_both {
-            if let Variant::Forward | Variant::Backward = busway_forward_backward {
+        if !busway_both.is_none() {
+            if !busway_forward_backward.is_none() && busway_forward_backward != busway_both {
                 warnings.push(TagsToLanesMsg::ambiguous_tags(tags.subset([
                     &busway_both_key,
                     &busway_forward_key,
                     &busway_backward_key,
                 ])));
             }
-            if let Variant::Forward | Variant::Backward = busway_root {
+            if !busway_root.is_none() && busway_root != busway_both {
                 warnings.push(TagsToLanesMsg::ambiguous_tags(tags.subset(&[
                     BUSWAY,
                     ONEWAY,
@@ -141,10 +136,9 @@ impl Scheme {
                     BUSWAY + "both",
                 ])));
             }
-            Ok(Scheme(Variant::Both))
-        } else if let Variant::Both | Variant::Forward | Variant::Backward = busway_forward_backward
-        {
-            if busway_root != Variant::None && busway_root != busway_forward_backward {
+            Ok(busway_both)
+        } else if !busway_forward_backward.is_none() {
+            if !busway_root.is_none() && busway_root != busway_forward_backward {
                 warnings.push(TagsToLanesMsg::ambiguous_tags(tags.subset(&[
                     BUSWAY,
                     ONEWAY,
@@ -153,33 +147,97 @@ impl Scheme {
                     busway_backward_key,
                 ])));
             }
-            Ok(Scheme(busway_forward_backward))
+            Ok(busway_forward_backward)
         } else {
-            Ok(Scheme(busway_root))
+            Ok(busway_root)
+        }
+    }
+
+    /// Handle `busway=*` tags
+    fn from_tags_busway(
+        tags: &TagsRef<'_>,
+        bus_oneway: Oneway,
+        locale: &Locale,
+        warnings: &mut RoadWarnings,
+    ) -> Self {
+        match (get_bus_lane(tags, &BUSWAY, warnings), bus_oneway) {
+            (Lane::None, _) => Self::default(),
+            (Lane::Lane, Oneway::No) => Self {
+                forward_side: Some(Direction::Forward),
+                backward_side: Some(Direction::Backward),
+            },
+            (Lane::Lane, Oneway::Yes) => Self {
+                forward_side: Some(Direction::Forward),
+                backward_side: None,
+            },
+            (Lane::Opposite, Oneway::No) => {
+                warnings.push(TagsToLanesMsg::deprecated(
+                    tags.subset(&[BUSWAY]),
+                    Tags::from_pair(BUSWAY + locale.driving_side.opposite().tag(), "lane"),
+                ));
+                Self {
+                    forward_side: None,
+                    backward_side: Some(Direction::Backward),
+                }
+            },
+            (Lane::Lane | Lane::Opposite, Oneway::Alternating | Oneway::Reversible) => {
+                warnings.push(TagsToLanesMsg::unsupported_tags(tags.subset(&[
+                    BUSWAY,
+                    ONEWAY,
+                    ONEWAY + "bus",
+                ])));
+                Self::default()
+            },
+            (Lane::Opposite, Oneway::Yes) => Self {
+                forward_side: None,
+                backward_side: Some(Direction::Backward),
+            },
         }
     }
 }
 
+/// `oneway:bus=*`, falling back to the oneway of the road
+fn bus_oneway(tags: &TagsRef<'_>, road_oneway: Oneway, warnings: &mut RoadWarnings) -> Oneway {
+    match tags.get(&(ONEWAY + "bus")) {
+        Some("yes") => Oneway::Yes,
+        Some("no") => Oneway::No,
+        None => road_oneway,
+        Some(v) => {
+            warnings.push(TagsToLanesMsg::unsupported_tag(ONEWAY + "bus", v));
+            road_oneway
+        },
+    }
+}
+
 pub(in crate::transform::tags_to_lanes) fn apply_busway(
     road: &mut RoadBuilder,
     scheme: &Scheme,
     locale: &Locale,
 ) -> Result<(), TagsToLanesMsg> {
-    if let Variant::Forward | Variant::Both = scheme.0 {
+    if scheme.forward_side.is_some() {
         road.forward_outside_mut()
             .ok_or_else(|| TagsToLanesMsg::unsupported_str("no forward lanes for busway"))?
             .set_bus(locale)?;
     }
-    if let Variant::Backward | Variant::Both = scheme.0 {
-        if let Some(backward_outside) = road.backward_outside_mut() {
-            backward_outside.set_bus(locale)?;
-        } else {
-            let forward_inside = road
-                .forward_inside_mut()
-                .ok_or_else(|| TagsToLanesMsg::unsupported_str("no forward lanes for busway"))?;
-            forward_inside.set_bus(locale)?;
-            forward_inside.direction = Infer::Direct(Direction::Backward);
-        }
+    match scheme.backward_side {
+        Some(Direction::Backward) => {
+            if let Some(backward_outside) = road.backward_outside_mut() {
+                backward_outside.set_bus(locale)?;
+            } else {
+                let forward_inside = road.forward_inside_mut().ok_or_else(|| {
+                    TagsToLanesMsg::unsupported_str("no forward lanes for busway")
+                })?;
+                forward_inside.set_bus(locale)?;
+                forward_inside.direction = Infer::Direct(Direction::Backward);
+            }
+        },
+        // A bus lane with the traffic on the backward side of a oneway road
+        Some(_) => {
+            road.forward_inside_mut()
+                .ok_or_else(|| TagsToLanesMsg::unsupported_str("no forward lanes for busway"))?
+                .set_bus(locale)?;
+        },
+        None => {},
     }
     Ok(())
 }
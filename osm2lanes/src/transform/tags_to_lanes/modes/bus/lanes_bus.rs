@@ -0,0 +1,178 @@
+use osm_tags::{TagKey, TagsRef};
+
+use super::BuswayScheme;
+use crate::locale::Locale;
+use crate::transform::tags_to_lanes::{LaneBuilder, Oneway, RoadBuilder, TagsTyped};
+use crate::transform::{RoadWarnings, TagsToLanesMsg};
+
+const LANES: TagKey = TagKey::from_static("lanes");
+
+/// Number of bus lanes in each direction, from `lanes:bus=*` or `lanes:psv=*`
+///
+/// The bus lanes are counted in `lanes=*`, and are the outer-most lanes of each direction.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(in crate::transform::tags_to_lanes) struct Scheme {
+    pub(super) forward: Option<usize>,
+    pub(super) backward: Option<usize>,
+}
+
+impl Scheme {
+    pub(in crate::transform::tags_to_lanes) fn from_tags(
+        tags: &TagsRef<'_>,
+        oneway: Oneway,
+        busway: &BuswayScheme,
+        warnings: &mut RoadWarnings,
+    ) -> Self {
+        let bus = LANES + "bus";
+        let psv = LANES + "psv";
+        let key = match (
+            tags.pairs_with_stem(&bus).is_empty(),
+            tags.pairs_with_stem(&psv).is_empty(),
+        ) {
+            (true, true) => return Self::default(),
+            (false, true) => bus,
+            (true, false) => psv,
+            (false, false) => {
+                warnings.push(TagsToLanesMsg::unsupported(
+                    "both lanes:bus and lanes:psv used",
+                    tags.subset(&[bus.clone(), psv]),
+                ));
+                bus
+            },
+        };
+        // The busway scheme locates the bus lanes
+        if !busway.is_none()
+            && tags.get(&(&key + "forward")).is_none()
+            && tags.get(&(&key + "backward")).is_none()
+        {
+            let total: Option<usize> = tags.get_parsed(&key, warnings);
+            if total.map_or(false, |total| {
+                busway.forward().checked_add(busway.backward()) != Some(total)
+            }) {
+                warnings.push(TagsToLanesMsg::ambiguous_tags(
+                    tags.subset(&[key, TagKey::from_static("busway")]),
+                ));
+            }
+            return Self::default();
+        }
+        Self::from_key(tags, &key, oneway, warnings)
+    }
+
+    /// Number of lanes in each direction from `<key>=*`, `<key>:forward=*` and `<key>:backward=*`,
+    /// e.g. `lanes:taxi=*`
+    pub(in crate::transform::tags_to_lanes::modes) fn from_key(
+        tags: &TagsRef<'_>,
+        key: &TagKey,
+        oneway: Oneway,
+        warnings: &mut RoadWarnings,
+    ) -> Self {
+        let sides = [key + "left", key + "right"];
+        if sides.iter().any(|side| tags.get(side).is_some()) {
+            warnings.push(TagsToLanesMsg::unsupported_tags(tags.subset(&sides)));
+        }
+
+        let directions = [key.clone(), key + "forward", key + "backward"];
+        let [total, forward, backward]: [Option<usize>; 3] = directions
+            .clone()
+            .map(|key| tags.get_parsed(&key, warnings));
+        let conflict = |warnings: &mut RoadWarnings| {
+            warnings.push(TagsToLanesMsg::ambiguous_tags(tags.subset(&directions)));
+        };
+        match (total, forward, backward) {
+            (None, forward, backward) => Self { forward, backward },
+            (Some(total), Some(forward), Some(backward)) => {
+                if forward.checked_add(backward) != Some(total) {
+                    conflict(warnings);
+                }
+                Self {
+                    forward: Some(forward),
+                    backward: Some(backward),
+                }
+            },
+            (Some(total), Some(forward), None) => Self {
+                forward: Some(forward),
+                backward: Some(total.checked_sub(forward).unwrap_or_else(|| {
+                    conflict(warnings);
+                    0
+                })),
+            },
+            (Some(total), None, Some(backward)) => Self {
+                forward: Some(total.checked_sub(backward).unwrap_or_else(|| {
+                    conflict(warnings);
+                    0
+                })),
+                backward: Some(backward),
+            },
+            (Some(total), None, None) if oneway.into() => Self {
+                forward: Some(total),
+                backward: Some(0),
+            },
+            #[allow(clippy::integer_arithmetic, clippy::integer_division)]
+            (Some(total), None, None) => {
+                if total % 2 != 0 {
+                    warnings.push(TagsToLanesMsg::ambiguous_str(
+                        "lane count cannot be evenly divided between the forward and backward",
+                    ));
+                }
+                let forward = (total + 1) / 2; // usize division rounded up.
+                Self {
+                    forward: Some(forward),
+                    backward: Some(total - forward),
+                }
+            },
+        }
+    }
+
+    /// Apply to the counted outer-most lanes of each direction
+    ///
+    /// Returns `false`, applying to no lanes, if more lanes are counted than exist.
+    pub(in crate::transform::tags_to_lanes::modes) fn apply_outside<E>(
+        &self,
+        road: &mut RoadBuilder,
+        mut apply: impl FnMut(&mut LaneBuilder) -> Result<(), E>,
+    ) -> Result<bool, E> {
+        let forward = self.forward.unwrap_or(0);
+        let backward = self.backward.unwrap_or(0);
+        if forward > road.forward_len() || backward > road.backward_len() {
+            return Ok(false);
+        }
+        for index in road.forward_len().saturating_sub(forward)..road.forward_len() {
+            if let Some(lane) = road.forward_mut(index) {
+                apply(lane)?;
+            }
+        }
+        for index in road.backward_len().saturating_sub(backward)..road.backward_len() {
+            if let Some(lane) = road.backward_mut(index) {
+                apply(lane)?;
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Designate the outer-most lanes of each direction as bus lanes
+pub(super) fn apply_lanes_bus(
+    road: &mut RoadBuilder,
+    scheme: &Scheme,
+    tags: &TagsRef<'_>,
+    locale: &Locale,
+    warnings: &mut RoadWarnings,
+) -> Result<(), TagsToLanesMsg> {
+    if !scheme.apply_outside(road, |lane| lane.set_bus(locale))? {
+        warnings.push(TagsToLanesMsg::unsupported(
+            "more bus lanes than lanes",
+            tags.subset(&[
+                LANES,
+                LANES + "forward",
+                LANES + "backward",
+                LANES + "bus",
+                LANES + "bus" + "forward",
+                LANES + "bus" + "backward",
+                LANES + "psv",
+                LANES + "psv" + "forward",
+                LANES + "psv" + "backward",
+            ]),
+        ));
+    }
+    Ok(())
+}
@@ -1,10 +1,13 @@
-use osm_tag_schemes::{LaneAccess, LaneDependentAccess};
-use osm_tags::{TagKey, Tags};
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use osm_tag_schemes::{Access, Conditional, LaneAccess, LaneDependent, LaneDependentAccess};
+use osm_tags::{TagKey, TagsRef};
 
 use crate::locale::Locale;
-use crate::road::Designated;
+use crate::road::{AccessAndDirection, Designated};
 use crate::transform::tags_to_lanes::{
-    Infer, LaneBuilder, LaneBuilderError, RoadBuilder, TagsNumeric, TagsToLanesMsg,
+    Infer, LaneBuilder, LaneBuilderError, RoadBuilder, TagsToLanesMsg,
 };
 use crate::transform::RoadWarnings;
 
@@ -12,7 +15,9 @@ mod busway;
 use busway::apply_busway;
 pub(in crate::transform::tags_to_lanes) use busway::Scheme as BuswayScheme;
 
-const LANES: TagKey = TagKey::from_static("lanes");
+mod lanes_bus;
+use lanes_bus::apply_lanes_bus;
+pub(in crate::transform::tags_to_lanes) use lanes_bus::Scheme as LanesBusScheme;
 
 impl LaneBuilder {
     #[allow(clippy::unnecessary_wraps)]
@@ -20,6 +25,40 @@ impl LaneBuilder {
         self.designated = Infer::Direct(Designated::Bus);
         Ok(())
     }
+
+    /// Designate the lane for buses, or allow or forbid buses in the lane,
+    /// from a single value of `bus:lanes=*` or `psv:lanes=*`
+    fn set_bus_access(
+        &mut self,
+        access: LaneAccess,
+        locale: &Locale,
+    ) -> Result<(), LaneBuilderError> {
+        let access = match access {
+            LaneAccess::Designated => return self.set_bus(locale),
+            LaneAccess::Yes => Access::Yes,
+            LaneAccess::No => Access::No,
+            LaneAccess::None => return Ok(()),
+        };
+        self.access.bus = Infer::Direct(AccessAndDirection {
+            access,
+            direction: None,
+        });
+        Ok(())
+    }
+
+    /// Bus access under a condition, from a single value of `bus:lanes:conditional=*`
+    fn push_bus_access_conditional(&mut self, access: LaneAccess, condition: &str) {
+        let access = match access {
+            LaneAccess::Designated => Access::Designated,
+            LaneAccess::Yes => Access::Yes,
+            LaneAccess::No => Access::No,
+            LaneAccess::None => return,
+        };
+        self.access.bus_conditional.push(Conditional {
+            value: access,
+            condition: condition.into(),
+        });
+    }
 }
 
 impl From<LaneBuilderError> for TagsToLanesMsg {
@@ -35,18 +74,9 @@ pub(in crate::transform::tags_to_lanes) struct BusLaneCount {
 }
 
 impl BusLaneCount {
-    pub(crate) fn from_tags(
-        busway: &BuswayScheme,
-        tags: &Tags,
-        _locale: &Locale,
-        warnings: &mut RoadWarnings,
-    ) -> Self {
-        let forward = tags
-            .get_parsed("lanes:bus:forward", warnings)
-            .unwrap_or_else(|| if busway.forward() { 1 } else { 0 });
-        let backward = tags
-            .get_parsed("lanes:bus:backward", warnings)
-            .unwrap_or_else(|| if busway.backward() { 1 } else { 0 });
+    pub(crate) fn from_tags(busway: &BuswayScheme, lanes_bus: &LanesBusScheme) -> Self {
+        let forward = lanes_bus.forward.unwrap_or_else(|| busway.forward());
+        let backward = lanes_bus.backward.unwrap_or_else(|| busway.backward());
         Self { forward, backward }
     }
 }
@@ -54,7 +84,8 @@ impl BusLaneCount {
 #[allow(clippy::unnecessary_wraps)]
 pub(in crate::transform::tags_to_lanes) fn bus(
     busway: &BuswayScheme,
-    tags: &Tags,
+    lanes_bus: &LanesBusScheme,
+    tags: &TagsRef<'_>,
     locale: &Locale,
     road: &mut RoadBuilder,
     warnings: &mut RoadWarnings,
@@ -70,7 +101,7 @@ pub(in crate::transform::tags_to_lanes) fn bus(
     ) {
         (false, false, false) => {},
         (true, _, false) => apply_busway(road, busway, locale)?,
-        (false, true, false) => lanes_bus(tags, locale, road, warnings)?,
+        (false, true, false) => apply_lanes_bus(road, lanes_bus, tags, locale, warnings)?,
         (false, false, true) => bus_lanes(tags, locale, road, warnings)?,
         _ => {
             return Err(TagsToLanesMsg::unsupported(
@@ -79,92 +110,42 @@ pub(in crate::transform::tags_to_lanes) fn bus(
             ))
         },
     }
-    Ok(())
-}
-
-#[allow(clippy::unnecessary_wraps)]
-fn lanes_bus(
-    tags: &Tags,
-    _locale: &Locale,
-    _road: &mut RoadBuilder,
-    warnings: &mut RoadWarnings,
-) -> Result<(), TagsToLanesMsg> {
-    warnings.push(TagsToLanesMsg::unimplemented_tags(tags.subset(&[
-        LANES + "psv",
-        LANES + "psv" + "forward",
-        LANES + "psv" + "backward",
-        LANES + "psv" + "left",
-        LANES + "psv" + "right",
-        LANES + "bus",
-        LANES + "bus" + "forward",
-        LANES + "bus" + "backward",
-        LANES + "bus" + "left",
-        LANES + "bus" + "right",
-    ])));
+    bus_lanes_conditional(tags, locale, road, warnings);
     Ok(())
 }
 
 fn bus_lanes(
-    tags: &Tags,
+    tags: &TagsRef<'_>,
     locale: &Locale,
     road: &mut RoadBuilder,
     _warnings: &mut RoadWarnings,
 ) -> Result<(), TagsToLanesMsg> {
     let bus_lanes = TagKey::from_static("bus:lanes");
     let psv_lanes = TagKey::from_static("psv:lanes");
+    let mismatch = || {
+        TagsToLanesMsg::unsupported(
+            "lane count mismatch",
+            tags.subset([
+                &bus_lanes,
+                &(&bus_lanes + "forward"),
+                &(&bus_lanes + "backward"),
+                &psv_lanes,
+                &(&psv_lanes + "forward"),
+                &(&psv_lanes + "backward"),
+            ]),
+        )
+    };
     match (
         LaneDependentAccess::from_tags(tags, &bus_lanes)?,
         LaneDependentAccess::from_tags(tags, &psv_lanes)?,
     ) {
-        // lanes:bus or lanes:psv
-        (Some(LaneDependentAccess::LeftToRight(lanes)), None)
-        | (None, Some(LaneDependentAccess::LeftToRight(lanes))) => {
-            if lanes.len() != road.len() {
-                return Err(TagsToLanesMsg::unsupported(
-                    "lane count mismatch",
-                    tags.subset([&bus_lanes, &psv_lanes]),
-                ));
-            }
-            for (lane, access) in road.lanes_ltr_mut(locale).zip(lanes.iter()) {
-                if let LaneAccess::Designated = access {
-                    lane.set_bus(locale)?;
-                }
-            }
-        },
-        // lanes:bus:forward and lanes:bus:backward, or lanes:psv:forward and lanes:psv:backward
-        (Some(LaneDependentAccess::Forward(lanes)), None)
-        | (None, Some(LaneDependentAccess::Forward(lanes))) => {
-            for (lane, access) in road.forward_ltr_mut(locale).zip(lanes.iter()) {
-                if let LaneAccess::Designated = access {
-                    lane.set_bus(locale)?;
-                }
-            }
-        },
-        (Some(LaneDependentAccess::Backward(lanes)), None)
-        | (None, Some(LaneDependentAccess::Backward(lanes))) => {
-            for (lane, access) in road.backward_ltr_mut(locale).zip(lanes.iter()) {
-                if let LaneAccess::Designated = access {
-                    lane.set_bus(locale)?;
-                }
-            }
-        },
-        (Some(LaneDependentAccess::ForwardBackward { forward, backward }), None)
-        | (None, Some(LaneDependentAccess::ForwardBackward { forward, backward })) => {
-            if forward.len().checked_add(backward.len()) != Some(road.len()) {
-                return Err(TagsToLanesMsg::unsupported(
-                    "lane count mismatch",
-                    tags.subset([&bus_lanes, &psv_lanes]),
-                ));
-            }
-            for (lane, access) in road.forward_ltr_mut(locale).zip(forward.iter()) {
-                if let LaneAccess::Designated = access {
-                    lane.set_bus(locale)?;
-                }
-            }
-            for (lane, access) in road.backward_ltr_mut(locale).zip(backward.iter()) {
-                if let LaneAccess::Designated = access {
-                    lane.set_bus(locale)?;
-                }
+        // bus:lanes[:{forward,backward}] or psv:lanes[:{forward,backward}]
+        (Some(lanes), None) | (None, Some(lanes)) => {
+            for (lane, access) in road
+                .lane_dependent_mut(lanes, locale)
+                .ok_or_else(mismatch)?
+            {
+                lane.set_bus_access(access, locale)?;
             }
         },
         (None, None) => {},
@@ -174,7 +155,7 @@ fn bus_lanes(
                 tags.subset([
                     "bus:lanes",
                     "bus:lanes:forward",
-                    "psv:lanes:backward",
+                    "bus:lanes:backward",
                     "psv:lanes",
                     "psv:lanes:forward",
                     "psv:lanes:backward",
@@ -184,3 +165,73 @@ fn bus_lanes(
     }
     Ok(())
 }
+
+/// Time-dependent bus lanes, from `bus:lanes:conditional=*` or `psv:lanes:conditional=*`,
+/// and their `:forward` and `:backward` variants,
+/// e.g. `bus:lanes:conditional=designated|no @ (Mo-Fr 07:00-10:00)`
+fn bus_lanes_conditional(
+    tags: &TagsRef<'_>,
+    locale: &Locale,
+    road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
+) {
+    type Lanes = fn(Vec<LaneAccess>) -> LaneDependentAccess;
+
+    for stem in ["bus:lanes", "psv:lanes"] {
+        let keys: [(TagKey, Lanes); 3] = [
+            (
+                TagKey::from(stem) + "conditional",
+                LaneDependent::LeftToRight,
+            ),
+            (
+                TagKey::from(stem) + "forward" + "conditional",
+                LaneDependent::Forward,
+            ),
+            (
+                TagKey::from(stem) + "backward" + "conditional",
+                LaneDependent::Backward,
+            ),
+        ];
+        for (key, lanes) in keys {
+            let value = match tags.get(&key) {
+                Some(value) => value,
+                None => continue,
+            };
+            let restrictions = match Conditional::<String>::split(value) {
+                Ok(restrictions) => restrictions,
+                Err(_e) => {
+                    warnings.push(TagsToLanesMsg::unsupported_tags(
+                        tags.subset(&[key.clone()]),
+                    ));
+                    continue;
+                },
+            };
+            for Conditional { value, condition } in restrictions {
+                let values = match value
+                    .split('|')
+                    .map(str::parse)
+                    .collect::<Result<Vec<LaneAccess>, _>>()
+                {
+                    Ok(values) => values,
+                    Err(_e) => {
+                        warnings.push(TagsToLanesMsg::unsupported_tags(
+                            tags.subset(&[key.clone()]),
+                        ));
+                        continue;
+                    },
+                };
+                match road.lane_dependent_mut(lanes(values), locale) {
+                    Some(lanes) => {
+                        for (lane, access) in lanes {
+                            lane.push_bus_access_conditional(access, &condition);
+                        }
+                    },
+                    None => warnings.push(TagsToLanesMsg::unsupported(
+                        "lane count mismatch",
+                        tags.subset(&[key.clone()]),
+                    )),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use alloc::string::String;
+
+use osm_tag_schemes::keys::FOOTWAY;
+use osm_tag_schemes::{Access, Smoothness, Surface};
+use osm_tags::{TagKey, Tags, TagsRef};
+
+use super::{side_attribute, CyclewayVariant};
+use crate::locale::Locale;
+use crate::metric::Metre;
+use crate::road::{AccessAndDirection, Designated, Direction};
+use crate::transform::tags::SIDEWALK;
+use crate::transform::tags_to_lanes::road::Width;
+use crate::transform::tags_to_lanes::{Infer, LaneBuilder, LaneType, TagsToLanesMsg};
+use crate::transform::RoadWarnings;
+
+impl LaneBuilder {
+    fn foot(_locale: &Locale) -> Self {
+        Self {
+            r#type: Infer::Direct(LaneType::Travel),
+            designated: Infer::Direct(Designated::Foot),
+            ..Default::default()
+        }
+    }
+    /// A sidewalk, with the `width=*`, `surface=*` and `smoothness=*` of `sidewalk:<side>:*`
+    pub(super) fn sidewalk(
+        tags: &TagsRef<'_>,
+        side: &TagKey,
+        locale: &Locale,
+        warnings: &mut RoadWarnings,
+    ) -> Self {
+        let mut foot = Self::foot(locale);
+        if let Some(width) = side_attribute::<Metre>(tags, &SIDEWALK, side, "width", warnings) {
+            foot.width = Width {
+                target: Infer::Direct(width),
+                ..Default::default()
+            };
+        }
+        if let Some(surface) = side_attribute::<Surface>(tags, &SIDEWALK, side, "surface", warnings)
+        {
+            foot.surface = Infer::Direct(surface);
+        }
+        if let Some(smoothness) =
+            side_attribute::<Smoothness>(tags, &SIDEWALK, side, "smoothness", warnings)
+        {
+            foot.smoothness = Infer::Direct(smoothness);
+        }
+        foot
+    }
+    /// Bicycles allowed on the sidewalk by `sidewalk:<side>:bicycle=*`,
+    /// sharing the sidewalk, or on a cycle path beside it with `sidewalk:<side>:segregated=yes`
+    ///
+    /// Returns the segregated cycle path, travelling in the `direction` of the side.
+    pub(super) fn sidewalk_bicycle(
+        &mut self,
+        tags: &TagsRef<'_>,
+        side: &TagKey,
+        direction: Direction,
+        warnings: &mut RoadWarnings,
+    ) -> Option<Self> {
+        let access = side_attribute::<Access>(tags, &SIDEWALK, side, "bicycle", warnings)?;
+        if access == Access::No {
+            return None;
+        }
+        let segregated = match side_attribute::<String>(
+            tags,
+            &SIDEWALK,
+            side,
+            "segregated",
+            warnings,
+        )
+        .as_deref()
+        {
+            None | Some("no") => false,
+            Some("yes") => true,
+            Some(_) => {
+                warnings.push(TagsToLanesMsg::unsupported_tags(tags.subset(&[
+                    SIDEWALK + side.as_str() + "segregated",
+                    SIDEWALK + "both" + "segregated",
+                    SIDEWALK + "segregated",
+                ])));
+                false
+            },
+        };
+        let access = Infer::Direct(AccessAndDirection {
+            access,
+            direction: None,
+        });
+        if segregated {
+            let mut cycle = Self {
+                r#type: Infer::Direct(LaneType::Travel),
+                direction: Infer::Direct(direction),
+                designated: Infer::Direct(Designated::Bicycle),
+                cycleway_variant: Some(CyclewayVariant::Track),
+                ..Default::default()
+            };
+            cycle.access.bicycle = access;
+            Some(cycle)
+        } else {
+            self.shared = Infer::Direct(Designated::Bicycle);
+            self.access.bicycle = access;
+            None
+        }
+    }
+    pub(super) fn is_bicycle(&self) -> bool {
+        self.designated.some() == Some(Designated::Bicycle)
+    }
+}
+
+/// The way is itself a sidewalk, `footway=sidewalk`, so has no sidewalks of its own
+///
+/// <https://wiki.openstreetmap.org/wiki/Tag:footway%3Dsidewalk>
+pub(super) fn is_sidewalk(tags: &TagsRef<'_>, warnings: &mut RoadWarnings) -> bool {
+    if !tags.is(&FOOTWAY, "sidewalk") {
+        return false;
+    }
+    let sidewalk_tags = tags.pairs_with_stem(&SIDEWALK);
+    if !sidewalk_tags.is_empty() {
+        warnings.push(TagsToLanesMsg::unsupported(
+            "sidewalk on a sidewalk",
+            tags.subset(
+                sidewalk_tags
+                    .iter()
+                    .map(|(key, _val)| *key)
+                    .chain([FOOTWAY.as_str()]),
+            ),
+        ));
+    }
+    true
+}
+
+#[derive(Clone, Copy)]
+pub(super) enum Sidewalk {
+    None,
+    No,
+    Yes,
+    Separate,
+}
+
+impl Sidewalk {
+    /// This processes sidewalk tags by the OSM spec.
+    /// No can be implied, e.g. we assume that sidewalk:left=yes implies sidewalk:right=no
+    /// None is when information may be incomplete and should be inferred,
+    /// e.g. when sidewalk=* is missing altogether,
+    /// but this may later become a No when combined with data from shoulder=*
+    /// We catch any tag combinations that violate the OSM spec
+    pub(super) fn from_tags(
+        tags: &TagsRef<'_>,
+        locale: &Locale,
+        warnings: &mut RoadWarnings,
+    ) -> Result<(Self, Self), TagsToLanesMsg> {
+        let err = || {
+            TagsToLanesMsg::unsupported_tags(tags.subset(&[
+                SIDEWALK,
+                SIDEWALK + "both",
+                SIDEWALK + locale.driving_side.tag(),
+                SIDEWALK + locale.driving_side.opposite().tag(),
+            ]))
+        };
+        let side = |value: Option<&str>| match value {
+            None => Ok(None),
+            Some("no") => Ok(Some(Sidewalk::No)),
+            Some("yes") => Ok(Some(Sidewalk::Yes)),
+            Some("separate") => Ok(Some(Sidewalk::Separate)),
+            Some(_) => Err(err()),
+        };
+        let sidewalks = match (
+            sidewalk(tags, warnings),
+            tags.get(&(SIDEWALK + "both")),
+            (
+                tags.get(&(SIDEWALK + locale.driving_side.tag())),
+                tags.get(&(SIDEWALK + locale.driving_side.opposite().tag())),
+            ),
+        ) {
+            (Some(v), None, (None, None)) => match v {
+                "none" => return Err(TagsToLanesMsg::deprecated_tag("sidewalk", "none")),
+                "no" => (Sidewalk::No, Sidewalk::No),
+                "yes" => {
+                    warnings.push(TagsToLanesMsg::ambiguous_tags(
+                        tags.subset(&[SIDEWALK, SIDEWALK + "both"]),
+                    ));
+                    (Sidewalk::Yes, Sidewalk::Yes)
+                },
+                "both" => (Sidewalk::Yes, Sidewalk::Yes),
+                s if s == locale.driving_side.tag().as_str() => (Sidewalk::Yes, Sidewalk::No),
+                s if s == locale.driving_side.opposite().tag().as_str() => {
+                    (Sidewalk::No, Sidewalk::Yes)
+                },
+                "separate" => (Sidewalk::Separate, Sidewalk::Separate),
+                _ => return Err(err()),
+            },
+            // sidewalk:both=
+            (None, Some(v), (None, None)) => {
+                let both = side(Some(v))?.unwrap_or(Sidewalk::None);
+                (both, both)
+            },
+            // sidewalk:left= and/or sidewalk:right=
+            (None, None, (forward, backward)) => match (side(forward)?, side(backward)?) {
+                // no scheme
+                (None, None) => (Sidewalk::None, Sidewalk::None),
+                // the untagged side is implied to have no sidewalk
+                (forward, backward) => (
+                    forward.unwrap_or(Sidewalk::No),
+                    backward.unwrap_or(Sidewalk::No),
+                ),
+            },
+            _ => return Err(err()),
+        };
+        Ok(sidewalks)
+    }
+}
+
+/// `sidewalk=*`, or the deprecated `footway=left|right|both|none` on a road
+fn sidewalk<'tags>(tags: &'tags TagsRef<'_>, warnings: &mut RoadWarnings) -> Option<&'tags str> {
+    match (tags.get(&SIDEWALK), tags.get(&FOOTWAY)) {
+        (None, Some(footway @ ("left" | "right" | "both" | "no" | "none"))) => {
+            let sidewalk = if footway == "none" { "no" } else { footway };
+            warnings.push(TagsToLanesMsg::deprecated(
+                tags.subset(&[FOOTWAY]),
+                Tags::from_pair(SIDEWALK, sidewalk),
+            ));
+            Some(sidewalk)
+        },
+        (sidewalk, _) => sidewalk,
+    }
+}
@@ -1,14 +1,20 @@
 /// Modes of travel
-use osm_tag_schemes::LaneDependentAccessError;
+use core::str::FromStr;
+
+use osm_tag_schemes::LaneDependentError;
+use osm_tags::{TagKey, TagsRef};
 
 use super::TagsToLanesMsg;
+use crate::transform::RoadWarnings;
 
 mod bicycle;
 pub(super) use bicycle::bicycle;
 pub(super) use bicycle::cycleway::Variant as CyclewayVariant;
 
 mod bus;
-pub(super) use bus::{bus, BusLaneCount, BuswayScheme};
+pub(super) use bus::{bus, BusLaneCount, BuswayScheme, LanesBusScheme};
+
+mod foot;
 
 mod foot_shoulder;
 pub(super) use foot_shoulder::foot_and_shoulder;
@@ -20,16 +26,44 @@ pub(super) use parking::parking;
 mod non_motorized;
 pub(super) use non_motorized::non_motorized;
 
-impl From<LaneDependentAccessError<'_>> for TagsToLanesMsg {
-    fn from(e: LaneDependentAccessError) -> Self {
+mod shoulder;
+
+mod taxi_hov;
+pub(super) use taxi_hov::taxi_and_hov;
+
+impl From<LaneDependentError<'_>> for TagsToLanesMsg {
+    fn from(e: LaneDependentError) -> Self {
         match e {
-            LaneDependentAccessError::Unknown(key, val) => {
-                TagsToLanesMsg::unsupported_tag(key, val)
-            },
-            LaneDependentAccessError::Conflict => {
+            LaneDependentError::Unknown(key, val) => TagsToLanesMsg::unsupported_tag(key, val),
+            LaneDependentError::Conflict => {
                 // TODO, more detail
                 TagsToLanesMsg::unsupported_str("conflicting tags")
             },
         }
     }
 }
+
+/// The most specific of `<key>:<side>:<attribute>`, `<key>:both:<attribute>`,
+/// and `<key>:<attribute>`, e.g. `sidewalk:left:surface=*`
+fn side_attribute<T: FromStr>(
+    tags: &TagsRef<'_>,
+    key: &TagKey,
+    side: &TagKey,
+    attribute: &str,
+    warnings: &mut RoadWarnings,
+) -> Option<T> {
+    let key = [
+        key + side.as_str() + attribute,
+        key + "both" + attribute,
+        key + attribute,
+    ]
+    .into_iter()
+    .find(|key| tags.get(key).is_some())?;
+    let value = tags.get(&key)?;
+    if let Ok(value) = value.parse() {
+        Some(value)
+    } else {
+        warnings.push(TagsToLanesMsg::unsupported_tag(key, value));
+        None
+    }
+}
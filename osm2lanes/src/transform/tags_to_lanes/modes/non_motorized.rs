@@ -1,24 +1,50 @@
-use osm_tag_schemes::keys::HIGHWAY;
-use osm_tag_schemes::Access;
-use osm_tags::Tags;
+use osm_tag_schemes::keys::{
+    CROSSING, CROSSING_REF, FOOT, FOOTWAY, HIGHWAY, LANES, MOTOR_VEHICLE, SEGREGATED, SHARED_SPACE,
+    VEHICLE,
+};
+use osm_tag_schemes::{Access, Crossing, HighwayType};
+use osm_tags::{TagKey, TagsRef};
 
 use crate::locale::Locale;
 use crate::road::{AccessAndDirection, Designated, Direction};
-use crate::transform::tags_to_lanes::{RoadBuilder, TagsToLanesMsg};
+use crate::transform::tags::CYCLEWAY;
+use crate::transform::tags_to_lanes::road::Width;
+use crate::transform::tags_to_lanes::{
+    LaneBuilder, LaneType, RoadBuilder, TagsToLanesMsg, TagsTyped,
+};
 use crate::transform::{Infer, RoadWarnings};
 
-#[allow(clippy::unnecessary_wraps, clippy::restriction)]
+#[allow(clippy::unnecessary_wraps)]
 pub(in crate::transform::tags_to_lanes) fn non_motorized(
-    tags: &Tags,
-    _locale: &Locale,
+    tags: &TagsRef<'_>,
+    locale: &Locale,
     road: &mut RoadBuilder,
     warnings: &mut RoadWarnings,
 ) -> Result<(), TagsToLanesMsg> {
-    // Easy special cases first.
-    if let Some(v @ ("steps" | "path")) = tags.get(&HIGHWAY) {
-        // TODO: how to avoid making this assumption?
-        assert_eq!(road.len(), 1);
-        let lane = road.forward_outside_mut().unwrap();
+    // Crossings over a road
+    // https://wiki.openstreetmap.org/wiki/Tag:footway%3Dcrossing
+    // https://wiki.openstreetmap.org/wiki/Tag:cycleway%3Dcrossing
+    let crossing_designated = match tags.get(&HIGHWAY) {
+        Some("footway" | "path") if tags.is(&FOOTWAY, "crossing") => Some(Designated::Foot),
+        Some("cycleway" | "path") if tags.is(&CYCLEWAY, "crossing") => Some(Designated::Bicycle),
+        _ => None,
+    };
+    if let Some(designated) = crossing_designated {
+        let crossing = crossing(tags, warnings);
+        let lane = single_lane(tags, road)?;
+        lane.designated.set(Infer::Direct(designated))?;
+        lane.direction.set(Infer::Direct(Direction::Both))?;
+        lane.crossing.set(Infer::Direct(crossing))?;
+        lane.access.motor.set(Infer::Direct(AccessAndDirection {
+            access: Access::No,
+            direction: None,
+        }))?;
+        return Ok(());
+    }
+
+    // Other easy special cases.
+    if let Some(v @ ("steps" | "path" | "footway")) = tags.get(&HIGHWAY) {
+        let lane = single_lane(tags, road)?;
         lane.designated.set(Infer::Direct(Designated::Foot))?;
         lane.direction.set(Infer::Direct(Direction::Both))?;
         lane.access.foot.set(Infer::Direct(AccessAndDirection {
@@ -37,5 +63,171 @@ pub(in crate::transform::tags_to_lanes) fn non_motorized(
         }
     }
 
+    match road.highway.r#type() {
+        HighwayType::Cycleway => cycleway(tags, locale, road, warnings)?,
+        HighwayType::Pedestrian => pedestrian(tags, locale, road, warnings)?,
+        HighwayType::LivingStreet => shared_space(locale, road)?,
+        _ => match tags.get(&SHARED_SPACE) {
+            None | Some("no") => {},
+            Some("yes") => shared_space(locale, road)?,
+            Some(_) => warnings.push(TagsToLanesMsg::unsupported_tags(
+                tags.subset(&[SHARED_SPACE]),
+            )),
+        },
+    }
+
+    Ok(())
+}
+
+/// A standalone cycle track, `highway=cycleway`,
+/// with a segregated footway beside it or foot traffic sharing the lanes
+fn cycleway(
+    tags: &TagsRef<'_>,
+    locale: &Locale,
+    road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
+) -> Result<(), TagsToLanesMsg> {
+    let foot: Option<Access> = tags.get_enum(&FOOT, warnings);
+    let segregated = tags.get_bool(&SEGREGATED, warnings).unwrap_or(false);
+
+    for lane in road.lanes_ltr_mut(locale) {
+        lane.designated.set(Infer::Direct(Designated::Bicycle))?;
+        lane.access.bicycle.set(Infer::Direct(AccessAndDirection {
+            access: Access::Designated,
+            direction: None,
+        }))?;
+        lane.access.motor.set(Infer::Direct(AccessAndDirection {
+            access: Access::No,
+            direction: None,
+        }))?;
+        if let (Some(foot), false) = (&foot, segregated) {
+            lane.access.foot.set(Infer::Direct(AccessAndDirection {
+                access: foot.clone(),
+                direction: None,
+            }))?;
+        }
+    }
+
+    match (foot, segregated) {
+        (Some(Access::No) | None, true) => {
+            warnings.push(TagsToLanesMsg::unsupported(
+                "segregated cycleway without foot access",
+                tags.subset(&[FOOT, SEGREGATED]),
+            ));
+        },
+        (Some(foot), true) => {
+            let mut footway = LaneBuilder {
+                r#type: Infer::Direct(LaneType::Travel),
+                direction: Infer::Direct(Direction::Both),
+                designated: Infer::Direct(Designated::Foot),
+                width: Width {
+                    target: Infer::Default(locale.travel_width(
+                        &Designated::Foot,
+                        None,
+                        HighwayType::Cycleway,
+                    )),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            footway.access.foot = Infer::Direct(AccessAndDirection {
+                access: foot,
+                direction: None,
+            });
+            road.push_forward_outside(footway);
+        },
+        (_, false) => {},
+    }
+    Ok(())
+}
+
+/// A pedestrian street, `highway=pedestrian`, as a single foot lane across the street,
+/// with vehicles only allowed by `vehicle=*` or `motor_vehicle=*`, e.g. `vehicle=destination`
+fn pedestrian(
+    tags: &TagsRef<'_>,
+    locale: &Locale,
+    road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
+) -> Result<(), TagsToLanesMsg> {
+    if road.len() != 1 {
+        return Err(TagsToLanesMsg::unsupported(
+            "lanes on a pedestrian street",
+            tags.subset(&[HIGHWAY, TagKey::from_static("lanes")]),
+        ));
+    }
+    let vehicle: Option<Access> = match (
+        tags.get_enum(&VEHICLE, warnings),
+        tags.get_enum(&MOTOR_VEHICLE, warnings),
+    ) {
+        (_, Some(motor_vehicle)) => Some(motor_vehicle),
+        (vehicle, None) => vehicle,
+    };
+    let lane = road.forward_outside_mut().unwrap();
+    lane.designated.set(Infer::Direct(Designated::Foot))?;
+    lane.direction.set(Infer::Direct(Direction::Both))?;
+    // Wider than the default lane of a road
+    if let Infer::None | Infer::Default(_) = lane.width.target {
+        lane.width.target = Infer::Default(locale.travel_width(
+            &Designated::Foot,
+            Some(Direction::Both),
+            HighwayType::Pedestrian,
+        ));
+    }
+    lane.access.foot.set(Infer::Direct(AccessAndDirection {
+        access: Access::Designated,
+        direction: None,
+    }))?;
+    lane.access.motor.set(Infer::Direct(AccessAndDirection {
+        access: vehicle.unwrap_or(Access::No),
+        direction: None,
+    }))?;
+    Ok(())
+}
+
+/// On a living street or shared space, `highway=living_street` or `shared_space=yes`,
+/// the vehicle lanes are shared with pedestrians at the speed limit implied by the locale
+fn shared_space(locale: &Locale, road: &mut RoadBuilder) -> Result<(), TagsToLanesMsg> {
+    let max_speed = locale.living_street_max_speed();
+    for lane in road.lanes_ltr_mut(locale) {
+        if lane.r#type.some() == Some(LaneType::Travel)
+            && matches!(
+                lane.designated.some(),
+                Some(Designated::Motor | Designated::Bus)
+            )
+        {
+            lane.shared = Infer::Direct(Designated::Foot);
+            lane.max_speed.set(Infer::Default(max_speed))?;
+        }
+    }
     Ok(())
 }
+
+/// The lane of a way mapped as a single path
+fn single_lane<'road>(
+    tags: &TagsRef<'_>,
+    road: &'road mut RoadBuilder,
+) -> Result<&'road mut LaneBuilder, TagsToLanesMsg> {
+    if road.len() != 1 {
+        return Err(TagsToLanesMsg::unsupported(
+            "path with more than one lane",
+            tags.subset(&[HIGHWAY, LANES]),
+        ));
+    }
+    road.forward_outside_mut()
+        .ok_or_else(|| TagsToLanesMsg::internal("path lane is not forward"))
+}
+
+/// Crossing detail, from `crossing=*` and `crossing_ref=*`
+fn crossing(tags: &TagsRef<'_>, warnings: &mut RoadWarnings) -> Crossing {
+    if tags.is(&CROSSING_REF, "zebra") {
+        return Crossing::Zebra;
+    }
+    match tags.get(&CROSSING).map(str::parse::<Crossing>) {
+        None => Crossing::Yes,
+        Some(Ok(crossing)) => crossing,
+        Some(Err(_)) => {
+            warnings.push(TagsToLanesMsg::unsupported_tags(tags.subset(&[CROSSING])));
+            Crossing::Yes
+        },
+    }
+}
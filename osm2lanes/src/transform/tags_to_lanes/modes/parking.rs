@@ -1,45 +1,349 @@
-use osm_tags::Tags;
+use alloc::string::String;
+use core::str::FromStr;
 
+use osm_tag_schemes::{
+    Parking, ParkingCondition, ParkingLane, ParkingOrientation, ParkingPosition,
+};
+use osm_tags::{tag_key, TagKey, TagsRef};
+
+use super::side_attribute;
 use crate::locale::Locale;
+use crate::metric::Metre;
 use crate::road::{Designated, Direction};
-use crate::transform::tags_to_lanes::{Infer, LaneBuilder, LaneType, RoadBuilder};
-use crate::transform::RoadError;
+use crate::transform::tags::PARKING;
+use crate::transform::tags_to_lanes::road::Width;
+use crate::transform::tags_to_lanes::{Infer, LaneBuilder, LaneType, RoadBuilder, TagsToLanesMsg};
+use crate::transform::{RoadError, RoadWarnings};
 
 impl LaneBuilder {
-    fn parking_forward(_locale: &Locale) -> Self {
-        Self {
-            r#type: Infer::Direct(LaneType::Parking),
-            direction: Infer::Direct(Direction::Forward),
-            designated: Infer::Direct(Designated::Motor),
-            ..Default::default()
+    /// A parking lane, or `None` if cars are parked on the kerb outside of the carriageway
+    fn parking(
+        direction: Direction,
+        orientation: Option<ParkingOrientation>,
+        position: Option<ParkingPosition>,
+    ) -> Option<Self> {
+        if position == Some(ParkingPosition::OnKerb) {
+            return None;
         }
-    }
-    fn parking_backward(_locale: &Locale) -> Self {
-        Self {
+        Some(Self {
             r#type: Infer::Direct(LaneType::Parking),
-            direction: Infer::Direct(Direction::Backward),
+            direction: Infer::Direct(direction),
             designated: Infer::Direct(Designated::Motor),
+            parking_orientation: orientation.into(),
+            parking_position: position.into(),
             ..Default::default()
-        }
+        })
     }
 }
 
+/// Parking lanes on either side of the road, from `parking:<side>=*`,
+/// or the deprecated `parking:lane:<side>=*` and `parking:lane:<side>:<orientation>=*`,
+/// with the `parking:condition:<side>=*` and the fee of either scheme
+///
+/// <https://wiki.openstreetmap.org/wiki/Key:parking>
 #[allow(clippy::unnecessary_wraps)]
 pub(in crate::transform::tags_to_lanes) fn parking(
-    tags: &Tags,
+    tags: &TagsRef<'_>,
     locale: &Locale,
     road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
 ) -> Result<(), RoadError> {
-    let has_parking = vec!["parallel", "diagonal", "perpendicular"];
-    let parking_lane_fwd = tags.is_any("parking:lane:right", &has_parking)
-        || tags.is_any("parking:lane:both", &has_parking);
-    let parking_lane_back = tags.is_any("parking:lane:left", &has_parking)
-        || tags.is_any("parking:lane:both", &has_parking);
-    if parking_lane_fwd {
-        road.push_forward_outside(LaneBuilder::parking_forward(locale));
+    let parking_lane = PARKING + "lane";
+    let sides = [
+        (locale.driving_side.tag(), Direction::Forward),
+        (locale.driving_side.opposite().tag(), Direction::Backward),
+    ];
+    let (key, [forward, backward]) = if ["both", "left", "right"]
+        .into_iter()
+        .any(|side| tags.get(&(PARKING + side)).is_some())
+    {
+        let deprecated = tags.pairs_with_stem(&parking_lane);
+        if !deprecated.is_empty() {
+            warnings.push(TagsToLanesMsg::deprecated_tags(
+                tags.subset(deprecated.iter().map(|(key, _val)| *key)),
+            ));
+        }
+        let both = value(tags, &(PARKING + "both"), warnings);
+        let parking = sides.map(|(side, direction)| {
+            let value = side_value::<Parking>(tags, &PARKING, &side, both, warnings)?;
+            parking_side(tags, value, &side, direction, warnings)
+        });
+        (PARKING, parking)
+    } else {
+        if let Some(value) = tags.get(&parking_lane) {
+            warnings.push(TagsToLanesMsg::unsupported_tag(parking_lane.clone(), value));
+        }
+        let both = value(tags, &(&parking_lane + "both"), warnings);
+        let parking = sides.map(|(side, direction)| {
+            let value = side_value::<ParkingLane>(tags, &parking_lane, &side, both, warnings)?;
+            let orientation = value.orientation()?;
+            let position = side_attribute(tags, &parking_lane, &side, orientation.into(), warnings);
+            LaneBuilder::parking(direction, Some(orientation), position)
+        });
+        (parking_lane, parking)
+    };
+    let condition = tag_key!("parking:condition");
+    let condition_both = value(tags, &tag_key!("parking:condition:both"), warnings);
+    let [forward, backward] = [
+        (forward, locale.driving_side.tag()),
+        (backward, locale.driving_side.opposite().tag()),
+    ]
+    .map(|(parking, side)| {
+        let mut parking = parking?;
+        parking.parking_condition =
+            side_value::<ParkingCondition>(tags, &condition, &side, condition_both, warnings)
+                .into();
+        parking.parking_fee = fee(tags, &key, &side, warnings).into();
+        Some(parking)
+    });
+    if let Some(parking) = forward {
+        road.push_forward_outside(parking);
     }
-    if parking_lane_back {
-        road.push_backward_outside(LaneBuilder::parking_backward(locale));
+    if let Some(parking) = backward {
+        road.push_backward_outside(parking);
     }
     Ok(())
 }
+
+/// A parking lane from `parking:<side>=*`,
+/// with the `orientation=*` and `width=*` of `parking:<side>:*`
+fn parking_side(
+    tags: &TagsRef<'_>,
+    value: Parking,
+    side: &TagKey,
+    direction: Direction,
+    warnings: &mut RoadWarnings,
+) -> Option<LaneBuilder> {
+    match value {
+        Parking::Lane
+        | Parking::StreetSide
+        | Parking::OnKerb
+        | Parking::HalfOnKerb
+        | Parking::Yes => {},
+        Parking::No | Parking::Separate => return None,
+        Parking::Shoulder => {
+            warnings.push(TagsToLanesMsg::unimplemented_tags(
+                tags.subset(&[PARKING + side.as_str(), PARKING + "both"]),
+            ));
+            return None;
+        },
+    }
+    let orientation = side_attribute(tags, &PARKING, side, "orientation", warnings);
+    let mut parking = LaneBuilder::parking(direction, orientation, value.position())?;
+    if let Some(width) = side_attribute::<Metre>(tags, &PARKING, side, "width", warnings) {
+        parking.width = Width {
+            target: Infer::Direct(width),
+            ..Default::default()
+        };
+    }
+    Some(parking)
+}
+
+/// Whether there is a fee to park, from `<key>:<side>:fee=yes|no`
+fn fee(
+    tags: &TagsRef<'_>,
+    key: &TagKey,
+    side: &TagKey,
+    warnings: &mut RoadWarnings,
+) -> Option<bool> {
+    match side_attribute::<String>(tags, key, side, "fee", warnings).as_deref() {
+        None => None,
+        Some("yes") => Some(true),
+        Some("no") => Some(false),
+        Some(_) => {
+            warnings.push(TagsToLanesMsg::unsupported_tags(tags.subset(&[
+                key + side.as_str() + "fee",
+                key + "both" + "fee",
+                key + "fee",
+            ])));
+            None
+        },
+    }
+}
+
+/// The value of `<key>:<side>=*`, defaulting to the value `both` of `<key>:both=*`
+fn side_value<T: FromStr + Copy + PartialEq>(
+    tags: &TagsRef<'_>,
+    key: &TagKey,
+    side: &TagKey,
+    both: Option<T>,
+    warnings: &mut RoadWarnings,
+) -> Option<T> {
+    let side_key = key + side.as_str();
+    match (value(tags, &side_key, warnings), both) {
+        (Some(side), Some(both)) => {
+            if side != both {
+                warnings.push(TagsToLanesMsg::ambiguous_tags(
+                    tags.subset(&[side_key, key + "both"]),
+                ));
+            }
+            Some(side)
+        },
+        (side, both) => side.or(both),
+    }
+}
+
+/// A known value of `<key>=*`
+fn value<T: FromStr>(tags: &TagsRef<'_>, key: &TagKey, warnings: &mut RoadWarnings) -> Option<T> {
+    let value = tags.get(key)?;
+    if let Ok(value) = value.parse() {
+        Some(value)
+    } else {
+        warnings.push(TagsToLanesMsg::unsupported_tag(key.clone(), value));
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use osm_tag_schemes::{HighwayType, ParkingCondition, ParkingOrientation, ParkingPosition};
+    use osm_tags::Tags;
+
+    use crate::locale::Locale;
+    use crate::metric::Metre;
+    use crate::road::{Designated, Direction, Lane};
+    use crate::transform::tags_to_lanes::error::TagsToLanesIssue;
+    use crate::transform::{tags_to_lanes, TagsToLanesConfig};
+
+    #[test]
+    fn parking_scheme_over_parking_lane() {
+        let tags = Tags::from_pairs([
+            ("highway", "residential"),
+            ("sidewalk", "no"),
+            ("parking:lane:both", "parallel"),
+            ("parking:left", "no"),
+            ("parking:right", "lane"),
+            ("parking:right:orientation", "diagonal"),
+            ("parking:right:width", "5"),
+        ])
+        .unwrap();
+        let output = tags_to_lanes(
+            &tags,
+            &Locale::builder().build(),
+            &TagsToLanesConfig {
+                include_separators: false,
+                ..TagsToLanesConfig::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(output.warnings.as_slice().len(), 1);
+        if let TagsToLanesIssue::Deprecated {
+            deprecated_tags, ..
+        } = &output.warnings.as_slice().get(0).unwrap().issue
+        {
+            assert_eq!(
+                deprecated_tags.to_str_pairs(),
+                Tags::from_pair("parking:lane:both", "parallel").to_str_pairs()
+            );
+        } else {
+            panic!("wrong TagsToLanesIssue")
+        }
+        let parking: Vec<_> = output
+            .road
+            .lanes
+            .iter()
+            .filter(|lane| matches!(lane, Lane::Parking { .. }))
+            .collect();
+        assert_eq!(
+            parking,
+            [&Lane::Parking {
+                direction: Direction::Forward,
+                designated: Designated::Motor,
+                width: Some(Metre::new(5.0)),
+                orientation: Some(ParkingOrientation::Diagonal),
+                position: Some(ParkingPosition::OnStreet),
+                condition: None,
+                fee: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn parking_position() {
+        let tags = Tags::from_pairs([
+            ("highway", "residential"),
+            ("sidewalk", "no"),
+            ("parking:lane:both", "parallel"),
+            ("parking:lane:left:parallel", "on_kerb"),
+            ("parking:lane:right:parallel", "half_on_kerb"),
+        ])
+        .unwrap();
+        let locale = Locale::builder().build();
+        let output = tags_to_lanes(
+            &tags,
+            &locale,
+            &TagsToLanesConfig {
+                include_separators: false,
+                ..TagsToLanesConfig::default()
+            },
+        )
+        .unwrap();
+        assert!(output.warnings.is_empty(), "{:?}", output.warnings);
+        // Parking on the kerb is not part of the road
+        let parking: Vec<_> = output
+            .road
+            .lanes
+            .iter()
+            .filter(|lane| matches!(lane, Lane::Parking { .. }))
+            .collect();
+        assert_eq!(
+            parking,
+            [&Lane::Parking {
+                direction: Direction::Forward,
+                designated: Designated::Motor,
+                width: None,
+                orientation: Some(ParkingOrientation::Parallel),
+                position: Some(ParkingPosition::HalfOnKerb),
+                condition: None,
+                fee: None,
+            }]
+        );
+        assert_eq!(
+            parking
+                .first()
+                .unwrap()
+                .width(&locale, HighwayType::Residential),
+            Metre::new(0.5_f64 * locale.parking_width(ParkingOrientation::Parallel).val())
+        );
+    }
+
+    #[test]
+    fn parking_condition_and_fee() {
+        let tags = Tags::from_pairs([
+            ("highway", "residential"),
+            ("sidewalk", "no"),
+            ("parking:lane:both", "parallel"),
+            ("parking:lane:right:fee", "yes"),
+            ("parking:condition:both", "residents"),
+            ("parking:condition:right", "ticket"),
+        ])
+        .unwrap();
+        let output = tags_to_lanes(
+            &tags,
+            &Locale::builder().build(),
+            &TagsToLanesConfig {
+                include_separators: false,
+                ..TagsToLanesConfig::default()
+            },
+        )
+        .unwrap();
+        // parking:condition:right overrides parking:condition:both
+        assert_eq!(output.warnings.as_slice().len(), 1);
+        let parking: Vec<_> = output
+            .road
+            .lanes
+            .iter()
+            .filter_map(|lane| match lane {
+                Lane::Parking { condition, fee, .. } => Some((*condition, *fee)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            parking,
+            [
+                (Some(ParkingCondition::Residents), None),
+                (Some(ParkingCondition::Ticket), Some(true)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use alloc::vec::Vec;
+
+use osm_tag_schemes::Access;
+use osm_tags::{TagKey, TagsRef};
+
+use super::side_attribute;
+use crate::locale::Locale;
+use crate::metric::Metre;
+use crate::road::AccessAndDirection;
+use crate::transform::tags::SHOULDER;
+use crate::transform::tags_to_lanes::road::Width;
+use crate::transform::tags_to_lanes::{Infer, LaneBuilder, LaneType, TagsToLanesMsg};
+use crate::transform::RoadWarnings;
+
+impl LaneBuilder {
+    /// A shoulder, with the `width=*` and `access:<mode>=*` of `shoulder:<side>:*`
+    pub(super) fn shoulder(
+        tags: &TagsRef<'_>,
+        side: &TagKey,
+        locale: &Locale,
+        warnings: &mut RoadWarnings,
+    ) -> Self {
+        let mut shoulder = Self {
+            r#type: Infer::Direct(LaneType::Shoulder),
+            ..Default::default()
+        };
+        if let Some(width) = side_attribute::<Metre>(tags, &SHOULDER, side, "width", warnings) {
+            shoulder.width = Width {
+                target: Infer::Direct(width),
+                ..Default::default()
+            }
+        } else if locale.is_netherlands() {
+            shoulder.width = Width {
+                target: Infer::Default(Metre::new(0.6)),
+                ..Default::default()
+            }
+        }
+        for (mode, access) in [
+            ("access:foot", &mut shoulder.access.foot),
+            ("access:bicycle", &mut shoulder.access.bicycle),
+            ("access:emergency", &mut shoulder.access.emergency),
+        ] {
+            if let Some(value) = side_attribute::<Access>(tags, &SHOULDER, side, mode, warnings) {
+                *access = Infer::Direct(AccessAndDirection {
+                    access: value,
+                    direction: None,
+                });
+            }
+        }
+        shoulder
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(super) enum Shoulder {
+    None,
+    Yes,
+    No,
+}
+
+impl Shoulder {
+    /// The shoulders on the forward and backward sides,
+    /// from `shoulder=*`, `shoulder:both=*`, or `shoulder:left=*` and `shoulder:right=*`
+    ///
+    /// <https://wiki.openstreetmap.org/wiki/Key:shoulder>
+    pub(super) fn from_tags(
+        tags: &TagsRef<'_>,
+        locale: &Locale,
+        warnings: &mut RoadWarnings,
+    ) -> Result<(Self, Self), TagsToLanesMsg> {
+        let unknown = tags.subset(
+            tags.iter_with_prefix(&SHOULDER)
+                .map(|(key, _val)| key)
+                .filter(|key| !Self::is_known_key(key)),
+        );
+        if !unknown.is_empty() {
+            warnings.push(TagsToLanesMsg::unsupported_tags(unknown));
+        }
+        let forward_key = SHOULDER + locale.driving_side.tag();
+        let backward_key = SHOULDER + locale.driving_side.opposite().tag();
+        let err = || {
+            TagsToLanesMsg::unsupported_tags(tags.subset(&[
+                SHOULDER,
+                SHOULDER + "both",
+                forward_key.clone(),
+                backward_key.clone(),
+            ]))
+        };
+        let side = |key: &TagKey| match tags.get(key) {
+            None => Ok(None),
+            Some("yes") => Ok(Some(Shoulder::Yes)),
+            Some("no") => Ok(Some(Shoulder::No)),
+            Some(_) => Err(err()),
+        };
+        Ok(
+            match (
+                tags.get(&SHOULDER),
+                side(&(SHOULDER + "both"))?,
+                (side(&forward_key)?, side(&backward_key)?),
+            ) {
+                (None, None, (None, None)) => (Shoulder::None, Shoulder::None),
+                (Some(v), None, (None, None)) => match v {
+                    "no" => (Shoulder::No, Shoulder::No),
+                    "yes" | "both" => (Shoulder::Yes, Shoulder::Yes),
+                    s if s == locale.driving_side.tag().as_str() => (Shoulder::Yes, Shoulder::No),
+                    s if s == locale.driving_side.opposite().tag().as_str() => {
+                        (Shoulder::No, Shoulder::Yes)
+                    },
+                    s => return Err(TagsToLanesMsg::unsupported_tag(SHOULDER, s)),
+                },
+                (None, Some(both), (None, None)) => (both, both),
+                // An untagged side is left to be inferred
+                (None, None, (forward, backward)) => (
+                    forward.unwrap_or(Shoulder::None),
+                    backward.unwrap_or(Shoulder::None),
+                ),
+                _ => return Err(err()),
+            },
+        )
+    }
+
+    /// `shoulder=*`, and the keys under it that are read for a side, see [`LaneBuilder::shoulder`]
+    fn is_known_key(key: &str) -> bool {
+        let mut parts = key.split(':').skip(1).peekable();
+        if matches!(parts.peek(), Some(&("both" | "left" | "right"))) {
+            parts.next();
+        }
+        matches!(
+            parts.collect::<Vec<_>>().as_slice(),
+            [] | ["width"] | ["access", "foot" | "bicycle" | "emergency"]
+        )
+    }
+}
@@ -0,0 +1,128 @@
+use osm_tag_schemes::{Access, LaneAccess, LaneDependentAccess};
+use osm_tags::{TagKey, TagsRef};
+
+use super::LanesBusScheme;
+use crate::locale::Locale;
+use crate::road::{AccessAndDirection, Designated};
+use crate::transform::tags_to_lanes::{Infer, LaneBuilder, RoadBuilder, TagsToLanesMsg};
+use crate::transform::RoadWarnings;
+
+const TAXI_LANES: TagKey = TagKey::from_static("taxi:lanes");
+const LANES_TAXI: TagKey = TagKey::from_static("lanes:taxi");
+const HOV: TagKey = TagKey::from_static("hov");
+const HOV_LANES: TagKey = TagKey::from_static("hov:lanes");
+
+/// Access of a single lane, from a value of `taxi:lanes=*` or `hov:lanes=*`
+fn lane_access(access: LaneAccess) -> Option<Infer<AccessAndDirection>> {
+    let access = match access {
+        LaneAccess::Designated => Access::Designated,
+        LaneAccess::Yes => Access::Yes,
+        LaneAccess::No => Access::No,
+        LaneAccess::None => return None,
+    };
+    Some(Infer::Direct(AccessAndDirection {
+        access,
+        direction: None,
+    }))
+}
+
+/// Taxi and high-occupancy vehicle lanes,
+/// as the access of the existing motor and bus lanes rather than a designation of their own
+///
+/// See <https://wiki.openstreetmap.org/wiki/Key:taxi> and <https://wiki.openstreetmap.org/wiki/Key:hov>
+pub(in crate::transform::tags_to_lanes) fn taxi_and_hov(
+    tags: &TagsRef<'_>,
+    locale: &Locale,
+    road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
+) -> Result<(), TagsToLanesMsg> {
+    taxi(tags, locale, road, warnings)?;
+    hov(tags, locale, road, warnings)?;
+    Ok(())
+}
+
+/// `taxi:lanes=*`, or the number of outer-most taxi lanes from `lanes:taxi=*`
+fn taxi(
+    tags: &TagsRef<'_>,
+    locale: &Locale,
+    road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
+) -> Result<(), TagsToLanesMsg> {
+    let taxi_lanes = LaneDependentAccess::from_tags(tags, &TAXI_LANES)?;
+    let lanes_taxi = !tags.pairs_with_stem(&LANES_TAXI).is_empty();
+    match (taxi_lanes, lanes_taxi) {
+        (None, false) => {},
+        (Some(lanes), false) => {
+            let lanes = road.lane_dependent_mut(lanes, locale).ok_or_else(|| {
+                TagsToLanesMsg::unsupported("lane count mismatch", tags.subset(&[TAXI_LANES]))
+            })?;
+            for (lane, access) in lanes {
+                if let Some(access) = lane_access(access) {
+                    lane.access.taxi = access;
+                }
+            }
+        },
+        (None, true) => {
+            let counts = LanesBusScheme::from_key(tags, &LANES_TAXI, road.oneway, warnings);
+            let designated = |lane: &mut LaneBuilder| -> Result<(), TagsToLanesMsg> {
+                lane.access.taxi = Infer::Direct(AccessAndDirection {
+                    access: Access::Designated,
+                    direction: None,
+                });
+                Ok(())
+            };
+            if !counts.apply_outside(road, designated)? {
+                warnings.push(TagsToLanesMsg::unsupported(
+                    "more taxi lanes than lanes",
+                    tags.subset(&[
+                        LANES_TAXI,
+                        &LANES_TAXI + "forward",
+                        &LANES_TAXI + "backward",
+                    ]),
+                ));
+            }
+        },
+        (Some(_), true) => {
+            return Err(TagsToLanesMsg::unsupported(
+                "more than one taxi lanes scheme used",
+                tags.subset(&[TAXI_LANES, LANES_TAXI]),
+            ));
+        },
+    }
+    Ok(())
+}
+
+/// `hov=*` for all motor lanes, refined per lane by `hov:lanes=*`
+fn hov(
+    tags: &TagsRef<'_>,
+    locale: &Locale,
+    road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
+) -> Result<(), TagsToLanesMsg> {
+    match tags.get(&HOV).map(str::parse::<LaneAccess>) {
+        None => {},
+        Some(Ok(access)) => {
+            if let Some(access) = lane_access(access) {
+                for lane in road.lanes_ltr_mut(locale) {
+                    if lane.designated.some() == Some(Designated::Motor) {
+                        lane.access.hov = access.clone();
+                    }
+                }
+            }
+        },
+        Some(Err(_unknown)) => {
+            warnings.push(TagsToLanesMsg::unsupported_tags(tags.subset(&[HOV])));
+        },
+    }
+    if let Some(lanes) = LaneDependentAccess::from_tags(tags, &HOV_LANES)? {
+        let lanes = road.lane_dependent_mut(lanes, locale).ok_or_else(|| {
+            TagsToLanesMsg::unsupported("lane count mismatch", tags.subset(&[HOV_LANES]))
+        })?;
+        for (lane, access) in lanes {
+            if let Some(access) = lane_access(access) {
+                lane.access.hov = access;
+            }
+        }
+    }
+    Ok(())
+}
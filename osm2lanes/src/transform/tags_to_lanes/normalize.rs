@@ -0,0 +1,95 @@
+use osm_tags::TagsRef;
+
+use super::TagsToLanesMsg;
+use crate::transform::RoadWarnings;
+
+/// The first part of keys with values that are not keywords, so keep their case and lists,
+/// e.g. `name=Main Street` or `maxspeed=DE:urban`
+const CASE_SENSITIVE_KEYS: &[&str] = &[
+    "description",
+    "destination",
+    "fixme",
+    "maxspeed",
+    "minspeed",
+    "network",
+    "note",
+    "opening_hours",
+    "operator",
+    "ref",
+    "source",
+    "traffic_sign",
+    "website",
+    "wikidata",
+    "wikipedia",
+];
+
+/// Normalize the values of the tags, such as `oneway=Yes ` to `oneway=yes`,
+/// warning with the values that were normalized, see [`Tags::normalize`]
+pub(super) fn normalize(tags: &mut TagsRef<'_>, warnings: &mut RoadWarnings) {
+    let original = tags.normalize(is_keyword);
+    if !original.is_empty() {
+        let normalized = tags.subset(original.iter().map(|(key, _val)| key));
+        warnings.push(TagsToLanesMsg::normalized(
+            original.into_owned(),
+            normalized,
+        ));
+    }
+}
+
+fn is_keyword(key: &str) -> bool {
+    let root = key.split(':').next().unwrap_or(key);
+    !(root.ends_with("name")
+        || CASE_SENSITIVE_KEYS.contains(&root)
+        || key.ends_with(":conditional"))
+}
+
+#[cfg(test)]
+mod tests {
+    use osm_tags::Tags;
+
+    use super::*;
+    use crate::transform::tags_to_lanes::error::TagsToLanesIssue;
+
+    #[test]
+    fn test_normalize() {
+        let mut tags = Tags::from_pairs([
+            ("highway", "Secondary"),
+            ("name", "Main Street "),
+            ("maxspeed", "DE:urban"),
+            ("cycleway:both", "lane; "),
+            (
+                "parking:both:restriction:conditional",
+                "no_parking @ (Mo 07:00-09:00; Fr 07:00-09:00)",
+            ),
+        ])
+        .unwrap();
+        let mut warnings = RoadWarnings::default();
+        normalize(&mut tags, &mut warnings);
+        assert_eq!(
+            tags.to_vec(),
+            [
+                "cycleway:both=lane",
+                "highway=secondary",
+                "maxspeed=DE:urban",
+                "name=Main Street",
+                "parking:both:restriction:conditional=no_parking @ (Mo 07:00-09:00; Fr 07:00-09:00)",
+            ]
+        );
+        match warnings.as_slice() {
+            [msg] => match &msg.issue {
+                TagsToLanesIssue::Normalized {
+                    original,
+                    normalized,
+                } => {
+                    assert_eq!(original.to_vec().len(), 3);
+                    assert_eq!(normalized.get("highway"), Some("secondary"));
+                },
+                issue => panic!("unexpected issue {:?}", issue),
+            },
+            warnings => panic!("unexpected warnings {:?}", warnings),
+        }
+
+        normalize(&mut tags, &mut warnings);
+        assert_eq!(warnings.as_slice().len(), 1);
+    }
+}
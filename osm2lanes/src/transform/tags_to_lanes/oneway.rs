@@ -1,5 +1,6 @@
-use osm_tag_schemes::keys::ONEWAY;
-use osm_tags::{TagKey, Tags};
+use osm_tag_schemes::keys::{DUAL_CARRIAGEWAY, ONEWAY};
+use osm_tag_schemes::Highway;
+use osm_tags::{TagKey, TagsRef};
 
 use super::TagsToLanesMsg;
 use crate::locale::Locale;
@@ -9,6 +10,10 @@ use crate::transform::RoadWarnings;
 pub enum Oneway {
     Yes,
     No,
+    /// A single lane used in one direction at a time, e.g. a single-lane bridge with traffic signals
+    Alternating,
+    /// All lanes used in one direction at a time, changed over the day, e.g. for the rush hour
+    Reversible,
 }
 
 impl From<bool> for Oneway {
@@ -24,8 +29,8 @@ impl From<bool> for Oneway {
 impl From<Oneway> for bool {
     fn from(oneway: Oneway) -> Self {
         match oneway {
-            Oneway::Yes => true,
-            Oneway::No => false,
+            Oneway::Yes | Oneway::Reversible => true,
+            Oneway::No | Oneway::Alternating => false,
         }
     }
 }
@@ -34,24 +39,36 @@ impl Oneway {
     pub const KEY: TagKey = TagKey::from_static("oneway");
 
     pub fn from_tags(
-        tags: &Tags,
-        _locale: &Locale,
+        tags: &TagsRef<'_>,
+        locale: &Locale,
         _warnings: &mut RoadWarnings,
     ) -> Result<Self, TagsToLanesMsg> {
-        Ok(
-            match (tags.get(&ONEWAY), tags.is("junction", "roundabout")) {
-                (Some("yes"), _) => Self::Yes,
-                (Some("no"), false) => Self::No,
-                (Some("no"), true) => {
-                    return Err(TagsToLanesMsg::ambiguous_tags(
-                        tags.subset(["oneway", "junction"]),
-                    ));
-                },
-                (Some(value), _) => {
-                    return Err(TagsToLanesMsg::unimplemented_tag(ONEWAY, value));
-                },
-                (None, roundabout) => Self::from(roundabout),
+        // Both a roundabout and one of a pair of dual carriageways only have forward lanes
+        let implied = tags.is("junction", "roundabout") || tags.is(&DUAL_CARRIAGEWAY, "yes");
+        Ok(match (tags.get(&ONEWAY), implied) {
+            (Some("yes"), _) => Self::Yes,
+            (Some("no"), false) => Self::No,
+            (Some("alternating"), false) => Self::Alternating,
+            (Some("reversible"), false) => Self::Reversible,
+            (Some("no" | "alternating" | "reversible"), true) => {
+                return Err(TagsToLanesMsg::ambiguous_tags(tags.subset(&[
+                    ONEWAY,
+                    TagKey::from_static("junction"),
+                    DUAL_CARRIAGEWAY,
+                ])));
             },
-        )
+            (Some(value), _) => {
+                return Err(TagsToLanesMsg::unimplemented_tag(ONEWAY, value));
+            },
+            (None, implied) => Self::from(
+                implied
+                    || Highway::from_tags(tags)
+                        .ok()
+                        .flatten()
+                        .map_or(false, |highway| {
+                            locale.is_oneway_by_default(highway.r#type())
+                        }),
+            ),
+        })
     }
 }
@@ -0,0 +1,141 @@
+use alloc::vec::Vec;
+
+use osm_tag_schemes::keys;
+use osm_tags::{TagKey, TagsRef};
+
+use super::road::{LaneBuilder, RoadBuilder};
+use super::TagsToLanesMsg;
+use crate::locale::Locale;
+use crate::metric::{Metre, Tonne};
+use crate::road::Restrictions;
+use crate::transform::RoadWarnings;
+
+/// A positive quantity with an optional unit suffix, e.g. `3.5` or `3.5 t`
+fn quantity(value: &str, unit: &str) -> Option<f64> {
+    value
+        .strip_suffix(unit)
+        .map_or(value, str::trim_end)
+        .parse::<f64>()
+        .ok()
+        .filter(|quantity| quantity.is_finite() && *quantity > 0.0_f64)
+}
+
+fn metre(value: &str) -> Option<Metre> {
+    value
+        .parse::<Metre>()
+        .ok()
+        .filter(|metre| metre.val() > 0.0_f64)
+}
+
+fn tonne(value: &str) -> Option<Tonne> {
+    quantity(value, "t").map(Tonne::new)
+}
+
+/// Parse a restriction value, where `none` is no restriction
+fn parse<T>(
+    tags: &TagsRef<'_>,
+    key: &TagKey,
+    value: &str,
+    parse: fn(&str) -> Option<T>,
+    warnings: &mut RoadWarnings,
+) -> Option<T> {
+    match value {
+        "" | "none" => None,
+        value => {
+            let parsed = parse(value);
+            if parsed.is_none() {
+                warnings.push(TagsToLanesMsg::unsupported_tags(tags.subset([key])));
+            }
+            parsed
+        },
+    }
+}
+
+fn get<T>(
+    tags: &TagsRef<'_>,
+    key: &TagKey,
+    parse_value: fn(&str) -> Option<T>,
+    warnings: &mut RoadWarnings,
+) -> Option<T> {
+    tags.get(key)
+        .and_then(|value| parse(tags, key, value, parse_value, warnings))
+}
+
+/// Restrictions on the whole way,
+/// from `maxheight=*`, `maxweight=*`, and `maxlength=*`
+pub(super) fn road_restrictions(
+    tags: &TagsRef<'_>,
+    warnings: &mut RoadWarnings,
+) -> Option<Restrictions> {
+    let restrictions = Restrictions {
+        max_height: get(tags, &keys::MAXHEIGHT, metre, warnings),
+        max_weight: get(tags, &keys::MAXWEIGHT, tonne, warnings),
+        max_length: get(tags, &keys::MAXLENGTH, metre, warnings),
+    };
+    if restrictions.is_empty() {
+        None
+    } else {
+        Some(restrictions)
+    }
+}
+
+/// Restrictions on individual lanes,
+/// from `maxheight:lanes=*`, `maxweight:lanes=*`, and `maxlength:lanes=*`
+pub(super) fn lane_restrictions(
+    tags: &TagsRef<'_>,
+    locale: &Locale,
+    road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
+) {
+    fn lanes<T>(
+        tags: &TagsRef<'_>,
+        key: &TagKey,
+        parse_value: fn(&str) -> Option<T>,
+        set: fn(&mut LaneBuilder, Option<T>),
+        locale: &Locale,
+        road: &mut RoadBuilder,
+        warnings: &mut RoadWarnings,
+    ) {
+        if let Some(value) = tags.get(key) {
+            let values: Vec<&str> = value.split('|').collect();
+            if values.len() != road.len() {
+                warnings.push(TagsToLanesMsg::unsupported(
+                    "lane count mismatch",
+                    tags.subset([key]),
+                ));
+                return;
+            }
+            for (lane, value) in road.lanes_ltr_mut(locale).zip(values) {
+                set(lane, parse(tags, key, value, parse_value, warnings));
+            }
+        }
+    }
+
+    lanes(
+        tags,
+        &keys::MAXHEIGHT_LANES,
+        metre,
+        |lane, max_height| lane.restrictions.max_height = max_height,
+        locale,
+        road,
+        warnings,
+    );
+    lanes(
+        tags,
+        &keys::MAXWEIGHT_LANES,
+        tonne,
+        |lane, max_weight| lane.restrictions.max_weight = max_weight,
+        locale,
+        road,
+        warnings,
+    );
+    lanes(
+        tags,
+        &keys::MAXLENGTH_LANES,
+        metre,
+        |lane, max_length| lane.restrictions.max_length = max_length,
+        locale,
+        road,
+        warnings,
+    );
+}
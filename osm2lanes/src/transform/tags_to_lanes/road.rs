@@ -1,8 +1,15 @@
-use std::collections::VecDeque;
-use std::iter;
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::iter;
 
-use osm_tag_schemes::{keys, Highway, HighwayError, Schemes};
-use osm_tags::{TagKey, Tags};
+use osm_tag_schemes::{
+    keys, Access as AccessValue, Conditional, Crossing, CyclewayLane, EmbeddedRails, Expressway,
+    Highway, HighwayError, HighwayType, LaneDependent, ParkingCondition, ParkingOrientation,
+    ParkingPosition, Schemes, Smoothness, Surface, TrolleyWire, TurnDirection,
+};
+use osm_tags::{Tags, TagsRef};
 
 use super::infer::Infer;
 use super::oneway::Oneway;
@@ -10,12 +17,13 @@ use super::separator::{
     lane_pair_to_semantic_separator, lane_to_inner_edge_separator, outer_edge_semantic_separator,
     semantic_edge_separator_to_lane, semantic_separator_to_lane,
 };
-use super::{TagSchemes, TagsToLanesMsg};
+use super::width::{LaneWidth, WidthConstraint};
+use super::{max_speed, TagSchemes, TagsToLanesMsg};
 use crate::locale::{DrivingSide, Locale};
 use crate::metric::{Metre, Speed};
 use crate::road::{
-    AccessAndDirection as LaneAccessAndDirection, AccessByType as LaneAccessByType, Designated,
-    Direction, Lane,
+    AccessAndDirection as LaneAccessAndDirection, AccessByType as LaneAccessByType, Delineation,
+    Designated, Destination, Direction, Lane, Restrictions, SeparatelyMapped,
 };
 use crate::transform::error::{RoadError, RoadWarnings};
 use crate::transform::tags_to_lanes::counts::{CentreTurnLaneScheme, Counts};
@@ -24,12 +32,13 @@ use crate::transform::tags_to_lanes::modes::{BusLaneCount, CyclewayVariant};
 #[derive(Debug)]
 pub(in crate::transform) struct LaneBuilderError(pub(crate) &'static str);
 
-impl std::fmt::Display for LaneBuilderError {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for LaneBuilderError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(f, "{}", self.0)
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for LaneBuilderError {}
 
 impl From<LaneBuilderError> for RoadError {
@@ -52,13 +61,58 @@ pub struct Width {
     pub max: Infer<Metre>,
 }
 
+impl Width {
+    /// Reconcile the target with the minimum and maximum,
+    /// where a width tagged for the lane is kept over an implied minimum or maximum.
+    ///
+    /// Returns the constraint that determined the width, `None` if there is no width.
+    pub(super) fn solve(&mut self) -> Option<WidthConstraint> {
+        let target = self.target.some()?;
+        let binding = |bound: &Infer<Metre>| match (bound, &self.target) {
+            (Infer::Direct(bound), _)
+            | (
+                Infer::Default(bound) | Infer::Calculated(bound),
+                Infer::Default(_) | Infer::Calculated(_),
+            ) => Some(*bound),
+            _ => None,
+        };
+        let min = binding(&self.min);
+        let max = binding(&self.max);
+        if let Some(min) = min.filter(|min| target.val() < min.val()) {
+            self.target = Infer::Calculated(min);
+            return Some(WidthConstraint::Minimum);
+        }
+        if let Some(max) = max.filter(|max| target.val() > max.val()) {
+            self.target = Infer::Calculated(max);
+            return Some(WidthConstraint::Maximum);
+        }
+        Some(match self.target {
+            Infer::Calculated(_) if min == Some(target) => WidthConstraint::Minimum,
+            Infer::Calculated(_) if max == Some(target) => WidthConstraint::Maximum,
+            // Only distributing the total width of the way calculates a width
+            Infer::Calculated(_) => WidthConstraint::Total,
+            _ => WidthConstraint::Target,
+        })
+    }
+}
+
+/// Painted buffer either side of the lane, inside towards the centre of the road
+#[derive(Clone, Default, Debug)]
+pub struct Buffer {
+    pub inside: Infer<Metre>,
+    pub outside: Infer<Metre>,
+}
+
 #[derive(Clone, Default, Debug)]
 pub struct Access {
     pub foot: Infer<LaneAccessAndDirection>,
     pub bicycle: Infer<LaneAccessAndDirection>,
     pub taxi: Infer<LaneAccessAndDirection>,
     pub bus: Infer<LaneAccessAndDirection>,
+    pub hov: Infer<LaneAccessAndDirection>,
     pub motor: Infer<LaneAccessAndDirection>,
+    pub emergency: Infer<LaneAccessAndDirection>,
+    pub bus_conditional: Vec<Conditional<AccessValue>>,
 }
 
 impl From<Access> for Option<LaneAccessByType> {
@@ -67,7 +121,10 @@ impl From<Access> for Option<LaneAccessByType> {
             && inferred.bicycle.is_none()
             && inferred.taxi.is_none()
             && inferred.bus.is_none()
+            && inferred.hov.is_none()
             && inferred.motor.is_none()
+            && inferred.emergency.is_none()
+            && inferred.bus_conditional.is_empty()
         {
             return None;
         }
@@ -76,7 +133,10 @@ impl From<Access> for Option<LaneAccessByType> {
             bicycle: inferred.bicycle.some(),
             taxi: inferred.taxi.some(),
             bus: inferred.bus.some(),
+            hov: inferred.hov.some(),
             motor: inferred.motor.some(),
+            emergency: inferred.emergency.some(),
+            bus_conditional: inferred.bus_conditional,
         })
     }
 }
@@ -87,26 +147,39 @@ pub struct LaneBuilder {
     // note: direction is always relative to the way
     pub direction: Infer<Direction>,
     pub designated: Infer<Designated>,
+    pub shared: Infer<Designated>,
     pub width: Width,
+    pub buffer: Buffer,
+    pub cycleway_lane: Infer<CyclewayLane>,
+    pub surface: Infer<Surface>,
+    pub smoothness: Infer<Smoothness>,
+    pub colour: Infer<String>,
+    pub embedded_rails: Infer<EmbeddedRails>,
+    pub trolley_wire: Infer<TrolleyWire>,
+    pub crossing: Infer<Crossing>,
     pub max_speed: Infer<Speed>,
+    pub restrictions: Restrictions,
+    pub destination: Destination,
+    pub turn: Infer<Vec<TurnDirection>>,
     pub access: Access,
+    pub parking_orientation: Infer<ParkingOrientation>,
+    pub parking_position: Infer<ParkingPosition>,
+    pub parking_condition: Infer<ParkingCondition>,
+    pub parking_fee: Infer<bool>,
     pub(super) cycleway_variant: Option<CyclewayVariant>,
 }
 
 impl LaneBuilder {
+    /// Build the lane, with how its width was determined
     #[allow(clippy::panic)]
     #[must_use]
-    fn build(self) -> Lane {
+    fn build(mut self) -> (Lane, Option<LaneWidth>) {
+        let lane_width = self.width.solve().map(|constraint| LaneWidth {
+            width: self.width.target,
+            constraint,
+        });
         let width = self.width.target.some();
-        assert!(
-            width.unwrap_or(Lane::DEFAULT_WIDTH).val()
-                >= self.width.min.some().unwrap_or(Metre::MIN).val()
-        );
-        assert!(
-            width.unwrap_or(Lane::DEFAULT_WIDTH).val()
-                <= self.width.max.some().unwrap_or(Metre::MAX).val()
-        );
-        match self.r#type.some() {
+        let lane = match self.r#type.some() {
             Some(LaneType::Travel) => {
                 let direction = if let Some(Designated::Foot) = self.designated.some() {
                     None
@@ -116,8 +189,32 @@ impl LaneBuilder {
                 Lane::Travel {
                     direction,
                     designated: self.designated.some().unwrap(),
+                    shared: self.shared.some(),
+                    delineation: match self.cycleway_variant {
+                        Some(CyclewayVariant::Lane) => Some(Delineation::Painted),
+                        Some(CyclewayVariant::Track) => Some(Delineation::Physical),
+                        _ => None,
+                    },
+                    cycleway_lane: self.cycleway_lane.some(),
                     width,
+                    surface: self.surface.some(),
+                    smoothness: self.smoothness.some(),
+                    colour: self.colour.some(),
+                    embedded_rails: self.embedded_rails.some(),
+                    trolley_wire: self.trolley_wire.some(),
+                    crossing: self.crossing.some(),
                     max_speed: self.max_speed.some(),
+                    restrictions: if self.restrictions.is_empty() {
+                        None
+                    } else {
+                        Some(self.restrictions)
+                    },
+                    destination: if self.destination.is_empty() {
+                        None
+                    } else {
+                        Some(Box::new(self.destination))
+                    },
+                    turn: self.turn.some(),
                     access: self.access.into(),
                 }
             },
@@ -125,10 +222,18 @@ impl LaneBuilder {
                 direction: self.direction.some().unwrap(),
                 designated: self.designated.some().unwrap(),
                 width,
+                orientation: self.parking_orientation.some(),
+                position: self.parking_position.some(),
+                condition: self.parking_condition.some(),
+                fee: self.parking_fee.some(),
+            },
+            Some(LaneType::Shoulder) => Lane::Shoulder {
+                width,
+                access: self.access.into(),
             },
-            Some(LaneType::Shoulder) => Lane::Shoulder { width },
             None => panic!(),
-        }
+        };
+        (lane, lane_width)
     }
 
     /// Create a mirrored version of the lane
@@ -140,11 +245,21 @@ impl LaneBuilder {
     }
 }
 
+/// Storage for the lanes of a [`RoadBuilder`],
+/// kept between transformations to reuse the allocations
+#[derive(Debug, Default)]
+pub(in crate::transform::tags_to_lanes) struct LaneBuffers {
+    forward: VecDeque<LaneBuilder>,
+    backward: VecDeque<LaneBuilder>,
+}
+
+#[derive(Clone)]
 pub(in crate::transform::tags_to_lanes) struct RoadBuilder {
     forward_lanes: VecDeque<LaneBuilder>,
     backward_lanes: VecDeque<LaneBuilder>,
     pub(crate) highway: Highway,
     pub(crate) oneway: Oneway,
+    pub(crate) separately_mapped: SeparatelyMapped,
 }
 
 impl RoadBuilder {
@@ -152,8 +267,9 @@ impl RoadBuilder {
     pub(crate) fn from(
         generic_schemes: &Schemes,
         crate_schemes: &TagSchemes,
-        tags: &Tags,
+        tags: &TagsRef<'_>,
         locale: &Locale,
+        buffers: LaneBuffers,
         warnings: &mut RoadWarnings,
     ) -> Result<Self, RoadError> {
         let oneway = crate_schemes.oneway;
@@ -181,7 +297,11 @@ impl RoadBuilder {
             },
         };
 
-        let designated = if tags.is("access", "no")
+        let cycleway = highway.r#type() == HighwayType::Cycleway;
+
+        let designated = if cycleway {
+            Designated::Bicycle
+        } else if tags.is("access", "no")
             && (tags.is("bus", "yes") || tags.is("psv", "yes")) // West Seattle
             || tags
                 .get("motor_vehicle:conditional")
@@ -194,27 +314,29 @@ impl RoadBuilder {
             Designated::Motor
         };
 
-        const MAXSPEED: TagKey = TagKey::from_static("maxspeed");
-        let max_speed = match tags.get(&MAXSPEED).map(str::parse::<Speed>).transpose() {
-            Ok(max_speed) => max_speed,
-            Err(e) => {
-                warnings.push(TagsToLanesMsg::unsupported(
-                    e.to_string(),
-                    tags.subset(&[MAXSPEED]),
-                ));
-                None
-            },
-        };
+        let max_speed = max_speed::way_max_speed(tags, highway.r#type(), locale, warnings);
+
+        // Unknown surfaces are left unset, the same as other way attributes
+        let surface = *generic_schemes.surface.as_ref().unwrap_or(&None);
 
-        let width = locale.travel_width(&designated, highway.r#type());
+        // Expressways are upgraded similar to a motorroad
+        let expressway = matches!(generic_schemes.expressway, Ok(Some(Expressway::Yes)));
+
+        let width = if cycleway {
+            locale.cycleway_width(oneway.into())
+        } else if expressway {
+            locale.expressway_travel_width(&designated, highway.r#type())
+        } else {
+            locale.travel_width(&designated, None, highway.r#type())
+        };
         let width = Width {
-            min: Infer::None,
+            min: Infer::Default(locale.min_travel_width(&designated)),
             target: Infer::Default(width),
             max: Infer::None,
         };
 
         let bus_lane_counts =
-            BusLaneCount::from_tags(&crate_schemes.busway, tags, locale, warnings);
+            BusLaneCount::from_tags(&crate_schemes.busway, &crate_schemes.lanes_bus);
         let centre_turn_lanes = CentreTurnLaneScheme::from_tags(tags, oneway, locale, warnings);
         let lane_counts = Counts::new(
             tags,
@@ -225,8 +347,31 @@ impl RoadBuilder {
             locale,
             warnings,
         );
+        #[cfg(feature = "log")]
         log::trace!("lane counts: {lane_counts:?}");
 
+        let LaneBuffers {
+            forward: mut forward_lanes,
+            backward: mut backward_lanes,
+        } = buffers;
+        forward_lanes.clear();
+        backward_lanes.clear();
+
+        let defaults = locale.defaults();
+        let default_access = |access: Option<AccessValue>| {
+            access.map_or(Infer::None, |access| {
+                Infer::Default(LaneAccessAndDirection {
+                    access,
+                    direction: None,
+                })
+            })
+        };
+        let access = Access {
+            foot: default_access(defaults.foot_access(highway.r#type(), expressway)),
+            bicycle: default_access(defaults.bicycle_access(highway.r#type(), expressway)),
+            ..Default::default()
+        };
+
         let road = if let Counts::Directional {
             forward,
             backward,
@@ -235,33 +380,45 @@ impl RoadBuilder {
         {
             // These are ordered from the road center, going outwards. Most of the members of fwd_side will
             // have Direction::Forward, but there can be exceptions with two-way cycletracks.
-            let mut forward_lanes: VecDeque<_> = iter::repeat_with(|| LaneBuilder {
-                r#type: Infer::Default(LaneType::Travel),
-                direction: Infer::Default(Direction::Forward),
-                designated: Infer::Default(designated),
-                max_speed: Infer::direct(max_speed),
-                width: width.clone(),
-                ..Default::default()
-            })
-            .take(forward.some().unwrap_or(0))
-            .collect();
-            let backward_lanes = iter::repeat_with(|| LaneBuilder {
-                r#type: Infer::Default(LaneType::Travel),
-                direction: Infer::Default(Direction::Backward),
-                designated: Infer::Default(designated),
-                max_speed: Infer::direct(max_speed),
-                width: width.clone(),
-                ..Default::default()
-            })
-            .take(backward.some().unwrap_or(0))
-            .collect();
+            forward_lanes.extend(
+                iter::repeat_with(|| LaneBuilder {
+                    r#type: Infer::Default(LaneType::Travel),
+                    direction: Infer::Default(if oneway == Oneway::Reversible {
+                        Direction::Reversible
+                    } else {
+                        Direction::Forward
+                    }),
+                    designated: Infer::Default(designated),
+                    max_speed,
+                    surface: Infer::direct(surface),
+                    width: width.clone(),
+                    access: access.clone(),
+                    ..Default::default()
+                })
+                .take(forward.some().unwrap_or(0)),
+            );
+            backward_lanes.extend(
+                iter::repeat_with(|| LaneBuilder {
+                    r#type: Infer::Default(LaneType::Travel),
+                    direction: Infer::Default(Direction::Backward),
+                    designated: Infer::Default(designated),
+                    max_speed,
+                    surface: Infer::direct(surface),
+                    width: width.clone(),
+                    access: access.clone(),
+                    ..Default::default()
+                })
+                .take(backward.some().unwrap_or(0)),
+            );
 
             if centre_turn_lane.some().unwrap_or(false) {
                 forward_lanes.push_front(LaneBuilder {
                     r#type: Infer::Default(LaneType::Travel),
                     direction: Infer::Default(Direction::Both),
                     designated: Infer::Default(designated),
+                    surface: Infer::direct(surface),
                     width,
+                    access,
                     ..Default::default()
                 });
             }
@@ -271,19 +428,29 @@ impl RoadBuilder {
                 backward_lanes,
                 highway: highway.clone(),
                 oneway,
+                separately_mapped: SeparatelyMapped::default(),
             }
         } else {
+            forward_lanes.push_back(LaneBuilder {
+                r#type: Infer::Default(LaneType::Travel),
+                direction: Infer::Default(if oneway == Oneway::Alternating {
+                    Direction::Alternating
+                } else {
+                    Direction::Both
+                }),
+                designated: Infer::Default(designated),
+                max_speed,
+                surface: Infer::direct(surface),
+                width,
+                access,
+                ..Default::default()
+            });
             RoadBuilder {
-                forward_lanes: VecDeque::from(vec![LaneBuilder {
-                    r#type: Infer::Default(LaneType::Travel),
-                    direction: Infer::Default(Direction::Both),
-                    designated: Infer::Default(designated),
-                    width,
-                    ..Default::default()
-                }]),
-                backward_lanes: VecDeque::new(),
+                forward_lanes,
+                backward_lanes,
                 highway: highway.clone(),
                 oneway,
+                separately_mapped: SeparatelyMapped::default(),
             }
         };
 
@@ -357,6 +524,40 @@ impl RoadBuilder {
     pub(crate) fn push_backward_outside(&mut self, lane: LaneBuilder) {
         self.backward_lanes.push_back(lane);
     }
+    /// Note a mode mapped as a separate way on the given side
+    pub(crate) fn push_separately_mapped(&mut self, side: DrivingSide, designated: Designated) {
+        let modes = match side {
+            DrivingSide::Left => &mut self.separately_mapped.left,
+            DrivingSide::Right => &mut self.separately_mapped.right,
+        };
+        if !modes.contains(&designated) {
+            modes.push(designated);
+        }
+    }
+    /// Get forward lane by index from the centre outwards
+    pub(crate) fn forward_mut(&mut self, index: usize) -> Option<&mut LaneBuilder> {
+        self.forward_lanes.get_mut(index)
+    }
+    /// Get backward lane by index from the centre outwards
+    pub(crate) fn backward_mut(&mut self, index: usize) -> Option<&mut LaneBuilder> {
+        self.backward_lanes.get_mut(index)
+    }
+    /// Insert forward lane by index from the centre outwards
+    pub(crate) fn insert_forward(&mut self, index: usize, lane: LaneBuilder) {
+        self.forward_lanes.insert(index, lane);
+    }
+    /// Insert backward lane by index from the centre outwards
+    pub(crate) fn insert_backward(&mut self, index: usize, lane: LaneBuilder) {
+        self.backward_lanes.insert(index, lane);
+    }
+    /// Get forward lanes from the centre outwards
+    pub(crate) fn forward_iter(&self) -> impl Iterator<Item = &LaneBuilder> {
+        self.forward_lanes.iter()
+    }
+    /// Get backward lanes from the centre outwards
+    pub(crate) fn backward_iter(&self) -> impl Iterator<Item = &LaneBuilder> {
+        self.backward_lanes.iter()
+    }
     /// Get lanes left to right
     pub(crate) fn lanes_ltr<'this>(
         &'this self,
@@ -438,21 +639,68 @@ impl RoadBuilder {
         }
     }
 
+    /// Get the lanes described by the values of a `:lanes` key, paired with their value
+    ///
+    /// Returns `None` if the number of values does not match the number of lanes.
+    pub(crate) fn lane_dependent_mut<'this, T>(
+        &'this mut self,
+        lanes: LaneDependent<T>,
+        locale: &Locale,
+    ) -> Option<Vec<(&'this mut LaneBuilder, T)>> {
+        match lanes {
+            LaneDependent::LeftToRight(values) => (values.len() == self.len())
+                .then(|| self.lanes_ltr_mut(locale).zip(values).collect()),
+            LaneDependent::Forward(values) => (values.len() == self.forward_len())
+                .then(|| self.forward_ltr_mut(locale).zip(values).collect()),
+            LaneDependent::Backward(values) => (values.len() == self.backward_len())
+                .then(|| self.backward_ltr_mut(locale).zip(values).collect()),
+            LaneDependent::ForwardBackward { forward, backward } => {
+                if forward.len() != self.forward_len() || backward.len() != self.backward_len() {
+                    return None;
+                }
+                let ltr = |lanes: &'this mut VecDeque<LaneBuilder>| -> Box<dyn Iterator<Item = &'this mut LaneBuilder>> {
+                    match locale.driving_side {
+                        DrivingSide::Left => Box::new(lanes.iter_mut().rev()),
+                        DrivingSide::Right => Box::new(lanes.iter_mut()),
+                    }
+                };
+                Some(
+                    ltr(&mut self.forward_lanes)
+                        .zip(forward)
+                        .chain(ltr(&mut self.backward_lanes).zip(backward))
+                        .collect(),
+                )
+            },
+        }
+    }
+
     /// Consume Road Builder to return Lanes left to right
     // TODO: a refactor...
     #[allow(
         clippy::needless_collect,
         clippy::unnecessary_wraps,
-        clippy::too_many_lines
+        clippy::too_many_lines,
+        clippy::integer_arithmetic,
+        clippy::type_complexity
     )]
     pub(crate) fn into_ltr(
         mut self,
-        tags: &Tags,
+        tags: &TagsRef<'_>,
         locale: &Locale,
         include_separators: bool,
         warnings: &mut RoadWarnings,
-    ) -> Result<(Vec<Lane>, Highway, Oneway), RoadError> {
-        let lanes: Vec<Lane> = if include_separators {
+    ) -> Result<
+        (
+            Vec<Lane>,
+            Vec<Option<LaneWidth>>,
+            Highway,
+            Oneway,
+            LaneBuffers,
+        ),
+        RoadError,
+    > {
+        let lane_count = self.forward_lanes.len() + self.backward_lanes.len();
+        let lanes: Vec<(Lane, Option<LaneWidth>)> = if include_separators {
             let forward_edge = self
                 .forward_outside()
                 .and_then(|lane| outer_edge_semantic_separator(lane, tags, locale))
@@ -484,7 +732,7 @@ impl RoadBuilder {
                     )
                 }),
                 [Some(lane), None] | [None, Some(lane)] => {
-                    lane_to_inner_edge_separator(lane.mirror()).map(Lane::mirror)
+                    lane_to_inner_edge_separator(lane.mirror(), tags).map(Lane::mirror)
                 },
                 [None, None] => return Err(RoadError::Msg(TagsToLanesMsg::internal("no lanes"))),
             };
@@ -545,65 +793,74 @@ impl RoadBuilder {
                 })
                 .collect();
 
-            let forward_lanes_with_separators: Vec<Option<Lane>> = self
+            // Collect into a single allocation:
+            // the lanes of the side drawn first are pushed from the centre outwards and reversed
+            let forward_lanes_with_separators = self
                 .forward_lanes
-                .into_iter()
+                .drain(..)
                 .map(LaneBuilder::build)
                 .map(Some)
                 .zip(
                     forward_separators
                         .into_iter()
-                        .chain(iter::once(forward_edge)),
+                        .chain(iter::once(forward_edge))
+                        .map(|separator| separator.map(|separator| (separator, None))),
                 )
                 .flat_map(|(a, b)| [a, b])
-                .collect();
-            let backward_lanes_with_separators: Vec<Option<Lane>> = self
+                .flatten();
+            let backward_lanes_with_separators = self
                 .backward_lanes
-                .into_iter()
+                .drain(..)
                 .map(LaneBuilder::build)
                 .map(Some)
                 .zip(
                     backward_separators
                         .into_iter()
-                        .chain(iter::once(backward_edge)),
+                        .chain(iter::once(backward_edge))
+                        .map(|separator| separator.map(|separator| (separator, None))),
                 )
                 .flat_map(|(a, b)| [a, b])
-                .collect();
+                .flatten();
 
+            let mut lanes = Vec::with_capacity(2 * lane_count + 1);
             match locale.driving_side {
-                DrivingSide::Left => forward_lanes_with_separators
-                    .into_iter()
-                    .rev()
-                    .chain(iter::once(middle_separator))
-                    .chain(backward_lanes_with_separators)
-                    .flatten()
-                    .collect(),
-                DrivingSide::Right => backward_lanes_with_separators
-                    .into_iter()
-                    .rev()
-                    .chain(iter::once(middle_separator))
-                    .chain(forward_lanes_with_separators)
-                    .flatten()
-                    .collect(),
+                DrivingSide::Left => {
+                    lanes.extend(forward_lanes_with_separators);
+                    lanes.reverse();
+                    lanes.extend(middle_separator.map(|separator| (separator, None)));
+                    lanes.extend(backward_lanes_with_separators);
+                },
+                DrivingSide::Right => {
+                    lanes.extend(backward_lanes_with_separators);
+                    lanes.reverse();
+                    lanes.extend(middle_separator.map(|separator| (separator, None)));
+                    lanes.extend(forward_lanes_with_separators);
+                },
             }
+            lanes
         } else {
             match locale.driving_side {
                 DrivingSide::Left => self
                     .forward_lanes
-                    .into_iter()
+                    .drain(..)
                     .rev()
-                    .chain(self.backward_lanes.into_iter())
+                    .chain(self.backward_lanes.drain(..))
                     .map(LaneBuilder::build)
                     .collect(),
                 DrivingSide::Right => self
                     .backward_lanes
-                    .into_iter()
+                    .drain(..)
                     .rev()
-                    .chain(self.forward_lanes.into_iter())
+                    .chain(self.forward_lanes.drain(..))
                     .map(LaneBuilder::build)
                     .collect(),
             }
         };
-        Ok((lanes, self.highway, self.oneway))
+        let (lanes, widths) = lanes.into_iter().unzip();
+        let buffers = LaneBuffers {
+            forward: self.forward_lanes,
+            backward: self.backward_lanes,
+        };
+        Ok((lanes, widths, self.highway, self.oneway, buffers))
     }
 }
@@ -1,11 +1,16 @@
-use celes::Country;
-use osm_tags::Tags;
+use alloc::vec;
+
+use osm_tag_schemes::{keys, HighwayType, PriorityRoad};
+use osm_tags::TagsRef;
 
 use crate::locale::Locale;
 use crate::metric::Metre;
 use crate::road::{Color, Designated, Direction, Lane, Marking, Markings, Style};
 use crate::transform::{RoadWarnings, TagsToLanesMsg};
 
+mod physical;
+pub(in crate::transform::tags_to_lanes) use physical::physical_devices;
+
 mod semantic;
 
 use semantic::{Overtake, Separator, SpeedClass};
@@ -22,7 +27,7 @@ impl From<&Separator> for crate::road::Semantic {
             Separator::Lane { .. } => Self::Lane,
             Separator::Centre { .. } => Self::Centre,
             Separator::Modal { .. } => Self::Modal,
-            Separator::_Buffer { .. } => Self::Buffer,
+            Separator::Buffer { .. } => Self::Buffer,
             Separator::Kerb { .. } => Self::Kerb,
             Separator::Verge { .. } => Self::Verge,
         }
@@ -51,17 +56,31 @@ enum DirectionChange {
 pub(in crate::transform::tags_to_lanes) fn lane_pair_to_semantic_separator(
     lanes: [&LaneBuilder; 2],
     road: &RoadBuilder,
-    tags: &Tags,
+    tags: &TagsRef<'_>,
     locale: &Locale,
     warnings: &mut RoadWarnings,
 ) -> Option<Separator> {
     let [inside, outside] = lanes;
+    // Painted buffer of a lane, e.g. between a cycle lane and the motor traffic
+    if let Some(width) = inside
+        .buffer
+        .outside
+        .some()
+        .or_else(|| outside.buffer.inside.some())
+    {
+        return Some(Separator::Buffer {
+            width,
+            style: Style::NoFill,
+        });
+    }
     let direction_change = match [inside.direction.some(), outside.direction.some()] {
-        [None | Some(Direction::Both), _] | [_, None | Some(Direction::Both)] => {
+        [Some(Direction::Forward), Some(Direction::Forward)]
+        | [Some(Direction::Backward), Some(Direction::Backward)]
+        | [Some(Direction::Reversible), Some(Direction::Reversible)] => DirectionChange::Same,
+        [None | Some(Direction::Both | Direction::Alternating | Direction::Reversible), _]
+        | [_, None | Some(Direction::Both | Direction::Alternating | Direction::Reversible)] => {
             DirectionChange::None
         },
-        [Some(Direction::Forward), Some(Direction::Forward)]
-        | [Some(Direction::Backward), Some(Direction::Backward)] => DirectionChange::Same,
         [Some(Direction::Forward), Some(Direction::Backward)]
         | [Some(Direction::Backward), Some(Direction::Forward)] => DirectionChange::Opposite,
     };
@@ -72,6 +91,17 @@ pub(in crate::transform::tags_to_lanes) fn lane_pair_to_semantic_separator(
         ],
         direction_change,
     ) {
+        // Segregated footway of a cycleway, separated by a line rather than a kerb
+        ([(_, Some(Designated::Bicycle)), (_, Some(Designated::Foot))], _)
+            if road.highway.r#type() == HighwayType::Cycleway =>
+        {
+            Some(Separator::Modal {
+                speed: inside.max_speed.map(SpeedClass::from),
+                change: LaneChange::default(),
+                inside: Designated::Bicycle,
+                outside: Designated::Foot,
+            })
+        },
         // Foot
         ([_, (_, Some(Designated::Foot))], _) => Some(Separator::Kerb {
             parking_condition: None,
@@ -121,10 +151,14 @@ fn motor_lane_pair_to_semantic_separator(
     [inside, _outside]: [&LaneBuilder; 2],
     direction_change: DirectionChange,
     road: &RoadBuilder,
-    _tags: &Tags,
+    tags: &TagsRef<'_>,
     locale: &Locale,
     _warnings: &mut RoadWarnings,
 ) -> Option<Separator> {
+    let priority_road = tags
+        .get(&keys::PRIORITY_ROAD)
+        .and_then(|priority_road| priority_road.parse::<PriorityRoad>().ok())
+        .map_or(false, PriorityRoad::has_priority);
     match road
         .lanes_ltr(locale)
         .filter(|lane| {
@@ -140,6 +174,7 @@ fn motor_lane_pair_to_semantic_separator(
             speed: inside.max_speed.map(SpeedClass::from),
             overtake: Overtake::default(),
             more_than_2_lanes: false,
+            priority_road,
         }),
         _ => match direction_change {
             DirectionChange::Same => Some(Separator::Lane {
@@ -150,6 +185,7 @@ fn motor_lane_pair_to_semantic_separator(
                 speed: inside.max_speed.map(SpeedClass::from),
                 overtake: Overtake::default(),
                 more_than_2_lanes: true,
+                priority_road,
             }),
         },
     }
@@ -162,7 +198,7 @@ pub(in crate::transform::tags_to_lanes) fn semantic_separator_to_lane(
     [inside, outside]: [&LaneBuilder; 2],
     separator: &Separator,
     _road: &RoadBuilder,
-    tags: &Tags,
+    tags: &TagsRef<'_>,
     locale: &Locale,
     warnings: &mut RoadWarnings,
 ) -> Option<Lane> {
@@ -178,21 +214,17 @@ pub(in crate::transform::tags_to_lanes) fn semantic_separator_to_lane(
         }),
         // Shoulder
         Separator::Shoulder { .. } => {
-            if tags.is("motorroad", "yes") {
-                if let Some(c) = &locale.country {
-                    if c == &Country::the_netherlands() {
-                        return Some(Lane::Separator {
-                            semantic: Some(separator.into()),
-                            // https://puc.overheid.nl/rijkswaterstaat/doc/PUC_125514_31/
-                            // 4.2.5 and 4.2.6
-                            markings: Some(Markings::new(vec![Marking {
-                                style: Style::SolidLine,
-                                color: Some(Color::White),
-                                width: Some(Marking::DEFAULT_WIDTH),
-                            }])),
-                        });
-                    }
-                }
+            if tags.is("motorroad", "yes") && locale.is_netherlands() {
+                return Some(Lane::Separator {
+                    semantic: Some(separator.into()),
+                    // https://puc.overheid.nl/rijkswaterstaat/doc/PUC_125514_31/
+                    // 4.2.5 and 4.2.6
+                    markings: Some(Markings::new(vec![Marking {
+                        style: Style::SolidLine,
+                        color: Some(Color::White),
+                        width: Some(Marking::DEFAULT_WIDTH),
+                    }])),
+                });
             }
             Some(Lane::Separator {
                 semantic: Some(separator.into()),
@@ -204,50 +236,58 @@ pub(in crate::transform::tags_to_lanes) fn semantic_separator_to_lane(
             })
         },
         Separator::Centre {
-            more_than_2_lanes, ..
+            more_than_2_lanes,
+            priority_road,
+            ..
         } => {
-            if tags.is("motorroad", "yes") {
-                if let Some(c) = &locale.country {
-                    if c == &Country::the_netherlands() {
-                        return Some(Lane::Separator {
-                            semantic: Some(separator.into()),
-                            // https://puc.overheid.nl/rijkswaterstaat/doc/PUC_125514_31/
-                            // 4.2.5 and 4.2.6
-                            markings: Some(Markings::new(vec![
-                                Marking {
-                                    style: Style::BrokenLine,
-                                    color: Some(Color::White),
-                                    width: Some(Metre::new(0.15_f64)),
-                                },
-                                Marking {
-                                    style: Style::SolidLine,
-                                    color: Some(Color::Green),
-                                    width: Some(2.0_f64 * Marking::DEFAULT_SPACE),
-                                },
-                                Marking {
-                                    style: Style::BrokenLine,
-                                    color: Some(Color::White),
-                                    width: Some(Metre::new(0.15_f64)),
-                                },
-                            ])),
-                        });
-                    }
-                }
-            }
-            if let Some(c) = &locale.country {
-                if c == &Country::the_united_kingdom_of_great_britain_and_northern_ireland() {
-                    return Some(Lane::Separator {
-                        semantic: Some(separator.into()),
-                        // https://assets.publishing.service.gov.uk/government/uploads/system/uploads/attachment_data/file/782724/traffic-signs-manual-chapter-03.pdf
-                        // Traffic Signs Manual, Chapter 3
-                        // Page 90, 9.3.3
-                        markings: Some(Markings::new(vec![Marking {
+            if tags.is("motorroad", "yes") && locale.is_netherlands() {
+                return Some(Lane::Separator {
+                    semantic: Some(separator.into()),
+                    // https://puc.overheid.nl/rijkswaterstaat/doc/PUC_125514_31/
+                    // 4.2.5 and 4.2.6
+                    markings: Some(Markings::new(vec![
+                        Marking {
                             style: Style::BrokenLine,
                             color: Some(Color::White),
-                            width: Some(Metre::new(0.100_f64)),
-                        }])),
-                    });
-                }
+                            width: Some(Metre::new(0.15_f64)),
+                        },
+                        Marking {
+                            style: Style::SolidLine,
+                            color: Some(Color::Green),
+                            width: Some(2.0_f64 * Marking::DEFAULT_SPACE),
+                        },
+                        Marking {
+                            style: Style::BrokenLine,
+                            color: Some(Color::White),
+                            width: Some(Metre::new(0.15_f64)),
+                        },
+                    ])),
+                });
+            }
+            if locale.is_united_kingdom() {
+                return Some(Lane::Separator {
+                    semantic: Some(separator.into()),
+                    // https://assets.publishing.service.gov.uk/government/uploads/system/uploads/attachment_data/file/782724/traffic-signs-manual-chapter-03.pdf
+                    // Traffic Signs Manual, Chapter 3
+                    // Page 90, 9.3.3
+                    markings: Some(Markings::new(vec![Marking {
+                        style: Style::BrokenLine,
+                        color: Some(Color::White),
+                        width: Some(Metre::new(0.100_f64)),
+                    }])),
+                });
+            }
+            if *priority_road && !*more_than_2_lanes && locale.has_solid_priority_road_centre_line()
+            {
+                // No overtaking on a priority road, so the centre line is continuous
+                return Some(Lane::Separator {
+                    semantic: Some(separator.into()),
+                    markings: Some(Markings::new(vec![Marking {
+                        style: Style::SolidLine,
+                        color: Some(locale.separator_motor_color()),
+                        width: Some(locale.separator_motor_width()),
+                    }])),
+                });
             }
             warnings.push(TagsToLanesMsg::separator_locale_unused(
                 inside.clone(),
@@ -295,34 +335,32 @@ pub(in crate::transform::tags_to_lanes) fn semantic_separator_to_lane(
             outside: designated,
             ..
         } => {
-            if let Some(c) = &locale.country {
-                if c == &Country::the_united_kingdom_of_great_britain_and_northern_ireland() {
-                    if designated == &Designated::Bus {
-                        return Some(Lane::Separator {
-                            semantic: Some(separator.into()),
-                            // https://assets.publishing.service.gov.uk/government/uploads/system/uploads/attachment_data/file/782724/traffic-signs-manual-chapter-03.pdf
-                            // Traffic Signs Manual, Chapter 3
-                            // Page 90, 9.3.3
-                            markings: Some(Markings::new(vec![Marking {
-                                style: Style::SolidLine,
-                                color: Some(Color::White),
-                                width: Some(Metre::new(0.250_f64)),
-                            }])),
-                        });
-                    }
-                    if designated == &Designated::Bicycle {
-                        return Some(Lane::Separator {
-                            semantic: Some(separator.into()),
-                            // https://assets.publishing.service.gov.uk/government/uploads/system/uploads/attachment_data/file/782724/traffic-signs-manual-chapter-03.pdf
-                            // Traffic Signs Manual, Chapter 3
-                            // Page 90, 9.3.3
-                            markings: Some(Markings::new(vec![Marking {
-                                style: Style::SolidLine,
-                                color: Some(Color::White),
-                                width: Some(Metre::new(0.150_f64)),
-                            }])),
-                        });
-                    }
+            if locale.is_united_kingdom() {
+                if designated == &Designated::Bus {
+                    return Some(Lane::Separator {
+                        semantic: Some(separator.into()),
+                        // https://assets.publishing.service.gov.uk/government/uploads/system/uploads/attachment_data/file/782724/traffic-signs-manual-chapter-03.pdf
+                        // Traffic Signs Manual, Chapter 3
+                        // Page 90, 9.3.3
+                        markings: Some(Markings::new(vec![Marking {
+                            style: Style::SolidLine,
+                            color: Some(Color::White),
+                            width: Some(Metre::new(0.250_f64)),
+                        }])),
+                    });
+                }
+                if designated == &Designated::Bicycle {
+                    return Some(Lane::Separator {
+                        semantic: Some(separator.into()),
+                        // https://assets.publishing.service.gov.uk/government/uploads/system/uploads/attachment_data/file/782724/traffic-signs-manual-chapter-03.pdf
+                        // Traffic Signs Manual, Chapter 3
+                        // Page 90, 9.3.3
+                        markings: Some(Markings::new(vec![Marking {
+                            style: Style::SolidLine,
+                            color: Some(Color::White),
+                            width: Some(Metre::new(0.150_f64)),
+                        }])),
+                    });
                 }
             }
             warnings.push(TagsToLanesMsg::separator_locale_unused(
@@ -342,21 +380,27 @@ pub(in crate::transform::tags_to_lanes) fn semantic_separator_to_lane(
             semantic: Some(separator.into()),
             markings: None,
         }),
-        // TODO
-        Separator::_Buffer { .. } => {
-            warnings.push(TagsToLanesMsg::separator_unknown(
-                inside.clone(),
-                outside.clone(),
-            ));
-            Some(Lane::Separator {
-                semantic: Some(separator.into()),
-                markings: Some(Markings::new(vec![Marking {
-                    style: Style::BrokenLine,
-                    color: Some(Color::Red),
+        // Lines either side of the painted area
+        Separator::Buffer { width, style } => Some(Lane::Separator {
+            semantic: Some(separator.into()),
+            markings: Some(Markings::new(vec![
+                Marking {
+                    style: Style::SolidLine,
+                    color: Some(Color::White),
                     width: Some(Marking::DEFAULT_WIDTH),
-                }])),
-            })
-        },
+                },
+                Marking {
+                    style: *style,
+                    color: None,
+                    width: Some(*width),
+                },
+                Marking {
+                    style: Style::SolidLine,
+                    color: Some(Color::White),
+                    width: Some(Marking::DEFAULT_WIDTH),
+                },
+            ])),
+        }),
     }
 }
 
@@ -366,19 +410,16 @@ pub(in crate::transform::tags_to_lanes) fn semantic_separator_to_lane(
 #[allow(clippy::unnecessary_wraps)]
 pub(super) fn outer_edge_semantic_separator(
     lane: &LaneBuilder,
-    tags: &Tags,
+    tags: &TagsRef<'_>,
     locale: &Locale,
 ) -> Option<EdgeSeparator> {
-    if lane.r#type.some() == Some(LaneType::Travel) {
-        if let Some(c) = &locale.country {
-            if c == &Country::the_united_kingdom_of_great_britain_and_northern_ireland()
-                && tags.is("parking:condition:both", "no_stopping")
-            {
-                return Some(EdgeSeparator::Hard {
-                    parking_condition: Some(ParkingCondition::NoStopping),
-                });
-            }
-        }
+    if lane.r#type.some() == Some(LaneType::Travel)
+        && locale.is_united_kingdom()
+        && tags.is("parking:condition:both", "no_stopping")
+    {
+        return Some(EdgeSeparator::Hard {
+            parking_condition: Some(ParkingCondition::NoStopping),
+        });
     }
     None
 }
@@ -389,7 +430,7 @@ pub(super) fn outer_edge_semantic_separator(
 pub(in crate::transform::tags_to_lanes) fn semantic_edge_separator_to_lane(
     separator: &EdgeSeparator,
     _road: &RoadBuilder,
-    _tags: &Tags,
+    _tags: &TagsRef<'_>,
     _locale: &Locale,
     _warnings: &mut RoadWarnings,
 ) -> Option<Lane> {
@@ -421,10 +462,16 @@ pub(in crate::transform::tags_to_lanes) fn semantic_edge_separator_to_lane(
 /// what should the separator be.
 /// Lanes are defined inside to outside
 #[allow(clippy::unnecessary_wraps)]
-pub(super) fn lane_to_inner_edge_separator(_lane: &LaneBuilder) -> Option<Lane> {
+pub(super) fn lane_to_inner_edge_separator(
+    _lane: &LaneBuilder,
+    tags: &TagsRef<'_>,
+) -> Option<Lane> {
     Some(Lane::Separator {
-        // TODO, semantic separator
-        semantic: None,
+        // The inner edge of a dual carriageway is beside the median, at the centre of the road
+        // TODO, semantic separator otherwise
+        semantic: tags
+            .is(&keys::DUAL_CARRIAGEWAY, "yes")
+            .then_some(crate::road::Semantic::Centre),
         markings: Some(Markings::new(vec![Marking {
             style: Style::SolidLine,
             color: Some(Color::White),
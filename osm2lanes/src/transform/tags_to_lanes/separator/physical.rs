@@ -0,0 +1,138 @@
+use osm_tag_schemes::{keys, RumbleStrips, Separation};
+use osm_tags::TagsRef;
+
+use crate::locale::Locale;
+use crate::metric::Metre;
+use crate::road::{Lane, Marking, Markings, Semantic, Style};
+use crate::transform::tags_to_lanes::TagsTyped;
+use crate::transform::{RoadWarnings, TagsToLanesMsg};
+
+/// Style of the device, `None` without physical separation
+fn separation_style(separation: Separation) -> Option<Style> {
+    match separation {
+        Separation::RumbleStrip => Some(Style::RumbleStrip),
+        Separation::BottsDots => Some(Style::BottsDots),
+        Separation::RaisedSeparator => Some(Style::RaisedSeparator),
+        Separation::Studs => Some(Style::Studs),
+        Separation::No => None,
+    }
+}
+
+/// Physical device of the given style, of no particular colour
+fn device(style: Style) -> Marking {
+    Marking {
+        style,
+        color: None,
+        width: Some(match style {
+            Style::RumbleStrip | Style::RaisedSeparator => Metre::new(0.3_f64),
+            _ => Marking::DEFAULT_SPACE,
+        }),
+    }
+}
+
+/// Add the device between the painted markings
+fn insert_middle(markings: &mut Markings, marking: Marking) {
+    if !markings
+        .iter()
+        .any(|existing| existing.style == marking.style)
+    {
+        let middle = markings.len() / 2;
+        markings.insert(middle, marking);
+    }
+}
+
+/// Replace the painted broken lines with botts' dots,
+/// which make up the line by themselves
+fn botts_dots(markings: &mut Markings) {
+    let mut replaced = false;
+    for marking in markings.iter_mut() {
+        if matches!(
+            marking.style,
+            Style::BrokenLine | Style::DashedLine | Style::DottedLine
+        ) {
+            marking.style = Style::BottsDots;
+            replaced = true;
+        }
+    }
+    if !replaced {
+        insert_middle(markings, device(Style::BottsDots));
+    }
+}
+
+/// Add the physical devices to the markings of the separators,
+/// from the tags or the locale defaults.
+///
+/// Rumble strips belong to the centre and the shoulder separators,
+/// other separation to the separators between lanes.
+pub(in crate::transform::tags_to_lanes) fn physical_devices(
+    lanes: &mut [Lane],
+    tags: &TagsRef<'_>,
+    locale: &Locale,
+    warnings: &mut RoadWarnings,
+) {
+    let rumble_strips: Option<RumbleStrips> = tags.get_enum(&keys::RUMBLE_STRIPS, warnings);
+    let separation: Option<Separation> = tags.get_enum(&keys::SEPARATION, warnings);
+
+    let mut has_shoulder = false;
+    let mut has_lane = false;
+    let mut previous_shoulder = false;
+    for lane in lanes.iter_mut() {
+        let is_shoulder = matches!(lane, Lane::Shoulder { .. });
+        if let Lane::Separator {
+            semantic: Some(semantic),
+            markings: Some(markings),
+        } = lane
+        {
+            match semantic {
+                Semantic::Centre => {
+                    if rumble_strips.map_or(false, RumbleStrips::centre) {
+                        insert_middle(markings, device(Style::RumbleStrip));
+                    }
+                },
+                Semantic::Shoulder => {
+                    has_shoulder = true;
+                    if rumble_strips.map_or(false, RumbleStrips::edge)
+                        && !markings.iter().any(|m| m.style == Style::RumbleStrip)
+                    {
+                        // On the side of the shoulder
+                        let index = if previous_shoulder { 0 } else { markings.len() };
+                        markings.insert(index, device(Style::RumbleStrip));
+                    }
+                },
+                Semantic::Lane | Semantic::Modal => {
+                    has_lane = true;
+                    match separation {
+                        Some(Separation::BottsDots) => botts_dots(markings),
+                        Some(separation) => {
+                            if let Some(style) = separation_style(separation) {
+                                insert_middle(markings, device(style));
+                            }
+                        },
+                        None => {
+                            // https://dot.ca.gov/programs/safety-programs/camutcd
+                            // Lane lines are marked with raised pavement markers
+                            if *semantic == Semantic::Lane && locale.is_california() {
+                                botts_dots(markings);
+                            }
+                        },
+                    }
+                },
+                _ => {},
+            }
+        }
+        previous_shoulder = is_shoulder;
+    }
+
+    if rumble_strips.map_or(false, RumbleStrips::edge) && !has_shoulder {
+        warnings.push(TagsToLanesMsg::unsupported(
+            "edge rumble strips without a shoulder",
+            tags.subset(&[keys::RUMBLE_STRIPS]),
+        ));
+    }
+    if separation.map_or(false, |separation| separation != Separation::No) && !has_lane {
+        warnings.push(TagsToLanesMsg::unsupported(
+            "separation without a separator between lanes",
+            tags.subset(&[keys::SEPARATION]),
+        ));
+    }
+}
@@ -87,6 +87,8 @@ pub enum Separator {
         speed: Infer<SpeedClass>,
         overtake: Overtake,
         more_than_2_lanes: bool,
+        /// The road has priority at junctions, see `priority_road=*`
+        priority_road: bool,
     },
     /// Road paint between different modes
     // TODO: solve directionality
@@ -96,8 +98,8 @@ pub enum Separator {
         inside: Designated,
         outside: Designated,
     },
-    /// Painted area
-    _Buffer { width: Metre, style: Style },
+    /// Painted area, see `cycleway:SIDE:buffer=*`
+    Buffer { width: Metre, style: Style },
     /// Kerb step
     // TODO: solve directionality
     Kerb {
@@ -0,0 +1,200 @@
+use alloc::vec::Vec;
+
+use osm_tag_schemes::keys;
+use osm_tags::TagsRef;
+
+use super::{tags_to_lanes, Config, LaneWidth, TagsToLanesMsg};
+use crate::locale::Locale;
+use crate::road::{Lane, Semantic};
+use crate::transform::{RoadError, RoadFromTags};
+
+/// Side of the road, relative to the direction of the road's way
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SidepathSide {
+    Left,
+    Right,
+}
+
+/// A separately mapped way alongside a road, such as a sidewalk or cycle track
+///
+/// The way is assumed to be drawn in the same direction as the road.
+#[derive(Clone, Copy, Debug)]
+pub struct Sidepath<'tags> {
+    pub tags: &'tags TagsRef<'tags>,
+    pub side: SidepathSide,
+    /// The caller has associated the way with the road,
+    /// otherwise the way must be tagged as a sidepath with `footway=sidewalk` or `is_sidepath=yes`
+    pub associated: bool,
+}
+
+impl Sidepath<'_> {
+    fn is_associated(&self) -> bool {
+        self.associated
+            || self.tags.is(&keys::FOOTWAY, "sidewalk")
+            || self.tags.is(&keys::IS_SIDEPATH, "yes")
+    }
+}
+
+/// Like [`tags_to_lanes`],
+/// but merge the lanes of separately mapped sidepaths into a single cross-section of the road.
+///
+/// Sidepaths are added to the outside of the road in the order given,
+/// those that are not associated with the road are skipped with a warning.
+///
+/// # Errors
+///
+/// See [`tags_to_lanes`], for the road or any of the sidepaths.
+pub fn tags_to_lanes_with_sidepaths(
+    tags: &TagsRef<'_>,
+    sidepaths: &[Sidepath<'_>],
+    locale: &Locale,
+    config: &Config,
+) -> Result<RoadFromTags, RoadError> {
+    let RoadFromTags {
+        mut road,
+        mut warnings,
+        mut widths,
+    } = tags_to_lanes(tags, locale, config)?;
+
+    for sidepath in sidepaths {
+        if !sidepath.is_associated() {
+            warnings.push(TagsToLanesMsg::unsupported(
+                "separate way is not a sidepath",
+                sidepath
+                    .tags
+                    .subset(&[keys::HIGHWAY, keys::FOOTWAY, keys::IS_SIDEPATH]),
+            ));
+            continue;
+        }
+        let RoadFromTags {
+            road: sidepath_road,
+            warnings: sidepath_warnings,
+            widths: sidepath_widths,
+        } = tags_to_lanes(sidepath.tags, locale, config)?;
+        warnings.append(sidepath_warnings);
+
+        let (mut lanes, mut sidepath_widths): (Vec<Lane>, Vec<Option<LaneWidth>>) = sidepath_road
+            .lanes
+            .into_iter()
+            .zip(sidepath_widths)
+            .filter(|(lane, _)| !lane.is_separator())
+            .unzip();
+        let verge = Lane::Separator {
+            semantic: Some(Semantic::Verge),
+            markings: None,
+        };
+        match sidepath.side {
+            SidepathSide::Left => {
+                if config.include_separators {
+                    lanes.push(verge);
+                    sidepath_widths.push(None);
+                }
+                lanes.append(&mut road.lanes);
+                road.lanes = lanes;
+                sidepath_widths.append(&mut widths);
+                widths = sidepath_widths;
+            },
+            SidepathSide::Right => {
+                if config.include_separators {
+                    road.lanes.push(verge);
+                    widths.push(None);
+                }
+                road.lanes.append(&mut lanes);
+                widths.append(&mut sidepath_widths);
+            },
+        }
+    }
+
+    if config.error_on_warnings && !warnings.is_empty() {
+        return config.contextualise(Err(warnings.into()));
+    }
+
+    config.contextualise(Ok(RoadFromTags {
+        road,
+        warnings,
+        widths,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use osm_tags::Tags;
+
+    use super::{tags_to_lanes, tags_to_lanes_with_sidepaths, Sidepath, SidepathSide};
+    use crate::locale::Locale;
+    use crate::road::{Designated, Lane, SeparatelyMapped};
+    use crate::transform::TagsToLanesConfig;
+
+    #[test]
+    fn test_sidepaths() {
+        let locale = Locale::builder().build();
+        let road = Tags::from_pairs([
+            ("highway", "tertiary"),
+            ("lanes", "2"),
+            ("sidewalk", "separate"),
+        ])
+        .unwrap();
+        let sidewalk = Tags::from_pairs([("highway", "footway"), ("footway", "sidewalk")]).unwrap();
+        let unrelated = Tags::from_pairs([("highway", "footway")]).unwrap();
+        let output = tags_to_lanes_with_sidepaths(
+            &road,
+            &[
+                Sidepath {
+                    tags: &sidewalk,
+                    side: SidepathSide::Right,
+                    associated: false,
+                },
+                Sidepath {
+                    tags: &unrelated,
+                    side: SidepathSide::Left,
+                    associated: false,
+                },
+            ],
+            &locale,
+            &TagsToLanesConfig::default(),
+        )
+        .unwrap();
+        // Only the unrelated footway adds a warning
+        let expected = tags_to_lanes(&road, &locale, &TagsToLanesConfig::default()).unwrap();
+        assert_eq!(
+            output.warnings.as_slice().len(),
+            expected.warnings.as_slice().len() + 1
+        );
+        let lanes: Vec<_> = output
+            .road
+            .lanes
+            .iter()
+            .filter(|lane| !lane.is_separator())
+            .collect();
+        assert_eq!(lanes.len(), 3);
+        assert!(matches!(
+            lanes.last(),
+            Some(Lane::Travel {
+                designated: Designated::Foot,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_separately_mapped() {
+        let locale = Locale::builder().build();
+        let road = Tags::from_pairs([
+            ("highway", "tertiary"),
+            ("lanes", "2"),
+            ("cycleway:left", "separate"),
+            ("sidewalk:right", "separate"),
+        ])
+        .unwrap();
+        let output = tags_to_lanes(&road, &locale, &TagsToLanesConfig::default()).unwrap();
+        assert_eq!(
+            output.road.separately_mapped,
+            Some(SeparatelyMapped {
+                left: vec![Designated::Bicycle],
+                right: vec![Designated::Foot],
+            })
+        );
+        assert!(!output.road.lanes.iter().any(Lane::is_bicycle));
+        assert!(!output.road.lanes.iter().any(Lane::is_foot));
+    }
+}
@@ -0,0 +1,49 @@
+use alloc::vec::Vec;
+
+use osm_tag_schemes::{keys, Surface};
+use osm_tags::TagsRef;
+
+use super::infer::Infer;
+use super::road::RoadBuilder;
+use super::TagsToLanesMsg;
+use crate::locale::Locale;
+use crate::transform::RoadWarnings;
+
+/// Per lane surfaces from `surface:lanes=*`, overriding `surface=*` of the way.
+///
+/// An empty value keeps the surface of the way for that lane.
+pub(super) fn surface_lanes(
+    tags: &TagsRef<'_>,
+    locale: &Locale,
+    road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
+) {
+    if let Some(value) = tags.get(&keys::SURFACE_LANES) {
+        let surfaces = value
+            .split('|')
+            .map(|surface| {
+                if surface.is_empty() {
+                    Ok(None)
+                } else {
+                    surface.parse::<Surface>().map(Some)
+                }
+            })
+            .collect::<Result<Vec<Option<Surface>>, _>>();
+        match surfaces {
+            Ok(surfaces) if surfaces.len() == road.len() => {
+                for (lane, surface) in road.lanes_ltr_mut(locale).zip(surfaces) {
+                    if let Some(surface) = surface {
+                        lane.surface = Infer::Direct(surface);
+                    }
+                }
+            },
+            Ok(_surfaces) => warnings.push(TagsToLanesMsg::unsupported(
+                "lane count mismatch",
+                tags.subset(&[keys::SURFACE_LANES]),
+            )),
+            Err(_parse_error) => {
+                warnings.push(TagsToLanesMsg::unsupported_tag(keys::SURFACE_LANES, value));
+            },
+        }
+    }
+}
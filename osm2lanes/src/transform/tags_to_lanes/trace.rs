@@ -0,0 +1,457 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use osm_tags::TagsRef;
+
+use super::road::RoadBuilder;
+use super::TagsToLanesMsg;
+use crate::locale::{DrivingSide, Locale};
+use crate::road::{align, Lane, LaneConnection, Printable};
+use crate::transform::error::{RoadError, RoadWarnings};
+use crate::transform::RoadFromTags;
+
+/// How a lane changed during a stage of the transformation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TraceChange {
+    Unchanged,
+    Added,
+    Modified,
+    /// The lane of the previous stage no longer exists, shown where it was
+    Removed,
+}
+
+/// A lane, as inferred at the end of a stage, or as it was before the stage removed it
+#[derive(Clone, Debug)]
+pub struct TraceLane {
+    /// The inferred values, including how confident the inference is
+    pub inferred: String,
+    pub change: TraceChange,
+}
+
+/// The road after a stage of the transformation
+#[derive(Clone, Debug)]
+pub struct TraceStep {
+    /// Name of the module
+    pub stage: &'static str,
+    /// What the module is responsible for
+    pub description: &'static str,
+    /// Lanes from left to right
+    pub lanes: Vec<TraceLane>,
+    /// Warnings raised during this stage
+    pub warnings: Vec<TagsToLanesMsg>,
+}
+
+/// Record of the intermediate decisions made while transforming tags to lanes
+#[derive(Clone, Debug, Default)]
+pub struct Trace {
+    pub steps: Vec<TraceStep>,
+    previous: [Vec<String>; 2],
+    warnings_seen: usize,
+}
+
+impl Trace {
+    #[allow(clippy::use_debug)]
+    pub(in crate::transform::tags_to_lanes) fn record(
+        &mut self,
+        stage: &'static str,
+        description: &'static str,
+        road: &RoadBuilder,
+        locale: &Locale,
+        warnings: &RoadWarnings,
+    ) {
+        let forward: Vec<String> = road
+            .forward_iter()
+            .map(|lane| format!("{:?}", lane))
+            .collect();
+        let backward: Vec<String> = road
+            .backward_iter()
+            .map(|lane| format!("{:?}", lane))
+            .collect();
+        let [previous_forward, previous_backward] = &self.previous;
+        let forward = changes(previous_forward, &forward);
+        let backward = changes(previous_backward, &backward);
+        let lanes = match locale.driving_side {
+            DrivingSide::Left => forward
+                .iter()
+                .rev()
+                .chain(backward.iter())
+                .cloned()
+                .collect(),
+            DrivingSide::Right => backward
+                .iter()
+                .rev()
+                .chain(forward.iter())
+                .cloned()
+                .collect(),
+        };
+        let new_warnings = warnings
+            .as_slice()
+            .get(self.warnings_seen..)
+            .unwrap_or_default()
+            .to_vec();
+        self.warnings_seen = warnings.as_slice().len();
+        let kept = |lanes: Vec<TraceLane>| {
+            lanes
+                .into_iter()
+                .filter(|lane| lane.change != TraceChange::Removed)
+                .map(|lane| lane.inferred)
+                .collect()
+        };
+        self.previous = [kept(forward), kept(backward)];
+        self.steps.push(TraceStep {
+            stage,
+            description,
+            lanes,
+            warnings: new_warnings,
+        });
+    }
+
+    /// Record the built lanes, compared to the road recorded after the last stage,
+    /// built without separators, such that the separators are added
+    #[allow(clippy::use_debug)]
+    #[allow(clippy::too_many_arguments)]
+    pub(in crate::transform::tags_to_lanes) fn record_lanes(
+        &mut self,
+        stage: &'static str,
+        description: &'static str,
+        previous: RoadBuilder,
+        lanes: &[Lane],
+        tags: &TagsRef<'_>,
+        locale: &Locale,
+        warnings: &RoadWarnings,
+    ) {
+        let previous: Vec<String> = previous
+            .into_ltr(tags, locale, false, &mut RoadWarnings::default())
+            .map(|(lanes, ..)| lanes)
+            .unwrap_or_default()
+            .iter()
+            .map(|lane| format!("{:?}", lane))
+            .collect();
+        let current: Vec<String> = lanes
+            .iter()
+            .filter(|lane| !lane.is_separator())
+            .map(|lane| format!("{:?}", lane))
+            .collect();
+        let mut changed = changes(&previous, &current).into_iter().peekable();
+        let mut traced = Vec::with_capacity(lanes.len());
+        for lane in lanes {
+            if lane.is_separator() {
+                traced.push(TraceLane {
+                    inferred: format!("{:?}", lane),
+                    change: TraceChange::Added,
+                });
+            } else {
+                while let Some(removed) =
+                    changed.next_if(|lane| lane.change == TraceChange::Removed)
+                {
+                    traced.push(removed);
+                }
+                traced.extend(changed.next());
+            }
+        }
+        traced.extend(changed);
+        let lanes = traced;
+        let new_warnings = warnings
+            .as_slice()
+            .get(self.warnings_seen..)
+            .unwrap_or_default()
+            .to_vec();
+        self.warnings_seen = warnings.as_slice().len();
+        self.steps.push(TraceStep {
+            stage,
+            description,
+            lanes,
+            warnings: new_warnings,
+        });
+    }
+
+    /// Render a standalone HTML page describing the transformation of `tags`
+    #[must_use]
+    pub fn html<'trace>(
+        &'trace self,
+        tags: &'trace TagsRef<'trace>,
+        result: &'trace Result<RoadFromTags, RoadError>,
+    ) -> TraceHtml<'trace> {
+        TraceHtml {
+            trace: self,
+            tags,
+            result,
+        }
+    }
+}
+
+/// HTML report of a [`Trace`], see [`Trace::html`]
+pub struct TraceHtml<'trace> {
+    trace: &'trace Trace,
+    tags: &'trace TagsRef<'trace>,
+    result: &'trace Result<RoadFromTags, RoadError>,
+}
+
+impl core::fmt::Display for TraceHtml<'_> {
+    #[allow(clippy::use_debug)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        writeln!(f, "<!DOCTYPE html>")?;
+        writeln!(f, "<html>")?;
+        writeln!(f, "<head>")?;
+        writeln!(f, "<meta charset=\"utf-8\">")?;
+        writeln!(f, "<title>osm2lanes trace</title>")?;
+        writeln!(f, "<style>")?;
+        writeln!(f, "table {{ border-collapse: collapse; }}")?;
+        writeln!(
+            f,
+            "td {{ border: 1px solid #999; padding: 2px 6px; font-family: monospace; }}"
+        )?;
+        writeln!(f, ".added {{ background: #cfc; }}")?;
+        writeln!(f, ".modified {{ background: #ffc; }}")?;
+        writeln!(
+            f,
+            ".removed {{ background: #fcc; text-decoration: line-through; }}"
+        )?;
+        writeln!(f, ".warning {{ color: #a60; }}")?;
+        writeln!(f, ".error {{ color: #c00; }}")?;
+        writeln!(f, "</style>")?;
+        writeln!(f, "</head>")?;
+        writeln!(f, "<body>")?;
+
+        writeln!(f, "<h1>Tags</h1>")?;
+        writeln!(f, "<table>")?;
+        for (key, val) in self.tags.to_str_pairs() {
+            writeln!(
+                f,
+                "<tr><td>{}</td><td>{}</td></tr>",
+                escape(key),
+                escape(val)
+            )?;
+        }
+        writeln!(f, "</table>")?;
+
+        writeln!(f, "<h1>Stages</h1>")?;
+        for step in &self.trace.steps {
+            writeln!(f, "<h2>{}</h2>", escape(step.stage))?;
+            writeln!(f, "<p>{}</p>", escape(step.description))?;
+            writeln!(f, "<table>")?;
+            for lane in &step.lanes {
+                let class = match lane.change {
+                    TraceChange::Unchanged => "unchanged",
+                    TraceChange::Added => "added",
+                    TraceChange::Modified => "modified",
+                    TraceChange::Removed => "removed",
+                };
+                writeln!(
+                    f,
+                    "<tr class=\"{class}\"><td>{class}</td><td>{}</td></tr>",
+                    escape(&lane.inferred)
+                )?;
+            }
+            writeln!(f, "</table>")?;
+            for warning in &step.warnings {
+                writeln!(
+                    f,
+                    "<p class=\"warning\">Warning: {}</p>",
+                    escape(&warning.to_string())
+                )?;
+            }
+        }
+
+        writeln!(f, "<h1>Result</h1>")?;
+        match self.result {
+            Ok(road_from_tags) => {
+                writeln!(f, "<table>")?;
+                write!(f, "<tr>")?;
+                for lane in &road_from_tags.road.lanes {
+                    write!(f, "<td>{}</td>", lane.as_utf8())?;
+                }
+                writeln!(f, "</tr>")?;
+                write!(f, "<tr>")?;
+                for lane in &road_from_tags.road.lanes {
+                    write!(f, "<td>{}</td>", escape(&format!("{:?}", lane)))?;
+                }
+                writeln!(f, "</tr>")?;
+                writeln!(f, "</table>")?;
+                for warning in road_from_tags.warnings.as_slice() {
+                    writeln!(
+                        f,
+                        "<p class=\"warning\">Warning: {}</p>",
+                        escape(&warning.to_string())
+                    )?;
+                }
+            },
+            Err(error) => {
+                writeln!(
+                    f,
+                    "<p class=\"error\">Error: {}</p>",
+                    escape(&error.to_string())
+                )?;
+            },
+        }
+
+        writeln!(f, "</body>")?;
+        writeln!(f, "</html>")
+    }
+}
+
+/// Compare lanes ordered from the centre outwards with those of the previous stage,
+/// aligned by their longest common subsequence,
+/// where a lane that is removed and another that is added in its place are modified
+fn changes(previous: &[String], current: &[String]) -> Vec<TraceLane> {
+    let mut lanes = Vec::with_capacity(current.len());
+    let mut removed = Vec::new();
+    let mut added = Vec::new();
+    for aligned in align(previous, current, |a, b| a == b) {
+        match aligned {
+            LaneConnection::Continues { to, .. } => {
+                flush_changes(&mut lanes, &mut removed, &mut added);
+                lanes.extend(current.get(to).map(|inferred| TraceLane {
+                    inferred: inferred.clone(),
+                    change: TraceChange::Unchanged,
+                }));
+            },
+            LaneConnection::Ends { from } => removed.extend(previous.get(from)),
+            LaneConnection::Begins { to } => added.extend(current.get(to)),
+        }
+    }
+    flush_changes(&mut lanes, &mut removed, &mut added);
+    lanes
+}
+
+/// The lanes between two unchanged lanes,
+/// modified in pairs of a removed and an added lane, with the rest added or removed
+fn flush_changes(lanes: &mut Vec<TraceLane>, removed: &mut Vec<&String>, added: &mut Vec<&String>) {
+    let modified = removed.len().min(added.len());
+    lanes.extend(added.drain(..).enumerate().map(|(i, inferred)| TraceLane {
+        inferred: inferred.clone(),
+        change: if i < modified {
+            TraceChange::Modified
+        } else {
+            TraceChange::Added
+        },
+    }));
+    lanes.extend(removed.drain(..).skip(modified).map(|inferred| TraceLane {
+        inferred: inferred.clone(),
+        change: TraceChange::Removed,
+    }));
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use osm_tags::Tags;
+
+    use super::changes;
+    use crate::locale::Locale;
+    use crate::transform::{tags_to_lanes_with_trace, TagsToLanesConfig, TraceChange};
+
+    #[test]
+    fn test_changes() {
+        let lanes = |lanes: &[&str]| {
+            lanes
+                .iter()
+                .map(|lane| (*lane).to_owned())
+                .collect::<Vec<_>>()
+        };
+        let changed = |previous: &[&str], current: &[&str]| {
+            changes(&lanes(previous), &lanes(current))
+                .into_iter()
+                .map(|lane| (lane.inferred, lane.change))
+                .collect::<Vec<_>>()
+        };
+        // A lane inserted in the middle
+        assert_eq!(
+            changed(&["a", "b", "c"], &["a", "x", "b", "c"]),
+            [
+                ("a".to_owned(), TraceChange::Unchanged),
+                ("x".to_owned(), TraceChange::Added),
+                ("b".to_owned(), TraceChange::Unchanged),
+                ("c".to_owned(), TraceChange::Unchanged),
+            ]
+        );
+        assert_eq!(
+            changed(&["a", "b", "c"], &["a", "c"]),
+            [
+                ("a".to_owned(), TraceChange::Unchanged),
+                ("b".to_owned(), TraceChange::Removed),
+                ("c".to_owned(), TraceChange::Unchanged),
+            ]
+        );
+        assert_eq!(
+            changed(&["a", "b", "c"], &["a", "y", "c", "d"]),
+            [
+                ("a".to_owned(), TraceChange::Unchanged),
+                ("y".to_owned(), TraceChange::Modified),
+                ("c".to_owned(), TraceChange::Unchanged),
+                ("d".to_owned(), TraceChange::Added),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trace() {
+        let tags = Tags::from_pairs([
+            ("highway", "secondary"),
+            ("lanes", "2"),
+            ("cycleway:right", "lane"),
+        ])
+        .unwrap();
+        let locale = Locale::builder().build();
+        let (result, trace) =
+            tags_to_lanes_with_trace(&tags, &locale, &TagsToLanesConfig::default());
+        assert!(result.is_ok());
+        assert_eq!(trace.steps.len(), 7);
+        let bicycle = trace
+            .steps
+            .iter()
+            .find(|step| step.stage == "bicycle")
+            .unwrap();
+        assert_eq!(
+            bicycle
+                .lanes
+                .iter()
+                .filter(|lane| lane.change == TraceChange::Added)
+                .count(),
+            1
+        );
+        let separators = trace.steps.last().unwrap();
+        assert_eq!(separators.stage, "separators");
+        assert!(separators
+            .lanes
+            .iter()
+            .any(|lane| lane.change == TraceChange::Added));
+        let html = trace.html(&tags, &result).to_string();
+        assert!(html.contains("<td>cycleway:right</td><td>lane</td>"));
+    }
+
+    #[test]
+    fn test_trace_final_lanes() {
+        let locale = Locale::builder().build();
+        let modified = |pairs: &[(&'static str, &'static str)]| {
+            let tags = Tags::from_pairs(pairs.iter().copied()).unwrap();
+            let (_result, trace) =
+                tags_to_lanes_with_trace(&tags, &locale, &TagsToLanesConfig::default());
+            trace
+                .steps
+                .last()
+                .unwrap()
+                .lanes
+                .iter()
+                .filter(|lane| lane.change == TraceChange::Modified)
+                .count()
+        };
+        assert_eq!(modified(&[("highway", "secondary"), ("lanes", "2")]), 0);
+        // The access of the motor vehicle lanes is applied after the stages
+        assert_eq!(
+            modified(&[
+                ("highway", "secondary"),
+                ("lanes", "2"),
+                ("motor_vehicle", "destination"),
+            ]),
+            2
+        );
+    }
+}
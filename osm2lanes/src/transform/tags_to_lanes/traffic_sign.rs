@@ -0,0 +1,122 @@
+use alloc::vec::Vec;
+
+use osm_tag_schemes::{keys, Access, TrafficSign};
+use osm_tags::{TagKey, TagsRef};
+
+use super::infer::Infer;
+use super::road::{LaneBuilder, RoadBuilder};
+use super::TagsToLanesMsg;
+use crate::locale::Locale;
+use crate::road::{AccessAndDirection, Designated};
+use crate::transform::RoadWarnings;
+
+/// What a traffic sign implies for the lanes it applies to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Implied {
+    Bus,
+    NoFoot,
+    NoBicycle,
+    NoMotor,
+}
+
+/// Signs by country and code, signs not listed here are ignored
+const SIGNS: &[(&str, &str, &[Implied])] = &[
+    // https://wiki.openstreetmap.org/wiki/DE:Verkehrszeichen_in_Deutschland
+    ("DE", "245", &[Implied::Bus]),
+    ("DE", "250", &[Implied::NoBicycle, Implied::NoMotor]),
+    ("DE", "251", &[Implied::NoMotor]),
+    ("DE", "254", &[Implied::NoBicycle]),
+    ("DE", "259", &[Implied::NoFoot]),
+    ("DE", "260", &[Implied::NoMotor]),
+    // https://wiki.openstreetmap.org/wiki/FR:Panneaux_routiers_en_France
+    ("FR", "B27a", &[Implied::Bus]),
+    ("FR", "B7b", &[Implied::NoMotor]),
+    ("FR", "B9a", &[Implied::NoFoot]),
+    ("FR", "B9b", &[Implied::NoBicycle]),
+];
+
+impl Implied {
+    fn from_sign(sign: TrafficSign<'_>) -> &'static [Self] {
+        sign.country
+            .and_then(|country| {
+                SIGNS
+                    .iter()
+                    .find(|(c, code, _)| *c == country && *code == sign.code)
+            })
+            .map_or(&[], |(_, _, implied)| implied)
+    }
+
+    fn apply(self, lane: &mut LaneBuilder, warnings: &mut RoadWarnings) {
+        const NO: Infer<AccessAndDirection> = Infer::Calculated(AccessAndDirection {
+            access: Access::No,
+            direction: None,
+        });
+        let result = match self {
+            Self::Bus => lane.designated.set(Infer::Calculated(Designated::Bus)),
+            Self::NoFoot => lane.access.foot.set(NO),
+            Self::NoBicycle => lane.access.bicycle.set(NO),
+            Self::NoMotor => lane.access.motor.set(NO),
+        };
+        if let Err(e) = result {
+            warnings.push(TagsToLanesMsg::from(e));
+        }
+    }
+}
+
+/// Designations and access restrictions implied by `traffic_sign=*`,
+/// applied to both directions, or to one direction for `traffic_sign:forward=*` and `traffic_sign:backward=*`.
+///
+/// A bus lane sign applies to the outermost lane of the direction,
+/// any other sign to all of its lanes.
+/// Tagged designations and access take precedence over those implied by a sign.
+pub(super) fn traffic_sign(
+    tags: &TagsRef<'_>,
+    locale: &Locale,
+    road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
+) {
+    fn implied(tags: &TagsRef<'_>, key: &TagKey) -> Vec<Implied> {
+        tags.get(key)
+            .map(TrafficSign::split)
+            .unwrap_or_default()
+            .into_iter()
+            .flat_map(|sign| Implied::from_sign(sign).iter().copied())
+            .collect()
+    }
+
+    let both = implied(tags, &keys::TRAFFIC_SIGN);
+    let forward = [both.as_slice(), &implied(tags, &keys::TRAFFIC_SIGN_FORWARD)].concat();
+    let backward = [
+        both.as_slice(),
+        &implied(tags, &keys::TRAFFIC_SIGN_BACKWARD),
+    ]
+    .concat();
+    for implied in forward {
+        match implied {
+            Implied::Bus => {
+                if let Some(lane) = road.forward_outside_mut() {
+                    implied.apply(lane, warnings);
+                }
+            },
+            _ => {
+                for lane in road.forward_ltr_mut(locale) {
+                    implied.apply(lane, warnings);
+                }
+            },
+        }
+    }
+    for implied in backward {
+        match implied {
+            Implied::Bus => {
+                if let Some(lane) = road.backward_outside_mut() {
+                    implied.apply(lane, warnings);
+                }
+            },
+            _ => {
+                for lane in road.backward_ltr_mut(locale) {
+                    implied.apply(lane, warnings);
+                }
+            },
+        }
+    }
+}
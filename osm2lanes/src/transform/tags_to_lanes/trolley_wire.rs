@@ -0,0 +1,46 @@
+use osm_tag_schemes::{keys, Schemes, TrolleyWire};
+use osm_tags::TagsRef;
+
+use super::infer::Infer;
+use super::road::RoadBuilder;
+use super::TagsToLanesMsg;
+use crate::locale::Locale;
+use crate::road::Designated;
+use crate::transform::RoadWarnings;
+
+/// Lanes below the overhead wires of trolleybuses, from `trolley_wire=*`,
+/// otherwise implied by `trolleybus=yes`.
+///
+/// The wires are above the bus lanes, or above the motor lanes if the road has no bus lanes.
+pub(super) fn trolley_wire(
+    tags: &TagsRef<'_>,
+    generic_schemes: &Schemes,
+    locale: &Locale,
+    road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
+) {
+    let wire = match generic_schemes.trolley_wire {
+        Ok(Some(wire)) => Infer::Direct(wire),
+        Ok(None) if tags.is(&keys::TROLLEYBUS, "yes") => Infer::Calculated(TrolleyWire::Yes),
+        Ok(None) => return,
+        Err(_) => {
+            warnings.push(TagsToLanesMsg::unsupported_tags(
+                tags.subset(&[keys::TROLLEY_WIRE]),
+            ));
+            return;
+        },
+    };
+    let designated = if road
+        .lanes_ltr(locale)
+        .any(|lane| lane.designated.some() == Some(Designated::Bus))
+    {
+        Designated::Bus
+    } else {
+        Designated::Motor
+    };
+    for lane in road.lanes_ltr_mut(locale) {
+        if lane.designated.some() == Some(designated) {
+            lane.trolley_wire = wire;
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use alloc::vec::Vec;
+
+use osm_tag_schemes::{keys, LaneDependent, LaneTurn};
+use osm_tags::{tag_key, TagsRef};
+
+use super::road::{LaneBuilder, RoadBuilder};
+use super::{Infer, TagsToLanesMsg};
+use crate::locale::Locale;
+use crate::road::Direction;
+use crate::transform::RoadWarnings;
+
+/// Set the turn markings of the lanes, in order
+///
+/// Returns `false`, setting nothing, if the number of lanes and values differ.
+fn set<'lane>(lanes: impl Iterator<Item = &'lane mut LaneBuilder>, turns: Vec<LaneTurn>) -> bool {
+    let lanes: Vec<_> = lanes.collect();
+    if lanes.len() != turns.len() {
+        return false;
+    }
+    for (lane, LaneTurn(turn)) in lanes.into_iter().zip(turns) {
+        if !turn.is_empty() {
+            lane.turn = Infer::Direct(turn);
+        }
+    }
+    true
+}
+
+/// Turn markings on individual lanes,
+/// from `turn:lanes=*`, `turn:lanes:forward=*`, `turn:lanes:backward=*`,
+/// and `turn:lanes:both_ways=*` for lanes used in both directions
+///
+/// <https://wiki.openstreetmap.org/wiki/Key:turn>
+pub(super) fn lane_turns(
+    tags: &TagsRef<'_>,
+    locale: &Locale,
+    road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
+) {
+    let is_direction =
+        |direction| move |lane: &&mut LaneBuilder| lane.direction.some() == Some(direction);
+    match LaneDependent::<LaneTurn>::from_tags(tags, &keys::TURN_LANES) {
+        Ok(None) => {},
+        Ok(Some(lanes)) => {
+            let set = match lanes {
+                LaneDependent::LeftToRight(turns) => set(
+                    road.lanes_ltr_mut(locale)
+                        .filter(|lane| lane.direction.some() != Some(Direction::Both)),
+                    turns,
+                ),
+                LaneDependent::Forward(turns) => set(
+                    road.forward_ltr_mut(locale)
+                        .filter(is_direction(Direction::Forward)),
+                    turns,
+                ),
+                LaneDependent::Backward(turns) => set(
+                    road.backward_ltr_mut(locale)
+                        .filter(is_direction(Direction::Backward)),
+                    turns,
+                ),
+                LaneDependent::ForwardBackward { forward, backward } => {
+                    set(
+                        road.forward_ltr_mut(locale)
+                            .filter(is_direction(Direction::Forward)),
+                        forward,
+                    ) && set(
+                        road.backward_ltr_mut(locale)
+                            .filter(is_direction(Direction::Backward)),
+                        backward,
+                    )
+                },
+            };
+            if !set {
+                warnings.push(TagsToLanesMsg::unsupported(
+                    "lane count mismatch",
+                    tags.subset(&[
+                        keys::TURN_LANES,
+                        keys::TURN_LANES + "forward",
+                        keys::TURN_LANES + "backward",
+                    ]),
+                ));
+            }
+        },
+        Err(e) => warnings.push(e.into()),
+    }
+
+    let both_ways = tag_key!("turn:lanes:both_ways");
+    if let Some(value) = tags.get(&both_ways) {
+        match value.parse::<LaneTurn>() {
+            Ok(turn) => {
+                let lanes = road
+                    .forward_ltr_mut(locale)
+                    .filter(is_direction(Direction::Both));
+                if !set(lanes, alloc::vec![turn]) {
+                    warnings.push(TagsToLanesMsg::unsupported(
+                        "lane count mismatch",
+                        tags.subset(&[both_ways]),
+                    ));
+                }
+            },
+            Err(_unknown) => {
+                warnings.push(TagsToLanesMsg::unsupported_tag(both_ways, value));
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use osm_tag_schemes::TurnDirection;
+    use osm_tags::Tags;
+
+    use crate::locale::Locale;
+    use crate::road::{Direction, Lane};
+    use crate::transform::{tags_to_lanes, TagsToLanesConfig};
+
+    #[test]
+    fn turn_lanes_forward_backward_both_ways() {
+        let tags = Tags::from_pairs([
+            ("highway", "secondary"),
+            ("lanes", "4"),
+            ("lanes:forward", "2"),
+            ("lanes:backward", "1"),
+            ("lanes:both_ways", "1"),
+            ("turn:lanes:forward", "left;through|right"),
+            ("turn:lanes:backward", "none"),
+            ("turn:lanes:both_ways", "left"),
+        ])
+        .unwrap();
+        let output = tags_to_lanes(
+            &tags,
+            &Locale::builder().build(),
+            &TagsToLanesConfig {
+                include_separators: false,
+                ..TagsToLanesConfig::default()
+            },
+        )
+        .unwrap();
+        assert!(output.warnings.is_empty(), "{:?}", output.warnings);
+        let turns: Vec<_> = output
+            .road
+            .lanes
+            .iter()
+            .filter_map(|lane| match lane {
+                Lane::Travel {
+                    direction, turn, ..
+                } => Some((*direction, turn.clone())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            turns,
+            [
+                (Some(Direction::Backward), None),
+                (Some(Direction::Both), Some(vec![TurnDirection::Left])),
+                (
+                    Some(Direction::Forward),
+                    Some(vec![TurnDirection::Left, TurnDirection::Through])
+                ),
+                (Some(Direction::Forward), Some(vec![TurnDirection::Right])),
+            ]
+        );
+    }
+
+    #[test]
+    fn turn_lanes_count_mismatch() {
+        let tags = Tags::from_pairs([
+            ("highway", "secondary"),
+            ("oneway", "yes"),
+            ("lanes", "2"),
+            ("turn:lanes", "left|through|right"),
+        ])
+        .unwrap();
+        let output = tags_to_lanes(
+            &tags,
+            &Locale::builder().build(),
+            &TagsToLanesConfig {
+                include_separators: false,
+                ..TagsToLanesConfig::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(output.warnings.as_slice().len(), 1);
+        assert!(output
+            .road
+            .lanes
+            .iter()
+            .all(|lane| !matches!(lane, Lane::Travel { turn: Some(_), .. })));
+    }
+}
@@ -0,0 +1,130 @@
+use alloc::vec::Vec;
+
+use osm_tags::{TagKey, Tags, TagsRef};
+
+use super::TagsToLanesMsg;
+use crate::transform::RoadWarnings;
+
+/// The first part of keys that describe lanes
+const LANE_KEYS: &[&str] = &[
+    "access",
+    "bicycle",
+    "bus",
+    "busway",
+    "change",
+    "cycleway",
+    "destination",
+    "foot",
+    "footway",
+    "hgv",
+    "hov",
+    "lanes",
+    "maxspeed",
+    "minspeed",
+    "motor_vehicle",
+    "oneway",
+    "overtaking",
+    "parking",
+    "placement",
+    "psv",
+    "separation",
+    "shoulder",
+    "sidewalk",
+    "taxi",
+    "turn",
+    "vehicle",
+    "width",
+];
+
+/// Keys shorter than this are too short to tell a typo from another key, e.g. `bus` and `bug`
+const MIN_TYPO_LEN: usize = 5;
+
+/// Warn on the tags that describe lanes, or look like typos of ones that do,
+/// that were never read by the transformation, e.g. `cylceway=lane`
+///
+/// Reads must be tracked, see [`Tags::with_read_tracking`].
+pub(super) fn unread_tags(tags: &TagsRef<'_>, warnings: &mut RoadWarnings) {
+    let unread: Vec<(TagKey, &str)> = tags
+        .unread()
+        .filter(|(key, _val)| is_lane_key(key))
+        .map(|(key, val)| (TagKey::from_ref(key), val))
+        .collect();
+    if unread.is_empty() {
+        return;
+    }
+    match Tags::from_pairs(unread) {
+        Ok(unread) => warnings.push(TagsToLanesMsg::unsupported("tags never read", unread)),
+        Err(e) => warnings.push(TagsToLanesMsg::from(e)),
+    }
+}
+
+fn is_lane_key(key: &str) -> bool {
+    let root = key.split(':').next().unwrap_or(key);
+    LANE_KEYS.iter().any(|lane_key| {
+        *lane_key == root || (root.len() >= MIN_TYPO_LEN && is_typo(root, lane_key))
+    })
+}
+
+/// One character substituted, inserted, removed, or swapped with its neighbour
+fn is_typo(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let prefix = a.iter().zip(b).take_while(|(a, b)| a == b).count();
+    let (a, b) = (
+        a.get(prefix..).unwrap_or_default(),
+        b.get(prefix..).unwrap_or_default(),
+    );
+    match (a, b) {
+        ([_, a @ ..], [_, b @ ..]) if a == b => true,
+        ([_, a @ ..], b) | (a, [_, b @ ..]) if a == b => true,
+        ([a0, a1, a @ ..], [b0, b1, b @ ..]) => a0 == b1 && a1 == b0 && a == b,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::locale::Locale;
+    use crate::transform::tags_to_lanes::error::TagsToLanesIssue;
+    use crate::transform::{tags_to_lanes, TagsToLanesConfig};
+
+    #[test]
+    fn test_unread_tags() {
+        let tags = Tags::from_pairs([
+            ("highway", "secondary"),
+            ("lanes", "2"),
+            ("cylceway", "lane"),
+            ("name", "Main Street"),
+        ])
+        .unwrap();
+        let locale = Locale::builder().build();
+        let config = TagsToLanesConfig {
+            include_separators: false,
+            ..TagsToLanesConfig::default()
+        };
+        let output = tags_to_lanes(&tags, &locale, &config).unwrap();
+        assert!(output.warnings.is_empty(), "{:?}", output.warnings);
+
+        let output = tags_to_lanes(&tags, &locale, &config.with_unread_tags_warning()).unwrap();
+        match output.warnings.as_slice() {
+            [msg] => match &msg.issue {
+                TagsToLanesIssue::Unsupported {
+                    tags: Some(unread), ..
+                } => assert_eq!(unread.to_str_pairs(), [("cylceway", "lane")]),
+                issue => panic!("unexpected issue {:?}", issue),
+            },
+            warnings => panic!("unexpected warnings {:?}", warnings),
+        }
+    }
+
+    #[test]
+    fn test_is_lane_key() {
+        assert!(is_lane_key("cycleway:left"));
+        assert!(is_lane_key("cylceway"));
+        assert!(is_lane_key("sidwalk"));
+        assert!(is_lane_key("parkingg:lane"));
+        assert!(!is_lane_key("name"));
+        assert!(!is_lane_key("lit"));
+        assert!(!is_lane_key("bug"));
+    }
+}
@@ -1,4 +1,4 @@
-use osm_tags::Tags;
+use osm_tags::{KeySet, TagsRef};
 
 use crate::locale::Locale;
 use crate::transform::{RoadWarnings, TagsToLanesMsg};
@@ -7,62 +7,135 @@ use crate::transform::{RoadWarnings, TagsToLanesMsg};
 /// Catch-all for unsupported or unimplemented but known constructs
 #[allow(clippy::unnecessary_wraps)]
 pub(in crate::transform::tags_to_lanes) fn unsupported(
-    tags: &Tags,
+    tags: &TagsRef<'_>,
     _locale: &Locale,
     warnings: &mut RoadWarnings,
 ) -> Result<(), TagsToLanesMsg> {
-    // https://wiki.openstreetmap.org/wiki/Key:access#Transport_mode_restrictions
-    const ACCESS_KEYS: [&str; 43] = [
-        "access",
-        "dog",
-        "ski",
-        "inline_skates",
-        "horse",
-        "vehicle",
-        "bicycle",
-        "electric_bicycle",
-        "carriage",
-        "hand_cart",
-        "quadracycle",
-        "trailer",
-        "caravan",
-        "motor_vehicle",
-        "motorcycle",
-        "moped",
-        "mofa",
-        "motorcar",
-        "motorhome",
-        "tourist_bus",
-        "coach",
-        "goods",
-        "hgv",
-        "hgv_articulated",
-        "bdouble",
-        "agricultural",
-        "golf_cart",
-        "atv",
-        "snowmobile",
-        "psv",
-        "bus",
-        "taxi",
-        "minibus",
-        "share_taxi",
-        "hov",
-        "car_sharing",
-        "emergency",
-        "hazmat",
-        "disabled",
-        "roadtrain",
-        "hgv_caravan",
-        "lhv",
-        "tank",
-    ];
-    if ACCESS_KEYS.into_iter().any(|k| tags.get(k).is_some()) {
+    if tags.iter().any(|(key, _val)| is_access_key(key)) {
         warnings.push(TagsToLanesMsg::unimplemented(
             "access",
             // TODO, TagTree should support subset
-            tags.subset(ACCESS_KEYS),
+            tags.subset(
+                tags.iter()
+                    .map(|(key, _val)| key)
+                    .filter(|key| is_access_key(key)),
+            ),
         ));
     }
     Ok(())
 }
+
+/// Transport mode restrictions not resolved by `access`,
+/// matched once per key of the way rather than looking up every key in the tags
+/// <https://wiki.openstreetmap.org/wiki/Key:access#Transport_mode_restrictions>
+const ACCESS_KEYS: KeySet<38> = KeySet::new([
+    "dog",
+    "ski",
+    "inline_skates",
+    "horse",
+    "electric_bicycle",
+    "carriage",
+    "hand_cart",
+    "quadracycle",
+    "trailer",
+    "caravan",
+    "motorcycle",
+    "moped",
+    "mofa",
+    "motorcar",
+    "motorhome",
+    "tourist_bus",
+    "coach",
+    "goods",
+    "hgv",
+    "hgv_articulated",
+    "bdouble",
+    "agricultural",
+    "golf_cart",
+    "atv",
+    "snowmobile",
+    "psv",
+    "bus",
+    "taxi",
+    "minibus",
+    "share_taxi",
+    "car_sharing",
+    "emergency",
+    "hazmat",
+    "disabled",
+    "roadtrain",
+    "hgv_caravan",
+    "lhv",
+    "tank",
+]);
+
+fn is_access_key(key: &str) -> bool {
+    ACCESS_KEYS.contains(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_access_key, ACCESS_KEYS};
+
+    #[test]
+    fn test_access_keys() {
+        assert_eq!(
+            ACCESS_KEYS.keys(),
+            &[
+                "dog",
+                "ski",
+                "inline_skates",
+                "horse",
+                "electric_bicycle",
+                "carriage",
+                "hand_cart",
+                "quadracycle",
+                "trailer",
+                "caravan",
+                "motorcycle",
+                "moped",
+                "mofa",
+                "motorcar",
+                "motorhome",
+                "tourist_bus",
+                "coach",
+                "goods",
+                "hgv",
+                "hgv_articulated",
+                "bdouble",
+                "agricultural",
+                "golf_cart",
+                "atv",
+                "snowmobile",
+                "psv",
+                "bus",
+                "taxi",
+                "minibus",
+                "share_taxi",
+                "car_sharing",
+                "emergency",
+                "hazmat",
+                "disabled",
+                "roadtrain",
+                "hgv_caravan",
+                "lhv",
+                "tank",
+            ]
+        );
+        for key in ACCESS_KEYS.keys() {
+            assert!(is_access_key(key), "{key}");
+        }
+        // Resolved by `access`, or only the root key is a restriction
+        for key in [
+            "access",
+            "bicycle",
+            "hov",
+            "foot",
+            "highway",
+            "bus:lanes",
+            "hgv:conditional",
+        ] {
+            assert!(!is_access_key(key), "{key}");
+        }
+    }
+}
@@ -0,0 +1,354 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use osm_tag_schemes::{keys, LaneDependent};
+use osm_tags::TagsRef;
+
+use super::infer::Infer;
+use super::road::{LaneBuilder, RoadBuilder};
+use super::{TagsToLanesMsg, TagsTyped};
+use crate::locale::Locale;
+use crate::metric::Metre;
+use crate::road::Lane;
+use crate::transform::RoadWarnings;
+
+/// Widths of individual lanes counted in `lanes=*`, from `width:lanes=*`,
+/// and its `:forward` and `:backward` variants, overriding the width implied by the locale.
+///
+/// An empty value keeps the implied width for that lane.
+pub(super) fn lane_widths(
+    tags: &TagsRef<'_>,
+    locale: &Locale,
+    road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
+) {
+    let key = keys::WIDTH_LANES;
+    match LaneDependent::<String>::from_tags(tags, &key) {
+        Ok(None) => {},
+        Ok(Some(lanes)) => {
+            if let Some(lanes) = road.lane_dependent_mut(lanes, locale) {
+                for (lane, value) in lanes {
+                    if value.is_empty() {
+                        continue;
+                    }
+                    match value.parse::<Metre>() {
+                        Ok(width) => lane.width.target = Infer::Direct(width),
+                        Err(e) => warnings.push(TagsToLanesMsg::unsupported(
+                            e.to_string(),
+                            tags.subset(&[&key + "forward", &key + "backward", key.clone()]),
+                        )),
+                    }
+                }
+            } else {
+                warnings.push(TagsToLanesMsg::unsupported(
+                    "lane count mismatch",
+                    tags.subset(&[&key + "forward", &key + "backward", key]),
+                ));
+            }
+        },
+        Err(e) => warnings.push(e.into()),
+    }
+}
+
+/// The constraint that determined the width of a lane
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum WidthConstraint {
+    /// The width tagged for the lane, or implied by the locale
+    Target,
+    /// A share of the total width of the way, from `width=*`
+    Total,
+    /// The minimum width of the lane
+    Minimum,
+    /// The maximum width of the lane
+    Maximum,
+}
+
+/// How the width of a lane was determined
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LaneWidth {
+    /// The width, tagged directly, implied by the locale, or calculated
+    pub width: Infer<Metre>,
+    /// The constraint that determined the width
+    pub constraint: WidthConstraint,
+}
+
+/// Width of a lane to distribute the total width across
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Share {
+    /// The width implied for the lane, the proportion of the total it receives
+    implied: f64,
+    /// A width that is not distributed, e.g. from `width:lanes=*`
+    fixed: Option<f64>,
+    min: f64,
+    max: f64,
+}
+
+impl Share {
+    fn from_lane(lane: &LaneBuilder) -> Self {
+        Self {
+            implied: lane
+                .width
+                .target
+                .some()
+                .unwrap_or(Lane::DEFAULT_WIDTH)
+                .val(),
+            fixed: match lane.width.target {
+                Infer::Direct(width) => Some(width.val()),
+                _ => None,
+            },
+            min: lane.width.min.some().map_or(0.0_f64, |min| min.val()),
+            max: lane.width.max.some().map_or(f64::INFINITY, |max| max.val()),
+        }
+    }
+}
+
+/// Distribute the total width across the shares, in proportion to their implied widths.
+///
+/// A share that would be outside of its minimum or maximum is fixed at that bound,
+/// and the remaining width distributed across the others.
+/// `None` if the fixed widths and minimums exceed the total.
+fn distribute(total: f64, shares: &[Share]) -> Option<Vec<f64>> {
+    let mut widths: Vec<Option<f64>> = shares.iter().map(|share| share.fixed).collect();
+    loop {
+        let remaining = total - widths.iter().flatten().sum::<f64>();
+        let implied: f64 = shares
+            .iter()
+            .zip(&widths)
+            .filter(|(_, width)| width.is_none())
+            .map(|(share, _)| share.implied)
+            .sum();
+        let minimums: f64 = shares
+            .iter()
+            .zip(&widths)
+            .filter(|(_, width)| width.is_none())
+            .map(|(share, _)| share.min)
+            .sum();
+        if remaining < minimums {
+            return None;
+        }
+        if implied <= 0.0_f64 {
+            // Nothing left to distribute to
+            return widths.into_iter().collect();
+        }
+        if remaining <= 0.0_f64 {
+            return None;
+        }
+        let scale = remaining / implied;
+        let mut bounded = false;
+        for (share, width) in shares.iter().zip(&mut widths) {
+            if width.is_none() {
+                let distributed = share.implied * scale;
+                if distributed < share.min {
+                    *width = Some(share.min);
+                    bounded = true;
+                } else if distributed > share.max {
+                    *width = Some(share.max);
+                    bounded = true;
+                }
+            }
+        }
+        if !bounded {
+            return shares
+                .iter()
+                .zip(widths)
+                .map(|(share, width)| Some(width.unwrap_or(share.implied * scale)))
+                .collect();
+        }
+    }
+}
+
+/// Total width of the lanes counted in `lanes=*` from `width=*`,
+/// distributed across the lanes in proportion to their implied widths.
+///
+/// Lane widths tagged directly, e.g. from `width:lanes=*`, are kept.
+pub(super) fn way_width(
+    tags: &TagsRef<'_>,
+    locale: &Locale,
+    road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
+) {
+    let total: Metre = match tags.get_metre(&keys::WIDTH, warnings) {
+        Some(total) => total,
+        None => return,
+    };
+    let shares: Vec<Share> = road
+        .lanes_ltr_mut(locale)
+        .map(|lane| Share::from_lane(lane))
+        .collect();
+    if let Some(widths) = distribute(total.val(), &shares) {
+        for ((lane, share), width) in road.lanes_ltr_mut(locale).zip(shares).zip(widths) {
+            if share.fixed.is_none() {
+                lane.width.target = Infer::Calculated(Metre::new(width));
+            }
+        }
+    } else {
+        warnings.push(TagsToLanesMsg::unsupported(
+            "width is less than the minimum width of the lanes",
+            tags.subset(&[keys::WIDTH]),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform::tags_to_lanes::road::Width;
+
+    const fn share(implied: f64) -> Share {
+        Share {
+            implied,
+            fixed: None,
+            min: 0.0_f64,
+            max: f64::INFINITY,
+        }
+    }
+
+    #[test]
+    fn test_distribute_proportional() {
+        assert_eq!(
+            distribute(9.0_f64, &[share(3.0_f64), share(1.5_f64)]),
+            Some(vec![6.0_f64, 3.0_f64])
+        );
+    }
+
+    #[test]
+    fn test_distribute_fixed_and_bounded() {
+        assert_eq!(
+            distribute(
+                10.0_f64,
+                &[
+                    Share {
+                        fixed: Some(4.0_f64),
+                        ..share(3.5_f64)
+                    },
+                    Share {
+                        max: 2.0_f64,
+                        ..share(2.0_f64)
+                    },
+                    share(2.0_f64),
+                ]
+            ),
+            Some(vec![4.0_f64, 2.0_f64, 4.0_f64])
+        );
+    }
+
+    #[test]
+    fn test_distribute_too_narrow() {
+        assert_eq!(
+            distribute(
+                3.0_f64,
+                &[
+                    Share {
+                        min: 2.0_f64,
+                        ..share(3.5_f64)
+                    },
+                    Share {
+                        min: 2.0_f64,
+                        ..share(3.5_f64)
+                    },
+                ]
+            ),
+            None
+        );
+        assert_eq!(
+            distribute(
+                6.0_f64,
+                &[
+                    Share {
+                        fixed: Some(4.0_f64),
+                        ..share(3.5_f64)
+                    },
+                    Share {
+                        fixed: Some(4.0_f64),
+                        ..share(3.5_f64)
+                    },
+                ]
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        let mut implied = Width {
+            min: Infer::Default(Metre::new(2.5)),
+            target: Infer::Default(Metre::new(1.5)),
+            max: Infer::None,
+        };
+        assert_eq!(implied.solve(), Some(WidthConstraint::Minimum));
+        assert_eq!(implied.target, Infer::Calculated(Metre::new(2.5)));
+
+        let mut tagged = Width {
+            min: Infer::Default(Metre::new(2.5)),
+            target: Infer::Direct(Metre::new(2.0)),
+            max: Infer::None,
+        };
+        assert_eq!(tagged.solve(), Some(WidthConstraint::Target));
+        assert_eq!(tagged.target, Infer::Direct(Metre::new(2.0)));
+
+        let mut distributed = Width {
+            min: Infer::Default(Metre::new(2.5)),
+            target: Infer::Calculated(Metre::new(3.0)),
+            max: Infer::None,
+        };
+        assert_eq!(distributed.solve(), Some(WidthConstraint::Total));
+
+        assert_eq!(Width::default().solve(), None);
+    }
+
+    #[test]
+    fn test_lane_widths_reversed() {
+        use osm_tags::Tags;
+
+        use crate::locale::Locale;
+        use crate::road::Lane;
+        use crate::transform::{tags_to_lanes, TagsToLanesConfig};
+
+        let tags = Tags::from_pairs([
+            ("highway", "secondary"),
+            ("oneway", "-1"),
+            ("lanes", "2"),
+            ("width:lanes", "3|2.5"),
+        ])
+        .unwrap();
+        let result = tags_to_lanes(
+            &tags,
+            &Locale::builder().build(),
+            &TagsToLanesConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(result.widths.len(), result.road.lanes.len());
+        let widths: Vec<Metre> = result
+            .road
+            .lanes
+            .iter()
+            .zip(&result.widths)
+            .filter_map(|(lane, lane_width)| match lane {
+                Lane::Travel {
+                    width: Some(width), ..
+                } => {
+                    assert_eq!(lane_width.as_ref().unwrap().width.some(), Some(*width));
+                    Some(*width)
+                },
+                _ => None,
+            })
+            .collect();
+        assert_eq!(widths, [Metre::new(2.5), Metre::new(3.0)]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_lane_width_serialize() {
+        let lane_width = LaneWidth {
+            width: Infer::Calculated(Metre::new(3.0)),
+            constraint: WidthConstraint::Total,
+        };
+        assert_eq!(
+            serde_json::to_string(&lane_width).unwrap(),
+            r#"{"width":{"calculated":3.0},"constraint":"total"}"#
+        );
+    }
+}